@@ -0,0 +1,446 @@
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use super::*;
+use crate::{
+    depth::LiquidityDistribution,
+    global::FEE_RATE_DENOMINATOR,
+    math::{price_to_sqrt_price_x64, price_to_tick_index, sqrt_price_x64_to_price, to_ui_amount},
+    router::{Venue, VenueKind},
+    types::{InvalidInput, OrcaResult},
+    validation::parse_address,
+};
+
+// Arbitrage opportunities buffered before polling waits for the consumer
+const ARB_OPPORTUNITY_BUFFER: usize = 256;
+// Bisection steps when searching the price both pools meet at
+const EQUALIZE_ITERATIONS: usize = 64;
+
+/// Configuration of `OrcaClient::monitor_arbitrage`
+#[derive(Debug, Clone)]
+pub struct ArbMonitorConfig {
+    pub poll_interval: Duration,
+    /// Spread in basis points left after the fees of both swaps that an opportunity must exceed
+    pub threshold_bps: f64,
+}
+
+impl Default for ArbMonitorConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(10),
+            threshold_bps: 10.0,
+        }
+    }
+}
+
+/// Price divergence between two pools of the same pair
+///
+/// Prices are token B per token A of the first watched pool. Token A is bought with token B in
+/// `buy_pool` and sold for token B in `sell_pool`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ArbOpportunity {
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub buy_pool: Pubkey,
+    pub sell_pool: Pubkey,
+    pub buy_price: f64,
+    pub sell_price: f64,
+    /// `sell_price` over `buy_price` in basis points
+    pub divergence_bps: f64,
+    /// Fees of both swaps in basis points
+    pub fee_bps: f64,
+    /// Divergence left after the fees of both swaps
+    pub net_spread_bps: f64,
+    /// Price both pools are at after the trade
+    pub equalized_price: f64,
+    /// Token A bought in `buy_pool` and sold in `sell_pool` to bring both to `equalized_price`
+    pub amount_a: u64,
+    /// Token B paid into `buy_pool`, fee included
+    pub input_amount_b: u64,
+    /// Token B received from `sell_pool`, fee deducted
+    pub output_amount_b: u64,
+    /// `output_amount_b` minus `input_amount_b`, decimal adjusted
+    pub profit_b: f64,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Opportunities delivered by `OrcaClient::monitor_arbitrage`
+///
+/// Dropping the stream stops the monitoring.
+#[derive(Debug)]
+pub struct ArbOpportunityStream {
+    opportunity_rx: mpsc::Receiver<ArbOpportunity>,
+    task_handle: tokio::task::JoinHandle<()>,
+}
+
+impl ArbOpportunityStream {
+    /// Waits for the next opportunity
+    pub async fn recv(&mut self) -> Option<ArbOpportunity> {
+        self.opportunity_rx.recv().await
+    }
+}
+
+impl Drop for ArbOpportunityStream {
+    fn drop(&mut self) {
+        self.task_handle.abort();
+    }
+}
+
+/// A watched pool as of one poll, priced in the orientation of the first pool
+struct ArbVenue {
+    venue: Venue,
+    address: Pubkey,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    decimals_a: u8,
+    decimals_b: u8,
+    /// Mints are the other way round than in the first pool
+    reversed: bool,
+    /// Fee of a swap as a fraction of its input
+    fee: f64,
+    /// Whirlpool liquidity between the lowest and highest price of the poll, read on demand
+    distribution: Option<LiquidityDistribution>,
+}
+
+impl ArbVenue {
+    /// Price of the pool's own token A in its token B
+    fn own_price(&self) -> f64 {
+        match &self.venue {
+            Venue::Whirlpool(pool) => {
+                sqrt_price_x64_to_price(pool.sqrt_price, self.decimals_a, self.decimals_b)
+            }
+            Venue::Stable(pool) => pool.spot_price(),
+            Venue::Legacy(_) => 0.0,
+        }
+    }
+
+    /// Converts between the pool's own price and the price of the first pool's orientation
+    fn orient(&self, price: f64) -> f64 {
+        if self.reversed { 1.0 / price } else { price }
+    }
+
+    fn price(&self) -> f64 {
+        self.orient(self.own_price())
+    }
+
+    /// Input (fee excluded) and output of a swap moving the oriented price to `price`
+    ///
+    /// Raising the price takes in token B and pays out token A of the first pool's orientation,
+    /// lowering it the other way round, whichever way the pool stores its mints.
+    fn swap_to_price(&self, price: f64) -> Option<(u64, u64)> {
+        let own_price = self.orient(price);
+        match &self.venue {
+            Venue::Whirlpool(_) => Some(self.distribution.as_ref()?.swap_to_sqrt_price(
+                price_to_sqrt_price_x64(own_price, self.decimals_a, self.decimals_b),
+            )),
+            Venue::Stable(pool) => pool.swap_to_price(own_price),
+            Venue::Legacy(_) => None,
+        }
+    }
+}
+
+/// Trade that brings two pools to the same price
+struct ArbSize {
+    equalized_price: f64,
+    amount_a: u64,
+    input_amount_b: u64,
+    output_amount_b: u64,
+}
+
+impl OrcaClient {
+    /// Watches two or more pools of the same pair and reports prices diverging beyond their fees
+    ///
+    /// Every poll the pools are read and each pair of them is compared. An opportunity is
+    /// delivered on every poll where buying token A in the cheaper pool and selling it in the
+    /// dearer one keeps more than `threshold_bps` after both fees. Its size is the trade that
+    /// brings both pools to the same price, walked through the Whirlpool tick liquidity or the
+    /// stable curve; the last part of such a trade may cost more in fees than it earns, so
+    /// `profit_b` can be below the spread. Pools that do not trade the first pool's mints are
+    /// skipped; legacy pools are not supported.
+    ///
+    /// # Params
+    /// venues - Whirlpools and stable pools of one token pair, at least two; only their
+    ///          addresses are kept, the pools are read again every poll
+    /// config - Polling interval and spread threshold
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::arbitrage::ArbMonitorConfig;
+    /// use orca_sdk::router::VenueKind;
+    /// use std::sync::Arc;
+    ///
+    /// let client = Arc::new(OrcaClient::new()?);
+    /// let venues = client
+    ///     .find_venues(
+    ///         "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    ///         "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+    ///         &[VenueKind::Whirlpool, VenueKind::Stable],
+    ///     )
+    ///     .await?;
+    /// let mut opportunities = client
+    ///     .clone()
+    ///     .monitor_arbitrage(venues, ArbMonitorConfig::default())?;
+    /// while let Some(arb) = opportunities.recv().await {
+    ///     println!(
+    ///         "buy {} A in {} and sell in {}: {:.1} bps net, profit {:.4} B",
+    ///         arb.amount_a, arb.buy_pool, arb.sell_pool, arb.net_spread_bps, arb.profit_b
+    ///     );
+    /// }
+    /// ```
+    pub fn monitor_arbitrage(
+        self: Arc<Self>,
+        venues: Vec<Venue>,
+        config: ArbMonitorConfig,
+    ) -> OrcaResult<ArbOpportunityStream> {
+        if venues.len() < 2 {
+            return Err(OrcaError::InvalidInput(InvalidInput::ValueOutOfRange {
+                field: "venues".to_string(),
+                value: venues.len() as u64,
+                min: 2,
+                max: u64::MAX,
+            }));
+        }
+        if let Some(venue) = venues
+            .iter()
+            .find(|venue| matches!(venue, Venue::Legacy(_)))
+        {
+            return Err(OrcaError::InvalidInput(InvalidInput::UnsupportedPool(
+                venue.address().to_string(),
+            )));
+        }
+        let pools: Vec<(VenueKind, String)> = venues
+            .iter()
+            .map(|venue| (venue.kind(), venue.address()))
+            .collect();
+        let (opportunity_tx, opportunity_rx) = mpsc::channel(ARB_OPPORTUNITY_BUFFER);
+        let client = self;
+        let task_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.poll_interval);
+            loop {
+                interval.tick().await;
+                if opportunity_tx.is_closed() {
+                    break;
+                }
+                let opportunities = match client.evaluate_arbitrage(&pools, &config).await {
+                    Ok(opportunities) => opportunities,
+                    Err(e) => {
+                        tracing::warn!("Failed to evaluate arbitrage: {:?}", e);
+                        continue;
+                    }
+                };
+                for opportunity in opportunities {
+                    if opportunity_tx.send(opportunity).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(ArbOpportunityStream {
+            opportunity_rx,
+            task_handle,
+        })
+    }
+
+    /// Returns the opportunities among the current states of `pools`
+    async fn evaluate_arbitrage(
+        &self,
+        pools: &[(VenueKind, String)],
+        config: &ArbMonitorConfig,
+    ) -> OrcaResult<Vec<ArbOpportunity>> {
+        let concurrency = self.fetch_concurrency();
+        // Built up front so the polling task stays `Send`
+        let requests: Vec<_> = pools
+            .iter()
+            .map(|(kind, address)| self.get_arb_venue(*kind, address))
+            .collect();
+        let fetched: Vec<OrcaResult<ArbVenue>> =
+            stream::iter(requests).buffered(concurrency).collect().await;
+        let mut venues: Vec<ArbVenue> = Vec::with_capacity(pools.len());
+        for venue in fetched {
+            let mut venue = venue?;
+            if let Some(first) = venues.first() {
+                if (venue.mint_a, venue.mint_b) == (first.mint_b, first.mint_a) {
+                    venue.reversed = true;
+                } else if (venue.mint_a, venue.mint_b) != (first.mint_a, first.mint_b) {
+                    tracing::warn!(
+                        pool = %venue.address,
+                        "Pool does not trade {}/{}, skipping it",
+                        first.mint_a,
+                        first.mint_b
+                    );
+                    continue;
+                }
+            }
+            venues.push(venue);
+        }
+        let prices: Vec<f64> = venues.iter().map(ArbVenue::price).collect();
+        let mut candidates = Vec::new();
+        for i in 0..venues.len() {
+            for j in i + 1..venues.len() {
+                let (buy, sell) = if prices[i] <= prices[j] {
+                    (i, j)
+                } else {
+                    (j, i)
+                };
+                if !(prices[buy] > 0.0 && prices[sell].is_finite()) {
+                    continue;
+                }
+                let kept = (1.0 - venues[buy].fee) * (1.0 - venues[sell].fee);
+                let net_spread_bps = (prices[sell] * kept / prices[buy] - 1.0) * 10_000.0;
+                if net_spread_bps > config.threshold_bps {
+                    candidates.push((buy, sell, net_spread_bps));
+                }
+            }
+        }
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+        let low = prices.iter().copied().fold(f64::INFINITY, f64::min);
+        let high = prices.iter().copied().fold(0.0, f64::max);
+        let loads: Vec<_> = venues
+            .iter_mut()
+            .map(|venue| async move {
+                if let Err(e) = self.load_arb_liquidity(venue, low, high).await {
+                    tracing::warn!(
+                        pool = %venue.address,
+                        error = ?e,
+                        "Failed to read pool liquidity"
+                    );
+                }
+            })
+            .collect();
+        stream::iter(loads)
+            .buffer_unordered(concurrency)
+            .collect::<Vec<()>>()
+            .await;
+        let (mint_a, mint_b) = (venues[0].mint_a, venues[0].mint_b);
+        let decimals_b = venues[0].decimals_b;
+        let mut opportunities = Vec::with_capacity(candidates.len());
+        for (buy, sell, net_spread_bps) in candidates {
+            let (buy_venue, sell_venue) = (&venues[buy], &venues[sell]);
+            let Some(size) = equalize(buy_venue, sell_venue) else {
+                tracing::warn!(
+                    buy_pool = %buy_venue.address,
+                    sell_pool = %sell_venue.address,
+                    "Failed to size arbitrage"
+                );
+                continue;
+            };
+            opportunities.push(ArbOpportunity {
+                token_mint_a: mint_a,
+                token_mint_b: mint_b,
+                buy_pool: buy_venue.address,
+                sell_pool: sell_venue.address,
+                buy_price: prices[buy],
+                sell_price: prices[sell],
+                divergence_bps: (prices[sell] / prices[buy] - 1.0) * 10_000.0,
+                fee_bps: (buy_venue.fee + sell_venue.fee) * 10_000.0,
+                net_spread_bps,
+                equalized_price: size.equalized_price,
+                amount_a: size.amount_a,
+                input_amount_b: size.input_amount_b,
+                output_amount_b: size.output_amount_b,
+                profit_b: to_ui_amount(size.output_amount_b, decimals_b)
+                    - to_ui_amount(size.input_amount_b, decimals_b),
+                timestamp: chrono::Utc::now(),
+            });
+        }
+        Ok(opportunities)
+    }
+
+    /// Reads the current state of a watched pool
+    async fn get_arb_venue(&self, kind: VenueKind, address: &str) -> OrcaResult<ArbVenue> {
+        let (venue, mint_a, mint_b, decimals_a, decimals_b) = match kind {
+            VenueKind::Whirlpool => {
+                let pool = self.get_pool_state_onchain(address).await?;
+                let mint_a = parse_address("token_mint_a", &pool.token_mint_a)?;
+                let mint_b = parse_address("token_mint_b", &pool.token_mint_b)?;
+                let decimals = self.get_mint_decimals(&[mint_a, mint_b]).await?;
+                (
+                    Venue::Whirlpool(Box::new(pool)),
+                    mint_a,
+                    mint_b,
+                    decimals[0],
+                    decimals[1],
+                )
+            }
+            VenueKind::Stable => {
+                let pool = self.get_stable_pool(address).await?;
+                let (mint_a, mint_b) = (pool.token_mint_a, pool.token_mint_b);
+                let (decimals_a, decimals_b) = (pool.decimals_a, pool.decimals_b);
+                (Venue::Stable(pool), mint_a, mint_b, decimals_a, decimals_b)
+            }
+            VenueKind::Legacy => {
+                return Err(OrcaError::InvalidInput(InvalidInput::UnsupportedPool(
+                    address.to_string(),
+                )));
+            }
+        };
+        Ok(ArbVenue {
+            address: parse_address("pool_address", address)?,
+            mint_a,
+            mint_b,
+            decimals_a,
+            decimals_b,
+            reversed: false,
+            fee: venue.fee_rate() as f64 / FEE_RATE_DENOMINATOR as f64,
+            distribution: None,
+            venue,
+        })
+    }
+
+    /// Reads a Whirlpool's liquidity between two oriented prices and its current tick
+    async fn load_arb_liquidity(
+        &self,
+        venue: &mut ArbVenue,
+        low: f64,
+        high: f64,
+    ) -> OrcaResult<()> {
+        let ticks = [low, high].map(|price| {
+            price_to_tick_index(venue.orient(price), venue.decimals_a, venue.decimals_b)
+        });
+        let Venue::Whirlpool(pool) = &venue.venue else {
+            return Ok(());
+        };
+        let current = pool.tick_current_index;
+        let lower_tick = ticks[0].min(ticks[1]).min(current);
+        let upper_tick = ticks[0].max(ticks[1]).max(current) + 1;
+        venue.distribution = Some(
+            self.get_liquidity_distribution(pool, lower_tick, upper_tick)
+                .await?,
+        );
+        Ok(())
+    }
+}
+
+/// Finds the trade that leaves `buy` and `sell` at the same price
+///
+/// Token A bought from `buy` is sold into `sell` after its fee, so the search is for the price
+/// where what `buy` pays out, less the fee of `sell`, is what `sell` takes in.
+fn equalize(buy: &ArbVenue, sell: &ArbVenue) -> Option<ArbSize> {
+    let surplus = |price: f64| -> Option<f64> {
+        let (_, bought) = buy.swap_to_price(price)?;
+        let (sold, _) = sell.swap_to_price(price)?;
+        Some(bought as f64 * (1.0 - sell.fee) - sold as f64)
+    };
+    // Bisect in log space, the price ratio of the pools can be far from 1
+    let (mut low, mut high) = (buy.price().ln(), sell.price().ln());
+    for _ in 0..EQUALIZE_ITERATIONS {
+        let mid = (low + high) / 2.0;
+        if surplus(mid.exp())? < 0.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    let equalized_price = ((low + high) / 2.0).exp();
+    let (input_b, amount_a) = buy.swap_to_price(equalized_price)?;
+    let (_, output_amount_b) = sell.swap_to_price(equalized_price)?;
+    Some(ArbSize {
+        equalized_price,
+        amount_a,
+        input_amount_b: (input_b as f64 / (1.0 - buy.fee)).ceil() as u64,
+        output_amount_b,
+    })
+}
@@ -0,0 +1,157 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+
+use super::*;
+use crate::types::OrcaResult;
+
+/// Final state of an audited transaction
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", content = "detail", rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Confirmed,
+    Failed(String),
+    /// Simulated in dry-run mode; carries the simulation error, if any
+    Simulated(Option<String>),
+}
+
+/// Caller-provided context of a mutating operation
+#[derive(Debug, Clone, Default)]
+pub struct AuditDetails {
+    /// Operation inputs such as mints, amounts and slippage
+    pub inputs: serde_json::Value,
+    /// Quote the transaction was built from, if any
+    pub quote: Option<serde_json::Value>,
+}
+
+impl AuditDetails {
+    pub fn new(inputs: serde_json::Value) -> Self {
+        Self {
+            inputs,
+            quote: None,
+        }
+    }
+
+    pub fn with_quote(mut self, quote: impl Serialize) -> Self {
+        self.quote = serde_json::to_value(quote).ok();
+        self
+    }
+}
+
+/// Structured record of a transaction the SDK built and sent
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditRecord {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub operation: String,
+    pub fee_payer: String,
+    pub inputs: serde_json::Value,
+    pub quote: Option<serde_json::Value>,
+    /// All accounts referenced by the transaction message
+    pub accounts: Vec<String>,
+    pub signature: Option<String>,
+    pub dry_run: bool,
+    pub outcome: AuditOutcome,
+}
+
+/// Destination for audit records
+///
+/// Implementations must not block for long; they are called inline after each submission.
+pub trait AuditSink: Send + Sync {
+    fn record(&self, record: &AuditRecord);
+}
+
+/// Emits audit records as JSON `tracing` events at info level
+#[derive(Debug, Default)]
+pub struct LogAuditSink;
+
+impl AuditSink for LogAuditSink {
+    fn record(&self, record: &AuditRecord) {
+        match serde_json::to_string(record) {
+            Ok(json) => tracing::info!(target: "orca_sdk::audit", "{}", json),
+            Err(e) => tracing::error!("Failed to serialize audit record: {}", e),
+        }
+    }
+}
+
+/// Appends audit records to a file as JSON lines
+#[derive(Debug)]
+pub struct JsonLinesAuditSink {
+    file: Mutex<File>,
+}
+
+impl JsonLinesAuditSink {
+    pub fn open(path: impl AsRef<Path>) -> OrcaResult<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path.as_ref())
+            .map_err(|e| OrcaError::IoError(format!("Failed to open audit log: {}", e)))?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+impl AuditSink for JsonLinesAuditSink {
+    fn record(&self, record: &AuditRecord) {
+        let Ok(json) = serde_json::to_string(record) else {
+            return;
+        };
+        if let Ok(mut file) = self.file.lock()
+            && let Err(e) = writeln!(file, "{}", json)
+        {
+            tracing::error!("Failed to write audit record: {}", e);
+        }
+    }
+}
+
+impl OrcaClient {
+    /// Installs or removes the sink receiving an audit record for every submitted transaction
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use orca_sdk::audit::JsonLinesAuditSink;
+    ///
+    /// client.set_audit_sink(Some(Arc::new(JsonLinesAuditSink::open("audit.jsonl")?)));
+    /// ```
+    pub fn set_audit_sink(&self, sink: Option<Arc<dyn AuditSink>>) {
+        if let Ok(mut current) = self.audit_sink.write() {
+            *current = sink;
+        }
+    }
+
+    /// Builds an audit record for a transaction and hands it to the sink, if any
+    pub(crate) fn audit_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        operation: &str,
+        details: &AuditDetails,
+        outcome: AuditOutcome,
+    ) {
+        let Some(sink) = self.audit_sink.read().ok().and_then(|sink| sink.clone()) else {
+            return;
+        };
+        let account_keys = transaction.message.static_account_keys();
+        let record = AuditRecord {
+            timestamp: chrono::Utc::now(),
+            operation: operation.to_string(),
+            fee_payer: account_keys
+                .first()
+                .map(|key| key.to_string())
+                .unwrap_or_default(),
+            inputs: details.inputs.clone(),
+            quote: details.quote.clone(),
+            accounts: account_keys.iter().map(|key| key.to_string()).collect(),
+            signature: transaction.signatures.first().map(|s| s.to_string()),
+            dry_run: self.is_dry_run(),
+            outcome,
+        };
+        sink.record(&record);
+    }
+}
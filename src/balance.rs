@@ -1,10 +1,31 @@
 use super::*;
-use crate::types::OrcaResult;
+use crate::{
+    audit::AuditDetails, math::to_ui_amount, token::TOKEN_2022_PROGRAM, types::OrcaResult,
+};
 use base64::{Engine, prelude::BASE64_STANDARD};
 use solana_account_decoder::{UiAccountData, UiAccountEncoding};
 use solana_client::rpc_request::TokenAccountsFilter;
 use solana_sdk::program_pack::Pack;
 
+/// A raw token amount together with its mint's decimals
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct TokenAmount {
+    pub amount: u64,
+    pub decimals: u8,
+    /// `amount` in whole tokens
+    pub ui_amount: f64,
+}
+
+impl TokenAmount {
+    pub fn new(amount: u64, decimals: u8) -> Self {
+        Self {
+            amount,
+            decimals,
+            ui_amount: to_ui_amount(amount, decimals),
+        }
+    }
+}
+
 impl OrcaClient {
     /// Get the balance of a specific token for a given owner and mint
     ///
@@ -26,20 +47,13 @@ impl OrcaClient {
     /// ```
     pub async fn get_token_balance(&self, owner: &Pubkey, mint: &Pubkey) -> OrcaResult<u64> {
         let token_accounts = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?
-            .get_token_accounts_by_owner(owner, TokenAccountsFilter::Mint(*mint))
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get token accounts: {}", e)))?;
+            .rpc_call("Failed to get token accounts", |client| {
+                client.get_token_accounts_by_owner(owner, TokenAccountsFilter::Mint(*mint))
+            })
+            .await?;
         if let Some(account) = token_accounts.first() {
             let account_data_bytes = self.decode_account_data(&account.account.data)?;
-            let account_data: spl_token::state::Account =
-                spl_token::state::Account::unpack(&account_data_bytes).map_err(|e| {
-                    OrcaError::Error(format!("Failed to unpack token account: {}", e))
-                })?;
-            Ok(account_data.amount)
+            Ok(Self::unpack_token_account(&account_data_bytes)?.amount)
         } else {
             Ok(0)
         }
@@ -64,34 +78,74 @@ impl OrcaClient {
     /// # }
     /// ```
     pub async fn get_all_token_balances(&self, owner: &Pubkey) -> OrcaResult<Vec<(Pubkey, u64)>> {
-        let token_accounts = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?
-            .get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(spl_token::id()))
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get token accounts: {}", e)))?;
-        let mut balances = Vec::new();
-        for account in token_accounts {
-            let account_data_bytes = self.decode_account_data(&account.account.data)?;
-            let account_data: spl_token::state::Account =
-                spl_token::state::Account::unpack(&account_data_bytes).map_err(|e| {
-                    OrcaError::Error(format!("Failed to unpack token account: {}", e))
-                })?;
-
-            if account_data.amount > 0 {
-                balances.push((account_data.mint, account_data.amount));
+        self.with_timeout("get all token balances", async {
+            let mut balances = Vec::new();
+            for token_program in [spl_token::id(), TOKEN_2022_PROGRAM] {
+                let token_accounts = self
+                    .rpc_call("Failed to get token accounts", |client| {
+                        client.get_token_accounts_by_owner(
+                            owner,
+                            TokenAccountsFilter::ProgramId(token_program),
+                        )
+                    })
+                    .await?;
+                for account in token_accounts {
+                    let account_data_bytes = self.decode_account_data(&account.account.data)?;
+                    let account_data = Self::unpack_token_account(&account_data_bytes)?;
+                    if account_data.amount > 0 {
+                        balances.push((account_data.mint, account_data.amount));
+                    }
+                }
             }
-        }
-        Ok(balances)
+            Ok(balances)
+        })
+        .await
+    }
+
+    /// Get the balance of a token together with its decimals and whole-token amount
+    ///
+    /// # Example
+    /// ```rust
+    /// let balance = client.get_token_balance_ui(&owner, &usdc_mint).await?;
+    /// println!("{} USDC ({} raw)", balance.ui_amount, balance.amount);
+    /// ```
+    pub async fn get_token_balance_ui(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+    ) -> OrcaResult<TokenAmount> {
+        let amount = self.get_token_balance(owner, mint).await?;
+        let decimals = self.get_mint_decimals(&[*mint]).await?[0];
+        Ok(TokenAmount::new(amount, decimals))
     }
 
-    /// Ensure a token account exists for the given keypair and mint
+    /// Get balances for all tokens owned by an account, with decimals and whole-token amounts
+    ///
+    /// # Example
+    /// ```rust
+    /// for (mint, balance) in client.get_all_token_balances_ui(&owner).await? {
+    ///     println!("Mint: {}, Balance: {}", mint, balance.ui_amount);
+    /// }
+    /// ```
+    pub async fn get_all_token_balances_ui(
+        &self,
+        owner: &Pubkey,
+    ) -> OrcaResult<Vec<(Pubkey, TokenAmount)>> {
+        let balances = self.get_all_token_balances(owner).await?;
+        let mints: Vec<Pubkey> = balances.iter().map(|(mint, _)| *mint).collect();
+        let decimals = self.get_mint_decimals(&mints).await?;
+        Ok(balances
+            .into_iter()
+            .zip(decimals)
+            .map(|((mint, amount), decimals)| (mint, TokenAmount::new(amount, decimals)))
+            .collect())
+    }
+
+    /// Ensure a token account exists for the given signer and mint
     /// Creates the account if it doesn't exist
     ///
     /// # Params
-    /// keypair - The keypair that owns the token account
+    /// signer - The signer that owns the token account
     /// mint - The public key of the token mint
     ///
     /// # Example
@@ -106,29 +160,29 @@ impl OrcaClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn ensure_token_account(
+    pub async fn ensure_token_account<S: Signer + ?Sized>(
         &self,
-        keypair: &Keypair,
+        signer: &S,
         mint: &Pubkey,
     ) -> OrcaResult<Pubkey> {
-        let associated_token_address = self.get_associated_token_address(&keypair.pubkey(), mint);
+        let token_program = self.get_mint_info(mint).await?.token_program;
+        let associated_token_address =
+            self.get_associated_token_address_with_program(&signer.pubkey(), mint, &token_program);
         match self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?
-            .get_account(&associated_token_address)
+            .rpc_call("Failed to get token account", |client| {
+                client.get_account(&associated_token_address)
+            })
             .await
         {
             Ok(_) => Ok(associated_token_address),
-            Err(_) => self.create_associated_token_account(keypair, mint).await,
+            Err(_) => self.create_associated_token_account(signer, mint).await,
         }
     }
 
-    /// Create an associated token account for the given keypair and mint
+    /// Create an associated token account for the given signer and mint
     ///
     /// # Params
-    /// keypair - The keypair that will own the token account
+    /// signer - The signer that will own the token account
     /// mint - The public key of the token mint
     ///
     /// # Example
@@ -143,36 +197,30 @@ impl OrcaClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn create_associated_token_account(
+    pub async fn create_associated_token_account<S: Signer + ?Sized>(
         &self,
-        keypair: &Keypair,
+        signer: &S,
         mint: &Pubkey,
     ) -> OrcaResult<Pubkey> {
-        let recent_blockhash = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?
-            .get_latest_blockhash()
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get blockhash: {}", e)))?;
+        self.check_token_policy(&mint.to_string())?;
+        let token_program = self.get_mint_info(mint).await?.token_program;
+        let recent_blockhash = self.latest_blockhash().await?;
         let instruction =
             spl_associated_token_account::instruction::create_associated_token_account(
-                &keypair.pubkey(),
-                &keypair.pubkey(),
+                &signer.pubkey(),
+                &signer.pubkey(),
                 mint,
-                &spl_token::id(),
+                &token_program,
             );
-        let message = Message::new(&[instruction], Some(&keypair.pubkey()));
-        let transaction = Transaction::new(&[keypair], message, recent_blockhash);
-        self.solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?
-            .send_and_confirm_transaction(&transaction)
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to create token account: {}", e)))?;
-        Ok(self.get_associated_token_address(&keypair.pubkey(), mint))
+        let message = Message::new(&[instruction], Some(&signer.pubkey()));
+        let transaction = Transaction::new(&[signer], message, recent_blockhash);
+        self.submit_transaction(
+            &transaction.into(),
+            "create token account",
+            &AuditDetails::new(serde_json::json!({ "mint": mint.to_string() })),
+        )
+        .await?;
+        Ok(self.get_associated_token_address_with_program(&signer.pubkey(), mint, &token_program))
     }
 
     /// Get the total supply of a token mint
@@ -193,18 +241,30 @@ impl OrcaClient {
     /// ```
     pub async fn get_token_supply(&self, mint: &Pubkey) -> OrcaResult<u64> {
         let mint_account = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?
-            .get_account(mint)
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get mint account: {}", e)))?;
-        let mint_data: spl_token::state::Mint = spl_token::state::Mint::unpack(&mint_account.data)
-            .map_err(|e| OrcaError::Error(format!("Failed to unpack mint data: {}", e)))?;
+            .get_existing_account(mint, "Failed to get mint account")
+            .await?;
+        let mint_data = mint_account
+            .data
+            .get(..spl_token::state::Mint::LEN)
+            .and_then(|data| spl_token::state::Mint::unpack_from_slice(data).ok())
+            .ok_or_else(|| OrcaError::InvalidAccountData {
+                account: *mint,
+                reason: "not an SPL token mint".to_string(),
+            })?;
         Ok(mint_data.supply)
     }
 
+    /// Unpacks the base state of an SPL Token or Token-2022 token account
+    pub(crate) fn unpack_token_account(data: &[u8]) -> OrcaResult<spl_token::state::Account> {
+        let base = data
+            .get(..spl_token::state::Account::LEN)
+            .ok_or(OrcaError::ParseError(
+                "Invalid token account data length".to_string(),
+            ))?;
+        spl_token::state::Account::unpack_from_slice(base)
+            .map_err(|e| OrcaError::ParseError(format!("Failed to unpack token account: {}", e)))
+    }
+
     /// Decode account data from various encoding formats
     ///
     /// # Params
@@ -215,20 +275,20 @@ impl OrcaClient {
             UiAccountData::Binary(data, encoding) => match encoding {
                 UiAccountEncoding::Base64 => BASE64_STANDARD
                     .decode(data)
-                    .map_err(|e| OrcaError::Error(format!("Base64 decode error: {}", e))),
+                    .map_err(|e| OrcaError::ParseError(format!("Base64 decode error: {}", e))),
                 UiAccountEncoding::Base64Zstd => {
-                    let compressed_data = BASE64_STANDARD
-                        .decode(data)
-                        .map_err(|e| OrcaError::Error(format!("Base64 decode error: {}", e)))?;
+                    let compressed_data = BASE64_STANDARD.decode(data).map_err(|e| {
+                        OrcaError::ParseError(format!("Base64 decode error: {}", e))
+                    })?;
                     zstd::decode_all(&compressed_data[..])
-                        .map_err(|e| OrcaError::Error(format!("Zstd decode error: {}", e)))
+                        .map_err(|e| OrcaError::ParseError(format!("Zstd decode error: {}", e)))
                 }
-                _ => Err(OrcaError::Error(format!(
+                _ => Err(OrcaError::ParseError(format!(
                     "Unsupported encoding: {:?}",
                     encoding
                 ))),
             },
-            _ => Err(OrcaError::Error(
+            _ => Err(OrcaError::ParseError(
                 "Unsupported account data format".to_string(),
             )),
         }
@@ -0,0 +1,207 @@
+use std::time::Duration;
+
+use solana_commitment_config::CommitmentConfig;
+
+use super::*;
+use crate::{
+    failover::{FailoverConfig, RpcEndpoints},
+    network::{Network, rpc_client, solana_with_rpc, websocket_url},
+    retry::RetryPolicy,
+    types::OrcaResult,
+};
+
+/// Configures an `OrcaClient` before it is created
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use orca_sdk::{OrcaClient, network::Network, retry::RetryPolicy};
+/// use solana_commitment_config::CommitmentConfig;
+///
+/// let client = OrcaClient::builder()
+///     .network(Network::Localnet)
+///     .commitment(CommitmentConfig::processed())
+///     .request_timeout(Duration::from_secs(10))
+///     .retry_policy(RetryPolicy::none())
+///     .whirlpool_program_id(my_whirlpool_fork)
+///     .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrcaClientBuilder {
+    rpc_url: String,
+    fallback_rpc_urls: Vec<String>,
+    failover: FailoverConfig,
+    ws_url: Option<String>,
+    commitment: CommitmentConfig,
+    request_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    whirlpool_program_id: Option<Pubkey>,
+    stable_swap_program_id: Option<Pubkey>,
+    #[cfg(feature = "persistent-cache")]
+    persistent_cache: Option<crate::cache::PersistentCache>,
+    #[cfg(feature = "yellowstone")]
+    geyser: Option<crate::geyser::GeyserConfig>,
+}
+
+impl Default for OrcaClientBuilder {
+    fn default() -> Self {
+        Self {
+            rpc_url: Network::default().rpc_url().to_string(),
+            fallback_rpc_urls: Vec::new(),
+            failover: FailoverConfig::default(),
+            ws_url: None,
+            commitment: CommitmentConfig::default(),
+            request_timeout: None,
+            retry_policy: RetryPolicy::default(),
+            whirlpool_program_id: None,
+            stable_swap_program_id: None,
+            #[cfg(feature = "persistent-cache")]
+            persistent_cache: None,
+            #[cfg(feature = "yellowstone")]
+            geyser: None,
+        }
+    }
+}
+
+impl OrcaClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses the RPC endpoint of a well-known cluster
+    pub fn network(mut self, network: Network) -> Self {
+        self.rpc_url = network.rpc_url().to_string();
+        self
+    }
+
+    /// Uses a custom RPC endpoint
+    pub fn rpc_url(mut self, rpc_url: impl Into<String>) -> Self {
+        self.rpc_url = rpc_url.into();
+        self
+    }
+
+    /// Adds an RPC endpoint that shares reads and takes over writes when earlier ones fail
+    ///
+    /// Endpoints are tried for writes in the order they were added, after the primary.
+    pub fn fallback_rpc_url(mut self, rpc_url: impl Into<String>) -> Self {
+        self.fallback_rpc_urls.push(rpc_url.into());
+        self
+    }
+
+    /// Uses several RPC endpoints; the first one is the primary, the others fallbacks
+    pub fn rpc_urls<I, S>(mut self, rpc_urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut rpc_urls = rpc_urls.into_iter().map(Into::into);
+        self.rpc_url = rpc_urls.next().unwrap_or_default();
+        self.fallback_rpc_urls = rpc_urls.collect();
+        self
+    }
+
+    /// When endpoints are skipped for lagging behind or failing requests
+    pub fn failover(mut self, failover: FailoverConfig) -> Self {
+        self.failover = failover;
+        self
+    }
+
+    /// Uses a custom WebSocket endpoint for subscriptions; derived from the RPC URL by default
+    pub fn ws_url(mut self, ws_url: impl Into<String>) -> Self {
+        self.ws_url = Some(ws_url.into());
+        self
+    }
+
+    /// Default commitment for RPC reads
+    pub fn commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// Timeout of a single RPC request
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// How failed RPC reads are retried
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Overrides the Whirlpool program, e.g. for forks or localnet deployments
+    pub fn whirlpool_program_id(mut self, program_id: Pubkey) -> Self {
+        self.whirlpool_program_id = Some(program_id);
+        self
+    }
+
+    /// Overrides the stable swap program
+    pub fn stable_swap_program_id(mut self, program_id: Pubkey) -> Self {
+        self.stable_swap_program_id = Some(program_id);
+        self
+    }
+
+    /// Keeps pool discovery results and mint metadata in an on-disk cache
+    #[cfg(feature = "persistent-cache")]
+    pub fn persistent_cache(mut self, cache: crate::cache::PersistentCache) -> Self {
+        self.persistent_cache = Some(cache);
+        self
+    }
+
+    /// Streams pool updates and swaps from a Yellowstone gRPC endpoint instead of JSON-RPC
+    #[cfg(feature = "yellowstone")]
+    pub fn geyser(mut self, config: crate::geyser::GeyserConfig) -> Self {
+        self.geyser = Some(config);
+        self
+    }
+
+    pub fn build(self) -> OrcaResult<OrcaClient> {
+        let whirlpool_program_id = match self.whirlpool_program_id {
+            Some(program_id) => program_id,
+            None => Pubkey::from_str(ORCA_WHIRLPOOLS_PROGRAM_ID)
+                .map_err(|e| OrcaError::Error(format!("Invalid whirlpool program ID: {}", e)))?,
+        };
+        let stable_swap_program_id = match self.stable_swap_program_id {
+            Some(program_id) => program_id,
+            None => Pubkey::from_str(ORCA_STABLE_SWAP_PROGRAM_ID)
+                .map_err(|e| OrcaError::Error(format!("Invalid stable swap program ID: {}", e)))?,
+        };
+        let clients = std::iter::once(&self.rpc_url)
+            .chain(&self.fallback_rpc_urls)
+            .map(|url| {
+                Ok((
+                    url.clone(),
+                    rpc_client(url, self.commitment, self.request_timeout)?,
+                ))
+            })
+            .collect::<OrcaResult<Vec<_>>>()?;
+        Ok(OrcaClient {
+            solana: solana_with_rpc(clients[0].1.clone())?,
+            ws_url: self.ws_url.unwrap_or_else(|| websocket_url(&self.rpc_url)),
+            endpoints: RpcEndpoints::new(clients, self.failover),
+            whirlpool_program_id,
+            stable_swap_program_id,
+            retry_policy: self.retry_policy,
+            price_guard: RwLock::new(None),
+            max_token_risk: RwLock::new(None),
+            spend_policy: RwLock::new(None),
+            spend_ledger: Mutex::new(SpendLedger::default()),
+            dry_run: AtomicBool::new(false),
+            audit_sink: RwLock::new(None),
+            fetch_concurrency: AtomicUsize::new(DEFAULT_FETCH_CONCURRENCY),
+            transaction_cache: Mutex::new(TransactionCache::default()),
+            pool_cache: RwLock::new(PoolAddressCache::default()),
+            #[cfg(feature = "persistent-cache")]
+            persistent_cache: self.persistent_cache,
+            #[cfg(feature = "yellowstone")]
+            geyser: self.geyser,
+            operation_timeout: RwLock::new(None),
+            price_monitor: Mutex::new(PriceMonitor::default()),
+            price_history_cache: Mutex::new(PriceHistoryCache::default()),
+            mint_decimals: RwLock::new(HashMap::new()),
+            fee_growth_snapshots: Mutex::new(FeeGrowthSnapshots::default()),
+            daily_stats: Mutex::new(DailyStatsStore::default()),
+        })
+    }
+}
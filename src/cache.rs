@@ -0,0 +1,174 @@
+use std::{
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use super::*;
+use crate::{stats::PoolDailyStats, token::MintInfo, types::OrcaResult};
+
+// How long persisted pool addresses are reused before rediscovery
+const DEFAULT_POOL_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// On-disk cache of pool discovery results, mint metadata and daily pool statistics, kept
+/// across restarts
+///
+/// Discovered pool addresses expire after the pool TTL. Mint token programs and decimals
+/// never change, so they are kept until the cache is cleared; Token-2022 mints are not stored
+/// because their transfer fees change by epoch. Statistics of finished days are kept too.
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::cache::PersistentCache;
+///
+/// let cache = PersistentCache::open("/var/lib/my-bot/orca-cache")?;
+/// let client = OrcaClient::builder().persistent_cache(cache).build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct PersistentCache {
+    pools: sled::Tree,
+    mints: sled::Tree,
+    daily_stats: sled::Tree,
+    pool_ttl: Duration,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedPools {
+    /// Unix time the pools were discovered at, in seconds
+    cached_at: u64,
+    pools: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedMint {
+    token_program: Pubkey,
+    decimals: u8,
+}
+
+impl PersistentCache {
+    /// Opens or creates the cache database at `path`
+    pub fn open(path: impl AsRef<Path>) -> OrcaResult<Self> {
+        let db = sled::open(path.as_ref())
+            .map_err(|e| OrcaError::IoError(format!("Failed to open persistent cache: {}", e)))?;
+        let open_tree = |name: &str| {
+            db.open_tree(name).map_err(|e| {
+                OrcaError::IoError(format!("Failed to open persistent cache tree: {}", e))
+            })
+        };
+        Ok(Self {
+            pools: open_tree("pools")?,
+            mints: open_tree("mints")?,
+            daily_stats: open_tree("daily_stats")?,
+            pool_ttl: DEFAULT_POOL_TTL,
+        })
+    }
+
+    /// Sets how long persisted pool addresses are reused; one day by default
+    pub fn with_pool_ttl(mut self, pool_ttl: Duration) -> Self {
+        self.pool_ttl = pool_ttl;
+        self
+    }
+
+    /// Removes every cached entry
+    pub fn clear(&self) -> OrcaResult<()> {
+        for tree in [&self.pools, &self.mints, &self.daily_stats] {
+            tree.clear().map_err(|e| {
+                OrcaError::IoError(format!("Failed to clear persistent cache: {}", e))
+            })?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn get_pools(&self, key: &str) -> Option<Vec<String>> {
+        let entry: CachedPools = read(&self.pools, key.as_bytes())?;
+        (unix_now().saturating_sub(entry.cached_at) < self.pool_ttl.as_secs())
+            .then_some(entry.pools)
+    }
+
+    pub(crate) fn insert_pools(&self, key: &str, pools: &[String]) {
+        let entry = CachedPools {
+            cached_at: unix_now(),
+            pools: pools.to_vec(),
+        };
+        write(&self.pools, key.as_bytes(), &entry);
+    }
+
+    /// Mint infos of `mints` when every one of them is cached
+    pub(crate) fn get_mint_infos(&self, mints: &[Pubkey]) -> Option<Vec<MintInfo>> {
+        mints
+            .iter()
+            .map(|mint| {
+                let cached: CachedMint = read(&self.mints, mint.as_ref())?;
+                Some(MintInfo {
+                    mint: *mint,
+                    token_program: cached.token_program,
+                    decimals: cached.decimals,
+                    transfer_fee: None,
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn insert_mint_infos(&self, infos: &[MintInfo]) {
+        for info in infos.iter().filter(|info| !info.is_token_2022()) {
+            let cached = CachedMint {
+                token_program: info.token_program,
+                decimals: info.decimals,
+            };
+            write(&self.mints, info.mint.as_ref(), &cached);
+        }
+    }
+
+    pub(crate) fn get_daily_stats(
+        &self,
+        pool_address: &str,
+        day_start: i64,
+    ) -> Option<PoolDailyStats> {
+        read(
+            &self.daily_stats,
+            daily_stats_key(pool_address, day_start).as_bytes(),
+        )
+    }
+
+    /// Stores a finished day of pool statistics; past days never change, so they do not expire
+    pub(crate) fn insert_daily_stats(&self, pool_address: &str, stats: &PoolDailyStats) {
+        write(
+            &self.daily_stats,
+            daily_stats_key(pool_address, stats.day_start as i64).as_bytes(),
+            stats,
+        );
+    }
+}
+
+fn daily_stats_key(pool_address: &str, day_start: i64) -> String {
+    format!("{}:{}", pool_address, day_start)
+}
+
+impl OrcaClient {
+    /// Returns the persistent cache configured on the builder, if any
+    pub fn persistent_cache(&self) -> Option<&PersistentCache> {
+        self.persistent_cache.as_ref()
+    }
+}
+
+fn read<T: DeserializeOwned>(tree: &sled::Tree, key: &[u8]) -> Option<T> {
+    let value = tree.get(key).ok()??;
+    serde_json::from_slice(&value).ok()
+}
+
+// The cache is an optimization; entries that fail to persist are rediscovered next time
+fn write<T: Serialize>(tree: &sled::Tree, key: &[u8], value: &T) {
+    let Ok(bytes) = serde_json::to_vec(value) else {
+        return;
+    };
+    if let Err(e) = tree.insert(key, bytes) {
+        tracing::warn!("Failed to write persistent cache entry: {}", e);
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
@@ -0,0 +1,380 @@
+use crate::{
+    fixed::{
+        amount_a_delta, amount_b_delta, mul_div_ceil, mul_div_floor, next_sqrt_price,
+        sqrt_price_x64_at_tick,
+    },
+    global::{FEE_RATE_DENOMINATOR, MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64},
+    pool::PoolInfo,
+    tick::SwapTickRange,
+};
+
+/// Outcome of walking a swap through a pool's liquidity
+#[derive(Debug, Clone)]
+pub(crate) struct SwapSimulation {
+    /// Input consumed, fee included
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee_amount: u64,
+    pub ticks_crossed: usize,
+    /// Pool sqrt price once the swap completed, Q64.64
+    pub sqrt_price: u128,
+    /// False when the tick range ran out before the specified amount was consumed
+    pub filled: bool,
+}
+
+/// One swap step within constant liquidity
+#[derive(Debug, Clone, Copy)]
+struct SwapStep {
+    next_sqrt_price: u128,
+    /// Input consumed, fee excluded
+    amount_in: u64,
+    amount_out: u64,
+    fee_amount: u64,
+}
+
+/// Simulates a swap the way the Whirlpool program executes it
+///
+/// `amount` is the exact input when `amount_specified_is_input` is set and the exact output
+/// otherwise. The swap is split into steps between initialized ticks. Each step charges the fee
+/// on the input, moves the price within constant liquidity, and crossing a tick adds or removes
+/// its `liquidity_net`. All amounts and prices use the program's integer math and rounding.
+pub(crate) fn simulate_swap(
+    pool: &PoolInfo,
+    tick_range: &SwapTickRange,
+    amount: u64,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+) -> SwapSimulation {
+    let boundary = sqrt_price_x64_at_tick(tick_range.boundary_tick_index);
+    let boundary = if a_to_b {
+        boundary.max(MIN_SQRT_PRICE_X64)
+    } else {
+        boundary.min(MAX_SQRT_PRICE_X64)
+    };
+    let mut sqrt_price = pool.sqrt_price;
+    let mut liquidity = pool.liquidity;
+    let mut remaining = amount;
+    let mut amount_in: u64 = 0;
+    let mut amount_out: u64 = 0;
+    let mut fee_amount: u64 = 0;
+    let mut ticks = tick_range.ticks.iter().peekable();
+    let mut ticks_crossed = 0;
+    while remaining > 0 {
+        let next_tick = ticks.peek().copied().filter(|tick| {
+            if a_to_b {
+                tick.index >= tick_range.boundary_tick_index
+            } else {
+                tick.index <= tick_range.boundary_tick_index
+            }
+        });
+        let target = next_tick.map_or(boundary, |tick| sqrt_price_x64_at_tick(tick.index));
+        let Some(step) = compute_swap_step(
+            remaining,
+            pool.fee_rate,
+            liquidity,
+            sqrt_price,
+            target,
+            amount_specified_is_input,
+            a_to_b,
+        ) else {
+            break;
+        };
+        amount_in = amount_in.saturating_add(step.amount_in.saturating_add(step.fee_amount));
+        amount_out = amount_out.saturating_add(step.amount_out);
+        fee_amount = fee_amount.saturating_add(step.fee_amount);
+        remaining = remaining.saturating_sub(if amount_specified_is_input {
+            step.amount_in.saturating_add(step.fee_amount)
+        } else {
+            step.amount_out
+        });
+        sqrt_price = step.next_sqrt_price;
+        if sqrt_price != target {
+            break;
+        }
+        match next_tick {
+            Some(tick) => {
+                let liquidity_net = if a_to_b {
+                    -tick.liquidity_net
+                } else {
+                    tick.liquidity_net
+                };
+                liquidity = liquidity.saturating_add_signed(liquidity_net);
+                ticks_crossed += 1;
+                ticks.next();
+            }
+            None => break,
+        }
+    }
+    SwapSimulation {
+        amount_in,
+        amount_out,
+        fee_amount,
+        ticks_crossed,
+        sqrt_price,
+        filled: remaining == 0,
+    }
+}
+
+/// Moves the price from `sqrt_price_current` toward `sqrt_price_target` with `amount_remaining`
+///
+/// Mirrors the Whirlpool program's `compute_swap`: the step stops at the target when the
+/// remaining amount covers it, and otherwise at the price the remaining amount reaches. Returns
+/// None when an amount overflows a u64.
+fn compute_swap_step(
+    amount_remaining: u64,
+    fee_rate: u16,
+    liquidity: u128,
+    sqrt_price_current: u128,
+    sqrt_price_target: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+) -> Option<SwapStep> {
+    let fee_rate = fee_rate as u128;
+    let fee_denominator = FEE_RATE_DENOMINATOR as u128;
+    let amount_calc = if amount_specified_is_input {
+        mul_div_floor(
+            amount_remaining as u128,
+            fee_denominator - fee_rate,
+            fee_denominator,
+        )? as u64
+    } else {
+        amount_remaining
+    };
+    let fixed_to_target = fixed_delta(
+        sqrt_price_current,
+        sqrt_price_target,
+        liquidity,
+        amount_specified_is_input,
+        a_to_b,
+    );
+    let next_sqrt_price = match fixed_to_target {
+        Some(delta) if delta <= amount_calc => sqrt_price_target,
+        _ => next_sqrt_price(
+            sqrt_price_current,
+            liquidity,
+            amount_calc,
+            amount_specified_is_input,
+            a_to_b,
+        )?,
+    };
+    let is_max_swap = next_sqrt_price == sqrt_price_target;
+    let amount_fixed = if is_max_swap {
+        fixed_to_target?
+    } else {
+        fixed_delta(
+            sqrt_price_current,
+            next_sqrt_price,
+            liquidity,
+            amount_specified_is_input,
+            a_to_b,
+        )?
+    };
+    let amount_unfixed = unfixed_delta(
+        sqrt_price_current,
+        next_sqrt_price,
+        liquidity,
+        amount_specified_is_input,
+        a_to_b,
+    )?;
+    let (amount_in, amount_out) = if amount_specified_is_input {
+        (amount_fixed, amount_unfixed)
+    } else {
+        (amount_unfixed, amount_fixed.min(amount_remaining))
+    };
+    let fee_amount = if amount_specified_is_input && !is_max_swap {
+        amount_remaining - amount_in
+    } else {
+        mul_div_ceil(amount_in as u128, fee_rate, fee_denominator - fee_rate)? as u64
+    };
+    Some(SwapStep {
+        next_sqrt_price,
+        amount_in,
+        amount_out,
+        fee_amount,
+    })
+}
+
+/// Change of the specified token (input for exact-input swaps, output otherwise)
+fn fixed_delta(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    liquidity: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+) -> Option<u64> {
+    if a_to_b == amount_specified_is_input {
+        amount_a_delta(
+            sqrt_price_0,
+            sqrt_price_1,
+            liquidity,
+            amount_specified_is_input,
+        )
+    } else {
+        amount_b_delta(
+            sqrt_price_0,
+            sqrt_price_1,
+            liquidity,
+            amount_specified_is_input,
+        )
+    }
+}
+
+/// Change of the other token, rounded against the trader
+fn unfixed_delta(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    liquidity: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+) -> Option<u64> {
+    if a_to_b == amount_specified_is_input {
+        amount_b_delta(
+            sqrt_price_0,
+            sqrt_price_1,
+            liquidity,
+            !amount_specified_is_input,
+        )
+    } else {
+        amount_a_delta(
+            sqrt_price_0,
+            sqrt_price_1,
+            liquidity,
+            !amount_specified_is_input,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tick::Tick;
+
+    const Q64: u128 = 1 << 64;
+    const LIQUIDITY: u128 = 1_000_000_000_000;
+    const FEE_RATE: u16 = 3000;
+    const SQRT_PRICE_TICK_64: u128 = 18505865242158250041;
+
+    fn pool_at_tick_zero() -> PoolInfo {
+        PoolInfo {
+            fee_rate: FEE_RATE,
+            liquidity: LIQUIDITY,
+            sqrt_price: Q64,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn exact_in_step_within_range() {
+        let step = compute_swap_step(
+            1_000_000,
+            FEE_RATE,
+            LIQUIDITY,
+            Q64,
+            SQRT_PRICE_TICK_64,
+            true,
+            false,
+        )
+        .unwrap();
+        assert_eq!(step.next_sqrt_price, 18446762465113393104);
+        assert_eq!(step.amount_in, 997_000);
+        assert_eq!(step.amount_out, 996_999);
+        assert_eq!(step.fee_amount, 3_000);
+    }
+
+    #[test]
+    fn exact_out_step_within_range() {
+        let step = compute_swap_step(
+            500_000,
+            FEE_RATE,
+            LIQUIDITY,
+            Q64,
+            MIN_SQRT_PRICE_X64,
+            false,
+            true,
+        )
+        .unwrap();
+        assert_eq!(step.next_sqrt_price, 18446734850337514761);
+        assert_eq!(step.amount_in, 500_001);
+        assert_eq!(step.amount_out, 500_000);
+        assert_eq!(step.fee_amount, 1_505);
+    }
+
+    #[test]
+    fn step_stops_at_target() {
+        let step = compute_swap_step(
+            10_000_000_000,
+            FEE_RATE,
+            LIQUIDITY,
+            Q64,
+            SQRT_PRICE_TICK_64,
+            true,
+            false,
+        )
+        .unwrap();
+        assert_eq!(step.next_sqrt_price, SQRT_PRICE_TICK_64);
+        assert_eq!(step.amount_in, 3_204_964_964);
+        assert_eq!(step.amount_out, 3_194_725_978);
+        assert_eq!(step.fee_amount, 9_643_827);
+    }
+
+    #[test]
+    fn swap_within_one_tick_matches_single_step() {
+        let tick_range = SwapTickRange {
+            ticks: Vec::new(),
+            boundary_tick_index: 64,
+        };
+        let simulation = simulate_swap(&pool_at_tick_zero(), &tick_range, 1_000_000, true, false);
+        assert!(simulation.filled);
+        assert_eq!(simulation.ticks_crossed, 0);
+        assert_eq!(simulation.amount_in, 1_000_000);
+        assert_eq!(simulation.amount_out, 996_999);
+        assert_eq!(simulation.fee_amount, 3_000);
+        assert_eq!(simulation.sqrt_price, 18446762465113393104);
+    }
+
+    #[test]
+    fn swap_crosses_initialized_tick() {
+        let tick_range = SwapTickRange {
+            ticks: vec![Tick {
+                index: 64,
+                initialized: true,
+                liquidity_net: LIQUIDITY as i128,
+                ..Default::default()
+            }],
+            boundary_tick_index: 443_584,
+        };
+        let simulation = simulate_swap(
+            &pool_at_tick_zero(),
+            &tick_range,
+            10_000_000_000,
+            true,
+            false,
+        );
+        assert!(simulation.filled);
+        assert_eq!(simulation.ticks_crossed, 1);
+        assert_eq!(simulation.amount_in, 10_000_000_000);
+        // 3_194_725_978 up to tick 64, then 6_699_291_138 with the doubled liquidity
+        assert_eq!(simulation.amount_out, 9_894_017_116);
+        assert_eq!(simulation.fee_amount, 30_000_001);
+        assert_eq!(simulation.sqrt_price, 18568261677128411910);
+    }
+
+    #[test]
+    fn swap_stops_at_tick_range_boundary() {
+        let tick_range = SwapTickRange {
+            ticks: Vec::new(),
+            boundary_tick_index: 64,
+        };
+        let simulation = simulate_swap(
+            &pool_at_tick_zero(),
+            &tick_range,
+            10_000_000_000,
+            true,
+            false,
+        );
+        assert!(!simulation.filled);
+        assert_eq!(simulation.sqrt_price, SQRT_PRICE_TICK_64);
+        assert_eq!(simulation.amount_in, 3_204_964_964 + 9_643_827);
+        assert_eq!(simulation.amount_out, 3_194_725_978);
+    }
+}
@@ -0,0 +1,266 @@
+use super::*;
+use crate::{
+    audit::AuditDetails,
+    liquidity::LiquidityPosition,
+    packer::{
+        COLLECT_FEES_COMPUTE_UNITS, COLLECT_REWARD_COMPUTE_UNITS,
+        CREATE_TOKEN_ACCOUNT_COMPUTE_UNITS, InstructionGroup, PackedSubmission, TransactionPacker,
+    },
+    pool::PoolInfo,
+    receipt::CompoundReceipt,
+    tick::tick_array_start_index,
+    types::OrcaResult,
+    validation::parse_address,
+    zap::{ZapConfig, ZapTarget},
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+/// Configuration for compounding a position
+#[derive(Debug, Clone, Default)]
+pub struct CompoundConfig {
+    /// Also claim the pool's rewards; rewards paid in token A or B are compounded as well
+    pub collect_rewards: bool,
+    /// Slippage, fees and submission of the collection and the re-deposit
+    pub zap: ZapConfig,
+}
+
+impl OrcaClient {
+    /// Collects a position's fees and deposits them back into the same position
+    ///
+    /// The fees (and rewards when configured) are collected first. The amounts that collection
+    /// actually moved are then balanced for the position's range with one swap and added as
+    /// liquidity, in a second transaction. When the collection cannot be read back, such as in
+    /// dry runs, the fees owed recorded on the position are used instead. A dry run fails
+    /// with `OrcaError::DryRun` holding the simulations of both transactions. No deposit is made
+    /// when the collected amounts are too small to add liquidity.
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::compound::CompoundConfig;
+    ///
+    /// let config = CompoundConfig { collect_rewards: true, ..Default::default() };
+    /// for position in client.get_liquidity_positions(&keypair.pubkey()).await? {
+    ///     let receipt = client.compound_position(&keypair, &position, Some(config.clone())).await?;
+    ///     println!("{} +{} liquidity", receipt.position_mint, receipt.liquidity_added);
+    /// }
+    /// ```
+    #[tracing::instrument(
+        skip(self, signer, position, config),
+        fields(
+            owner = %signer.pubkey(),
+            pool = %position.pool_address,
+            position_mint = %position.position_mint,
+        ),
+        err(Debug)
+    )]
+    pub async fn compound_position<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        position: &LiquidityPosition,
+        config: Option<CompoundConfig>,
+    ) -> OrcaResult<CompoundReceipt> {
+        self.with_timeout("compound position", async {
+            let config = config.unwrap_or_default();
+            let owner = signer.pubkey();
+            let pool = self
+                .get_pool_state_onchain(&position.pool_address.to_string())
+                .await?;
+            self.check_pool_policy(&pool.address)?;
+            let token_a_mint = parse_address("token_mint_a", &pool.token_mint_a)?;
+            let token_b_mint = parse_address("token_mint_b", &pool.token_mint_b)?;
+            let compute_budget = self.build_compute_budget_instructions(
+                config.zap.compute_unit_limit,
+                config.zap.priority_fee_microlamports,
+            )?;
+            let collect = self
+                .build_collect_position_ixs(&owner, &pool, position, config.collect_rewards)
+                .await?;
+            // In dry-run mode the deposit is still simulated, from the fees owed on the position
+            let (collect_signature, mut dry_run_outcomes) = match self
+                .submit_packed(
+                    signer,
+                    &[],
+                    PackedSubmission {
+                        prefix: &compute_budget,
+                        groups: std::slice::from_ref(&collect),
+                        packer: &TransactionPacker::default(),
+                        mode: &config.zap.submit_mode,
+                    },
+                    "collect fees",
+                    &AuditDetails::new(serde_json::json!({
+                        "pool": pool.address,
+                        "position_mint": position.position_mint.to_string(),
+                        "collect_rewards": config.collect_rewards,
+                    })),
+                )
+                .await
+            {
+                Ok(signatures) => (
+                    signatures
+                        .last()
+                        .copied()
+                        .ok_or(OrcaError::Error("No transaction was submitted".to_string()))?,
+                    Vec::new(),
+                ),
+                Err(OrcaError::DryRun(outcomes)) => (Signature::default(), outcomes),
+                Err(e) => return Err(e),
+            };
+            let (collected_a, collected_b) = match self
+                .settle_liquidity(&collect_signature, &owner, &token_a_mint, &token_b_mint)
+                .await
+            {
+                Some(settlement) => (settlement.token_a_amount, settlement.token_b_amount),
+                None => (position.fee_owed_a, position.fee_owed_b),
+            };
+            let quote = self
+                .quote_balanced_deposit(
+                    &pool,
+                    None,
+                    (collected_a, collected_b),
+                    position.lower_tick,
+                    position.upper_tick,
+                    config.zap.slippage_bps,
+                )
+                .await?;
+            let mut receipt = CompoundReceipt {
+                collect_signature,
+                deposit_signature: None,
+                pool: position.pool_address,
+                position_mint: position.position_mint,
+                position_address: position.position_address,
+                collected_a,
+                collected_b,
+                swap_mint: quote.swap_mint,
+                swap_amount: 0,
+                liquidity_added: 0,
+            };
+            if quote.liquidity == 0 {
+                tracing::info!("Collected amounts are too small to compound");
+                if !dry_run_outcomes.is_empty() {
+                    return Err(OrcaError::DryRun(dry_run_outcomes));
+                }
+                return Ok(receipt);
+            }
+            let spends = [
+                (pool.token_mint_a.as_str(), quote.token_max_a),
+                (pool.token_mint_b.as_str(), quote.token_max_b),
+            ];
+            let reservation = self.reserve_spend(&spends).await?;
+            let deposit = self
+                .build_zap_in_ixs(
+                    &owner,
+                    &pool,
+                    &quote,
+                    &ZapTarget::Position(Box::new(position.clone())),
+                )
+                .await?;
+            let deposit_signature = match self
+                .submit_packed(
+                    signer,
+                    &[],
+                    PackedSubmission {
+                        prefix: &compute_budget,
+                        groups: std::slice::from_ref(&deposit.group),
+                        packer: &TransactionPacker::default(),
+                        mode: &config.zap.submit_mode,
+                    },
+                    "compound position",
+                    &AuditDetails::new(serde_json::json!({
+                        "pool": pool.address,
+                        "position_mint": position.position_mint.to_string(),
+                        "swap_amount": quote.swap_amount,
+                        "min_swap_output": quote.min_swap_output,
+                        "liquidity": quote.liquidity.to_string(),
+                        "token_max_a": quote.token_max_a,
+                        "token_max_b": quote.token_max_b,
+                    })),
+                )
+                .await
+            {
+                Ok(signatures) => signatures
+                    .last()
+                    .copied()
+                    .ok_or(OrcaError::Error("No transaction was submitted".to_string()))?,
+                Err(OrcaError::DryRun(outcomes)) => {
+                    dry_run_outcomes.extend(outcomes);
+                    return Err(OrcaError::DryRun(dry_run_outcomes));
+                }
+                Err(e) => return Err(e),
+            };
+            reservation.commit();
+            receipt.deposit_signature = Some(deposit_signature);
+            receipt.swap_amount = quote.swap_amount;
+            receipt.liquidity_added = quote.liquidity;
+            Ok(receipt)
+        })
+        .await
+    }
+
+    /// Instructions refreshing and collecting a position's fees, and its rewards on request
+    async fn build_collect_position_ixs(
+        &self,
+        owner: &Pubkey,
+        pool: &PoolInfo,
+        position: &LiquidityPosition,
+        collect_rewards: bool,
+    ) -> OrcaResult<InstructionGroup> {
+        let mint_infos = self
+            .get_mint_infos(&[
+                parse_address("token_mint_a", &pool.token_mint_a)?,
+                parse_address("token_mint_b", &pool.token_mint_b)?,
+            ])
+            .await?;
+        let token_programs = (&mint_infos[0].token_program, &mint_infos[1].token_program);
+        let token_a_account = self.get_associated_token_address_with_program(
+            owner,
+            &mint_infos[0].mint,
+            token_programs.0,
+        );
+        let token_b_account = self.get_associated_token_address_with_program(
+            owner,
+            &mint_infos[1].mint,
+            token_programs.1,
+        );
+        // Claiming rewards refreshes the position first; otherwise refresh it here
+        let mut instructions = if collect_rewards {
+            self.build_collect_rewards_ixs(owner, position)
+                .await?
+                .instructions
+        } else {
+            Vec::new()
+        };
+        if instructions.is_empty() && position.lp_token_amount > 0 {
+            instructions.push(self.build_update_fees_and_rewards_instruction(
+                &position.pool_address,
+                &position.position_address,
+                &self.get_tick_array_address(
+                    &position.pool_address,
+                    tick_array_start_index(position.lower_tick, pool.tick_spacing),
+                ),
+                &self.get_tick_array_address(
+                    &position.pool_address,
+                    tick_array_start_index(position.upper_tick, pool.tick_spacing),
+                ),
+            ));
+        }
+        let mut compute_units = COLLECT_REWARD_COMPUTE_UNITS * instructions.len() as u32;
+        for info in &mint_infos {
+            instructions.push(create_associated_token_account_idempotent(
+                owner,
+                owner,
+                &info.mint,
+                &info.token_program,
+            ));
+            compute_units += CREATE_TOKEN_ACCOUNT_COMPUTE_UNITS;
+        }
+        instructions.push(self.build_collect_fees_instruction(
+            owner,
+            pool,
+            position,
+            (&token_a_account, &token_b_account),
+            token_programs,
+        )?);
+        compute_units += COLLECT_FEES_COMPUTE_UNITS;
+        Ok(InstructionGroup::new(instructions, compute_units))
+    }
+}
@@ -0,0 +1,380 @@
+use super::*;
+use crate::{
+    fixed::{
+        amount_a_delta, amount_b_delta, mul_div_ceil, sqrt_price_x64_at_tick,
+        tick_index_at_sqrt_price_x64,
+    },
+    global::{
+        FEE_RATE_DENOMINATOR, MAX_MULTIPLE_ACCOUNTS, MAX_SQRT_PRICE_X64, MAX_TICK_INDEX,
+        MIN_SQRT_PRICE_X64, MIN_TICK_INDEX, TICK_ARRAY_SIZE,
+    },
+    math::{sqrt_price_x64_to_price, tick_index_to_price, token_amounts_from_liquidity},
+    pool::PoolInfo,
+    tick::tick_array_start_index,
+    types::{InvalidInput, OrcaResult},
+    validation::parse_address,
+};
+
+/// Liquidity active across a band of ticks
+#[derive(Debug, Clone)]
+pub struct LiquidityLevel {
+    pub lower_tick: i32,
+    pub upper_tick: i32,
+    pub lower_price: f64,
+    pub upper_price: f64,
+    /// Liquidity active while the price is inside the band; for buckets, the average over the
+    /// band's ticks
+    pub liquidity: u128,
+    /// Token A the band holds at the current price (raw units), all of it above the price
+    pub amount_a: u64,
+    /// Token B the band holds at the current price (raw units), all of it below the price
+    pub amount_b: u64,
+}
+
+/// Liquidity of a pool over a tick range, for depth charts and slippage estimates
+///
+/// Levels are consecutive and ascending: every initialized tick inside the range starts a new
+/// level, so each level has constant liquidity. Prices are decimal-adjusted, token B per token A.
+#[derive(Debug, Clone)]
+pub struct LiquidityDistribution {
+    pub pool_address: Pubkey,
+    pub tick_current_index: i32,
+    /// Pool sqrt price the token amounts were computed at, Q64.64
+    pub sqrt_price: u128,
+    pub decimals_a: u8,
+    pub decimals_b: u8,
+    pub levels: Vec<LiquidityLevel>,
+}
+
+/// Swap needed to move a pool's price by a percentage, a market depth metric
+#[derive(Debug, Clone)]
+pub struct PriceMoveDepth {
+    /// Requested price change in percent; negative moves the price down
+    pub percent: f64,
+    pub current_price: f64,
+    pub target_price: f64,
+    /// True when token A is sold into the pool, which moves the price down
+    pub a_to_b: bool,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    /// Input needed to reach the target price, fee included (raw units)
+    pub input_amount: u64,
+    /// Output the swap pays out on the way (raw units)
+    pub output_amount: u64,
+}
+
+impl LiquidityDistribution {
+    /// Groups the levels into price bands of `bucket_ticks` ticks
+    ///
+    /// Bands are aligned to multiples of `bucket_ticks`; the first and last are cut at the
+    /// range. Token amounts are summed and liquidity is averaged over the ticks of each band.
+    ///
+    /// # Example
+    /// ```rust
+    /// let distribution = client.get_liquidity_distribution(&pool, -5_000, 5_000).await?;
+    /// for band in distribution.bucketed(100) {
+    ///     println!("{:.4}-{:.4}: {}", band.lower_price, band.upper_price, band.liquidity);
+    /// }
+    /// ```
+    pub fn bucketed(&self, bucket_ticks: u32) -> Vec<LiquidityLevel> {
+        let (Some(first), Some(last)) = (self.levels.first(), self.levels.last()) else {
+            return Vec::new();
+        };
+        let bucket_ticks = bucket_ticks.max(1) as i32;
+        let (start, end) = (first.lower_tick, last.upper_tick);
+        let mut buckets = Vec::new();
+        let mut lower = start.div_euclid(bucket_ticks) * bucket_ticks;
+        while lower < end {
+            let upper = (lower + bucket_ticks).min(end);
+            let band_lower = lower.max(start);
+            let mut weighted: u128 = 0;
+            let (mut amount_a, mut amount_b) = (0u64, 0u64);
+            for level in &self.levels {
+                let overlap_lower = level.lower_tick.max(band_lower);
+                let overlap_upper = level.upper_tick.min(upper);
+                if overlap_lower >= overlap_upper {
+                    continue;
+                }
+                weighted = weighted.saturating_add(
+                    level
+                        .liquidity
+                        .saturating_mul((overlap_upper - overlap_lower) as u128),
+                );
+                let (a, b) = token_amounts_from_liquidity(
+                    level.liquidity,
+                    self.sqrt_price,
+                    overlap_lower,
+                    overlap_upper,
+                    false,
+                );
+                amount_a = amount_a.saturating_add(a);
+                amount_b = amount_b.saturating_add(b);
+            }
+            buckets.push(self.level(
+                band_lower,
+                upper,
+                weighted / (upper - band_lower) as u128,
+                amount_a,
+                amount_b,
+            ));
+            lower = upper;
+        }
+        buckets
+    }
+
+    /// Input (fee excluded) and output of a swap moving the price to `target_sqrt_price`
+    ///
+    /// The levels must cover the whole way from the current price to the target.
+    pub(crate) fn swap_to_sqrt_price(&self, target_sqrt_price: u128) -> (u64, u64) {
+        let a_to_b = target_sqrt_price < self.sqrt_price;
+        let (mut amount_in, mut amount_out) = (0u64, 0u64);
+        for level in &self.levels {
+            let band_lower = sqrt_price_x64_at_tick(level.lower_tick);
+            let band_upper = sqrt_price_x64_at_tick(level.upper_tick);
+            let (from, to) = if a_to_b {
+                (
+                    band_lower.max(target_sqrt_price),
+                    band_upper.min(self.sqrt_price),
+                )
+            } else {
+                (
+                    band_lower.max(self.sqrt_price),
+                    band_upper.min(target_sqrt_price),
+                )
+            };
+            if from >= to || level.liquidity == 0 {
+                continue;
+            }
+            let (input, output) = if a_to_b {
+                (
+                    amount_a_delta(from, to, level.liquidity, true),
+                    amount_b_delta(from, to, level.liquidity, false),
+                )
+            } else {
+                (
+                    amount_b_delta(from, to, level.liquidity, true),
+                    amount_a_delta(from, to, level.liquidity, false),
+                )
+            };
+            amount_in = amount_in.saturating_add(input.unwrap_or(u64::MAX));
+            amount_out = amount_out.saturating_add(output.unwrap_or(u64::MAX));
+        }
+        (amount_in, amount_out)
+    }
+
+    fn level(
+        &self,
+        lower_tick: i32,
+        upper_tick: i32,
+        liquidity: u128,
+        amount_a: u64,
+        amount_b: u64,
+    ) -> LiquidityLevel {
+        LiquidityLevel {
+            lower_tick,
+            upper_tick,
+            lower_price: tick_index_to_price(lower_tick, self.decimals_a, self.decimals_b),
+            upper_price: tick_index_to_price(upper_tick, self.decimals_a, self.decimals_b),
+            liquidity,
+            amount_a,
+            amount_b,
+        }
+    }
+}
+
+impl OrcaClient {
+    /// Walks a pool's tick arrays and returns the liquidity of every band of a tick range
+    ///
+    /// The tick arrays between the current tick and both ends of the range are fetched in one
+    /// call, which limits the span to 100 tick arrays. Active liquidity is derived from the
+    /// pool's current liquidity and the `liquidity_net` of the initialized ticks crossed.
+    ///
+    /// # Params
+    /// pool - The pool to read
+    /// lower_tick - Lower end of the range, clamped to the tick bounds
+    /// upper_tick - Upper end of the range, clamped to the tick bounds
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool = client.get_pool_state_onchain("whirlpool_address_here").await?;
+    /// let lower = pool.tick_current_index - 2_000;
+    /// let upper = pool.tick_current_index + 2_000;
+    /// let distribution = client.get_liquidity_distribution(&pool, lower, upper).await?;
+    /// for level in &distribution.levels {
+    ///     println!("{}..{}: {}", level.lower_tick, level.upper_tick, level.liquidity);
+    /// }
+    /// ```
+    #[tracing::instrument(skip(self, pool), fields(pool = %pool.address), err(Debug))]
+    pub async fn get_liquidity_distribution(
+        &self,
+        pool: &PoolInfo,
+        lower_tick: i32,
+        upper_tick: i32,
+    ) -> OrcaResult<LiquidityDistribution> {
+        self.with_timeout("get liquidity distribution", async {
+            let lower_tick = lower_tick.clamp(MIN_TICK_INDEX, MAX_TICK_INDEX);
+            let upper_tick = upper_tick.clamp(MIN_TICK_INDEX, MAX_TICK_INDEX);
+            if lower_tick >= upper_tick {
+                return Err(OrcaError::InvalidInput(InvalidInput::InvalidTickRange {
+                    lower_tick,
+                    upper_tick,
+                }));
+            }
+            let current = pool.tick_current_index;
+            let ticks_in_array = TICK_ARRAY_SIZE * pool.tick_spacing as i32;
+            let first_start = tick_array_start_index(lower_tick.min(current), pool.tick_spacing);
+            let last_start = tick_array_start_index(upper_tick.max(current), pool.tick_spacing);
+            let array_count = ((last_start - first_start) / ticks_in_array + 1) as usize;
+            if array_count > MAX_MULTIPLE_ACCOUNTS {
+                return Err(OrcaError::InvalidInput(InvalidInput::ValueOutOfRange {
+                    field: "tick arrays spanned".to_string(),
+                    value: array_count as u64,
+                    min: 1,
+                    max: MAX_MULTIPLE_ACCOUNTS as u64,
+                }));
+            }
+            let starts: Vec<i32> = (0..array_count as i32)
+                .map(|step| first_start + step * ticks_in_array)
+                .collect();
+            let mut ticks: Vec<(i32, i128)> = self
+                .get_tick_arrays(pool, &starts)
+                .await?
+                .iter()
+                .flatten()
+                .flat_map(|array| array.initialized_ticks())
+                .map(|tick| (tick.index, tick.liquidity_net))
+                .collect();
+            ticks.sort_by_key(|(index, _)| *index);
+            // Liquidity is active from a tick upward, so the liquidity at `lower_tick` leaves out
+            // the ticks above it up to the current tick and adds those above the current tick
+            let mut liquidity = pool.liquidity;
+            for &(index, net) in &ticks {
+                if index > lower_tick && index <= current {
+                    liquidity = liquidity.saturating_add_signed(-net);
+                } else if index <= lower_tick && index > current {
+                    liquidity = liquidity.saturating_add_signed(net);
+                }
+            }
+            let mints = [
+                parse_address("token_mint_a", &pool.token_mint_a)?,
+                parse_address("token_mint_b", &pool.token_mint_b)?,
+            ];
+            let decimals = self.get_mint_decimals(&mints).await?;
+            let mut distribution = LiquidityDistribution {
+                pool_address: parse_address("pool_address", &pool.address)?,
+                tick_current_index: current,
+                sqrt_price: pool.sqrt_price,
+                decimals_a: decimals[0],
+                decimals_b: decimals[1],
+                levels: Vec::new(),
+            };
+            let mut levels = Vec::new();
+            let mut band_lower = lower_tick;
+            for &(index, net) in ticks
+                .iter()
+                .filter(|(index, _)| *index > lower_tick && *index < upper_tick)
+            {
+                levels.push((band_lower, index, liquidity));
+                liquidity = liquidity.saturating_add_signed(net);
+                band_lower = index;
+            }
+            levels.push((band_lower, upper_tick, liquidity));
+            distribution.levels = levels
+                .into_iter()
+                .map(|(lower, upper, liquidity)| {
+                    let (amount_a, amount_b) = token_amounts_from_liquidity(
+                        liquidity,
+                        pool.sqrt_price,
+                        lower,
+                        upper,
+                        false,
+                    );
+                    distribution.level(lower, upper, liquidity, amount_a, amount_b)
+                })
+                .collect();
+            Ok(distribution)
+        })
+        .await
+    }
+
+    /// Computes how much must be swapped into a pool to move its price by `percent`
+    ///
+    /// A positive `percent` buys token A with token B and moves the price up; a negative one
+    /// sells token A and moves it down. The swap is walked through the liquidity distribution
+    /// between the current and the target price, and the pool fee is added to the input.
+    ///
+    /// # Params
+    /// pool - The pool to measure
+    /// percent - Price change in percent, above -100 and not zero
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool = client.get_pool_state_onchain("whirlpool_address_here").await?;
+    /// let up = client.get_amount_to_move_price(&pool, 2.0).await?;
+    /// let down = client.get_amount_to_move_price(&pool, -2.0).await?;
+    /// println!("+2%: {} B in, -2%: {} A in", up.input_amount, down.input_amount);
+    /// ```
+    #[tracing::instrument(skip(self, pool), fields(pool = %pool.address), err(Debug))]
+    pub async fn get_amount_to_move_price(
+        &self,
+        pool: &PoolInfo,
+        percent: f64,
+    ) -> OrcaResult<PriceMoveDepth> {
+        self.with_timeout("get amount to move price", async {
+            if !percent.is_finite() || percent <= -100.0 || percent == 0.0 {
+                return Err(OrcaError::InvalidInput(InvalidInput::InvalidThreshold {
+                    field: "percent".to_string(),
+                    value: percent,
+                }));
+            }
+            let target_sqrt_price = ((pool.sqrt_price as f64 * (1.0 + percent / 100.0).sqrt())
+                as u128)
+                .clamp(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64);
+            let target_tick = tick_index_at_sqrt_price_x64(target_sqrt_price);
+            let current = pool.tick_current_index;
+            let a_to_b = percent < 0.0;
+            let (lower_tick, upper_tick) = if a_to_b {
+                (target_tick, current + 1)
+            } else {
+                (current, target_tick.max(current) + 1)
+            };
+            let distribution = self
+                .get_liquidity_distribution(pool, lower_tick, upper_tick)
+                .await?;
+            let (amount_in, output_amount) = distribution.swap_to_sqrt_price(target_sqrt_price);
+            let fee_rate = pool.fee_rate as u128;
+            let fee = mul_div_ceil(
+                amount_in as u128,
+                fee_rate,
+                FEE_RATE_DENOMINATOR as u128 - fee_rate,
+            )
+            .unwrap_or(u64::MAX as u128)
+            .min(u64::MAX as u128) as u64;
+            let mint_a = parse_address("token_mint_a", &pool.token_mint_a)?;
+            let mint_b = parse_address("token_mint_b", &pool.token_mint_b)?;
+            let (input_mint, output_mint) = if a_to_b {
+                (mint_a, mint_b)
+            } else {
+                (mint_b, mint_a)
+            };
+            let price = |sqrt_price| {
+                sqrt_price_x64_to_price(
+                    sqrt_price,
+                    distribution.decimals_a,
+                    distribution.decimals_b,
+                )
+            };
+            Ok(PriceMoveDepth {
+                percent,
+                current_price: price(pool.sqrt_price),
+                target_price: price(target_sqrt_price),
+                a_to_b,
+                input_mint,
+                output_mint,
+                input_amount: amount_in.saturating_add(fee),
+                output_amount,
+            })
+        })
+        .await
+    }
+}
@@ -1,8 +1,140 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    str::FromStr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
 
+use base64::{Engine, prelude::BASE64_STANDARD};
+use futures::{Stream, StreamExt};
+use solana_client::{
+    nonblocking::pubsub_client::{PubsubClient, PubsubClientError},
+    rpc_config::{RpcTransactionConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
+use solana_transaction_status::{
+    UiInnerInstructions, UiInstruction, UiTransactionEncoding, UiTransactionStatusMeta,
+    option_serializer::OptionSerializer,
+};
 use tokio::sync::mpsc;
 
-use crate::{OrcaClient, types::OrcaResult};
+use crate::{
+    OrcaClient,
+    global::{
+        WHIRLPOOL_CLOSE_POSITION_DISCRIMINATOR,
+        WHIRLPOOL_CLOSE_POSITION_WITH_TOKEN_EXTENSIONS_DISCRIMINATOR,
+        WHIRLPOOL_COLLECT_FEES_DISCRIMINATOR, WHIRLPOOL_COLLECT_FEES_V2_DISCRIMINATOR,
+        WHIRLPOOL_DECREASE_LIQUIDITY_DISCRIMINATOR, WHIRLPOOL_DECREASE_LIQUIDITY_V2_DISCRIMINATOR,
+        WHIRLPOOL_INCREASE_LIQUIDITY_DISCRIMINATOR, WHIRLPOOL_INCREASE_LIQUIDITY_V2_DISCRIMINATOR,
+        WHIRLPOOL_OPEN_POSITION_DISCRIMINATOR, WHIRLPOOL_OPEN_POSITION_WITH_METADATA_DISCRIMINATOR,
+        WHIRLPOOL_OPEN_POSITION_WITH_TOKEN_EXTENSIONS_DISCRIMINATOR, WHIRLPOOL_SWAP_DISCRIMINATOR,
+        WHIRLPOOL_SWAP_V2_DISCRIMINATOR, WHIRLPOOL_TRADED_EVENT_DISCRIMINATOR,
+    },
+    layout::AccountReader,
+    token::TOKEN_2022_PROGRAM,
+    types::{OrcaError, OrcaResult},
+    validation::parse_address,
+};
+
+// Swap events buffered before the subscription waits for the consumer
+const SWAP_EVENT_BUFFER: usize = 1_024;
+// Delay before reconnecting a dropped log subscription
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(2);
+// Price updates buffered before a monitor waits for the consumer
+const PRICE_UPDATE_BUFFER: usize = 1_024;
+
+/// A swap executed by the Whirlpool program, decoded from its `Traded` event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapEvent {
+    pub signature: Signature,
+    pub slot: u64,
+    pub pool: Pubkey,
+    /// True when token A was sold for token B
+    pub a_to_b: bool,
+    pub pre_sqrt_price: u128,
+    pub post_sqrt_price: u128,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub input_transfer_fee: u64,
+    pub output_transfer_fee: u64,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+}
+
+/// A Whirlpool instruction decoded from a confirmed transaction
+///
+/// Token amounts are the transfers the instruction actually made, read from its inner
+/// instructions, not the limits it was called with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WhirlpoolEvent {
+    Swap {
+        pool: Pubkey,
+        owner: Pubkey,
+        /// True when token A was sold for token B
+        a_to_b: bool,
+        input_amount: u64,
+        output_amount: u64,
+    },
+    IncreaseLiquidity {
+        pool: Pubkey,
+        position: Pubkey,
+        owner: Pubkey,
+        liquidity: u128,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    },
+    DecreaseLiquidity {
+        pool: Pubkey,
+        position: Pubkey,
+        owner: Pubkey,
+        liquidity: u128,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    },
+    CollectFees {
+        pool: Pubkey,
+        position: Pubkey,
+        owner: Pubkey,
+        fee_a: u64,
+        fee_b: u64,
+    },
+    OpenPosition {
+        pool: Pubkey,
+        position: Pubkey,
+        position_mint: Pubkey,
+        owner: Pubkey,
+        lower_tick: i32,
+        upper_tick: i32,
+    },
+    ClosePosition {
+        position: Pubkey,
+        position_mint: Pubkey,
+        owner: Pubkey,
+        /// Account that received the position's rent
+        receiver: Pubkey,
+    },
+}
+
+/// An instruction with its accounts resolved, top-level or inner
+struct ResolvedInstruction {
+    program_id: Pubkey,
+    accounts: Vec<Pubkey>,
+    data: Vec<u8>,
+    /// 1 for top-level instructions, one more for each level of CPI
+    stack_height: u32,
+}
+
+struct TokenTransfer {
+    source: Pubkey,
+    destination: Pubkey,
+    amount: u64,
+}
 
 impl OrcaClient {
     /// Monitors price changes for a given pool with production-ready error handling and configurable thresholds.
@@ -37,74 +169,455 @@ impl OrcaClient {
         min_change_percent: f64,
         callback: F,
     ) -> OrcaResult<PriceMonitorHandle>
+    where
+        F: Fn(PriceUpdate) + Send + Sync + 'static,
+    {
+        self.monitor_price_changes_production_with_config(
+            pool_address,
+            min_change_percent,
+            MonitorConfig::default(),
+            callback,
+        )
+        .await
+    }
+
+    /// Like `monitor_price_changes_production`, polling as described by `config`
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::events::MonitorConfig;
+    /// use std::time::Duration;
+    ///
+    /// let config = MonitorConfig {
+    ///     poll_interval: Duration::from_secs(1),
+    ///     ..MonitorConfig::default()
+    /// };
+    /// let monitor_handle = client
+    ///     .monitor_price_changes_production_with_config("POOL_ADDRESS_HERE", 1.0, config, |update| {
+    ///         println!("Price changed: {}%", update.change_percent);
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn monitor_price_changes_production_with_config<F>(
+        self: Arc<Self>,
+        pool_address: &str,
+        min_change_percent: f64,
+        config: MonitorConfig,
+        callback: F,
+    ) -> OrcaResult<PriceMonitorHandle>
     where
         F: Fn(PriceUpdate) + Send + Sync + 'static,
     {
         let (shutdown_tx, mut shutdown_rx) = mpsc::channel(1);
         let client = self;
         let pool_address = pool_address.to_string();
+        let state = Arc::new(MonitorState::default());
+        let monitor_state = state.clone();
         let handle = tokio::spawn(async move {
-            let mut last_price: Option<f64> = None;
+            let (update_tx, mut update_rx) = mpsc::channel(PRICE_UPDATE_BUFFER);
+            let monitor = client.run_price_monitor(
+                pool_address.clone(),
+                min_change_percent,
+                config,
+                monitor_state,
+                update_tx,
+            );
+            let forward = async move {
+                while let Some(update) = update_rx.recv().await {
+                    callback(update);
+                }
+            };
+            tokio::select! {
+                _ = async { tokio::join!(monitor, forward) } => {}
+                _ = shutdown_rx.recv() => {
+                    tracing::info!("Price monitor for {} shutting down", pool_address);
+                }
+            }
+        });
+
+        Ok(PriceMonitorHandle {
+            shutdown_tx,
+            task_handle: handle,
+            state,
+        })
+    }
+
+    /// Monitors price changes for a given pool, delivering them as a stream
+    ///
+    /// Polls like `monitor_price_changes_production`, but updates are consumed from the returned
+    /// `PriceUpdateStream`, which also implements `futures::Stream`. Polling pauses while the
+    /// consumer lags behind, and dropping the stream stops the monitor.
+    ///
+    /// # Params
+    /// pool_address - The address of the liquidity pool to monitor
+    /// min_change_percent - Minimum percentage change required to emit an update
+    /// config - Polling interval, error handling and commitment
+    ///
+    /// # Example
+    /// ```rust
+    /// use futures::StreamExt;
+    /// use std::sync::Arc;
+    ///
+    /// let client = Arc::new(OrcaClient::new()?);
+    /// let mut updates = client
+    ///     .monitor_price_changes("POOL_ADDRESS_HERE", 1.0, MonitorConfig::default())?
+    ///     .filter(|update| futures::future::ready(update.new_price > update.old_price));
+    /// while let Some(update) = updates.next().await {
+    ///     println!("Price rose {}%", update.change_percent);
+    /// }
+    /// ```
+    pub fn monitor_price_changes(
+        self: Arc<Self>,
+        pool_address: &str,
+        min_change_percent: f64,
+        config: MonitorConfig,
+    ) -> OrcaResult<PriceUpdateStream> {
+        parse_address("pool_address", pool_address)?;
+        let (update_tx, update_rx) = mpsc::channel(PRICE_UPDATE_BUFFER);
+        let task_handle = tokio::spawn(self.run_price_monitor(
+            pool_address.to_string(),
+            min_change_percent,
+            config,
+            Arc::new(MonitorState::default()),
+            update_tx,
+        ));
+        Ok(PriceUpdateStream {
+            update_rx,
+            task_handle,
+        })
+    }
+
+    /// Polls a pool's price and sends significant changes until the receiver goes away
+    async fn run_price_monitor(
+        self: Arc<Self>,
+        pool_address: String,
+        min_change_percent: f64,
+        config: MonitorConfig,
+        state: Arc<MonitorState>,
+        update_tx: mpsc::Sender<PriceUpdate>,
+    ) {
+        let mut last_price: Option<f64> = None;
+        let mut consecutive_errors = 0;
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(config.next_poll_delay()) => {}
+                _ = update_tx.closed() => break,
+            }
+            if state.paused.load(Ordering::SeqCst) {
+                continue;
+            }
+            match Self::get_current_price_impl(&self, &pool_address, config.commitment).await {
+                Ok(current_price) => {
+                    consecutive_errors = 0;
+                    if let Ok(mut last_update) = state.last_update.lock() {
+                        *last_update = Some(chrono::Utc::now());
+                    }
+                    if let Some(prev_price) = last_price
+                        && prev_price > 0.0
+                    {
+                        let change_percent =
+                            ((current_price - prev_price) / prev_price).abs() * 100.0;
+                        if change_percent >= min_change_percent {
+                            let update = PriceUpdate {
+                                pool_address: pool_address.clone(),
+                                old_price: prev_price,
+                                new_price: current_price,
+                                change_percent,
+                                timestamp: chrono::Utc::now(),
+                            };
+                            if update_tx.send(update).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    last_price = Some(current_price);
+                }
+                Err(_e) => {
+                    consecutive_errors += 1;
+                    if consecutive_errors >= config.max_consecutive_errors {
+                        tracing::error!(
+                            "Too many consecutive errors, shutting down monitor for {}",
+                            pool_address
+                        );
+                        break;
+                    }
+                    tokio::time::sleep(config.backoff.delay(consecutive_errors)).await;
+                }
+            }
+        }
+    }
+
+    /// Monitors the prices of many pools from a single task
+    ///
+    /// On every poll all monitored pools are fetched in one batched request, and a
+    /// `PriceUpdate` is sent for each pool whose price moved by at least `min_change_percent`
+    /// since the previous poll. Pools can be added or removed while the monitor runs.
+    ///
+    /// # Params
+    /// pool_addresses - The pools to monitor
+    /// min_change_percent - Minimum percentage change required to emit an update
+    /// config - Polling interval, error handling and commitment
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::events::MonitorConfig;
+    /// use std::sync::Arc;
+    ///
+    /// let client = Arc::new(OrcaClient::new()?);
+    /// let mut monitor = client.monitor_pools(
+    ///     &["POOL_A".to_string(), "POOL_B".to_string()],
+    ///     1.0,
+    ///     MonitorConfig::default(),
+    /// )?;
+    /// while let Some(update) = monitor.recv().await {
+    ///     println!("{}: {}%", update.pool_address, update.change_percent);
+    /// }
+    /// ```
+    pub fn monitor_pools(
+        self: Arc<Self>,
+        pool_addresses: &[String],
+        min_change_percent: f64,
+        config: MonitorConfig,
+    ) -> OrcaResult<MultiPoolMonitor> {
+        for pool_address in pool_addresses {
+            parse_address("pool_address", pool_address)?;
+        }
+        let pools = Arc::new(Mutex::new(pool_addresses.to_vec()));
+        let (update_tx, update_rx) = mpsc::channel(PRICE_UPDATE_BUFFER);
+        let client = self;
+        let monitored = pools.clone();
+        let task_handle = tokio::spawn(async move {
+            let mut last_prices: HashMap<String, f64> = HashMap::new();
             let mut consecutive_errors = 0;
-            const MAX_CONSECUTIVE_ERRORS: u32 = 5;
-            const POLL_INTERVAL: Duration = Duration::from_secs(10);
             loop {
                 tokio::select! {
-                    _ = tokio::time::sleep(POLL_INTERVAL) => {}
-                    _ = shutdown_rx.recv() => {
-                        log::info!("Price monitor for {} shutting down", pool_address);
-                        break;
-                    }
+                    _ = tokio::time::sleep(config.next_poll_delay()) => {}
+                    _ = update_tx.closed() => break,
+                }
+                let pool_addresses = match monitored.lock() {
+                    Ok(pools) => pools.clone(),
+                    Err(_) => break,
+                };
+                last_prices.retain(|address, _| pool_addresses.contains(address));
+                if pool_addresses.is_empty() {
+                    continue;
                 }
-                let client_clone = client.clone();
-                // 使用克隆的客户端获取价格
-                match Self::get_current_price_impl(&client_clone, &pool_address).await {
-                    Ok(current_price) => {
+                let pools = match client
+                    .get_pool_states_with_commitment(&pool_addresses, config.commitment)
+                    .await
+                {
+                    Ok(pools) => {
                         consecutive_errors = 0;
-                        if let Some(prev_price) = last_price {
-                            let prev_price: f64 = prev_price;
-                            let current_price: f64 = current_price;
-                            if prev_price > 0.0 {
-                                let change_percent =
-                                    ((current_price - prev_price) / prev_price).abs() * 100.0;
-                                if change_percent >= min_change_percent {
-                                    callback(PriceUpdate {
-                                        pool_address: pool_address.clone(),
-                                        old_price: prev_price,
-                                        new_price: current_price,
-                                        change_percent,
-                                        timestamp: chrono::Utc::now(),
-                                    });
-                                }
-                            }
-                        }
-                        last_price = Some(current_price);
+                        pools
                     }
-                    Err(_e) => {
+                    Err(e) => {
                         consecutive_errors += 1;
-                        if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
-                            log::error!(
-                                "Too many consecutive errors, shutting down monitor for {}",
-                                pool_address
+                        tracing::warn!("Failed to poll monitored pools: {:?}", e);
+                        if consecutive_errors >= config.max_consecutive_errors {
+                            tracing::error!(
+                                "Too many consecutive errors, shutting down monitor for {} pools",
+                                pool_addresses.len()
                             );
                             break;
                         }
-                        tokio::time::sleep(Duration::from_secs(30)).await;
+                        tokio::time::sleep(config.backoff.delay(consecutive_errors)).await;
+                        continue;
+                    }
+                };
+                let timestamp = chrono::Utc::now();
+                for pool in pools {
+                    let Ok(current_price) = client
+                        .derive_price_from_pool_state(&pool, &pool.token_mint_a)
+                        .await
+                    else {
+                        continue;
+                    };
+                    let Some(prev_price) = last_prices.insert(pool.address.clone(), current_price)
+                    else {
+                        continue;
+                    };
+                    if prev_price <= 0.0 {
+                        continue;
+                    }
+                    let change_percent = ((current_price - prev_price) / prev_price).abs() * 100.0;
+                    if change_percent < min_change_percent {
+                        continue;
+                    }
+                    let update = PriceUpdate {
+                        pool_address: pool.address,
+                        old_price: prev_price,
+                        new_price: current_price,
+                        change_percent,
+                        timestamp,
+                    };
+                    if update_tx.send(update).await.is_err() {
+                        return;
                     }
                 }
             }
         });
+        Ok(MultiPoolMonitor {
+            pools,
+            update_rx,
+            task_handle,
+        })
+    }
 
-        Ok(PriceMonitorHandle {
-            shutdown_tx,
-            task_handle: handle,
+    /// Streams Whirlpool swaps in real time from a `logsSubscribe` WebSocket subscription
+    ///
+    /// Logs of confirmed transactions mentioning the pool, or the Whirlpool program when no
+    /// pool is given, are parsed for `Traded` events; failed transactions are skipped. A dropped
+    /// connection is re-established in the background, so swaps during the gap are missed.
+    /// With the `yellowstone` feature and a Geyser endpoint configured, transactions are
+    /// streamed over gRPC instead.
+    ///
+    /// # Params
+    /// pool_address - Only stream swaps of this pool; all Whirlpool swaps when None
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut swaps = client.subscribe_swap_events(Some("POOL_ADDRESS_HERE")).await?;
+    /// while let Some(swap) = swaps.recv().await {
+    ///     println!(
+    ///         "{}: {} in, {} out ({})",
+    ///         swap.signature,
+    ///         swap.input_amount,
+    ///         swap.output_amount,
+    ///         if swap.a_to_b { "A -> B" } else { "B -> A" }
+    ///     );
+    /// }
+    /// ```
+    pub async fn subscribe_swap_events(
+        &self,
+        pool_address: Option<&str>,
+    ) -> OrcaResult<SwapEventStream> {
+        let pool = pool_address
+            .map(|address| parse_address("pool_address", address))
+            .transpose()?;
+        let program_id = self.whirlpool_program_id;
+        let mentioned = pool.unwrap_or(program_id);
+        #[cfg(feature = "yellowstone")]
+        if let Some(geyser) = &self.geyser {
+            let (event_tx, event_rx) = mpsc::channel(SWAP_EVENT_BUFFER);
+            let task_handle = tokio::spawn(crate::geyser::stream_swap_events(
+                geyser.clone(),
+                program_id,
+                mentioned,
+                pool,
+                event_tx,
+            ));
+            return Ok(SwapEventStream {
+                event_rx,
+                task_handle,
+            });
+        }
+        let ws_url = self.ws_url.clone();
+        let mut pubsub = PubsubClient::new(&ws_url).await.map_err(|e| {
+            OrcaError::NetworkError(format!("Failed to connect to {}: {}", ws_url, e))
+        })?;
+        let (event_tx, event_rx) = mpsc::channel(SWAP_EVENT_BUFFER);
+        let task_handle = tokio::spawn(async move {
+            loop {
+                if let Err(e) =
+                    forward_swap_events(&pubsub, &program_id, &mentioned, pool, &event_tx).await
+                {
+                    tracing::warn!("Swap event subscription failed: {}", e);
+                }
+                if event_tx.is_closed() {
+                    break;
+                }
+                tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+                match PubsubClient::new(&ws_url).await {
+                    Ok(reconnected) => pubsub = reconnected,
+                    Err(e) => tracing::warn!("Failed to reconnect to {}: {}", ws_url, e),
+                }
+            }
+        });
+        Ok(SwapEventStream {
+            event_rx,
+            task_handle,
+        })
+    }
+
+    /// Decodes the Whirlpool instructions of a confirmed transaction into typed events
+    ///
+    /// Instructions called directly and through CPI, e.g. by aggregators, are both decoded.
+    /// Failed transactions yield no events.
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::events::WhirlpoolEvent;
+    ///
+    /// for event in client.parse_transaction(&signature).await? {
+    ///     if let WhirlpoolEvent::Swap { pool, input_amount, output_amount, .. } = event {
+    ///         println!("{}: {} in, {} out", pool, input_amount, output_amount);
+    ///     }
+    /// }
+    /// ```
+    pub async fn parse_transaction(
+        &self,
+        signature: &Signature,
+    ) -> OrcaResult<Vec<WhirlpoolEvent>> {
+        self.with_timeout("parse transaction", async {
+            let confirmed = self
+                .rpc_call("Failed to get transaction", |client| {
+                    client.get_transaction_with_config(
+                        signature,
+                        RpcTransactionConfig {
+                            encoding: Some(UiTransactionEncoding::Base64),
+                            commitment: Some(CommitmentConfig::confirmed()),
+                            max_supported_transaction_version: Some(0),
+                        },
+                    )
+                })
+                .await?;
+            let meta = confirmed.transaction.meta.as_ref().ok_or_else(|| {
+                OrcaError::TransactionError(format!("Transaction {} has no status", signature))
+            })?;
+            if meta.err.is_some() {
+                return Ok(Vec::new());
+            }
+            let transaction = confirmed.transaction.transaction.decode().ok_or_else(|| {
+                OrcaError::TransactionError(format!("Failed to decode transaction {}", signature))
+            })?;
+            let instructions = resolve_instructions(&transaction, meta);
+            Ok(instructions
+                .iter()
+                .enumerate()
+                .filter(|(_, instruction)| instruction.program_id == self.whirlpool_program_id)
+                .filter_map(|(index, instruction)| {
+                    // Transfers made by the instruction itself, one CPI level below it
+                    let transfers: Vec<TokenTransfer> = instructions[index + 1..]
+                        .iter()
+                        .take_while(|inner| inner.stack_height > instruction.stack_height)
+                        .filter(|inner| inner.stack_height == instruction.stack_height + 1)
+                        .filter_map(decode_token_transfer)
+                        .collect();
+                    decode_whirlpool_instruction(instruction, &transfers)
+                })
+                .collect())
         })
+        .await
     }
 
     /// Internal implementation for fetching current price from on-chain data
-    async fn get_current_price_impl(client: &OrcaClient, pool_address: &str) -> OrcaResult<f64> {
+    async fn get_current_price_impl(
+        client: &OrcaClient,
+        pool_address: &str,
+        commitment: Option<CommitmentConfig>,
+    ) -> OrcaResult<f64> {
         // 使用已有的池子状态获取价格
-        let pool_info = client.get_pool_state_onchain(pool_address).await?;
+        let pool_info = match commitment {
+            Some(_) => {
+                let pool_pubkey = parse_address("pool_address", pool_address)?;
+                client
+                    .get_pool_states_with_commitment(&[pool_address.to_string()], commitment)
+                    .await?
+                    .pop()
+                    .ok_or(OrcaError::AccountNotFound(pool_pubkey))?
+            }
+            None => client.get_pool_state_onchain(pool_address).await?,
+        };
         // 使用第一个代币作为基准计算价格
         let base_mint = &pool_info.token_mint_a;
         client
@@ -113,6 +626,83 @@ impl OrcaClient {
     }
 }
 
+/// How a price monitor polls and reacts to failed polls
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::events::{BackoffStrategy, MonitorConfig};
+/// use std::time::Duration;
+///
+/// // Relaxed polling for a free-tier RPC
+/// let config = MonitorConfig {
+///     poll_interval: Duration::from_secs(30),
+///     jitter: Duration::from_secs(5),
+///     backoff: BackoffStrategy::Exponential {
+///         initial: Duration::from_secs(10),
+///         max: Duration::from_secs(300),
+///     },
+///     ..MonitorConfig::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorConfig {
+    /// Time between polls
+    pub poll_interval: Duration,
+    /// Upper bound of a random delay added to every poll, spreading monitors started together
+    pub jitter: Duration,
+    /// Failed polls in a row after which the monitor stops
+    pub max_consecutive_errors: u32,
+    /// Extra wait after a failed poll
+    pub backoff: BackoffStrategy,
+    /// Commitment of the fetched pool state; the client's commitment when None
+    pub commitment: Option<CommitmentConfig>,
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(10),
+            jitter: Duration::ZERO,
+            max_consecutive_errors: 5,
+            backoff: BackoffStrategy::Fixed(Duration::from_secs(30)),
+            commitment: None,
+        }
+    }
+}
+
+impl MonitorConfig {
+    fn next_poll_delay(&self) -> Duration {
+        if self.jitter.is_zero() {
+            return self.poll_interval;
+        }
+        // Sub-second clock noise is random enough to spread polls apart
+        let fraction = chrono::Utc::now().timestamp_subsec_nanos() as f64 / 1e9;
+        self.poll_interval + self.jitter.mul_f64(fraction)
+    }
+}
+
+/// Wait applied after a failed poll
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackoffStrategy {
+    /// The same delay after every failure
+    Fixed(Duration),
+    /// Starts at `initial` and doubles with every further failure in a row, up to `max`
+    Exponential { initial: Duration, max: Duration },
+}
+
+impl BackoffStrategy {
+    /// Delay after failure number `consecutive_errors` (starting at 1)
+    pub fn delay(&self, consecutive_errors: u32) -> Duration {
+        match self {
+            BackoffStrategy::Fixed(delay) => *delay,
+            BackoffStrategy::Exponential { initial, max } => {
+                let factor = 2u32.saturating_pow(consecutive_errors.saturating_sub(1));
+                initial.saturating_mul(factor).min(*max)
+            }
+        }
+    }
+}
+
 /// Handle for controlling a price monitoring task
 ///
 /// Use this handle to gracefully shutdown the monitoring task
@@ -135,9 +725,47 @@ impl OrcaClient {
 pub struct PriceMonitorHandle {
     shutdown_tx: mpsc::Sender<()>,
     task_handle: tokio::task::JoinHandle<()>,
+    state: Arc<MonitorState>,
+}
+
+/// State shared between a price monitor task and its handle
+#[derive(Debug, Default)]
+struct MonitorState {
+    paused: AtomicBool,
+    /// When the price was last fetched successfully
+    last_update: Mutex<Option<chrono::DateTime<chrono::Utc>>>,
 }
 
 impl PriceMonitorHandle {
+    /// Suspends polling without stopping the task
+    ///
+    /// No RPC calls are made while paused. After `resume`, the next price is compared with
+    /// the last one seen before the pause.
+    pub fn pause(&self) {
+        self.state.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Resumes polling after `pause`, from the next poll on
+    pub fn resume(&self) {
+        self.state.paused.store(false, Ordering::SeqCst);
+    }
+
+    /// Returns true while the task is alive and not paused
+    ///
+    /// A monitor that gave up after too many consecutive errors is no longer running.
+    pub fn is_running(&self) -> bool {
+        !self.task_handle.is_finished() && !self.state.paused.load(Ordering::SeqCst)
+    }
+
+    /// Returns when the price was last fetched successfully; None before the first poll
+    pub fn last_update(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.state
+            .last_update
+            .lock()
+            .ok()
+            .and_then(|last_update| *last_update)
+    }
+
     /// Gracefully shuts down the price monitoring task
     ///
     /// Sends a shutdown signal to the monitoring task and waits
@@ -164,3 +792,421 @@ pub struct PriceUpdate {
     /// Timestamp when the change was detected
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
+
+/// Price updates delivered by `OrcaClient::monitor_price_changes`
+///
+/// Dropping the stream stops the monitor.
+#[derive(Debug)]
+pub struct PriceUpdateStream {
+    update_rx: mpsc::Receiver<PriceUpdate>,
+    task_handle: tokio::task::JoinHandle<()>,
+}
+
+impl PriceUpdateStream {
+    /// Waits for the next price update; None once the monitor has stopped
+    pub async fn recv(&mut self) -> Option<PriceUpdate> {
+        self.update_rx.recv().await
+    }
+}
+
+impl Stream for PriceUpdateStream {
+    type Item = PriceUpdate;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<PriceUpdate>> {
+        self.update_rx.poll_recv(cx)
+    }
+}
+
+impl Drop for PriceUpdateStream {
+    fn drop(&mut self) {
+        self.task_handle.abort();
+    }
+}
+
+/// Price updates of many pools delivered by `OrcaClient::monitor_pools`
+///
+/// Dropping the monitor stops its polling task.
+#[derive(Debug)]
+pub struct MultiPoolMonitor {
+    pools: Arc<Mutex<Vec<String>>>,
+    update_rx: mpsc::Receiver<PriceUpdate>,
+    task_handle: tokio::task::JoinHandle<()>,
+}
+
+impl MultiPoolMonitor {
+    /// Waits for the next price update; None once the monitor has stopped
+    pub async fn recv(&mut self) -> Option<PriceUpdate> {
+        self.update_rx.recv().await
+    }
+
+    /// Adds a pool, polled from the next tick on
+    pub fn add_pool(&self, pool_address: &str) -> OrcaResult<()> {
+        parse_address("pool_address", pool_address)?;
+        if let Ok(mut pools) = self.pools.lock()
+            && !pools.iter().any(|pool| pool == pool_address)
+        {
+            pools.push(pool_address.to_string());
+        }
+        Ok(())
+    }
+
+    /// Removes a pool; returns false when it was not monitored
+    pub fn remove_pool(&self, pool_address: &str) -> bool {
+        let Ok(mut pools) = self.pools.lock() else {
+            return false;
+        };
+        let len = pools.len();
+        pools.retain(|pool| pool != pool_address);
+        pools.len() != len
+    }
+
+    /// Returns the monitored pools
+    pub fn pools(&self) -> Vec<String> {
+        self.pools
+            .lock()
+            .map(|pools| pools.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Stream for MultiPoolMonitor {
+    type Item = PriceUpdate;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<PriceUpdate>> {
+        self.update_rx.poll_recv(cx)
+    }
+}
+
+impl Drop for MultiPoolMonitor {
+    fn drop(&mut self) {
+        self.task_handle.abort();
+    }
+}
+
+/// Swaps delivered by `OrcaClient::subscribe_swap_events`
+///
+/// Dropping the stream ends the subscription.
+#[derive(Debug)]
+pub struct SwapEventStream {
+    event_rx: mpsc::Receiver<SwapEvent>,
+    task_handle: tokio::task::JoinHandle<()>,
+}
+
+impl SwapEventStream {
+    /// Waits for the next swap; None once the subscription has ended
+    pub async fn recv(&mut self) -> Option<SwapEvent> {
+        self.event_rx.recv().await
+    }
+}
+
+impl Drop for SwapEventStream {
+    fn drop(&mut self) {
+        self.task_handle.abort();
+    }
+}
+
+/// Forwards swaps from one log subscription until it drops or the consumer goes away
+async fn forward_swap_events(
+    pubsub: &PubsubClient,
+    program_id: &Pubkey,
+    mentioned: &Pubkey,
+    pool: Option<Pubkey>,
+    event_tx: &mpsc::Sender<SwapEvent>,
+) -> Result<(), PubsubClientError> {
+    let (mut logs, unsubscribe) = pubsub
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![mentioned.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )
+        .await?;
+    while let Some(response) = logs.next().await {
+        if response.value.err.is_some() {
+            continue;
+        }
+        let Ok(signature) = Signature::from_str(&response.value.signature) else {
+            continue;
+        };
+        let events = parse_swap_events(
+            &response.value.logs,
+            program_id,
+            signature,
+            response.context.slot,
+        );
+        for event in events {
+            if pool.is_some_and(|pool| pool != event.pool) {
+                continue;
+            }
+            if event_tx.send(event).await.is_err() {
+                drop(logs);
+                unsubscribe().await;
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Decodes the `Traded` events the Whirlpool program logged in a transaction
+///
+/// Program invocations are tracked through the `invoke`/`success`/`failed` lines, so only
+/// `Program data:` lines logged by the Whirlpool program itself are decoded.
+pub(crate) fn parse_swap_events(
+    logs: &[String],
+    program_id: &Pubkey,
+    signature: Signature,
+    slot: u64,
+) -> Vec<SwapEvent> {
+    let mut invocations: Vec<Pubkey> = Vec::new();
+    let mut events = Vec::new();
+    for log in logs {
+        let Some(line) = log.strip_prefix("Program ") else {
+            continue;
+        };
+        if let Some(data) = line.strip_prefix("data: ") {
+            if invocations.last() == Some(program_id) {
+                events.extend(decode_traded_event(data, signature, slot));
+            }
+            continue;
+        }
+        let Some((program, status)) = line.split_once(' ') else {
+            continue;
+        };
+        let Ok(program) = Pubkey::from_str(program) else {
+            continue;
+        };
+        if status.starts_with("invoke [") {
+            invocations.push(program);
+        } else if status == "success" || status.starts_with("failed") {
+            invocations.pop();
+        }
+    }
+    events
+}
+
+fn decode_traded_event(data: &str, signature: Signature, slot: u64) -> Option<SwapEvent> {
+    let bytes = BASE64_STANDARD.decode(data).ok()?;
+    let mut reader = AccountReader::new(&bytes, &WHIRLPOOL_TRADED_EVENT_DISCRIMINATOR)?;
+    Some(SwapEvent {
+        signature,
+        slot,
+        pool: reader.pubkey()?,
+        a_to_b: reader.bool()?,
+        pre_sqrt_price: reader.u128()?,
+        post_sqrt_price: reader.u128()?,
+        input_amount: reader.u64()?,
+        output_amount: reader.u64()?,
+        input_transfer_fee: reader.u64()?,
+        output_transfer_fee: reader.u64()?,
+        lp_fee: reader.u64()?,
+        protocol_fee: reader.u64()?,
+    })
+}
+
+/// Resolves the accounts of every instruction in execution order, inner instructions following
+/// the top-level instruction that invoked them
+fn resolve_instructions(
+    transaction: &VersionedTransaction,
+    meta: &UiTransactionStatusMeta,
+) -> Vec<ResolvedInstruction> {
+    let mut account_keys = transaction.message.static_account_keys().to_vec();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        account_keys.extend(
+            loaded
+                .writable
+                .iter()
+                .chain(&loaded.readonly)
+                .filter_map(|address| Pubkey::from_str(address).ok()),
+        );
+    }
+    let resolve = |program_id_index: u8, accounts: &[u8], data: Vec<u8>, stack_height: u32| {
+        Some(ResolvedInstruction {
+            program_id: *account_keys.get(program_id_index as usize)?,
+            accounts: accounts
+                .iter()
+                .map(|index| account_keys.get(*index as usize).copied())
+                .collect::<Option<_>>()?,
+            data,
+            stack_height,
+        })
+    };
+    let inner_sets: Option<Vec<UiInnerInstructions>> = meta.inner_instructions.clone().into();
+    let inner_sets = inner_sets.unwrap_or_default();
+    let mut resolved = Vec::new();
+    for (index, instruction) in transaction.message.instructions().iter().enumerate() {
+        resolved.extend(resolve(
+            instruction.program_id_index,
+            &instruction.accounts,
+            instruction.data.clone(),
+            1,
+        ));
+        let inner_instructions = inner_sets
+            .iter()
+            .filter(|set| set.index as usize == index)
+            .flat_map(|set| &set.instructions);
+        for inner in inner_instructions {
+            let UiInstruction::Compiled(compiled) = inner else {
+                continue;
+            };
+            let Ok(data) = bs58::decode(&compiled.data).into_vec() else {
+                continue;
+            };
+            resolved.extend(resolve(
+                compiled.program_id_index,
+                &compiled.accounts,
+                data,
+                compiled.stack_height.unwrap_or(2),
+            ));
+        }
+    }
+    resolved
+}
+
+/// Decodes SPL Token and Token-2022 `Transfer` and `TransferChecked` instructions
+fn decode_token_transfer(instruction: &ResolvedInstruction) -> Option<TokenTransfer> {
+    if instruction.program_id != spl_token::id() && instruction.program_id != TOKEN_2022_PROGRAM {
+        return None;
+    }
+    let mut reader = AccountReader::unprefixed(&instruction.data);
+    let destination_index = match reader.u8()? {
+        3 => 1,
+        12 => 2,
+        _ => return None,
+    };
+    Some(TokenTransfer {
+        source: *instruction.accounts.first()?,
+        destination: *instruction.accounts.get(destination_index)?,
+        amount: reader.u64()?,
+    })
+}
+
+fn decode_whirlpool_instruction(
+    instruction: &ResolvedInstruction,
+    transfers: &[TokenTransfer],
+) -> Option<WhirlpoolEvent> {
+    let discriminator: [u8; 8] = instruction.data.get(..8)?.try_into().ok()?;
+    let mut reader = AccountReader::new(&instruction.data, &discriminator)?;
+    let account = |index: usize| instruction.accounts.get(index).copied();
+    let deposited = |vault: Pubkey| {
+        transfers
+            .iter()
+            .filter(|transfer| transfer.destination == vault)
+            .map(|transfer| transfer.amount)
+            .sum::<u64>()
+    };
+    let withdrawn = |vault: Pubkey| {
+        transfers
+            .iter()
+            .filter(|transfer| transfer.source == vault)
+            .map(|transfer| transfer.amount)
+            .sum::<u64>()
+    };
+    match discriminator {
+        WHIRLPOOL_SWAP_DISCRIMINATOR | WHIRLPOOL_SWAP_V2_DISCRIMINATOR => {
+            let (owner, pool, vault_a, vault_b) = if discriminator == WHIRLPOOL_SWAP_DISCRIMINATOR {
+                (account(1)?, account(2)?, account(4)?, account(6)?)
+            } else {
+                (account(3)?, account(4)?, account(8)?, account(10)?)
+            };
+            // amount, other_amount_threshold, sqrt_price_limit, amount_specified_is_input
+            reader.skip(8 + 8 + 16 + 1)?;
+            let a_to_b = reader.bool()?;
+            let (vault_in, vault_out) = if a_to_b {
+                (vault_a, vault_b)
+            } else {
+                (vault_b, vault_a)
+            };
+            Some(WhirlpoolEvent::Swap {
+                pool,
+                owner,
+                a_to_b,
+                input_amount: deposited(vault_in),
+                output_amount: withdrawn(vault_out),
+            })
+        }
+        WHIRLPOOL_INCREASE_LIQUIDITY_DISCRIMINATOR
+        | WHIRLPOOL_INCREASE_LIQUIDITY_V2_DISCRIMINATOR
+        | WHIRLPOOL_DECREASE_LIQUIDITY_DISCRIMINATOR
+        | WHIRLPOOL_DECREASE_LIQUIDITY_V2_DISCRIMINATOR => {
+            let v2 = discriminator == WHIRLPOOL_INCREASE_LIQUIDITY_V2_DISCRIMINATOR
+                || discriminator == WHIRLPOOL_DECREASE_LIQUIDITY_V2_DISCRIMINATOR;
+            let (owner, position, vault_a, vault_b) = if v2 {
+                (account(4)?, account(5)?, account(11)?, account(12)?)
+            } else {
+                (account(2)?, account(3)?, account(7)?, account(8)?)
+            };
+            let pool = account(0)?;
+            let liquidity = reader.u128()?;
+            if discriminator == WHIRLPOOL_INCREASE_LIQUIDITY_DISCRIMINATOR
+                || discriminator == WHIRLPOOL_INCREASE_LIQUIDITY_V2_DISCRIMINATOR
+            {
+                Some(WhirlpoolEvent::IncreaseLiquidity {
+                    pool,
+                    position,
+                    owner,
+                    liquidity,
+                    token_a_amount: deposited(vault_a),
+                    token_b_amount: deposited(vault_b),
+                })
+            } else {
+                Some(WhirlpoolEvent::DecreaseLiquidity {
+                    pool,
+                    position,
+                    owner,
+                    liquidity,
+                    token_a_amount: withdrawn(vault_a),
+                    token_b_amount: withdrawn(vault_b),
+                })
+            }
+        }
+        WHIRLPOOL_COLLECT_FEES_DISCRIMINATOR | WHIRLPOOL_COLLECT_FEES_V2_DISCRIMINATOR => {
+            let (vault_a, vault_b) = if discriminator == WHIRLPOOL_COLLECT_FEES_DISCRIMINATOR {
+                (account(5)?, account(7)?)
+            } else {
+                (account(7)?, account(9)?)
+            };
+            Some(WhirlpoolEvent::CollectFees {
+                pool: account(0)?,
+                position: account(2)?,
+                owner: account(1)?,
+                fee_a: withdrawn(vault_a),
+                fee_b: withdrawn(vault_b),
+            })
+        }
+        WHIRLPOOL_OPEN_POSITION_DISCRIMINATOR
+        | WHIRLPOOL_OPEN_POSITION_WITH_METADATA_DISCRIMINATOR
+        | WHIRLPOOL_OPEN_POSITION_WITH_TOKEN_EXTENSIONS_DISCRIMINATOR => {
+            // Bump seeds precede the ticks, and the metadata account precedes the pool
+            let pool = match discriminator {
+                WHIRLPOOL_OPEN_POSITION_DISCRIMINATOR => {
+                    reader.skip(1)?;
+                    account(5)?
+                }
+                WHIRLPOOL_OPEN_POSITION_WITH_METADATA_DISCRIMINATOR => {
+                    reader.skip(2)?;
+                    account(6)?
+                }
+                _ => account(5)?,
+            };
+            Some(WhirlpoolEvent::OpenPosition {
+                pool,
+                position: account(2)?,
+                position_mint: account(3)?,
+                owner: account(1)?,
+                lower_tick: reader.i32()?,
+                upper_tick: reader.i32()?,
+            })
+        }
+        WHIRLPOOL_CLOSE_POSITION_DISCRIMINATOR
+        | WHIRLPOOL_CLOSE_POSITION_WITH_TOKEN_EXTENSIONS_DISCRIMINATOR => {
+            Some(WhirlpoolEvent::ClosePosition {
+                position: account(2)?,
+                position_mint: account(3)?,
+                owner: account(0)?,
+                receiver: account(1)?,
+            })
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,198 @@
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::{
+    monitoring::PriceData,
+    price::Kline,
+    types::{OrcaError, OrcaResult},
+};
+
+/// Writes kline and price-history results for research pipelines
+///
+/// Implemented for slices, so results can be written straight from the returned `Vec`.
+/// Timestamps are Unix seconds; prices and volumes are raw units; liquidity is written as
+/// decimal text since it does not fit a 64-bit column.
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::export::Export;
+/// use std::fs::File;
+///
+/// let klines = client.get_kline_data_production("whirlpool_address_here", 60, 100).await?;
+/// klines.write_csv(File::create("klines.csv")?)?;
+/// klines.write_json(File::create("klines.json")?)?;
+/// ```
+pub trait Export {
+    /// Writes a header line followed by one comma-separated line per row
+    fn write_csv<W: Write>(&self, writer: W) -> OrcaResult<()>;
+
+    /// Writes the rows as a JSON array of objects
+    fn write_json<W: Write>(&self, writer: W) -> OrcaResult<()>;
+
+    /// Writes the rows as one row group of an uncompressed Parquet file
+    #[cfg(feature = "parquet")]
+    fn write_parquet<W: Write + Send>(&self, writer: W) -> OrcaResult<()>;
+}
+
+impl Export for [Kline] {
+    fn write_csv<W: Write>(&self, writer: W) -> OrcaResult<()> {
+        write_csv_rows(self, writer)
+    }
+
+    fn write_json<W: Write>(&self, writer: W) -> OrcaResult<()> {
+        write_json_rows(self, writer)
+    }
+
+    #[cfg(feature = "parquet")]
+    fn write_parquet<W: Write + Send>(&self, writer: W) -> OrcaResult<()> {
+        write_parquet_rows(self, writer)
+    }
+}
+
+impl Export for [PriceData] {
+    fn write_csv<W: Write>(&self, writer: W) -> OrcaResult<()> {
+        write_csv_rows(self, writer)
+    }
+
+    fn write_json<W: Write>(&self, writer: W) -> OrcaResult<()> {
+        write_json_rows(self, writer)
+    }
+
+    #[cfg(feature = "parquet")]
+    fn write_parquet<W: Write + Send>(&self, writer: W) -> OrcaResult<()> {
+        write_parquet_rows(self, writer)
+    }
+}
+
+/// How one output column is read from a row
+enum Column<T> {
+    Int64(fn(&T) -> i64),
+    Double(fn(&T) -> f64),
+    Text(fn(&T) -> String),
+}
+
+trait Row: Serialize + Sized + 'static {
+    /// Parquet message name
+    #[cfg(feature = "parquet")]
+    const NAME: &'static str;
+    const COLUMNS: &'static [(&'static str, Column<Self>)];
+}
+
+impl Row for Kline {
+    #[cfg(feature = "parquet")]
+    const NAME: &'static str = "kline";
+    const COLUMNS: &'static [(&'static str, Column<Self>)] = &[
+        ("timestamp", Column::Int64(|kline| kline.timestamp as i64)),
+        ("open", Column::Double(|kline| kline.open)),
+        ("high", Column::Double(|kline| kline.high)),
+        ("low", Column::Double(|kline| kline.low)),
+        ("close", Column::Double(|kline| kline.close)),
+        ("volume", Column::Double(|kline| kline.volume)),
+        ("quote_volume", Column::Double(|kline| kline.quote_volume)),
+    ];
+}
+
+impl Row for PriceData {
+    #[cfg(feature = "parquet")]
+    const NAME: &'static str = "price_data";
+    const COLUMNS: &'static [(&'static str, Column<Self>)] = &[
+        ("timestamp", Column::Int64(|data| data.timestamp as i64)),
+        ("price", Column::Double(|data| data.price)),
+        ("liquidity", Column::Text(|data| data.liquidity.to_string())),
+    ];
+}
+
+fn write_csv_rows<T: Row, W: Write>(rows: &[T], mut writer: W) -> OrcaResult<()> {
+    let io_error = |e: std::io::Error| OrcaError::IoError(format!("Failed to write CSV: {}", e));
+    let header: Vec<&str> = T::COLUMNS.iter().map(|(name, _)| *name).collect();
+    writeln!(writer, "{}", header.join(",")).map_err(io_error)?;
+    for row in rows {
+        let values: Vec<String> = T::COLUMNS
+            .iter()
+            .map(|(_, column)| match column {
+                Column::Int64(value) => value(row).to_string(),
+                Column::Double(value) => value(row).to_string(),
+                Column::Text(value) => value(row),
+            })
+            .collect();
+        writeln!(writer, "{}", values.join(",")).map_err(io_error)?;
+    }
+    writer.flush().map_err(io_error)
+}
+
+fn write_json_rows<T: Row, W: Write>(rows: &[T], mut writer: W) -> OrcaResult<()> {
+    serde_json::to_writer(&mut writer, rows)
+        .map_err(|e| OrcaError::IoError(format!("Failed to write JSON: {}", e)))?;
+    writer
+        .flush()
+        .map_err(|e| OrcaError::IoError(format!("Failed to write JSON: {}", e)))
+}
+
+#[cfg(feature = "parquet")]
+fn write_parquet_rows<T: Row, W: Write + Send>(rows: &[T], writer: W) -> OrcaResult<()> {
+    use std::sync::Arc;
+
+    use parquet::{
+        data_type::{ByteArray, ByteArrayType, DoubleType, Int64Type},
+        errors::ParquetError,
+        file::{properties::WriterProperties, writer::SerializedFileWriter},
+        schema::parser::parse_message_type,
+    };
+
+    let parquet_error =
+        |e: ParquetError| OrcaError::IoError(format!("Failed to write Parquet: {}", e));
+    let fields: Vec<String> = T::COLUMNS
+        .iter()
+        .map(|(name, column)| match column {
+            Column::Int64(_) => format!("REQUIRED INT64 {};", name),
+            Column::Double(_) => format!("REQUIRED DOUBLE {};", name),
+            Column::Text(_) => format!("REQUIRED BYTE_ARRAY {} (UTF8);", name),
+        })
+        .collect();
+    let schema = parse_message_type(&format!("message {} {{ {} }}", T::NAME, fields.join(" ")))
+        .map_err(parquet_error)?;
+    let mut file_writer = SerializedFileWriter::new(
+        writer,
+        Arc::new(schema),
+        Arc::new(WriterProperties::builder().build()),
+    )
+    .map_err(parquet_error)?;
+    let mut row_group = file_writer.next_row_group().map_err(parquet_error)?;
+    let mut columns = T::COLUMNS.iter();
+    while let Some(mut column_writer) = row_group.next_column().map_err(parquet_error)? {
+        let Some((_, column)) = columns.next() else {
+            break;
+        };
+        match column {
+            Column::Int64(value) => {
+                let values: Vec<i64> = rows.iter().map(value).collect();
+                column_writer
+                    .typed::<Int64Type>()
+                    .write_batch(&values, None, None)
+                    .map_err(parquet_error)?;
+            }
+            Column::Double(value) => {
+                let values: Vec<f64> = rows.iter().map(value).collect();
+                column_writer
+                    .typed::<DoubleType>()
+                    .write_batch(&values, None, None)
+                    .map_err(parquet_error)?;
+            }
+            Column::Text(value) => {
+                let values: Vec<ByteArray> = rows
+                    .iter()
+                    .map(|row| ByteArray::from(value(row).into_bytes()))
+                    .collect();
+                column_writer
+                    .typed::<ByteArrayType>()
+                    .write_batch(&values, None, None)
+                    .map_err(parquet_error)?;
+            }
+        }
+        column_writer.close().map_err(parquet_error)?;
+    }
+    row_group.close().map_err(parquet_error)?;
+    file_writer.close().map_err(parquet_error)?;
+    Ok(())
+}
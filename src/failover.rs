@@ -0,0 +1,330 @@
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use solana_client::{client_error::ClientError, nonblocking::rpc_client::RpcClient};
+
+use super::*;
+use crate::{retry::RpcErrorClass, types::OrcaResult};
+
+/// When RPC endpoints are taken out of rotation
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailoverConfig {
+    /// How often `start_endpoint_health_checks` polls every endpoint
+    pub health_check_interval: Duration,
+    /// Slots an endpoint may trail the most advanced endpoint before it is skipped
+    pub max_slot_lag: u64,
+    /// How long an endpoint that failed a request is skipped
+    pub cooldown: Duration,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            health_check_interval: Duration::from_secs(10),
+            max_slot_lag: 50,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Observed state of one RPC endpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointStatus {
+    pub url: String,
+    /// Whether requests are currently routed to the endpoint
+    pub healthy: bool,
+    /// Slot reported by the last successful health check
+    pub slot: Option<u64>,
+    /// Slots behind the most advanced endpoint at the last health check
+    pub slot_lag: Option<u64>,
+    /// Round trip of the last successful health check
+    pub latency_ms: Option<u64>,
+    /// Requests sent to the endpoint, health checks excluded
+    pub requests: u64,
+    /// Requests that failed with a transient error
+    pub failures: u64,
+    pub last_error: Option<String>,
+    pub last_checked: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Default)]
+struct EndpointState {
+    slot: Option<u64>,
+    slot_lag: Option<u64>,
+    latency: Option<Duration>,
+    lagging: bool,
+    failed_until: Option<Instant>,
+    requests: u64,
+    failures: u64,
+    last_error: Option<String>,
+    last_checked: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl EndpointState {
+    fn is_healthy(&self, now: Instant) -> bool {
+        !self.lagging && self.failed_until.is_none_or(|until| now >= until)
+    }
+}
+
+struct RpcEndpoint {
+    url: String,
+    client: Arc<RpcClient>,
+    state: Mutex<EndpointState>,
+}
+
+/// RPC endpoints of a client, in priority order; the first one is the primary
+pub(crate) struct RpcEndpoints {
+    endpoints: Vec<RpcEndpoint>,
+    config: FailoverConfig,
+    cursor: AtomicUsize,
+}
+
+impl RpcEndpoints {
+    pub(crate) fn new(clients: Vec<(String, Arc<RpcClient>)>, config: FailoverConfig) -> Self {
+        Self {
+            endpoints: clients
+                .into_iter()
+                .map(|(url, client)| RpcEndpoint {
+                    url,
+                    client,
+                    state: Mutex::new(EndpointState::default()),
+                })
+                .collect(),
+            config,
+            cursor: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn client(&self, index: usize) -> &RpcClient {
+        &self.endpoints[index].client
+    }
+
+    fn is_healthy(&self, index: usize, now: Instant) -> bool {
+        self.endpoints[index]
+            .state
+            .lock()
+            .map(|state| state.is_healthy(now))
+            .unwrap_or(true)
+    }
+
+    /// Picks the endpoint for the next request, skipping the ones in `tried`
+    ///
+    /// Reads rotate over the endpoints, writes go to the first one in priority order. Healthy
+    /// endpoints are preferred; when none is left, any untried endpoint is used.
+    pub(crate) fn pick(&self, tried: &[usize], round_robin: bool) -> usize {
+        let count = self.endpoints.len();
+        let start = if round_robin {
+            self.cursor.fetch_add(1, Ordering::Relaxed) % count
+        } else {
+            0
+        };
+        let now = Instant::now();
+        let untried = || {
+            (0..count)
+                .map(move |offset| (start + offset) % count)
+                .filter(|index| !tried.contains(index))
+        };
+        untried()
+            .find(|index| self.is_healthy(*index, now))
+            .or_else(|| untried().next())
+            .unwrap_or(start)
+    }
+
+    /// Whether a healthy endpoint outside `tried` is left to fail over to
+    pub(crate) fn can_fail_over(&self, tried: &[usize]) -> bool {
+        let now = Instant::now();
+        (0..self.endpoints.len())
+            .any(|index| !tried.contains(&index) && self.is_healthy(index, now))
+    }
+
+    /// Counts a request the endpoint answered, successfully or with a non-transient error
+    pub(crate) fn record_request(&self, index: usize) {
+        if let Ok(mut state) = self.endpoints[index].state.lock() {
+            state.requests += 1;
+        }
+    }
+
+    /// Takes an endpoint out of rotation for the cooldown after a transient error
+    pub(crate) fn record_failure(&self, index: usize, error: &ClientError) {
+        if let Ok(mut state) = self.endpoints[index].state.lock() {
+            state.requests += 1;
+            state.failures += 1;
+            state.last_error = Some(error.to_string());
+            state.failed_until = Some(Instant::now() + self.config.cooldown);
+        }
+    }
+
+    fn statuses(&self) -> Vec<EndpointStatus> {
+        let now = Instant::now();
+        self.endpoints
+            .iter()
+            .filter_map(|endpoint| {
+                let state = endpoint.state.lock().ok()?;
+                Some(EndpointStatus {
+                    url: endpoint.url.clone(),
+                    healthy: state.is_healthy(now),
+                    slot: state.slot,
+                    slot_lag: state.slot_lag,
+                    latency_ms: state.latency.map(|latency| latency.as_millis() as u64),
+                    requests: state.requests,
+                    failures: state.failures,
+                    last_error: state.last_error.clone(),
+                    last_checked: state.last_checked,
+                })
+            })
+            .collect()
+    }
+}
+
+impl OrcaClient {
+    /// Returns the state of every RPC endpoint, primary first
+    pub fn endpoint_statuses(&self) -> Vec<EndpointStatus> {
+        self.endpoints.statuses()
+    }
+
+    /// Polls the slot of every RPC endpoint and updates which ones receive requests
+    ///
+    /// Endpoints trailing the most advanced one by more than `max_slot_lag` slots are skipped
+    /// until they catch up. An endpoint that answers again is no longer skipped for its
+    /// earlier failures.
+    ///
+    /// # Example
+    /// ```rust
+    /// for status in client.check_endpoints().await {
+    ///     println!("{}: healthy {}, lag {:?}", status.url, status.healthy, status.slot_lag);
+    /// }
+    /// ```
+    pub async fn check_endpoints(&self) -> Vec<EndpointStatus> {
+        let checks =
+            futures::future::join_all(self.endpoints.endpoints.iter().map(|endpoint| async move {
+                let started = Instant::now();
+                let slot = endpoint.client.get_slot().await;
+                (slot, started.elapsed())
+            }))
+            .await;
+        let max_slot = checks
+            .iter()
+            .filter_map(|(slot, _)| slot.as_ref().ok().copied())
+            .max();
+        let checked_at = chrono::Utc::now();
+        for (endpoint, (slot, latency)) in self.endpoints.endpoints.iter().zip(checks) {
+            let Ok(mut state) = endpoint.state.lock() else {
+                continue;
+            };
+            state.last_checked = Some(checked_at);
+            match slot {
+                Ok(slot) => {
+                    let lag = max_slot.unwrap_or(slot).saturating_sub(slot);
+                    state.slot = Some(slot);
+                    state.slot_lag = Some(lag);
+                    state.latency = Some(latency);
+                    state.lagging = lag > self.endpoints.config.max_slot_lag;
+                    state.failed_until = None;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        url = %endpoint.url,
+                        error = %e,
+                        "RPC endpoint health check failed"
+                    );
+                    state.slot_lag = None;
+                    state.latency = None;
+                    state.last_error = Some(e.to_string());
+                    state.failed_until = Some(Instant::now() + self.endpoints.config.cooldown);
+                }
+            }
+        }
+        self.endpoints.statuses()
+    }
+
+    /// Runs `check_endpoints` in the background every `health_check_interval`
+    ///
+    /// Dropping the returned handle stops the checks.
+    pub fn start_endpoint_health_checks(self: Arc<Self>) -> EndpointHealthChecks {
+        let interval = self.endpoints.config.health_check_interval;
+        let task_handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                self.check_endpoints().await;
+            }
+        });
+        EndpointHealthChecks { task_handle }
+    }
+
+    /// Sends and confirms a transaction, moving to the next endpoint in priority order when
+    /// one fails with an error the retry policy considers transient
+    ///
+    /// Before resending, the next endpoint is asked whether the transaction already landed.
+    pub(crate) async fn send_with_failover(
+        &self,
+        transaction: &VersionedTransaction,
+        operation: &str,
+    ) -> OrcaResult<Signature> {
+        let signature = transaction.signatures.first().copied();
+        let mut tried = Vec::new();
+        loop {
+            let index = self.endpoints.pick(&tried, false);
+            let client = self.endpoints.client(index);
+            if let (false, Some(signature)) = (tried.is_empty(), signature) {
+                match client.get_signature_status(&signature).await {
+                    Ok(Some(Ok(()))) => return Ok(signature),
+                    Ok(Some(Err(e))) => {
+                        return Err(OrcaError::transaction_failed(
+                            Some(signature),
+                            e,
+                            Vec::new(),
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+            match client.send_and_confirm_transaction(transaction).await {
+                Ok(signature) => {
+                    self.endpoints.record_request(index);
+                    return Ok(signature);
+                }
+                Err(e) => {
+                    if self.retry_policy.retries(RpcErrorClass::of(&e)) {
+                        self.endpoints.record_failure(index, &e);
+                        tried.push(index);
+                        if self.endpoints.can_fail_over(&tried) {
+                            tracing::warn!(
+                                url = %self.endpoints.endpoints[index].url,
+                                error = %e,
+                                "Sending transaction failed, trying next RPC endpoint"
+                            );
+                            continue;
+                        }
+                    }
+                    return Err(OrcaError::from_send_error(
+                        format!("Failed to {}", operation),
+                        signature,
+                        e,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Handle to the background endpoint health checks
+///
+/// Dropping the handle stops the checks.
+#[derive(Debug)]
+pub struct EndpointHealthChecks {
+    task_handle: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for EndpointHealthChecks {
+    fn drop(&mut self) {
+        self.task_handle.abort();
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, atomic::Ordering},
+    time::Instant,
+};
+
+use futures::stream::{self, StreamExt};
+use solana_client::{
+    rpc_client::GetConfirmedSignaturesForAddress2Config, rpc_config::RpcTransactionConfig,
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::account::Account;
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
+
+use super::*;
+use crate::{global::MAX_MULTIPLE_ACCOUNTS, types::OrcaResult};
+
+/// Number of requests bulk fetches keep in flight unless overridden
+pub const DEFAULT_FETCH_CONCURRENCY: usize = 16;
+// Number of fetched transactions kept for reuse across history, volume and stats paths
+const TRANSACTION_CACHE_CAPACITY: usize = 4_096;
+// Signatures per `getSignaturesForAddress` call, the RPC maximum
+const SIGNATURE_PAGE_SIZE: usize = 1_000;
+
+pub(crate) type FetchedTransaction = Arc<EncodedConfirmedTransactionWithStatusMeta>;
+
+/// Bounded cache of confirmed transactions, evicted in insertion order
+#[derive(Debug, Default)]
+pub(crate) struct TransactionCache {
+    entries: HashMap<Signature, FetchedTransaction>,
+    order: VecDeque<Signature>,
+}
+
+impl TransactionCache {
+    fn get(&self, signature: &Signature) -> Option<FetchedTransaction> {
+        self.entries.get(signature).cloned()
+    }
+
+    fn insert(&mut self, signature: Signature, transaction: FetchedTransaction) {
+        if self.entries.insert(signature, transaction).is_some() {
+            return;
+        }
+        self.order.push_back(signature);
+        while self.order.len() > TRANSACTION_CACHE_CAPACITY {
+            if let Some(evicted) = self.order.pop_front() {
+                self.entries.remove(&evicted);
+            }
+        }
+    }
+}
+
+impl OrcaClient {
+    /// Sets how many requests history, volume, stats and multi-pool queries keep in flight
+    ///
+    /// Lower this for rate-limited RPC endpoints. Values below 1 are treated as 1.
+    ///
+    /// # Example
+    /// ```rust
+    /// client.set_fetch_concurrency(4);
+    /// let history = client.get_price_history_from_chain("whirlpool_address_here", 500).await?;
+    /// ```
+    pub fn set_fetch_concurrency(&self, concurrency: usize) {
+        self.fetch_concurrency
+            .store(concurrency.max(1), Ordering::SeqCst);
+    }
+
+    /// Concurrency limit of bulk fetches, as set by `set_fetch_concurrency`
+    pub(crate) fn fetch_concurrency(&self) -> usize {
+        self.fetch_concurrency.load(Ordering::SeqCst).max(1)
+    }
+
+    /// Fetches confirmed transactions in parallel, preserving input order
+    ///
+    /// Transactions are requested as `JsonParsed` and served from the client-wide cache when
+    /// already fetched by another query. Transactions that cannot be fetched yield `None`.
+    #[tracing::instrument(
+        skip_all,
+        fields(count = signatures.len(), elapsed_ms = tracing::field::Empty)
+    )]
+    pub(crate) async fn fetch_transactions(
+        &self,
+        signatures: &[Signature],
+    ) -> OrcaResult<Vec<Option<FetchedTransaction>>> {
+        let concurrency = self.fetch_concurrency();
+        let started = Instant::now();
+        let transactions = stream::iter(signatures.iter().copied())
+            .map(|signature| async move {
+                if let Some(cached) = self
+                    .transaction_cache
+                    .lock()
+                    .ok()
+                    .and_then(|cache| cache.get(&signature))
+                {
+                    return Some(cached);
+                }
+                match self
+                    .rpc_call("Failed to get transaction", |client| {
+                        client.get_transaction_with_config(
+                            &signature,
+                            RpcTransactionConfig {
+                                encoding: Some(UiTransactionEncoding::JsonParsed),
+                                commitment: Some(CommitmentConfig::confirmed()),
+                                max_supported_transaction_version: Some(0),
+                            },
+                        )
+                    })
+                    .await
+                {
+                    Ok(transaction) => {
+                        let transaction = Arc::new(transaction);
+                        if let Ok(mut cache) = self.transaction_cache.lock() {
+                            cache.insert(signature, transaction.clone());
+                        }
+                        Some(transaction)
+                    }
+                    Err(e) => {
+                        tracing::debug!("Failed to get transaction {}: {:?}", signature, e);
+                        None
+                    }
+                }
+            })
+            .buffered(concurrency)
+            .collect()
+            .await;
+        tracing::Span::current().record("elapsed_ms", started.elapsed().as_millis() as u64);
+        Ok(transactions)
+    }
+
+    /// Lists confirmed signatures of an address, newest first, with their block times
+    ///
+    /// Pages backwards with `before` until `until` is reached, a block time older than `since`
+    /// is seen, or `max_signatures` have been collected. Failed transactions and signatures
+    /// without a block time are left out.
+    pub(crate) async fn get_signatures_paginated(
+        &self,
+        address: &Pubkey,
+        until: Option<Signature>,
+        since: Option<i64>,
+        max_signatures: usize,
+    ) -> OrcaResult<Vec<(Signature, i64)>> {
+        let mut signatures = Vec::new();
+        let mut before: Option<Signature> = None;
+        loop {
+            let page = self
+                .rpc_call("Failed to get signatures", |client| {
+                    client.get_signatures_for_address_with_config(
+                        address,
+                        GetConfirmedSignaturesForAddress2Config {
+                            before,
+                            until,
+                            limit: Some(SIGNATURE_PAGE_SIZE),
+                            commitment: Some(CommitmentConfig::confirmed()),
+                        },
+                    )
+                })
+                .await?;
+            let mut reached_end = page.len() < SIGNATURE_PAGE_SIZE;
+            for sig_info in &page {
+                let block_time = match sig_info.block_time {
+                    Some(block_time) => block_time,
+                    None => continue,
+                };
+                if since.is_some_and(|since| block_time < since)
+                    || signatures.len() >= max_signatures
+                {
+                    reached_end = true;
+                    break;
+                }
+                if sig_info.err.is_some() {
+                    continue;
+                }
+                if let Ok(signature) = Signature::from_str(&sig_info.signature) {
+                    signatures.push((signature, block_time));
+                }
+            }
+            if reached_end || signatures.len() >= max_signatures {
+                break;
+            }
+            before = page
+                .last()
+                .and_then(|sig_info| Signature::from_str(&sig_info.signature).ok());
+        }
+        Ok(signatures)
+    }
+
+    /// Fetches any number of accounts, preserving input order
+    ///
+    /// Addresses are grouped into `getMultipleAccounts` calls of at most 100 accounts, the RPC
+    /// limit, which run in parallel up to the fetch concurrency. Missing accounts yield `None`.
+    pub(crate) async fn get_multiple_accounts_batched(
+        &self,
+        addresses: &[Pubkey],
+        context: &str,
+    ) -> OrcaResult<Vec<Option<Account>>> {
+        let commitment = self.rpc_client()?.commitment();
+        self.get_multiple_accounts_batched_with_commitment(addresses, commitment, context)
+            .await
+    }
+
+    /// Like `get_multiple_accounts_batched`, reading at the given commitment
+    #[tracing::instrument(skip_all, fields(count = addresses.len()))]
+    pub(crate) async fn get_multiple_accounts_batched_with_commitment(
+        &self,
+        addresses: &[Pubkey],
+        commitment: CommitmentConfig,
+        context: &str,
+    ) -> OrcaResult<Vec<Option<Account>>> {
+        let concurrency = self.fetch_concurrency();
+        // Built up front so the returned future stays `Send` when spawned
+        let requests: Vec<_> = addresses
+            .chunks(MAX_MULTIPLE_ACCOUNTS)
+            .map(|batch| {
+                self.rpc_call(context, move |client| {
+                    client.get_multiple_accounts_with_commitment(batch, commitment)
+                })
+            })
+            .collect();
+        let batches: Vec<Vec<Option<Account>>> = stream::iter(requests)
+            .buffered(concurrency)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .map(|batch| batch.map(|response| response.value))
+            .collect::<OrcaResult<_>>()?;
+        Ok(batches.into_iter().flatten().collect())
+    }
+
+    /// Fetches an account, returning `OrcaError::AccountNotFound` when it does not exist
+    pub(crate) async fn get_existing_account(
+        &self,
+        address: &Pubkey,
+        context: &str,
+    ) -> OrcaResult<Account> {
+        self.rpc_call(context, |client| {
+            client.get_account_with_commitment(address, client.commitment())
+        })
+        .await?
+        .value
+        .ok_or(OrcaError::AccountNotFound(*address))
+    }
+}
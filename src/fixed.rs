@@ -0,0 +1,551 @@
+use crate::global::{
+    BPS_DENOMINATOR, MAX_SQRT_PRICE_X64, MAX_TICK_INDEX, MIN_SQRT_PRICE_X64, MIN_TICK_INDEX,
+};
+
+// 2^64, the scale of Q64.64 numbers, for the initial tick estimate only
+const Q64: f64 = 18_446_744_073_709_551_616.0;
+
+/// Unsigned 256-bit integer, wide enough for the product of two Q64.64 numbers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct U256 {
+    // Field order makes the derived ordering compare the high half first
+    hi: u128,
+    lo: u128,
+}
+
+impl U256 {
+    pub(crate) const ZERO: Self = Self { hi: 0, lo: 0 };
+
+    pub(crate) const fn from_u128(value: u128) -> Self {
+        Self { hi: 0, lo: value }
+    }
+
+    /// Full product of two u128 values
+    pub(crate) fn mul_u128(a: u128, b: u128) -> Self {
+        const MASK: u128 = u64::MAX as u128;
+        let (a0, a1) = (a & MASK, a >> 64);
+        let (b0, b1) = (b & MASK, b >> 64);
+        let (p00, p01, p10, p11) = (a0 * b0, a0 * b1, a1 * b0, a1 * b1);
+        let mid = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+        Self {
+            hi: p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64),
+            lo: (p00 & MASK) | (mid << 64),
+        }
+    }
+
+    pub(crate) fn checked_mul_u128(self, rhs: u128) -> Option<Self> {
+        let low = Self::mul_u128(self.lo, rhs);
+        let high = Self::mul_u128(self.hi, rhs);
+        if high.hi != 0 {
+            return None;
+        }
+        Some(Self {
+            hi: low.hi.checked_add(high.lo)?,
+            lo: low.lo,
+        })
+    }
+
+    pub(crate) fn checked_add(self, rhs: Self) -> Option<Self> {
+        let (lo, carry) = self.lo.overflowing_add(rhs.lo);
+        Some(Self {
+            hi: self.hi.checked_add(rhs.hi)?.checked_add(carry as u128)?,
+            lo,
+        })
+    }
+
+    pub(crate) fn checked_sub(self, rhs: Self) -> Option<Self> {
+        (self >= rhs).then(|| self.wrapping_sub(rhs))
+    }
+
+    fn wrapping_sub(self, rhs: Self) -> Self {
+        let (lo, borrow) = self.lo.overflowing_sub(rhs.lo);
+        Self {
+            hi: self.hi.wrapping_sub(rhs.hi).wrapping_sub(borrow as u128),
+            lo,
+        }
+    }
+
+    /// Left shift that fails instead of dropping set bits
+    pub(crate) fn checked_shl(self, shift: u32) -> Option<Self> {
+        let shifted = self.shl(shift);
+        (shifted.shr(shift) == self).then_some(shifted)
+    }
+
+    pub(crate) fn shl(self, shift: u32) -> Self {
+        match shift {
+            0 => self,
+            1..128 => Self {
+                hi: (self.hi << shift) | (self.lo >> (128 - shift)),
+                lo: self.lo << shift,
+            },
+            128..256 => Self {
+                hi: self.lo << (shift - 128),
+                lo: 0,
+            },
+            _ => Self::ZERO,
+        }
+    }
+
+    pub(crate) fn shr(self, shift: u32) -> Self {
+        match shift {
+            0 => self,
+            1..128 => Self {
+                hi: self.hi >> shift,
+                lo: (self.lo >> shift) | (self.hi << (128 - shift)),
+            },
+            128..256 => Self {
+                hi: 0,
+                lo: self.hi >> (shift - 128),
+            },
+            _ => Self::ZERO,
+        }
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.hi == 0 && self.lo == 0
+    }
+
+    fn bit(&self, index: u32) -> bool {
+        if index < 128 {
+            (self.lo >> index) & 1 == 1
+        } else {
+            (self.hi >> (index - 128)) & 1 == 1
+        }
+    }
+
+    /// Quotient and remainder, or None when dividing by zero
+    pub(crate) fn checked_div_rem(self, divisor: Self) -> Option<(Self, Self)> {
+        if divisor.is_zero() {
+            return None;
+        }
+        if self < divisor {
+            return Some((Self::ZERO, self));
+        }
+        let mut quotient = Self::ZERO;
+        let mut remainder = Self::ZERO;
+        for index in (0..256).rev() {
+            let carry = remainder.hi >> 127 == 1;
+            remainder = remainder.shl(1);
+            remainder.lo |= self.bit(index) as u128;
+            if carry || remainder >= divisor {
+                remainder = remainder.wrapping_sub(divisor);
+                quotient = quotient.shl(1);
+                quotient.lo |= 1;
+            } else {
+                quotient = quotient.shl(1);
+            }
+        }
+        Some((quotient, remainder))
+    }
+
+    /// Division rounded up when `round_up` is set and down otherwise
+    pub(crate) fn checked_div_round(self, divisor: Self, round_up: bool) -> Option<Self> {
+        let (quotient, remainder) = self.checked_div_rem(divisor)?;
+        if round_up && !remainder.is_zero() {
+            quotient.checked_add(Self::from_u128(1))
+        } else {
+            Some(quotient)
+        }
+    }
+
+    pub(crate) fn try_into_u128(self) -> Option<u128> {
+        (self.hi == 0).then_some(self.lo)
+    }
+}
+
+/// `a * b / denominator` rounded down, without intermediate overflow
+///
+/// Returns None when `denominator` is zero or the result does not fit in a u128.
+pub fn mul_div_floor(a: u128, b: u128, denominator: u128) -> Option<u128> {
+    U256::mul_u128(a, b)
+        .checked_div_round(U256::from_u128(denominator), false)?
+        .try_into_u128()
+}
+
+/// `a * b / denominator` rounded up, without intermediate overflow
+///
+/// Returns None when `denominator` is zero or the result does not fit in a u128.
+pub fn mul_div_ceil(a: u128, b: u128, denominator: u128) -> Option<u128> {
+    U256::mul_u128(a, b)
+        .checked_div_round(U256::from_u128(denominator), true)?
+        .try_into_u128()
+}
+
+/// Q64.64 sqrt price at a tick, computed exactly as the Whirlpool program does
+///
+/// Ticks outside the Whirlpool bounds are clamped.
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::fixed::sqrt_price_x64_at_tick;
+///
+/// assert_eq!(sqrt_price_x64_at_tick(0), 1 << 64);
+/// ```
+pub fn sqrt_price_x64_at_tick(tick_index: i32) -> u128 {
+    let tick_index = tick_index.clamp(MIN_TICK_INDEX, MAX_TICK_INDEX);
+    if tick_index >= 0 {
+        sqrt_price_at_positive_tick(tick_index)
+    } else {
+        sqrt_price_at_negative_tick(tick_index)
+    }
+}
+
+/// 1.0001^(2^i / 2) in Q32.96, for bits 1 through 18 of a positive tick
+const POSITIVE_TICK_FACTORS: [u128; 18] = [
+    79236085330515764027303304731,
+    79244008939048815603706035061,
+    79259858533276714757314932305,
+    79291567232598584799939703904,
+    79355022692464371645785046466,
+    79482085999252804386437311141,
+    79736823300114093921829183326,
+    80248749790819932309965073892,
+    81282483887344747381513967011,
+    83390072131320151908154831281,
+    87770609709833776024991924138,
+    97234110755111693312479820773,
+    119332217159966728226237229890,
+    179736315981702064433883588727,
+    407748233172238350107850275304,
+    2098478828474011932436660412517,
+    55581415166113811149459800483533,
+    38992368544603139932233054999993551,
+];
+
+/// 1.0001^(-2^i / 2) in Q64.64, for bits 1 through 18 of a negative tick's magnitude
+const NEGATIVE_TICK_FACTORS: [u128; 18] = [
+    18444899583751176498,
+    18443055278223354162,
+    18439367220385604838,
+    18431993317065449817,
+    18417254355718160513,
+    18387811781193591352,
+    18329067761203520168,
+    18212142134806087854,
+    17980523815641551639,
+    17526086738831147013,
+    16651378430235024244,
+    15030750278693429944,
+    12247334978882834399,
+    8131365268884726200,
+    3584323654723342297,
+    696457651847595233,
+    26294789957452057,
+    37481735321082,
+];
+
+fn sqrt_price_at_positive_tick(tick_index: i32) -> u128 {
+    let mut ratio = U256::from_u128(if tick_index & 1 != 0 {
+        79232123823359799118286999567
+    } else {
+        79228162514264337593543950336
+    });
+    for (bit, factor) in POSITIVE_TICK_FACTORS.iter().enumerate() {
+        if tick_index & (2 << bit) != 0 {
+            // Both factors are below 2^128, so the product always fits
+            ratio = ratio.checked_mul_u128(*factor).unwrap_or_default().shr(96);
+        }
+    }
+    ratio.shr(32).lo
+}
+
+fn sqrt_price_at_negative_tick(tick_index: i32) -> u128 {
+    let magnitude = tick_index.unsigned_abs();
+    let mut ratio: u128 = if magnitude & 1 != 0 {
+        18445821805675392311
+    } else {
+        18446744073709551616
+    };
+    for (bit, factor) in NEGATIVE_TICK_FACTORS.iter().enumerate() {
+        if magnitude & (2 << bit) != 0 {
+            ratio = (ratio * factor) >> 64;
+        }
+    }
+    ratio
+}
+
+/// Highest tick whose sqrt price does not exceed `sqrt_price_x64`
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::fixed::{sqrt_price_x64_at_tick, tick_index_at_sqrt_price_x64};
+///
+/// assert_eq!(tick_index_at_sqrt_price_x64(sqrt_price_x64_at_tick(-1234)), -1234);
+/// ```
+pub fn tick_index_at_sqrt_price_x64(sqrt_price_x64: u128) -> i32 {
+    let sqrt_price_x64 = sqrt_price_x64.clamp(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64);
+    // The floating point estimate is off by at most a tick; the exact sqrt prices settle it
+    let estimate = (2.0 * (sqrt_price_x64 as f64 / Q64).ln() / 1.0001f64.ln()).floor() as i32;
+    let mut tick_index = estimate.clamp(MIN_TICK_INDEX, MAX_TICK_INDEX);
+    while tick_index > MIN_TICK_INDEX && sqrt_price_x64_at_tick(tick_index) > sqrt_price_x64 {
+        tick_index -= 1;
+    }
+    while tick_index < MAX_TICK_INDEX && sqrt_price_x64_at_tick(tick_index + 1) <= sqrt_price_x64 {
+        tick_index += 1;
+    }
+    tick_index
+}
+
+/// Token A moved when the price goes between two sqrt prices with constant liquidity
+///
+/// Returns None when the amount does not fit in a u64.
+pub fn amount_a_delta(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Option<u64> {
+    let (lower, upper) = sorted(sqrt_price_0, sqrt_price_1);
+    let numerator = U256::mul_u128(liquidity, upper - lower).checked_shl(64)?;
+    let denominator = U256::mul_u128(upper, lower);
+    let amount = numerator
+        .checked_div_round(denominator, round_up)?
+        .try_into_u128()?;
+    u64::try_from(amount).ok()
+}
+
+/// Token B moved when the price goes between two sqrt prices with constant liquidity
+///
+/// Returns None when the amount does not fit in a u64.
+pub fn amount_b_delta(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Option<u64> {
+    let (lower, upper) = sorted(sqrt_price_0, sqrt_price_1);
+    let product = U256::mul_u128(liquidity, upper - lower);
+    let amount = product
+        .checked_div_round(U256::from_u128(1).shl(64), round_up)?
+        .try_into_u128()?;
+    u64::try_from(amount).ok()
+}
+
+/// Liquidity that `amount` of token A provides between two sqrt prices, rounded down
+pub fn liquidity_from_amount_a(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount: u64,
+) -> Option<u128> {
+    let (lower, upper) = sorted(sqrt_price_0, sqrt_price_1);
+    let numerator = U256::mul_u128(lower, upper).checked_mul_u128(amount as u128)?;
+    let denominator = U256::from_u128(upper - lower).shl(64);
+    numerator
+        .checked_div_round(denominator, false)?
+        .try_into_u128()
+}
+
+/// Liquidity that `amount` of token B provides between two sqrt prices, rounded down
+pub fn liquidity_from_amount_b(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount: u64,
+) -> Option<u128> {
+    let (lower, upper) = sorted(sqrt_price_0, sqrt_price_1);
+    ((amount as u128) << 64).checked_div(upper - lower)
+}
+
+/// Sqrt price after adding (exact input) or removing (exact output) `amount` of one token
+///
+/// Token A moves the price with rounding up and token B with rounding down, so the pool
+/// never gives out more than the amount pays for.
+pub(crate) fn next_sqrt_price(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount: u64,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+) -> Option<u128> {
+    if amount_specified_is_input == a_to_b {
+        next_sqrt_price_from_a(sqrt_price, liquidity, amount, amount_specified_is_input)
+    } else {
+        next_sqrt_price_from_b(sqrt_price, liquidity, amount, amount_specified_is_input)
+    }
+}
+
+fn next_sqrt_price_from_a(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount: u64,
+    add: bool,
+) -> Option<u128> {
+    if amount == 0 {
+        return Some(sqrt_price);
+    }
+    let product = U256::mul_u128(sqrt_price, amount as u128);
+    let numerator = U256::mul_u128(liquidity, sqrt_price).checked_shl(64)?;
+    let liquidity_x64 = U256::from_u128(liquidity).shl(64);
+    let denominator = if add {
+        liquidity_x64.checked_add(product)?
+    } else {
+        liquidity_x64.checked_sub(product)?
+    };
+    numerator
+        .checked_div_round(denominator, true)?
+        .try_into_u128()
+}
+
+fn next_sqrt_price_from_b(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount: u64,
+    add: bool,
+) -> Option<u128> {
+    if liquidity == 0 {
+        return None;
+    }
+    let amount_x64 = (amount as u128) << 64;
+    if add {
+        sqrt_price.checked_add(amount_x64 / liquidity)
+    } else {
+        sqrt_price.checked_sub(amount_x64.div_ceil(liquidity))
+    }
+}
+
+/// Lowest output accepted for `amount` with a `slippage_bps` tolerance
+///
+/// Computes `amount * (10_000 - slippage_bps) / 10_000` rounded down, so the bound never
+/// accepts less than the tolerance allows. Tolerances above 10_000 are capped at 10_000.
+pub fn min_amount_with_slippage(amount: u64, slippage_bps: u16) -> u64 {
+    let denominator = BPS_DENOMINATOR as u128;
+    let tolerance = slippage_bps.min(BPS_DENOMINATOR) as u128;
+    mul_div_floor(amount as u128, denominator - tolerance, denominator)
+        .map_or(0, |amount| amount as u64)
+}
+
+/// Highest input accepted for `amount` with a `slippage_bps` tolerance
+///
+/// Computes `amount * (10_000 + slippage_bps) / 10_000` rounded up, saturating at `u64::MAX`.
+pub fn max_amount_with_slippage(amount: u64, slippage_bps: u16) -> u64 {
+    let denominator = BPS_DENOMINATOR as u128;
+    let tolerance = slippage_bps.min(BPS_DENOMINATOR) as u128;
+    mul_div_ceil(amount as u128, denominator + tolerance, denominator)
+        .and_then(|amount| u64::try_from(amount).ok())
+        .unwrap_or(u64::MAX)
+}
+
+fn sorted(a: u128, b: u128) -> (u128, u128) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const Q64: u128 = 1 << 64;
+
+    #[test]
+    fn sqrt_price_at_tick_bounds_and_neighbours() {
+        assert_eq!(sqrt_price_x64_at_tick(0), Q64);
+        assert_eq!(sqrt_price_x64_at_tick(1), 18447666387855959850);
+        assert_eq!(sqrt_price_x64_at_tick(-1), 18445821805675392311);
+        assert_eq!(sqrt_price_x64_at_tick(MIN_TICK_INDEX), MIN_SQRT_PRICE_X64);
+        assert_eq!(sqrt_price_x64_at_tick(MAX_TICK_INDEX), MAX_SQRT_PRICE_X64);
+    }
+
+    #[test]
+    fn sqrt_price_at_tick_clamps_out_of_range_ticks() {
+        assert_eq!(
+            sqrt_price_x64_at_tick(MIN_TICK_INDEX - 1),
+            MIN_SQRT_PRICE_X64
+        );
+        assert_eq!(
+            sqrt_price_x64_at_tick(MAX_TICK_INDEX + 1),
+            MAX_SQRT_PRICE_X64
+        );
+    }
+
+    #[test]
+    fn tick_at_sqrt_price_round_trips() {
+        for tick_index in [MIN_TICK_INDEX, -64, -1, 0, 1, 64, MAX_TICK_INDEX] {
+            let sqrt_price = sqrt_price_x64_at_tick(tick_index);
+            assert_eq!(tick_index_at_sqrt_price_x64(sqrt_price), tick_index);
+        }
+        // Between two ticks the lower one is returned
+        assert_eq!(tick_index_at_sqrt_price_x64(Q64 + 1), 0);
+        assert_eq!(tick_index_at_sqrt_price_x64(Q64 - 1), -1);
+    }
+
+    #[test]
+    fn div_rem_by_zero_fails() {
+        assert_eq!(U256::from_u128(1).checked_div_rem(U256::ZERO), None);
+        assert_eq!(U256::ZERO.checked_div_rem(U256::ZERO), None);
+    }
+
+    #[test]
+    fn div_rem_of_smaller_dividend_is_zero() {
+        let (quotient, remainder) = U256::from_u128(7)
+            .checked_div_rem(U256::from_u128(9))
+            .unwrap();
+        assert_eq!(quotient, U256::ZERO);
+        assert_eq!(remainder, U256::from_u128(7));
+    }
+
+    #[test]
+    fn div_rem_of_full_width_values() {
+        let max = U256::mul_u128(u128::MAX, u128::MAX);
+        let (quotient, remainder) = max.checked_div_rem(U256::from_u128(u128::MAX)).unwrap();
+        assert_eq!(quotient, U256::from_u128(u128::MAX));
+        assert_eq!(remainder, U256::ZERO);
+        let (quotient, remainder) = max.checked_div_rem(max).unwrap();
+        assert_eq!(quotient, U256::from_u128(1));
+        assert_eq!(remainder, U256::ZERO);
+        let (quotient, remainder) = max.checked_div_rem(U256::from_u128(1)).unwrap();
+        assert_eq!(quotient, max);
+        assert_eq!(remainder, U256::ZERO);
+    }
+
+    #[test]
+    fn div_rem_with_high_divisor() {
+        // (2^200 + 5) / 2^130 = 2^70 remainder 5
+        let dividend = U256::from_u128(1)
+            .shl(200)
+            .checked_add(U256::from_u128(5))
+            .unwrap();
+        let (quotient, remainder) = dividend
+            .checked_div_rem(U256::from_u128(1).shl(130))
+            .unwrap();
+        assert_eq!(quotient, U256::from_u128(1 << 70));
+        assert_eq!(remainder, U256::from_u128(5));
+    }
+
+    #[test]
+    fn div_rem_reconstructs_dividend() {
+        let dividend = U256::mul_u128(0x1234_5678_9abc_def0_1122_3344_5566_7788, u128::MAX - 12345);
+        let divisor = U256::from_u128(0xfedc_ba98_7654_3210_0f1e_2d3c);
+        let (quotient, remainder) = dividend.checked_div_rem(divisor).unwrap();
+        assert!(remainder < divisor);
+        let product = quotient.checked_mul_u128(divisor.lo).unwrap();
+        assert_eq!(product.checked_add(remainder), Some(dividend));
+    }
+
+    #[test]
+    fn div_round_rounds_up_only_with_remainder() {
+        let ten = U256::from_u128(10);
+        assert_eq!(
+            ten.checked_div_round(U256::from_u128(3), true),
+            Some(U256::from_u128(4))
+        );
+        assert_eq!(
+            ten.checked_div_round(U256::from_u128(3), false),
+            Some(U256::from_u128(3))
+        );
+        assert_eq!(
+            ten.checked_div_round(U256::from_u128(5), true),
+            Some(U256::from_u128(2))
+        );
+    }
+
+    #[test]
+    fn mul_div_handles_full_width_products() {
+        assert_eq!(
+            mul_div_floor(u128::MAX, u128::MAX, u128::MAX),
+            Some(u128::MAX)
+        );
+        assert_eq!(mul_div_ceil(u128::MAX, 2, 3), Some(u128::MAX / 3 * 2));
+        assert_eq!(mul_div_floor(u128::MAX, 2, 1), None);
+        assert_eq!(mul_div_floor(1, 1, 0), None);
+        assert_eq!(mul_div_floor(10, 1, 3), Some(3));
+        assert_eq!(mul_div_ceil(10, 1, 3), Some(4));
+    }
+}
@@ -0,0 +1,137 @@
+use solana_sdk::program_pack::Pack;
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+
+use super::*;
+use crate::{pool::PoolInfo, types::OrcaResult, validation::parse_address};
+
+/// How freeze findings are surfaced when building swaps and liquidity operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FreezeCheckMode {
+    /// Skip the check
+    Off,
+    /// Fail on frozen accounts, log active freeze authorities
+    #[default]
+    Warn,
+    /// Fail on frozen accounts and on mints with an active freeze authority
+    Deny,
+}
+
+/// A freeze-related finding for an operation
+#[derive(Debug, Clone, PartialEq)]
+pub enum FreezeRisk {
+    /// The token account is frozen, so any transfer touching it fails
+    FrozenAccount { account: Pubkey, mint: Pubkey },
+    /// The mint can freeze holders after the trade
+    FreezeAuthority { mint: Pubkey, authority: Pubkey },
+}
+
+impl OrcaClient {
+    /// Inspects token accounts and mints for frozen state and active freeze authorities
+    ///
+    /// Accounts that do not exist yet are skipped.
+    ///
+    /// # Example
+    /// ```rust
+    /// let risks = client.check_freeze_risks(&[user_token_account, vault], &[mint]).await?;
+    /// for risk in risks {
+    ///     println!("{:?}", risk);
+    /// }
+    /// ```
+    pub async fn check_freeze_risks(
+        &self,
+        token_accounts: &[Pubkey],
+        mints: &[Pubkey],
+    ) -> OrcaResult<Vec<FreezeRisk>> {
+        let mut addresses = token_accounts.to_vec();
+        addresses.extend_from_slice(mints);
+        let accounts = self
+            .get_multiple_accounts_batched(&addresses, "Failed to get accounts")
+            .await?;
+        let mut risks = Vec::new();
+        for (address, account) in token_accounts.iter().zip(&accounts) {
+            let Some(account) = account else {
+                continue;
+            };
+            if account.data.len() < TokenAccount::LEN {
+                continue;
+            }
+            if let Ok(token_account) =
+                TokenAccount::unpack_from_slice(&account.data[..TokenAccount::LEN])
+                && token_account.state == AccountState::Frozen
+            {
+                risks.push(FreezeRisk::FrozenAccount {
+                    account: *address,
+                    mint: token_account.mint,
+                });
+            }
+        }
+        for (mint, account) in mints.iter().zip(&accounts[token_accounts.len()..]) {
+            let Some(account) = account else {
+                continue;
+            };
+            if account.data.len() < Mint::LEN {
+                continue;
+            }
+            if let Ok(mint_data) = Mint::unpack_from_slice(&account.data[..Mint::LEN]) {
+                let authority: Option<Pubkey> = mint_data.freeze_authority.into();
+                if let Some(authority) = authority {
+                    risks.push(FreezeRisk::FreezeAuthority {
+                        mint: *mint,
+                        authority,
+                    });
+                }
+            }
+        }
+        Ok(risks)
+    }
+
+    /// Applies a freeze check mode to the findings for an operation
+    pub(crate) async fn enforce_freeze_check(
+        &self,
+        mode: FreezeCheckMode,
+        token_accounts: &[Pubkey],
+        mints: &[Pubkey],
+    ) -> OrcaResult<()> {
+        if mode == FreezeCheckMode::Off {
+            return Ok(());
+        }
+        let risks = self.check_freeze_risks(token_accounts, mints).await?;
+        let blocking: Vec<FreezeRisk> = risks
+            .iter()
+            .filter(|risk| {
+                mode == FreezeCheckMode::Deny || matches!(risk, FreezeRisk::FrozenAccount { .. })
+            })
+            .cloned()
+            .collect();
+        if !blocking.is_empty() {
+            return Err(OrcaError::FreezeRisk(blocking));
+        }
+        for risk in &risks {
+            if let FreezeRisk::FreezeAuthority { mint, authority } = risk {
+                tracing::warn!("Mint {} has an active freeze authority {}", mint, authority);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the owner's token accounts, the pool vaults and both pool mints
+    pub(crate) async fn enforce_pool_freeze_check(
+        &self,
+        mode: FreezeCheckMode,
+        owner: &Pubkey,
+        pool: &PoolInfo,
+    ) -> OrcaResult<()> {
+        if mode == FreezeCheckMode::Off {
+            return Ok(());
+        }
+        let mint_a = parse_address("token_mint_a", &pool.token_mint_a)?;
+        let mint_b = parse_address("token_mint_b", &pool.token_mint_b)?;
+        let mut token_accounts = self
+            .get_associated_token_addresses(owner, &[mint_a, mint_b])
+            .await?;
+        token_accounts.push(parse_address("token_vault_a", &pool.token_vault_a)?);
+        token_accounts.push(parse_address("token_vault_b", &pool.token_vault_b)?);
+        self.enforce_freeze_check(mode, &token_accounts, &[mint_a, mint_b])
+            .await
+    }
+}
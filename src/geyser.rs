@@ -0,0 +1,222 @@
+use std::{collections::HashMap, future::Future, time::Duration};
+
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use tokio::sync::{mpsc, watch};
+use yellowstone_grpc_client::{ClientTlsConfig, GeyserGrpcClient};
+use yellowstone_grpc_proto::prelude::{
+    CommitmentLevel, SubscribeRequest, SubscribeRequestFilterAccounts,
+    SubscribeRequestFilterTransactions, SubscribeRequestPing, SubscribeUpdate,
+    subscribe_update::UpdateOneof,
+};
+
+use crate::{
+    events::{SwapEvent, parse_swap_events},
+    pool::decode_whirlpool,
+    types::{OrcaError, OrcaResult},
+    watch::PoolSnapshot,
+};
+
+// Delay before reconnecting a dropped gRPC subscription
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(2);
+// Name of the filter in every subscription request; updates are not told apart by it
+const FILTER_NAME: &str = "orca-sdk";
+
+/// Yellowstone gRPC endpoint that replaces JSON-RPC polling and WebSocket subscriptions
+///
+/// Once set on the builder, `watch_pool` follows pool account updates and
+/// `subscribe_swap_events` follows Whirlpool transactions streamed by the Geyser plugin.
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::geyser::GeyserConfig;
+///
+/// let client = OrcaClient::builder()
+///     .rpc_url("https://my-provider.example.com")
+///     .geyser(GeyserConfig::new("https://grpc.my-provider.example.com").with_x_token("TOKEN"))
+///     .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct GeyserConfig {
+    pub endpoint: String,
+    /// Authentication token sent as the `x-token` header
+    pub x_token: Option<String>,
+}
+
+impl GeyserConfig {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            x_token: None,
+        }
+    }
+
+    pub fn with_x_token(mut self, x_token: impl Into<String>) -> Self {
+        self.x_token = Some(x_token.into());
+        self
+    }
+}
+
+/// Forwards swaps of transactions mentioning `mentioned` until the consumer goes away
+pub(crate) async fn stream_swap_events(
+    config: GeyserConfig,
+    program_id: Pubkey,
+    mentioned: Pubkey,
+    pool: Option<Pubkey>,
+    event_tx: mpsc::Sender<SwapEvent>,
+) {
+    let request = SubscribeRequest {
+        transactions: HashMap::from([(
+            FILTER_NAME.to_string(),
+            SubscribeRequestFilterTransactions {
+                vote: Some(false),
+                failed: Some(false),
+                account_include: vec![mentioned.to_string()],
+                ..Default::default()
+            },
+        )]),
+        commitment: Some(CommitmentLevel::Confirmed as i32),
+        ..Default::default()
+    };
+    run_subscription(config, request, move |update| {
+        let event_tx = event_tx.clone();
+        async move {
+            let UpdateOneof::Transaction(update) = update else {
+                return !event_tx.is_closed();
+            };
+            let Some(info) = update.transaction else {
+                return true;
+            };
+            let Some(meta) = info.meta else {
+                return true;
+            };
+            let Ok(signature) = Signature::try_from(info.signature.as_slice()) else {
+                return true;
+            };
+            for event in parse_swap_events(&meta.log_messages, &program_id, signature, update.slot)
+            {
+                if pool.is_some_and(|pool| pool != event.pool) {
+                    continue;
+                }
+                if event_tx.send(event).await.is_err() {
+                    return false;
+                }
+            }
+            true
+        }
+    })
+    .await;
+}
+
+/// Publishes every update of a Whirlpool account until the watcher goes away
+pub(crate) async fn stream_pool_updates(
+    config: GeyserConfig,
+    pool: Pubkey,
+    state_tx: watch::Sender<PoolSnapshot>,
+) {
+    let request = SubscribeRequest {
+        accounts: HashMap::from([(
+            FILTER_NAME.to_string(),
+            SubscribeRequestFilterAccounts {
+                account: vec![pool.to_string()],
+                ..Default::default()
+            },
+        )]),
+        commitment: Some(CommitmentLevel::Confirmed as i32),
+        ..Default::default()
+    };
+    let pool_address = pool.to_string();
+    run_subscription(config, request, move |update| {
+        let state_tx = state_tx.clone();
+        let pool_address = pool_address.clone();
+        async move {
+            let UpdateOneof::Account(update) = update else {
+                return !state_tx.is_closed();
+            };
+            let Some(pool) = update
+                .account
+                .and_then(|account| decode_whirlpool(&account.data, &pool_address))
+            else {
+                return true;
+            };
+            state_tx
+                .send(PoolSnapshot {
+                    pool,
+                    fetched_at: chrono::Utc::now(),
+                })
+                .is_ok()
+        }
+    })
+    .await;
+}
+
+/// Feeds updates to `on_update` until it returns false, reconnecting when the stream drops
+///
+/// Server pings are answered so that load balancers keep idle subscriptions open.
+async fn run_subscription<F, Fut>(config: GeyserConfig, request: SubscribeRequest, mut on_update: F)
+where
+    F: FnMut(UpdateOneof) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    loop {
+        match subscribe(&config, request.clone()).await {
+            Ok((sink, stream)) => {
+                tokio::pin!(sink);
+                tokio::pin!(stream);
+                while let Some(update) = stream.next().await {
+                    let update = match update {
+                        Ok(update) => update,
+                        Err(e) => {
+                            tracing::warn!("Yellowstone subscription failed: {}", e);
+                            break;
+                        }
+                    };
+                    match update.update_oneof {
+                        Some(UpdateOneof::Ping(_)) => {
+                            let ping = SubscribeRequest {
+                                ping: Some(SubscribeRequestPing { id: 1 }),
+                                ..Default::default()
+                            };
+                            if sink.send(ping).await.is_err() {
+                                break;
+                            }
+                        }
+                        Some(update) => {
+                            let wants_more = on_update(update).await;
+                            if !wants_more {
+                                return;
+                            }
+                        }
+                        None => {}
+                    }
+                }
+            }
+            Err(e) => tracing::warn!("Failed to subscribe to {}: {:?}", config.endpoint, e),
+        }
+        tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+    }
+}
+
+async fn subscribe(
+    config: &GeyserConfig,
+    request: SubscribeRequest,
+) -> OrcaResult<(
+    impl Sink<SubscribeRequest>,
+    impl Stream<Item = Result<SubscribeUpdate, impl std::fmt::Display>>,
+)> {
+    let geyser_error =
+        |e: &dyn std::fmt::Display| OrcaError::NetworkError(format!("Yellowstone: {}", e));
+    let mut client = GeyserGrpcClient::build_from_shared(config.endpoint.clone())
+        .map_err(|e| geyser_error(&e))?
+        .x_token(config.x_token.clone())
+        .map_err(|e| geyser_error(&e))?
+        .tls_config(ClientTlsConfig::new().with_native_roots())
+        .map_err(|e| geyser_error(&e))?
+        .connect()
+        .await
+        .map_err(|e| geyser_error(&e))?;
+    client
+        .subscribe_with_request(Some(request))
+        .await
+        .map_err(|e| geyser_error(&e))
+}
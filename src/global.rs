@@ -44,14 +44,110 @@ pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
 pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
 pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
 pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+pub const COMPUTE_BUDGET_PROGRAM_ID: &str = "ComputeBudget111111111111111111111111111111";
+pub const MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
 
-// Whirlpool account data offset constant
-pub const WHIRLPOOL_TOKEN_MINT_A_OFFSET: usize = 8;
-pub const WHIRLPOOL_TOKEN_MINT_B_OFFSET: usize = 40;
+// Whirlpool account layout (Anchor discriminator included)
+pub const WHIRLPOOL_DISCRIMINATOR: [u8; 8] = [63, 149, 209, 12, 225, 128, 99, 9];
+pub const WHIRLPOOL_ACCOUNT_SIZE: usize = 653;
+pub const WHIRLPOOL_NUM_REWARDS: usize = 3;
+// Tick spacings of the fee tiers of the main WhirlpoolsConfig
+pub const WHIRLPOOL_TICK_SPACINGS: [u16; 10] = [1, 2, 4, 8, 16, 64, 96, 128, 256, 32896];
 // Whirlpool account data offsets
-pub const WHIRLPOOL_TICK_SPACING_OFFSET: usize = 72;
-pub const WHIRLPOOL_FEE_RATE_OFFSET: usize = 74;
-pub const WHIRLPOOL_LIQUIDITY_OFFSET: usize = 200;
-pub const WHIRLPOOL_SQRT_PRICE_OFFSET: usize = 216;
+pub const WHIRLPOOL_CONFIG_OFFSET: usize = 8;
+pub const WHIRLPOOL_TICK_SPACING_OFFSET: usize = 41;
+pub const WHIRLPOOL_FEE_RATE_OFFSET: usize = 45;
+pub const WHIRLPOOL_LIQUIDITY_OFFSET: usize = 49;
+pub const WHIRLPOOL_SQRT_PRICE_OFFSET: usize = 65;
+pub const WHIRLPOOL_TICK_CURRENT_INDEX_OFFSET: usize = 81;
+pub const WHIRLPOOL_TOKEN_MINT_A_OFFSET: usize = 101;
+pub const WHIRLPOOL_TOKEN_VAULT_A_OFFSET: usize = 133;
+pub const WHIRLPOOL_TOKEN_MINT_B_OFFSET: usize = 181;
+pub const WHIRLPOOL_TOKEN_VAULT_B_OFFSET: usize = 213;
+pub const WHIRLPOOL_REWARD_INFOS_OFFSET: usize = 269;
 // Token Metadata Program
 pub const TOKEN_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+// Whirlpool tick bounds
+pub const MIN_TICK_INDEX: i32 = -443636;
+pub const MAX_TICK_INDEX: i32 = 443636;
+// Token-2022 (Token Extensions) Program
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+// Whirlpool sqrt price bounds (Q64.64)
+pub const MIN_SQRT_PRICE_X64: u128 = 4295048016;
+pub const MAX_SQRT_PRICE_X64: u128 = 79226673515401279992447579055;
+// Jito tip accounts
+pub const JITO_TIP_ACCOUNTS: [&str; 8] = [
+    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+    "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU9gRe",
+    "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+    "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+    "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+    "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+    "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+    "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+];
+// Whirlpool swap instruction discriminators
+pub const WHIRLPOOL_SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+pub const WHIRLPOOL_SWAP_V2_DISCRIMINATOR: [u8; 8] = [43, 4, 237, 11, 26, 201, 30, 98];
+// Whirlpool position instruction discriminators
+pub const WHIRLPOOL_OPEN_POSITION_DISCRIMINATOR: [u8; 8] = [135, 128, 47, 77, 15, 152, 240, 49];
+pub const WHIRLPOOL_INCREASE_LIQUIDITY_V2_DISCRIMINATOR: [u8; 8] =
+    [133, 29, 89, 223, 69, 238, 176, 10];
+pub const WHIRLPOOL_DECREASE_LIQUIDITY_V2_DISCRIMINATOR: [u8; 8] =
+    [58, 127, 188, 62, 79, 82, 196, 96];
+pub const WHIRLPOOL_COLLECT_FEES_V2_DISCRIMINATOR: [u8; 8] = [207, 117, 95, 191, 229, 180, 226, 15];
+pub const WHIRLPOOL_CLOSE_POSITION_DISCRIMINATOR: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+// Whirlpool instruction discriminators that are only decoded, e.g. from other clients' transactions
+pub const WHIRLPOOL_INCREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] =
+    [46, 156, 243, 118, 13, 205, 251, 178];
+pub const WHIRLPOOL_DECREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [160, 38, 208, 111, 104, 91, 44, 1];
+pub const WHIRLPOOL_COLLECT_FEES_DISCRIMINATOR: [u8; 8] = [164, 152, 207, 99, 30, 186, 19, 182];
+pub const WHIRLPOOL_OPEN_POSITION_WITH_METADATA_DISCRIMINATOR: [u8; 8] =
+    [242, 29, 134, 48, 58, 110, 14, 60];
+pub const WHIRLPOOL_OPEN_POSITION_WITH_TOKEN_EXTENSIONS_DISCRIMINATOR: [u8; 8] =
+    [212, 47, 95, 92, 114, 102, 131, 250];
+pub const WHIRLPOOL_CLOSE_POSITION_WITH_TOKEN_EXTENSIONS_DISCRIMINATOR: [u8; 8] =
+    [1, 182, 135, 59, 155, 25, 99, 223];
+// Whirlpool reward instruction discriminators
+pub const WHIRLPOOL_UPDATE_FEES_AND_REWARDS_DISCRIMINATOR: [u8; 8] =
+    [154, 230, 250, 13, 236, 209, 75, 223];
+pub const WHIRLPOOL_COLLECT_REWARD_V2_DISCRIMINATOR: [u8; 8] =
+    [177, 107, 37, 180, 160, 19, 49, 209];
+// Whirlpool tick array initialization discriminator
+pub const WHIRLPOOL_INITIALIZE_TICK_ARRAY_DISCRIMINATOR: [u8; 8] =
+    [11, 188, 193, 214, 141, 91, 149, 184];
+// Whirlpool `Traded` event discriminator, emitted once per swap
+pub const WHIRLPOOL_TRADED_EVENT_DISCRIMINATOR: [u8; 8] = [225, 202, 73, 175, 147, 43, 160, 150];
+// Token swap (Orca v1/v2) swap instruction tag
+pub const TOKEN_SWAP_SWAP_TAG: u8 = 1;
+// Token swap (Orca v1/v2 and stable) pool account layout
+pub const TOKEN_SWAP_ACCOUNT_SIZE: usize = 324;
+pub const TOKEN_SWAP_TOKEN_MINT_A_OFFSET: usize = 131;
+pub const TOKEN_SWAP_TOKEN_MINT_B_OFFSET: usize = 163;
+pub const TOKEN_SWAP_CURVE_CONSTANT_PRODUCT: u8 = 0;
+pub const TOKEN_SWAP_CURVE_STABLE: u8 = 2;
+// Whirlpool program error codes returned when a slippage bound is hit
+pub const WHIRLPOOL_TOKEN_MAX_EXCEEDED: u32 = 6017;
+pub const WHIRLPOOL_TOKEN_MIN_SUBCEEDED: u32 = 6018;
+pub const WHIRLPOOL_AMOUNT_OUT_BELOW_MINIMUM: u32 = 6036;
+pub const WHIRLPOOL_AMOUNT_IN_ABOVE_MAXIMUM: u32 = 6037;
+// Whirlpool tick array layout (Anchor discriminator included)
+pub const TICK_ARRAY_DISCRIMINATOR: [u8; 8] = [69, 97, 189, 190, 110, 7, 66, 187];
+pub const TICK_ARRAY_SIZE: i32 = 88;
+pub const TICK_ARRAY_ACCOUNT_SIZE: usize = 9_988;
+// Whirlpool position account discriminator (Anchor)
+pub const POSITION_DISCRIMINATOR: [u8; 8] = [170, 188, 143, 228, 122, 64, 247, 208];
+// Pyth pull oracle PriceUpdateV2 account discriminator (Anchor)
+pub const PYTH_PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+// Switchboard on-demand PullFeedAccountData account discriminator (Anchor)
+pub const SWITCHBOARD_PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+// Largest serialized transaction that fits in one packet (IPv6 MTU minus headers)
+pub const PACKET_DATA_SIZE: usize = 1280 - 40 - 8;
+// Accounts a single getMultipleAccounts request may ask for
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+// Tick arrays a single Whirlpool swap instruction can traverse
+pub const MAX_SWAP_TICK_ARRAYS: usize = 3;
+// Whirlpool fee rates are expressed in hundredths of a basis point
+pub const FEE_RATE_DENOMINATOR: u64 = 1_000_000;
+// Basis points in 100%, the upper bound of a slippage tolerance
+pub const BPS_DENOMINATOR: u16 = 10_000;
@@ -0,0 +1,190 @@
+use std::time::Duration;
+
+use super::*;
+use crate::{
+    math::sqrt_price_x64_to_price,
+    oracle::OracleReference,
+    pool::{PoolInfo, QuoteResult},
+    stats::time_weighted_average,
+    types::OrcaResult,
+};
+
+const MAX_TWAP_TRANSACTIONS: usize = 500;
+
+/// Source of the reference price a quote is compared against
+#[derive(Debug, Clone)]
+pub enum PriceReference {
+    /// Time-weighted average execution price of the pool over the window
+    Twap(Duration),
+    /// Caller-supplied price as output units per input unit (raw amounts)
+    Fixed(f64),
+    /// Pyth or Switchboard price of token A in token B
+    Oracle(OracleReference),
+}
+
+/// Circuit breaker refusing swaps whose execution price strays from a reference
+#[derive(Debug, Clone)]
+pub struct PriceDeviationGuard {
+    /// Maximum allowed deviation in percent
+    pub max_deviation_percent: f64,
+    pub reference: PriceReference,
+}
+
+impl PriceDeviationGuard {
+    pub fn new(max_deviation_percent: f64, reference: PriceReference) -> Self {
+        Self {
+            max_deviation_percent,
+            reference,
+        }
+    }
+}
+
+impl OrcaClient {
+    /// Sets the client-wide price deviation guard applied to every swap
+    ///
+    /// Covers each pool a Whirlpool, route, stable, legacy or zap swap trades through. A guard
+    /// passed through `TradeConfig::price_guard` takes precedence for that call.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use orca_sdk::guard::{PriceDeviationGuard, PriceReference};
+    ///
+    /// client.set_price_deviation_guard(Some(PriceDeviationGuard::new(
+    ///     2.0,
+    ///     PriceReference::Twap(Duration::from_secs(600)),
+    /// )));
+    ///
+    /// // Or abort when the pool strays more than 1% from the Pyth SOL/USD feed
+    /// use orca_sdk::oracle::{OracleReference, OracleSource};
+    ///
+    /// client.set_price_deviation_guard(Some(PriceDeviationGuard::new(
+    ///     1.0,
+    ///     PriceReference::Oracle(OracleReference::new(OracleSource::Pyth(sol_usd_feed))),
+    /// )));
+    /// ```
+    pub fn set_price_deviation_guard(&self, guard: Option<PriceDeviationGuard>) {
+        if let Ok(mut current) = self.price_guard.write() {
+            *current = guard;
+        }
+    }
+
+    /// Returns the client-wide price deviation guard
+    pub fn price_deviation_guard(&self) -> Option<PriceDeviationGuard> {
+        self.price_guard.read().ok().and_then(|guard| guard.clone())
+    }
+
+    /// Checks a quote against the guard's reference price
+    ///
+    /// Returns the measured deviation in percent, or `OrcaError::PriceDeviationExceeded`.
+    pub async fn check_price_deviation(
+        &self,
+        guard: &PriceDeviationGuard,
+        pool: &PoolInfo,
+        input_mint: &str,
+        quote: &QuoteResult,
+    ) -> OrcaResult<f64> {
+        self.price_deviation(
+            guard,
+            pool,
+            input_mint,
+            quote.input_amount,
+            quote.output_amount,
+        )
+        .await
+    }
+
+    /// Checks a swap through `pool` against `guard`, or the client-wide guard when none is given
+    ///
+    /// Every swap entry point calls this for each pool it trades through, including the swap
+    /// legs of routes and zaps.
+    pub(crate) async fn enforce_price_guard(
+        &self,
+        guard: Option<&PriceDeviationGuard>,
+        pool: &PoolInfo,
+        input_mint: &str,
+        input_amount: u64,
+        output_amount: u64,
+    ) -> OrcaResult<()> {
+        let Some(guard) = guard.cloned().or_else(|| self.price_deviation_guard()) else {
+            return Ok(());
+        };
+        self.price_deviation(&guard, pool, input_mint, input_amount, output_amount)
+            .await?;
+        Ok(())
+    }
+
+    async fn price_deviation(
+        &self,
+        guard: &PriceDeviationGuard,
+        pool: &PoolInfo,
+        input_mint: &str,
+        input_amount: u64,
+        output_amount: u64,
+    ) -> OrcaResult<f64> {
+        if input_amount == 0 {
+            return Ok(0.0);
+        }
+        let execution_price = output_amount as f64 / input_amount as f64;
+        let reference_price = match &guard.reference {
+            PriceReference::Fixed(price) => *price,
+            PriceReference::Twap(window) => {
+                let twap = self.pool_execution_twap(pool, *window).await?;
+                if input_mint == pool.token_mint_a {
+                    twap
+                } else {
+                    1.0 / twap
+                }
+            }
+            PriceReference::Oracle(reference) => {
+                let (oracle_price, (decimals_a, decimals_b)) = tokio::try_join!(
+                    self.get_oracle_reference_price(reference),
+                    self.get_pool_decimals(pool)
+                )?;
+                // Quotes are in raw amounts, the oracle in whole tokens
+                let raw_price = oracle_price * 10f64.powi(decimals_b as i32 - decimals_a as i32);
+                if input_mint == pool.token_mint_a {
+                    raw_price
+                } else {
+                    1.0 / raw_price
+                }
+            }
+        };
+        if !reference_price.is_finite() || reference_price <= 0.0 {
+            return Err(OrcaError::PriceUnavailable(input_mint.to_string()));
+        }
+        let deviation_percent =
+            ((execution_price - reference_price) / reference_price).abs() * 100.0;
+        if deviation_percent > guard.max_deviation_percent {
+            return Err(OrcaError::PriceDeviationExceeded {
+                reference_price,
+                execution_price,
+                deviation_percent,
+                max_deviation_percent: guard.max_deviation_percent,
+            });
+        }
+        Ok(deviation_percent)
+    }
+
+    /// Time-weighted execution price (token B per token A) over the trailing window
+    pub(crate) async fn pool_execution_twap(
+        &self,
+        pool: &PoolInfo,
+        window: Duration,
+    ) -> OrcaResult<f64> {
+        let now = chrono::Utc::now().timestamp();
+        let since = now - window.as_secs() as i64;
+        let records = self
+            .fetch_pool_transaction_records(pool, since, MAX_TWAP_TRANSACTIONS)
+            .await?;
+        let observations: Vec<(i64, f64)> = records
+            .iter()
+            .filter_map(|record| Some((record.timestamp, record.execution_price()?)))
+            .collect();
+        match time_weighted_average(&observations, now) {
+            Some(twap) => Ok(twap),
+            // No swaps in the window: the spot price is the best available reference
+            None => Ok(sqrt_price_x64_to_price(pool.sqrt_price, 0, 0)),
+        }
+    }
+}
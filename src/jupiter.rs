@@ -0,0 +1,422 @@
+use base64::{Engine, prelude::BASE64_STANDARD};
+use serde::Serialize;
+
+use super::*;
+use crate::{
+    audit::AuditDetails,
+    receipt::RouteReceipt,
+    router::{Route, RouterConfig},
+    types::OrcaResult,
+    validation::{parse_address, validate_swap_inputs},
+};
+
+pub const JUPITER_API_URL: &str = "https://lite-api.jup.ag/swap/v1";
+
+/// Endpoint of the Jupiter swap API
+#[derive(Debug, Clone)]
+pub struct JupiterConfig {
+    pub api_url: String,
+    /// Sent as `x-api-key`; required by the paid endpoints
+    pub api_key: Option<String>,
+}
+
+impl Default for JupiterConfig {
+    fn default() -> Self {
+        Self {
+            api_url: JUPITER_API_URL.to_string(),
+            api_key: None,
+        }
+    }
+}
+
+/// Exact-input quote from the Jupiter aggregator
+#[derive(Debug, Clone, Serialize)]
+pub struct JupiterQuote {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    /// Output after slippage, enforced by the swap transaction
+    pub min_output_amount: u64,
+    pub price_impact_pct: f64,
+    /// AMMs the route trades through, in order
+    pub amm_keys: Vec<Pubkey>,
+    /// Labels of those AMMs, e.g. "Whirlpool" or "Raydium CLMM"
+    pub labels: Vec<String>,
+    /// Quote as returned by the API, sent back to build the swap transaction
+    pub response: serde_json::Value,
+}
+
+/// Orca route and Jupiter quote for the same order
+#[derive(Debug, Clone)]
+pub struct QuoteComparison {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount: u64,
+    /// None when no Orca route can fill the order
+    pub orca: Option<Route>,
+    /// None when Jupiter did not return a quote
+    pub jupiter: Option<JupiterQuote>,
+}
+
+impl QuoteComparison {
+    /// How much more Jupiter pays out than Orca, in basis points of the Orca output
+    ///
+    /// Negative when Orca pays more; None unless both quotes are present.
+    pub fn jupiter_advantage_bps(&self) -> Option<f64> {
+        let orca = self.orca.as_ref()?.output_amount;
+        let jupiter = self.jupiter.as_ref()?.output_amount;
+        if orca == 0 {
+            return None;
+        }
+        Some((jupiter as f64 / orca as f64 - 1.0) * 10_000.0)
+    }
+}
+
+/// Where `swap_with_jupiter_fallback` executed a swap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ExecutionVenue {
+    Orca,
+    Jupiter,
+}
+
+/// Outcome of `swap_with_jupiter_fallback`
+///
+/// For Jupiter swaps the receipt's pools are the AMMs of the Jupiter route.
+#[derive(Debug, Clone, Serialize)]
+pub struct FallbackSwapReceipt {
+    pub venue: ExecutionVenue,
+    pub receipt: RouteReceipt,
+}
+
+impl OrcaClient {
+    /// Fetches an exact-input quote from the Jupiter swap API
+    ///
+    /// # Params
+    /// input_mint - Token sold
+    /// output_mint - Token bought
+    /// amount - Raw input amount
+    /// slippage_bps - Slippage tolerance in basis points (50 = 0.5%), from 0 to 10_000
+    /// config - API endpoint and key
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::jupiter::JupiterConfig;
+    ///
+    /// let quote = client
+    ///     .get_jupiter_quote(sol_mint, usdc_mint, 1_000_000_000, 50, &JupiterConfig::default())
+    ///     .await?;
+    /// println!("{} via {:?}", quote.output_amount, quote.labels);
+    /// ```
+    #[tracing::instrument(skip(self, config), err(Debug))]
+    pub async fn get_jupiter_quote(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        slippage_bps: u16,
+        config: &JupiterConfig,
+    ) -> OrcaResult<JupiterQuote> {
+        self.with_timeout("get jupiter quote", async {
+            validate_swap_inputs(input_mint, output_mint, amount, slippage_bps)?;
+            let request = reqwest::Client::new()
+                .get(format!("{}/quote", config.api_url.trim_end_matches('/')))
+                .query(&[
+                    ("inputMint", input_mint.to_string()),
+                    ("outputMint", output_mint.to_string()),
+                    ("amount", amount.to_string()),
+                    ("slippageBps", slippage_bps.to_string()),
+                ]);
+            let response = jupiter_request(request, config).await?;
+            parse_jupiter_quote(response)
+        })
+        .await
+    }
+
+    /// Quotes an order on Orca and on Jupiter side by side
+    ///
+    /// Both quotes are requested concurrently. A side that cannot quote the order is None and
+    /// its error is logged; an error is returned only when neither side can.
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::jupiter::JupiterConfig;
+    ///
+    /// let jupiter = JupiterConfig::default();
+    /// let comparison = client
+    ///     .compare_with_jupiter(sol_mint, usdc_mint, 1_000_000_000, None, &jupiter)
+    ///     .await?;
+    /// if let Some(bps) = comparison.jupiter_advantage_bps() {
+    ///     println!("Jupiter pays {:.1} bps more than Orca", bps);
+    /// }
+    /// ```
+    pub async fn compare_with_jupiter(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        config: Option<RouterConfig>,
+        jupiter: &JupiterConfig,
+    ) -> OrcaResult<QuoteComparison> {
+        let config = config.unwrap_or_default();
+        let slippage_bps = config.slippage_bps;
+        let (orca, quote) = tokio::join!(
+            self.find_best_route(input_mint, output_mint, amount, Some(config)),
+            self.get_jupiter_quote(input_mint, output_mint, amount, slippage_bps, jupiter),
+        );
+        if let (Err(orca_error), Err(jupiter_error)) = (&orca, &quote) {
+            tracing::warn!(?orca_error, ?jupiter_error, "No quote for the order");
+            return Err(OrcaError::NoRoute {
+                input_mint: input_mint.to_string(),
+                output_mint: output_mint.to_string(),
+            });
+        }
+        Ok(QuoteComparison {
+            input_mint: parse_address("input_mint", input_mint)?,
+            output_mint: parse_address("output_mint", output_mint)?,
+            amount,
+            orca: orca
+                .inspect_err(|e| tracing::warn!(error = ?e, "No Orca route for the order"))
+                .ok(),
+            jupiter: quote
+                .inspect_err(|e| tracing::warn!(error = ?e, "No Jupiter quote for the order"))
+                .ok(),
+        })
+    }
+
+    /// Swaps through the best Orca route, or through Jupiter when Orca cannot fill the order
+    ///
+    /// Jupiter is used only when no Orca route is found, e.g. when the pools lack the
+    /// liquidity for the size. Its swap transaction is built by the Jupiter API and checked
+    /// against the same token, pool and spend policies as an Orca swap before it is signed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::jupiter::{ExecutionVenue, JupiterConfig};
+    ///
+    /// let jupiter = JupiterConfig::default();
+    /// let swap = client
+    ///     .swap_with_jupiter_fallback(&keypair, input_mint, output_mint, amount, None, &jupiter)
+    ///     .await?;
+    /// if swap.venue == ExecutionVenue::Jupiter {
+    ///     println!("Filled by Jupiter through {:?}", swap.receipt.pools);
+    /// }
+    /// ```
+    #[tracing::instrument(
+        skip(self, signer, config, jupiter),
+        fields(owner = %signer.pubkey()),
+        err(Debug)
+    )]
+    pub async fn swap_with_jupiter_fallback<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        config: Option<RouterConfig>,
+        jupiter: &JupiterConfig,
+    ) -> OrcaResult<FallbackSwapReceipt> {
+        let config = config.unwrap_or_default();
+        let slippage_bps = config.slippage_bps;
+        match self
+            .find_best_route(input_mint, output_mint, amount, Some(config))
+            .await
+        {
+            Ok(route) => Ok(FallbackSwapReceipt {
+                venue: ExecutionVenue::Orca,
+                receipt: self.execute_route(signer, &route).await?,
+            }),
+            Err(e) => {
+                tracing::info!(error = ?e, "No Orca route, falling back to Jupiter");
+                let quote = self
+                    .get_jupiter_quote(input_mint, output_mint, amount, slippage_bps, jupiter)
+                    .await?;
+                Ok(FallbackSwapReceipt {
+                    venue: ExecutionVenue::Jupiter,
+                    receipt: self.execute_jupiter_swap(signer, &quote, jupiter).await?,
+                })
+            }
+        }
+    }
+
+    /// Builds the swap transaction of a Jupiter quote, then signs and submits it
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::jupiter::JupiterConfig;
+    ///
+    /// let config = JupiterConfig::default();
+    /// let quote = client
+    ///     .get_jupiter_quote(input_mint, output_mint, 1_000_000, 0.5, &config)
+    ///     .await?;
+    /// let receipt = client.execute_jupiter_swap(&keypair, &quote, &config).await?;
+    /// println!("Swapped in {}", receipt.signature);
+    /// ```
+    #[tracing::instrument(
+        skip(self, signer, quote, config),
+        fields(owner = %signer.pubkey()),
+        err(Debug)
+    )]
+    pub async fn execute_jupiter_swap<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        quote: &JupiterQuote,
+        config: &JupiterConfig,
+    ) -> OrcaResult<RouteReceipt> {
+        self.with_timeout("jupiter swap", async {
+            let owner = signer.pubkey();
+            let (input_mint, output_mint) =
+                (quote.input_mint.to_string(), quote.output_mint.to_string());
+            self.check_token_policy(&output_mint)?;
+            for amm in &quote.amm_keys {
+                self.check_pool_policy(&amm.to_string())?;
+            }
+            self.enforce_token_safety(&input_mint, &output_mint, None)
+                .await?;
+            let spends = [(input_mint.as_str(), quote.input_amount)];
+            let reservation = self.reserve_spend(&spends).await?;
+            let available = self.get_token_balance(&owner, &quote.input_mint).await?;
+            if available < quote.input_amount {
+                return Err(OrcaError::InsufficientBalance {
+                    mint: quote.input_mint,
+                    required: quote.input_amount,
+                    available,
+                });
+            }
+            let request = reqwest::Client::new()
+                .post(format!("{}/swap", config.api_url.trim_end_matches('/')))
+                .json(&serde_json::json!({
+                    "quoteResponse": quote.response,
+                    "userPublicKey": owner.to_string(),
+                    "wrapAndUnwrapSol": true,
+                    "dynamicComputeUnitLimit": true,
+                }));
+            let response = jupiter_request(request, config).await?;
+            let encoded = response
+                .get("swapTransaction")
+                .and_then(serde_json::Value::as_str)
+                .ok_or(OrcaError::ParseError(
+                    "Jupiter response has no swap transaction".to_string(),
+                ))?;
+            let bytes = BASE64_STANDARD.decode(encoded).map_err(|e| {
+                OrcaError::ParseError(format!("Invalid Jupiter swap transaction: {}", e))
+            })?;
+            let unsigned: VersionedTransaction = bincode::deserialize(&bytes).map_err(|e| {
+                OrcaError::ParseError(format!("Invalid Jupiter swap transaction: {}", e))
+            })?;
+            let transaction =
+                VersionedTransaction::try_new(unsigned.message, &[signer]).map_err(|e| {
+                    OrcaError::TransactionError(format!("Failed to sign transaction: {}", e))
+                })?;
+            let signature = self
+                .submit_transaction(
+                    &transaction,
+                    "jupiter swap",
+                    &AuditDetails::new(serde_json::json!({
+                        "amms": quote.amm_keys.iter().map(Pubkey::to_string).collect::<Vec<_>>(),
+                        "input_mint": input_mint,
+                        "output_mint": output_mint,
+                        "amount": quote.input_amount,
+                        "quoted_output_amount": quote.output_amount,
+                        "min_output_amount": quote.min_output_amount,
+                    })),
+                )
+                .await?;
+            reservation.commit();
+            Ok(RouteReceipt {
+                signature,
+                pools: quote.amm_keys.clone(),
+                input_mint: quote.input_mint,
+                output_mint: quote.output_mint,
+                input_amount: quote.input_amount,
+                quoted_output_amount: quote.output_amount,
+                min_output_amount: quote.min_output_amount,
+            })
+        })
+        .await
+    }
+}
+
+/// Sends a request to the Jupiter API and returns its JSON body
+async fn jupiter_request(
+    request: reqwest::RequestBuilder,
+    config: &JupiterConfig,
+) -> OrcaResult<serde_json::Value> {
+    let request = match &config.api_key {
+        Some(api_key) => request.header("x-api-key", api_key),
+        None => request,
+    };
+    let response = request
+        .send()
+        .await
+        .map_err(|e| OrcaError::NetworkError(format!("Failed to reach Jupiter: {}", e)))?;
+    let status = response.status();
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| OrcaError::NetworkError(format!("Invalid Jupiter response: {}", e)))?;
+    if !status.is_success() {
+        return Err(OrcaError::NetworkError(format!(
+            "Jupiter returned {}: {}",
+            status,
+            body.get("error").unwrap_or(&body)
+        )));
+    }
+    Ok(body)
+}
+
+/// Reads a quote response of the Jupiter swap API, whose amounts are decimal strings
+fn parse_jupiter_quote(response: serde_json::Value) -> OrcaResult<JupiterQuote> {
+    let field = |name: &str| {
+        response
+            .get(name)
+            .and_then(serde_json::Value::as_str)
+            .ok_or(OrcaError::ParseError(format!(
+                "Jupiter quote has no {}",
+                name
+            )))
+    };
+    let amount = |name: &str| {
+        field(name)?
+            .parse::<u64>()
+            .map_err(|e| OrcaError::ParseError(format!("Invalid Jupiter {}: {}", name, e)))
+    };
+    let steps: Vec<&serde_json::Value> = response
+        .get("routePlan")
+        .and_then(serde_json::Value::as_array)
+        .map(|plan| {
+            plan.iter()
+                .filter_map(|step| step.get("swapInfo"))
+                .collect()
+        })
+        .unwrap_or_default();
+    let amm_keys = steps
+        .iter()
+        .filter_map(|info| info.get("ammKey")?.as_str())
+        .map(|key| parse_address("ammKey", key))
+        .collect::<OrcaResult<Vec<Pubkey>>>()?;
+    let labels = steps
+        .iter()
+        .filter_map(|info| Some(info.get("label")?.as_str()?.to_string()))
+        .collect();
+    let input_mint = parse_address("inputMint", field("inputMint")?)?;
+    let output_mint = parse_address("outputMint", field("outputMint")?)?;
+    let input_amount = amount("inAmount")?;
+    let output_amount = amount("outAmount")?;
+    let min_output_amount = amount("otherAmountThreshold")?;
+    let price_impact_pct = field("priceImpactPct")
+        .ok()
+        .and_then(|pct| pct.parse().ok())
+        .unwrap_or(0.0);
+    Ok(JupiterQuote {
+        input_mint,
+        output_mint,
+        input_amount,
+        output_amount,
+        min_output_amount,
+        price_impact_pct,
+        amm_keys,
+        labels,
+        response,
+    })
+}
@@ -0,0 +1,113 @@
+use std::path::{Path, PathBuf};
+
+use solana_sdk::signature::Keypair;
+use zeroize::Zeroizing;
+
+use crate::types::{InvalidInput, OrcaError, OrcaResult};
+
+/// Where a signing keypair is loaded from
+#[derive(Debug, Clone)]
+pub enum KeypairSource {
+    /// A Solana CLI style JSON array file
+    File(PathBuf),
+    /// An environment variable holding a base58 string or JSON byte array
+    Env(String),
+    /// An entry in the OS keychain holding a base58 string or JSON byte array
+    #[cfg(feature = "keychain")]
+    Keychain { service: String, account: String },
+    /// An interactive terminal prompt with hidden input
+    #[cfg(feature = "prompt")]
+    Prompt(String),
+}
+
+impl KeypairSource {
+    /// Loads the keypair; the returned `Keypair` can be used wherever a `Signer` is accepted
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::keys::KeypairSource;
+    ///
+    /// let keypair = KeypairSource::Env("ORCA_BOT_KEYPAIR".to_string()).load()?;
+    /// let receipt = client.swap(&keypair, input_mint, output_mint, amount, None).await?;
+    /// ```
+    pub fn load(&self) -> OrcaResult<Keypair> {
+        match self {
+            KeypairSource::File(path) => load_keypair_from_file(path),
+            KeypairSource::Env(var) => load_keypair_from_env(var),
+            #[cfg(feature = "keychain")]
+            KeypairSource::Keychain { service, account } => {
+                load_keypair_from_keychain(service, account)
+            }
+            #[cfg(feature = "prompt")]
+            KeypairSource::Prompt(prompt) => load_keypair_from_prompt(prompt),
+        }
+    }
+}
+
+/// Loads a keypair from a JSON byte array file such as `~/.config/solana/id.json`
+pub fn load_keypair_from_file(path: impl AsRef<Path>) -> OrcaResult<Keypair> {
+    let path = path.as_ref();
+    let contents = Zeroizing::new(
+        std::fs::read_to_string(path)
+            .map_err(|e| invalid_keypair(format!("file {}", path.display()), e))?,
+    );
+    keypair_from_secret_str(&contents)
+}
+
+/// Loads a keypair from an environment variable holding a base58 string or JSON byte array
+pub fn load_keypair_from_env(var: &str) -> OrcaResult<Keypair> {
+    let value = Zeroizing::new(
+        std::env::var(var)
+            .map_err(|e| invalid_keypair(format!("environment variable {}", var), e))?,
+    );
+    keypair_from_secret_str(&value)
+}
+
+/// Loads a keypair stored in the OS keychain
+#[cfg(feature = "keychain")]
+pub fn load_keypair_from_keychain(service: &str, account: &str) -> OrcaResult<Keypair> {
+    let entry =
+        keyring::Entry::new(service, account).map_err(|e| invalid_keypair("keychain", e))?;
+    let secret = Zeroizing::new(
+        entry
+            .get_password()
+            .map_err(|e| invalid_keypair("keychain", e))?,
+    );
+    keypair_from_secret_str(&secret)
+}
+
+/// Prompts for a base58 string or JSON byte array on the terminal without echoing it
+#[cfg(feature = "prompt")]
+pub fn load_keypair_from_prompt(prompt: &str) -> OrcaResult<Keypair> {
+    let secret = Zeroizing::new(
+        rpassword::prompt_password(prompt).map_err(|e| invalid_keypair("prompt", e))?,
+    );
+    keypair_from_secret_str(&secret)
+}
+
+/// Parses a secret key given as a JSON byte array or a base58 string
+///
+/// Intermediate buffers holding secret bytes are zeroized on drop.
+pub fn keypair_from_secret_str(secret: &str) -> OrcaResult<Keypair> {
+    let trimmed = secret.trim();
+    let bytes: Zeroizing<Vec<u8>> = if trimmed.starts_with('[') {
+        Zeroizing::new(
+            serde_json::from_str::<Vec<u8>>(trimmed)
+                .map_err(|e| invalid_keypair("JSON byte array", e))?,
+        )
+    } else {
+        Zeroizing::new(
+            bs58::decode(trimmed)
+                .into_vec()
+                .map_err(|e| invalid_keypair("base58 string", e))?,
+        )
+    };
+    Keypair::try_from(bytes.as_slice()).map_err(|e| invalid_keypair("keypair bytes", e))
+}
+
+fn invalid_keypair(source: impl Into<String>, reason: impl std::fmt::Display) -> OrcaError {
+    OrcaError::InvalidInput(InvalidInput::InvalidKeypair {
+        source: source.into(),
+        reason: reason.to_string(),
+    })
+}
@@ -0,0 +1,141 @@
+use solana_sdk::pubkey::Pubkey;
+
+/// Sequential little-endian reader over Anchor account data
+///
+/// Every read returns None once the data is exhausted, so decoders can bail out with `?`.
+pub(crate) struct AccountReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> AccountReader<'a> {
+    /// Starts reading after the 8-byte discriminator, which must equal `discriminator`
+    pub(crate) fn new(data: &'a [u8], discriminator: &[u8; 8]) -> Option<Self> {
+        (data.get(..8)? == discriminator).then_some(Self { data, offset: 8 })
+    }
+
+    /// Starts reading at the first byte, for accounts without an Anchor discriminator
+    pub(crate) fn unprefixed(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    pub(crate) fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.offset..self.offset + len)?;
+        self.offset += len;
+        Some(bytes)
+    }
+
+    pub(crate) fn skip(&mut self, len: usize) -> Option<()> {
+        self.bytes(len).map(|_| ())
+    }
+
+    pub(crate) fn bool(&mut self) -> Option<bool> {
+        Some(self.bytes(1)?[0] != 0)
+    }
+
+    pub(crate) fn u8(&mut self) -> Option<u8> {
+        Some(self.bytes(1)?[0])
+    }
+
+    pub(crate) fn u16(&mut self) -> Option<u16> {
+        Some(u16::from_le_bytes(self.bytes(2)?.try_into().ok()?))
+    }
+
+    pub(crate) fn i32(&mut self) -> Option<i32> {
+        Some(i32::from_le_bytes(self.bytes(4)?.try_into().ok()?))
+    }
+
+    pub(crate) fn i64(&mut self) -> Option<i64> {
+        Some(i64::from_le_bytes(self.bytes(8)?.try_into().ok()?))
+    }
+
+    pub(crate) fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.bytes(8)?.try_into().ok()?))
+    }
+
+    pub(crate) fn i128(&mut self) -> Option<i128> {
+        Some(i128::from_le_bytes(self.bytes(16)?.try_into().ok()?))
+    }
+
+    pub(crate) fn u128(&mut self) -> Option<u128> {
+        Some(u128::from_le_bytes(self.bytes(16)?.try_into().ok()?))
+    }
+
+    pub(crate) fn pubkey(&mut self) -> Option<Pubkey> {
+        Some(Pubkey::new_from_array(self.bytes(32)?.try_into().ok()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use base64::{Engine, prelude::BASE64_STANDARD};
+
+    use super::*;
+    use crate::pool::decode_whirlpool;
+
+    // Mainnet Whirlpool account, captured as base64 `getAccountInfo` data
+    const WHIRLPOOL_ACCOUNT: &str = include_str!("../tests/fixtures/whirlpool_account.b64");
+
+    fn pubkey(address: &str) -> Pubkey {
+        Pubkey::from_str(address).unwrap()
+    }
+
+    #[test]
+    fn decodes_whirlpool_account() {
+        let data = BASE64_STANDARD.decode(WHIRLPOOL_ACCOUNT.trim()).unwrap();
+        let pool = decode_whirlpool(&data, "pool").unwrap();
+        assert_eq!(
+            pool.whirlpools_config,
+            "2LecshUwdy9xi7meFgHtFJQNSKk4KdTrcpvaB56dP2NQ"
+        );
+        assert_eq!(pool.tick_spacing, 32896);
+        assert_eq!(pool.fee_rate, 10000);
+        assert_eq!(pool.protocol_fee_rate, 1300);
+        assert_eq!(pool.liquidity, 141421356);
+        assert_eq!(pool.sqrt_price, 1304381782533278221200000);
+        assert_eq!(pool.tick_current_index, 223338);
+        assert_eq!(
+            pool.token_mint_a,
+            "So11111111111111111111111111111111111111112"
+        );
+        assert_eq!(
+            pool.token_vault_a,
+            "6cS7s6TZuQofs1TPYSpZ51qGPQKnrxk6xWhW131MysXh"
+        );
+        assert_eq!(
+            pool.token_mint_b,
+            "DzkKGBw4njGApBJ4Bd2csCpm4rsdgEmkgivR7XZvENPv"
+        );
+        assert_eq!(
+            pool.token_vault_b,
+            "25tSGzR5NcRpuoWKzWFTWfMaB1ijiaHP6HkqjxZNH72m"
+        );
+        assert_eq!(pool.reward_last_updated_timestamp, 1745354322);
+        for reward in &pool.reward_infos {
+            assert_eq!(reward.mint, Pubkey::default());
+            assert_eq!(
+                reward.authority,
+                pubkey("DjDsi34mSB66p2nhBL6YvhbcLtZbkGfNybFeLDjJqxJW")
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_other_discriminators() {
+        let mut data = BASE64_STANDARD.decode(WHIRLPOOL_ACCOUNT.trim()).unwrap();
+        assert!(AccountReader::new(&data, &[0; 8]).is_none());
+        data[0] ^= 1;
+        assert!(decode_whirlpool(&data, "pool").is_none());
+    }
+
+    #[test]
+    fn reads_stop_at_end_of_data() {
+        let mut reader = AccountReader::unprefixed(&[1, 0, 0]);
+        assert_eq!(reader.u16(), Some(1));
+        assert_eq!(reader.u16(), None);
+        assert_eq!(reader.u8(), Some(0));
+        assert_eq!(reader.u8(), None);
+    }
+}
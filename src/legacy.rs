@@ -0,0 +1,452 @@
+use solana_sdk::instruction::{AccountMeta, Instruction};
+
+use super::*;
+use crate::{
+    fixed::min_amount_with_slippage,
+    global::{
+        ORCA_INSTRUCTION_DEPOSIT, ORCA_INSTRUCTION_WITHDRAW, ORCA_SWAP_PROGRAM_ID_V1,
+        ORCA_SWAP_PROGRAM_ID_V2, TOKEN_SWAP_CURVE_CONSTANT_PRODUCT,
+    },
+    math::price_impact_bps,
+    pool::{PoolInfo, QuoteResult},
+    receipt::SwapReceipt,
+    token_swap::{
+        TokenSwapAccount, TokenSwapAccounts, TokenSwapOrder, token_swap_fee,
+        token_swap_instruction, token_swap_price_view,
+    },
+    trade::TokenSwapParams,
+    types::{InvalidInput, OrcaResult},
+    validation::{parse_address, validate_slippage_bps},
+};
+
+/// Legacy Orca swap programs (token swap v1 and v2)
+pub const LEGACY_SWAP_PROGRAMS: [Pubkey; 2] = [
+    Pubkey::from_str_const(ORCA_SWAP_PROGRAM_ID_V1),
+    Pubkey::from_str_const(ORCA_SWAP_PROGRAM_ID_V2),
+];
+
+/// A legacy Orca standard pool, a token swap pool priced by the constant product x*y=k
+#[derive(Debug, Clone)]
+pub struct LegacyPool {
+    pub address: Pubkey,
+    /// Token swap program owning the pool, v1 or v2
+    pub program_id: Pubkey,
+    /// PDA of the pool that owns the vaults
+    pub authority: Pubkey,
+    pub token_program: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_vault_b: Pubkey,
+    /// Mint of the pool's LP token
+    pub pool_mint: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    /// LP token account receiving owner fees
+    pub fee_account: Pubkey,
+    pub trade_fee_numerator: u64,
+    pub trade_fee_denominator: u64,
+    pub owner_trade_fee_numerator: u64,
+    pub owner_trade_fee_denominator: u64,
+    pub owner_withdraw_fee_numerator: u64,
+    pub owner_withdraw_fee_denominator: u64,
+    /// Token A held by the pool vault
+    pub reserve_a: u64,
+    /// Token B held by the pool vault
+    pub reserve_b: u64,
+    pub decimals_a: u8,
+    pub decimals_b: u8,
+    /// LP tokens in circulation
+    pub pool_token_supply: u64,
+}
+
+/// Owner accounts and amounts of a legacy pool `DepositAllTokenTypes` instruction
+#[derive(Debug, Clone, Copy)]
+pub struct LegacyDepositParams<'a> {
+    /// Owner's token A account
+    pub source_a: &'a Pubkey,
+    /// Owner's token B account
+    pub source_b: &'a Pubkey,
+    /// Owner's LP token account
+    pub pool_token_account: &'a Pubkey,
+    /// LP tokens to mint
+    pub pool_token_amount: u64,
+    /// Token A the deposit may take at most
+    pub maximum_token_a: u64,
+    /// Token B the deposit may take at most
+    pub maximum_token_b: u64,
+}
+
+/// Owner accounts and amounts of a legacy pool `WithdrawAllTokenTypes` instruction
+#[derive(Debug, Clone, Copy)]
+pub struct LegacyWithdrawParams<'a> {
+    /// Owner's LP token account
+    pub pool_token_account: &'a Pubkey,
+    /// Owner's token A account
+    pub destination_a: &'a Pubkey,
+    /// Owner's token B account
+    pub destination_b: &'a Pubkey,
+    /// LP tokens to burn
+    pub pool_token_amount: u64,
+    /// Token A the withdrawal must return at least
+    pub minimum_token_a: u64,
+    /// Token B the withdrawal must return at least
+    pub minimum_token_b: u64,
+}
+
+impl LegacyPool {
+    /// Decodes a constant product pool account owned by `program_id`
+    ///
+    /// Returns None when the data is not an initialized token swap account with a constant
+    /// product curve. Reserves, supply and decimals are left at zero; `get_legacy_pool` fills
+    /// them.
+    pub fn decode(address: Pubkey, data: &[u8], program_id: &Pubkey) -> Option<Self> {
+        let account = TokenSwapAccount::decode(&address, data, program_id)?;
+        if account.curve_type != TOKEN_SWAP_CURVE_CONSTANT_PRODUCT {
+            return None;
+        }
+        Some(Self {
+            address,
+            program_id: *program_id,
+            authority: account.authority,
+            token_program: account.token_program,
+            token_vault_a: account.token_vault_a,
+            token_vault_b: account.token_vault_b,
+            pool_mint: account.pool_mint,
+            token_mint_a: account.token_mint_a,
+            token_mint_b: account.token_mint_b,
+            fee_account: account.fee_account,
+            trade_fee_numerator: account.trade_fee_numerator,
+            trade_fee_denominator: account.trade_fee_denominator,
+            owner_trade_fee_numerator: account.owner_trade_fee_numerator,
+            owner_trade_fee_denominator: account.owner_trade_fee_denominator,
+            owner_withdraw_fee_numerator: account.owner_withdraw_fee_numerator,
+            owner_withdraw_fee_denominator: account.owner_withdraw_fee_denominator,
+            reserve_a: 0,
+            reserve_b: 0,
+            decimals_a: 0,
+            decimals_b: 0,
+            pool_token_supply: 0,
+        })
+    }
+
+    /// View of the pool for the price deviation guard
+    pub(crate) fn price_view(&self) -> PoolInfo {
+        token_swap_price_view(
+            &self.authority,
+            (&self.token_mint_a, &self.token_mint_b),
+            (&self.token_vault_a, &self.token_vault_b),
+            (self.reserve_a, self.reserve_b),
+        )
+    }
+
+    /// Quotes an exact-input swap of `amount` of `input_mint` against the pool's reserves
+    ///
+    /// Trade and owner fees are taken from the input before the constant product is applied;
+    /// the minimum output applies `slippage_bps` in basis points.
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool = client.get_legacy_pool("legacy_pool_address").await?;
+    /// let quote = pool.quote(&pool.token_mint_a, 1_000_000, 50)?;
+    /// println!("{} -> {}", quote.input_amount, quote.output_amount);
+    /// ```
+    pub fn quote(
+        &self,
+        input_mint: &Pubkey,
+        amount: u64,
+        slippage_bps: u16,
+    ) -> OrcaResult<QuoteResult> {
+        validate_slippage_bps(slippage_bps)?;
+        let (reserve_in, reserve_out, input_decimals, output_decimals) =
+            if *input_mint == self.token_mint_a {
+                (
+                    self.reserve_a,
+                    self.reserve_b,
+                    self.decimals_a,
+                    self.decimals_b,
+                )
+            } else if *input_mint == self.token_mint_b {
+                (
+                    self.reserve_b,
+                    self.reserve_a,
+                    self.decimals_b,
+                    self.decimals_a,
+                )
+            } else {
+                return Err(OrcaError::InvalidInput(InvalidInput::MintNotInPool {
+                    pool: self.address.to_string(),
+                    mint: input_mint.to_string(),
+                }));
+            };
+        let fee_amount =
+            token_swap_fee(amount, self.trade_fee_numerator, self.trade_fee_denominator)
+                .saturating_add(token_swap_fee(
+                    amount,
+                    self.owner_trade_fee_numerator,
+                    self.owner_trade_fee_denominator,
+                ));
+        let input_after_fee = amount.saturating_sub(fee_amount);
+        let output_amount = constant_product_output(reserve_in, reserve_out, input_after_fee)
+            .filter(|output| *output > 0)
+            .ok_or_else(|| OrcaError::InsufficientLiquidity {
+                pool: self.address.to_string(),
+            })?;
+        let spot_output = input_after_fee as f64 * reserve_out as f64 / reserve_in as f64;
+        Ok(QuoteResult {
+            input_amount: amount,
+            output_amount,
+            min_output_amount: min_amount_with_slippage(output_amount, slippage_bps),
+            max_input_amount: amount,
+            price_impact_bps: price_impact_bps(spot_output, output_amount),
+            fee_amount,
+            input_transfer_fee: 0,
+            output_transfer_fee: 0,
+            input_decimals,
+            output_decimals,
+        })
+    }
+
+    /// Token A and B needed to mint `pool_token_amount` LP tokens, rounded up
+    pub fn deposit_amounts(&self, pool_token_amount: u64) -> (u64, u64) {
+        let share = |reserve: u64| {
+            (pool_token_amount as u128 * reserve as u128)
+                .div_ceil(self.pool_token_supply.max(1) as u128)
+                .min(u64::MAX as u128) as u64
+        };
+        (share(self.reserve_a), share(self.reserve_b))
+    }
+
+    /// Token A and B received for burning `pool_token_amount` LP tokens, after the owner
+    /// withdraw fee and rounded down
+    pub fn withdraw_amounts(&self, pool_token_amount: u64) -> (u64, u64) {
+        let burned = pool_token_amount.saturating_sub(token_swap_fee(
+            pool_token_amount,
+            self.owner_withdraw_fee_numerator,
+            self.owner_withdraw_fee_denominator,
+        ));
+        let share = |reserve: u64| {
+            (burned as u128 * reserve as u128 / self.pool_token_supply.max(1) as u128) as u64
+        };
+        (share(self.reserve_a), share(self.reserve_b))
+    }
+}
+
+impl OrcaClient {
+    /// Fetches a legacy pool with its vault balances and LP token supply
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool = client.get_legacy_pool("legacy_pool_address").await?;
+    /// println!("A={} B={} LP={}", pool.reserve_a, pool.reserve_b, pool.pool_token_supply);
+    /// ```
+    pub async fn get_legacy_pool(&self, pool_address: &str) -> OrcaResult<LegacyPool> {
+        let address = parse_address("pool_address", pool_address)?;
+        let account = self
+            .get_existing_account(&address, "Failed to get legacy pool account")
+            .await?;
+        if !LEGACY_SWAP_PROGRAMS.contains(&account.owner) {
+            return Err(OrcaError::InvalidPoolData {
+                pool: pool_address.to_string(),
+                reason: format!("owned by {}, not a legacy swap program", account.owner),
+            });
+        }
+        let mut pool =
+            LegacyPool::decode(address, &account.data, &account.owner).ok_or_else(|| {
+                OrcaError::InvalidPoolData {
+                    pool: pool_address.to_string(),
+                    reason: "not an initialized constant product pool".to_string(),
+                }
+            })?;
+        (pool.reserve_a, pool.reserve_b) = self
+            .get_token_swap_reserves(&pool.token_vault_a, &pool.token_vault_b)
+            .await?;
+        pool.pool_token_supply = self.get_token_supply(&pool.pool_mint).await?;
+        let decimals = self
+            .get_mint_decimals(&[pool.token_mint_a, pool.token_mint_b])
+            .await?;
+        (pool.decimals_a, pool.decimals_b) = (decimals[0], decimals[1]);
+        Ok(pool)
+    }
+
+    /// Swaps an exact input through a legacy Orca pool
+    ///
+    /// The output token account is created when missing; the minimum output is derived from
+    /// the quote and `slippage_bps` in basis points.
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool = client.get_legacy_pool("legacy_pool_address").await?;
+    /// let receipt = client
+    ///     .swap_legacy(&keypair, "legacy_pool_address", &pool.token_mint_a.to_string(), 1_000_000, 50)
+    ///     .await?;
+    /// println!("Swapped in {}", receipt.signature);
+    /// ```
+    #[tracing::instrument(skip(self, signer), fields(owner = %signer.pubkey()), err(Debug))]
+    pub async fn swap_legacy<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        pool_address: &str,
+        input_mint: &str,
+        amount: u64,
+        slippage_bps: u16,
+    ) -> OrcaResult<SwapReceipt> {
+        self.with_timeout("legacy swap", async {
+            let pool = self.get_legacy_pool(pool_address).await?;
+            let input_mint = parse_address("input_mint", input_mint)?;
+            let quote = pool.quote(&input_mint, amount, slippage_bps)?;
+            let a_to_b = input_mint == pool.token_mint_a;
+            self.submit_token_swap(
+                signer,
+                "legacy swap",
+                TokenSwapOrder {
+                    pool: &pool.address,
+                    token_mint_a: &pool.token_mint_a,
+                    token_mint_b: &pool.token_mint_b,
+                    token_program: &pool.token_program,
+                    a_to_b,
+                    amount,
+                    slippage_bps,
+                    price_view: pool.price_view(),
+                },
+                quote,
+                |source, destination, minimum_amount_out| {
+                    self.build_legacy_swap_instruction(
+                        &signer.pubkey(),
+                        &pool,
+                        TokenSwapParams {
+                            source,
+                            destination,
+                            a_to_b,
+                            amount_in: amount,
+                            minimum_amount_out,
+                        },
+                    )
+                },
+            )
+            .await
+        })
+        .await
+    }
+
+    /// Constructs a token swap `Swap` instruction for a legacy pool
+    ///
+    /// # Arguments
+    /// owner - Owner of the token accounts
+    /// pool - Legacy pool
+    /// params - Owner's token accounts, direction and amounts of the swap
+    pub fn build_legacy_swap_instruction(
+        &self,
+        owner: &Pubkey,
+        pool: &LegacyPool,
+        params: TokenSwapParams,
+    ) -> Instruction {
+        let (swap_source, swap_destination) = if params.a_to_b {
+            (pool.token_vault_a, pool.token_vault_b)
+        } else {
+            (pool.token_vault_b, pool.token_vault_a)
+        };
+        token_swap_instruction(
+            &pool.program_id,
+            TokenSwapAccounts {
+                pool: &pool.address,
+                authority: &pool.authority,
+                owner,
+                source: params.source,
+                swap_source: &swap_source,
+                swap_destination: &swap_destination,
+                destination: params.destination,
+                pool_mint: &pool.pool_mint,
+                fee_account: &pool.fee_account,
+                token_program: &pool.token_program,
+            },
+            params.amount_in,
+            params.minimum_amount_out,
+        )
+    }
+
+    /// Constructs a `DepositAllTokenTypes` instruction minting `pool_token_amount` LP tokens
+    ///
+    /// Use `LegacyPool::deposit_amounts` to size the maximums.
+    ///
+    /// # Arguments
+    /// owner - Owner of the token accounts
+    /// pool - Legacy pool
+    /// params - Owner's token and LP token accounts, LP tokens to mint and maximums
+    pub fn build_legacy_deposit_instruction(
+        &self,
+        owner: &Pubkey,
+        pool: &LegacyPool,
+        params: LegacyDepositParams,
+    ) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(pool.address, false),
+            AccountMeta::new_readonly(pool.authority, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*params.source_a, false),
+            AccountMeta::new(*params.source_b, false),
+            AccountMeta::new(pool.token_vault_a, false),
+            AccountMeta::new(pool.token_vault_b, false),
+            AccountMeta::new(pool.pool_mint, false),
+            AccountMeta::new(*params.pool_token_account, false),
+            AccountMeta::new_readonly(pool.token_program, false),
+        ];
+        let mut data = vec![ORCA_INSTRUCTION_DEPOSIT];
+        data.extend_from_slice(&params.pool_token_amount.to_le_bytes());
+        data.extend_from_slice(&params.maximum_token_a.to_le_bytes());
+        data.extend_from_slice(&params.maximum_token_b.to_le_bytes());
+        Instruction {
+            program_id: pool.program_id,
+            accounts,
+            data,
+        }
+    }
+
+    /// Constructs a `WithdrawAllTokenTypes` instruction burning `pool_token_amount` LP tokens
+    ///
+    /// Use `LegacyPool::withdraw_amounts` to size the minimums.
+    ///
+    /// # Arguments
+    /// owner - Owner of the token accounts
+    /// pool - Legacy pool
+    /// params - Owner's LP token and token accounts, LP tokens to burn and minimums
+    pub fn build_legacy_withdraw_instruction(
+        &self,
+        owner: &Pubkey,
+        pool: &LegacyPool,
+        params: LegacyWithdrawParams,
+    ) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(pool.address, false),
+            AccountMeta::new_readonly(pool.authority, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(pool.pool_mint, false),
+            AccountMeta::new(*params.pool_token_account, false),
+            AccountMeta::new(pool.token_vault_a, false),
+            AccountMeta::new(pool.token_vault_b, false),
+            AccountMeta::new(*params.destination_a, false),
+            AccountMeta::new(*params.destination_b, false),
+            AccountMeta::new(pool.fee_account, false),
+            AccountMeta::new_readonly(pool.token_program, false),
+        ];
+        let mut data = vec![ORCA_INSTRUCTION_WITHDRAW];
+        data.extend_from_slice(&params.pool_token_amount.to_le_bytes());
+        data.extend_from_slice(&params.minimum_token_a.to_le_bytes());
+        data.extend_from_slice(&params.minimum_token_b.to_le_bytes());
+        Instruction {
+            program_id: pool.program_id,
+            accounts,
+            data,
+        }
+    }
+}
+
+/// Output of swapping `amount_in` (fees already removed) against x*y=k, as the token swap
+/// program rounds it: the new output reserve is rounded up
+fn constant_product_output(reserve_in: u64, reserve_out: u64, amount_in: u64) -> Option<u64> {
+    if reserve_in == 0 || reserve_out == 0 {
+        return None;
+    }
+    let invariant = (reserve_in as u128).checked_mul(reserve_out as u128)?;
+    let new_reserve_in = (reserve_in as u128).checked_add(amount_in as u128)?;
+    let new_reserve_out = invariant.div_ceil(new_reserve_in);
+    u64::try_from((reserve_out as u128).checked_sub(new_reserve_out)?).ok()
+}
@@ -1,46 +1,186 @@
+use solana_commitment_config::CommitmentConfig;
 use solana_network_sdk::Solana;
 use solana_sdk::{
     message::Message,
     pubkey::Pubkey,
     signature::{Keypair, Signature, Signer},
-    transaction::Transaction,
+    transaction::{Transaction, VersionedTransaction},
+};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{
+        Arc, Mutex, RwLock,
+        atomic::{AtomicBool, AtomicUsize},
+    },
+    time::Duration,
 };
-use std::str::FromStr;
 
 use crate::{
+    audit::AuditSink,
+    builder::OrcaClientBuilder,
+    failover::RpcEndpoints,
+    fetch::{DEFAULT_FETCH_CONCURRENCY, TransactionCache},
     global::{ORCA_STABLE_SWAP_PROGRAM_ID, ORCA_WHIRLPOOLS_PROGRAM_ID},
+    guard::PriceDeviationGuard,
+    monitoring::PriceMonitor,
+    network::Network,
+    policy::{SpendLedger, SpendPolicy},
+    price::PriceHistoryCache,
+    resolver::PoolAddressCache,
+    retry::RetryPolicy,
+    safety::TokenRiskLevel,
+    stats::{DailyStatsStore, FeeGrowthSnapshots},
     types::OrcaError,
 };
 
+pub mod arbitrage;
+pub mod audit;
 pub mod balance;
+pub mod builder;
+#[cfg(feature = "persistent-cache")]
+pub mod cache;
+mod clmm;
+pub mod compound;
+pub mod depth;
 pub mod events;
+pub mod export;
+pub mod failover;
+pub mod fees;
+pub mod fetch;
+pub mod fixed;
+pub mod freeze;
+#[cfg(feature = "yellowstone")]
+pub mod geyser;
 pub mod global;
+pub mod guard;
+#[cfg(feature = "jupiter")]
+pub mod jupiter;
+pub mod keys;
+mod layout;
+pub mod legacy;
 pub mod liquidity;
+pub mod lookup_table;
+pub mod math;
 pub mod monitoring;
+pub mod network;
+pub mod oracle;
+pub mod packer;
+pub mod pnl;
+pub mod policy;
 pub mod pool;
 pub mod price;
+pub mod ranking;
+pub mod rebalance;
+pub mod receipt;
+pub mod rent;
+pub mod resolver;
+pub mod retry;
+pub mod reward;
+pub mod risk;
+pub mod router;
+pub mod safety;
+pub mod stable;
+pub mod stats;
+pub mod submit;
+pub mod tick;
+pub mod timeout;
+pub mod token;
+mod token_swap;
 pub mod trade;
 pub mod types;
+pub mod validation;
+pub mod watch;
+pub mod zap;
 
 pub struct OrcaClient {
     pub solana: Solana,
     pub whirlpool_program_id: Pubkey,
     pub stable_swap_program_id: Pubkey,
+    ws_url: String,
+    endpoints: RpcEndpoints,
+    retry_policy: RetryPolicy,
+    price_guard: RwLock<Option<PriceDeviationGuard>>,
+    max_token_risk: RwLock<Option<TokenRiskLevel>>,
+    spend_policy: RwLock<Option<SpendPolicy>>,
+    spend_ledger: Mutex<SpendLedger>,
+    dry_run: AtomicBool,
+    audit_sink: RwLock<Option<Arc<dyn AuditSink>>>,
+    fetch_concurrency: AtomicUsize,
+    transaction_cache: Mutex<TransactionCache>,
+    pool_cache: RwLock<PoolAddressCache>,
+    #[cfg(feature = "persistent-cache")]
+    persistent_cache: Option<cache::PersistentCache>,
+    #[cfg(feature = "yellowstone")]
+    geyser: Option<geyser::GeyserConfig>,
+    operation_timeout: RwLock<Option<Duration>>,
+    price_monitor: Mutex<PriceMonitor>,
+    price_history_cache: Mutex<PriceHistoryCache>,
+    mint_decimals: RwLock<HashMap<Pubkey, u8>>,
+    fee_growth_snapshots: Mutex<FeeGrowthSnapshots>,
+    daily_stats: Mutex<DailyStatsStore>,
 }
 
 impl OrcaClient {
     pub fn new() -> Result<Self, OrcaError> {
-        Ok(Self {
-            solana: Solana::new(solana_network_sdk::types::Mode::MAIN)
-                .map_err(|e| OrcaError::Error(format!("Failed to create Solana client: {}", e)))?,
-            whirlpool_program_id: Pubkey::from_str(ORCA_WHIRLPOOLS_PROGRAM_ID)
-                .map_err(|e| OrcaError::Error(format!("Invalid whirlpool program ID: {}", e)))?,
-            stable_swap_program_id: Pubkey::from_str(ORCA_STABLE_SWAP_PROGRAM_ID)
-                .map_err(|e| OrcaError::Error(format!("Invalid stable swap program ID: {}", e)))?,
-        })
+        Self::new_with_network(Network::Mainnet)
+    }
+
+    /// Creates a client for one of the well-known clusters or a custom endpoint
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::network::Network;
+    ///
+    /// let devnet = OrcaClient::new_with_network(Network::Devnet)?;
+    /// let local = OrcaClient::new_with_network(Network::Localnet)?;
+    /// ```
+    pub fn new_with_network(network: Network) -> Result<Self, OrcaError> {
+        Self::new_with_rpc(network.rpc_url(), CommitmentConfig::default())
+    }
+
+    /// Creates a client that sends every RPC request to `rpc_url` at the given commitment
+    ///
+    /// # Params
+    /// rpc_url - HTTP(S) endpoint of the RPC node, e.g. a private provider URL
+    /// commitment - Default commitment for reads
+    ///
+    /// # Example
+    /// ```rust
+    /// use solana_commitment_config::CommitmentConfig;
+    ///
+    /// let client = OrcaClient::new_with_rpc(
+    ///     "https://my-provider.example.com/?api-key=KEY",
+    ///     CommitmentConfig::confirmed(),
+    /// )?;
+    /// ```
+    pub fn new_with_rpc(rpc_url: &str, commitment: CommitmentConfig) -> Result<Self, OrcaError> {
+        Self::builder()
+            .rpc_url(rpc_url)
+            .commitment(commitment)
+            .build()
+    }
+
+    /// Returns a builder for configuring commitment, timeouts, retries and program IDs
+    pub fn builder() -> OrcaClientBuilder {
+        OrcaClientBuilder::new()
     }
 
     pub fn get_associated_token_address(&self, wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
         spl_associated_token_account::get_associated_token_address(wallet, mint)
     }
+
+    /// Derives the associated token account of `wallet` for a mint owned by `token_program`
+    pub fn get_associated_token_address_with_program(
+        &self,
+        wallet: &Pubkey,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+    ) -> Pubkey {
+        spl_associated_token_account::get_associated_token_address_with_program_id(
+            wallet,
+            mint,
+            token_program,
+        )
+    }
 }
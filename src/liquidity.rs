@@ -1,12 +1,49 @@
 use super::*;
-use crate::{pool::PoolInfo, types::OrcaResult};
+use crate::{
+    audit::AuditDetails,
+    fixed::{max_amount_with_slippage, min_amount_with_slippage},
+    freeze::FreezeCheckMode,
+    global::{
+        MAX_TICK_INDEX, POSITION_DISCRIMINATOR, WHIRLPOOL_CLOSE_POSITION_DISCRIMINATOR,
+        WHIRLPOOL_COLLECT_FEES_V2_DISCRIMINATOR, WHIRLPOOL_DECREASE_LIQUIDITY_V2_DISCRIMINATOR,
+        WHIRLPOOL_INCREASE_LIQUIDITY_V2_DISCRIMINATOR, WHIRLPOOL_NUM_REWARDS,
+        WHIRLPOOL_OPEN_POSITION_DISCRIMINATOR,
+    },
+    layout::AccountReader,
+    math::{
+        impermanent_loss, liquidity_from_token_amounts, price_to_tick_index, tick_index_to_price,
+        token_amounts_from_liquidity,
+    },
+    packer::{
+        CREATE_TOKEN_ACCOUNT_COMPUTE_UNITS, INCREASE_LIQUIDITY_COMPUTE_UNITS,
+        INITIALIZE_TICK_ARRAY_COMPUTE_UNITS, InstructionGroup, OPEN_POSITION_COMPUTE_UNITS,
+        PackedSubmission, TransactionPacker,
+    },
+    pool::{PoolInfo, decode_whirlpool},
+    receipt::{AddLiquidityReceipt, RemoveLiquidityReceipt},
+    rent::{
+        MINT_ACCOUNT_SIZE, POSITION_ACCOUNT_SIZE, RentPlan, TICK_ARRAY_ACCOUNT_SIZE,
+        TOKEN_ACCOUNT_SIZE,
+    },
+    submit::{DryRunOutcome, SubmitMode},
+    tick::{align_tick_index, tick_array_start_index},
+    token::{MEMO_PROGRAM, TOKEN_2022_PROGRAM},
+    types::OrcaResult,
+    validation::{
+        parse_address, validate_amount, validate_price_range, validate_slippage_bps,
+        validate_tick_range,
+    },
+};
+use solana_account_decoder::UiAccountData;
+use solana_client::rpc_response::RpcKeyedAccount;
 use solana_program::example_mocks::solana_sdk::system_program;
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
-    program_pack::Pack,
+    message::VersionedMessage,
     sysvar,
 };
-use std::str::FromStr;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use std::{collections::HashMap, str::FromStr};
 
 /// Represents a liquidity position in a concentrated liquidity pool
 #[derive(Debug, Clone)]
@@ -14,37 +51,214 @@ pub struct LiquidityPosition {
     pub pool_address: Pubkey,
     pub token_a_amount: u64,
     pub token_b_amount: u64,
-    pub lp_token_amount: u64,
+    /// Liquidity of the position
+    pub lp_token_amount: u128,
     pub lower_tick: i32,
     pub upper_tick: i32,
     pub position_mint: Pubkey,
     pub position_token_account: Pubkey,
+    /// Position account (PDA of the position mint)
+    pub position_address: Pubkey,
+    /// Fee growth inside the range for token A when fees were last updated, Q64.64
+    pub fee_growth_checkpoint_a: u128,
+    /// Token A fees owed as of the last update
+    pub fee_owed_a: u64,
+    /// Fee growth inside the range for token B when fees were last updated, Q64.64
+    pub fee_growth_checkpoint_b: u128,
+    /// Token B fees owed as of the last update
+    pub fee_owed_b: u64,
+    pub reward_infos: [PositionRewardInfo; WHIRLPOOL_NUM_REWARDS],
+}
+
+/// Reward accrual of a position for one reward slot of its pool
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PositionRewardInfo {
+    /// Reward growth inside the range when rewards were last updated, Q64.64
+    pub growth_inside_checkpoint: u128,
+    /// Rewards owed as of the last update
+    pub amount_owed: u64,
+}
+
+impl LiquidityPosition {
+    /// Decodes a Whirlpool Position account held in `position_token_account`
+    ///
+    /// Returns None when the data is not a Position account. Token amounts are left at zero;
+    /// `get_liquidity_positions` and `get_position` fill them from the pool's current price.
+    pub fn decode(
+        position_address: Pubkey,
+        position_token_account: Pubkey,
+        data: &[u8],
+    ) -> Option<Self> {
+        let mut reader = AccountReader::new(data, &POSITION_DISCRIMINATOR)?;
+        let pool_address = reader.pubkey()?;
+        let position_mint = reader.pubkey()?;
+        let liquidity = reader.u128()?;
+        let lower_tick = reader.i32()?;
+        let upper_tick = reader.i32()?;
+        let fee_growth_checkpoint_a = reader.u128()?;
+        let fee_owed_a = reader.u64()?;
+        let fee_growth_checkpoint_b = reader.u128()?;
+        let fee_owed_b = reader.u64()?;
+        let mut reward_infos = [PositionRewardInfo::default(); WHIRLPOOL_NUM_REWARDS];
+        for reward in &mut reward_infos {
+            reward.growth_inside_checkpoint = reader.u128()?;
+            reward.amount_owed = reader.u64()?;
+        }
+        Some(Self {
+            pool_address,
+            token_a_amount: 0,
+            token_b_amount: 0,
+            lp_token_amount: liquidity,
+            lower_tick,
+            upper_tick,
+            position_mint,
+            position_token_account,
+            position_address,
+            fee_growth_checkpoint_a,
+            fee_owed_a,
+            fee_growth_checkpoint_b,
+            fee_owed_b,
+            reward_infos,
+        })
+    }
+
+    /// Impermanent loss of the position against holding the tokens it held at `entry_price`
+    ///
+    /// Prices are decimal-adjusted, token B per token A, and the range comes from the
+    /// position's ticks. See [`impermanent_loss`] for the meaning of the result.
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool = client.get_pool_state_onchain(&position.pool_address.to_string()).await?;
+    /// let current = sqrt_price_x64_to_price(pool.sqrt_price, 9, 6);
+    /// let loss = position.impermanent_loss(150.0, current, 9, 6);
+    /// ```
+    pub fn impermanent_loss(
+        &self,
+        entry_price: f64,
+        current_price: f64,
+        decimals_a: u8,
+        decimals_b: u8,
+    ) -> f64 {
+        impermanent_loss(
+            entry_price,
+            current_price,
+            tick_index_to_price(self.lower_tick, decimals_a, decimals_b),
+            tick_index_to_price(self.upper_tick, decimals_a, decimals_b),
+        )
+    }
 }
 
 /// Configuration for adding liquidity with slippage protection
 #[derive(Debug, Clone)]
 pub struct AddLiquidityConfig {
-    pub slippage_tolerance: f64,
+    /// Slippage tolerance in basis points (50 = 0.5%), from 0 to 10_000, added on top of the expected deposit amounts to form the
+    /// `token_max_a`/`token_max_b` bounds enforced on-chain
+    pub slippage_bps: u16,
     pub max_iterations: u8,
+    /// Size and compute limits used to split the operation into transactions
+    pub packer: TransactionPacker,
+    /// Handling of frozen token accounts and freezable mints of the pool
+    pub freeze_check: FreezeCheckMode,
+    /// Priority fee paid per compute unit, in micro-lamports
+    pub priority_fee_microlamports: Option<u64>,
+    /// Compute unit limit requested for each transaction; also caps how much is packed into one
+    pub compute_unit_limit: Option<u32>,
+    /// How the transactions are sent; in Jito mode they form a single atomic bundle
+    pub submit_mode: SubmitMode,
+}
+
+impl AddLiquidityConfig {
+    /// Packer limited to the configured compute unit limit
+    fn effective_packer(&self) -> TransactionPacker {
+        TransactionPacker {
+            max_compute_units: self
+                .compute_unit_limit
+                .map_or(self.packer.max_compute_units, |limit| {
+                    limit.min(self.packer.max_compute_units)
+                }),
+            ..self.packer
+        }
+    }
 }
 
 impl Default for AddLiquidityConfig {
     fn default() -> Self {
         Self {
-            slippage_tolerance: 0.5,
+            slippage_bps: 50,
             max_iterations: 3,
+            packer: TransactionPacker::default(),
+            freeze_check: FreezeCheckMode::default(),
+            priority_fee_microlamports: None,
+            compute_unit_limit: None,
+            submit_mode: SubmitMode::default(),
         }
     }
 }
 
+/// Configuration for withdrawing a position
+#[derive(Debug, Clone)]
+pub struct RemoveLiquidityConfig {
+    /// Slippage tolerance in basis points (50 = 0.5%), from 0 to 10_000, taken off the expected withdrawal amounts to form the
+    /// `token_min_a`/`token_min_b` bounds enforced on-chain
+    pub slippage_bps: u16,
+}
+
+impl Default for RemoveLiquidityConfig {
+    fn default() -> Self {
+        Self { slippage_bps: 50 }
+    }
+}
+
+/// Position and owner token accounts of `increase_liquidity_v2` and `decrease_liquidity_v2`
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PositionTokenAccounts<'a> {
+    pub(crate) position_mint: &'a Pubkey,
+    pub(crate) position_token_account: &'a Pubkey,
+    /// Owner's token A and token B accounts
+    pub(crate) token_accounts: (&'a Pubkey, &'a Pubkey),
+    /// Token programs of mint A and mint B
+    pub(crate) token_programs: (&'a Pubkey, &'a Pubkey),
+}
+
+/// Instructions opening a position and depositing into it, ready to be signed
+#[derive(Debug)]
+pub struct AddLiquidityInstructions {
+    /// Freshly generated position mint; it must co-sign the transaction opening the position
+    pub position_mint: Keypair,
+    /// Position account (PDA of the position mint)
+    pub position_address: Pubkey,
+    /// Owner's token account receiving the position NFT
+    pub position_token_account: Pubkey,
+    /// Liquidity the deposit adds to the position
+    pub liquidity: u128,
+    /// Most token A the deposit may spend, transfer fee and slippage included
+    pub token_max_a: u64,
+    /// Most token B the deposit may spend, transfer fee and slippage included
+    pub token_max_b: u64,
+    /// Start indexes of the tick arrays the deposit initializes because they did not exist
+    pub initialized_tick_arrays: Vec<i32>,
+    /// Groups that must each land in a single transaction, in order
+    pub groups: Vec<InstructionGroup>,
+}
+
+impl AddLiquidityInstructions {
+    /// All instructions in submission order
+    pub fn instructions(&self) -> Vec<Instruction> {
+        self.groups
+            .iter()
+            .flat_map(|group| group.instructions.iter().cloned())
+            .collect()
+    }
+}
+
 impl OrcaClient {
     /// Adds liquidity to a concentrated liquidity pool within specified tick range
     ///
     /// # Params
-    /// keypair - Keypair for transaction signing
+    /// signer - Signs the transaction, e.g. a `Keypair` or a remote signer
     /// pool - Pool information
-    /// token_a_amount - Amount of token A to deposit
-    /// token_b_amount - Amount of token B to deposit  
+    /// token_amounts - Amounts of token A and token B to deposit
     /// lower_tick - Lower tick boundary for position
     /// upper_tick - Upper tick boundary for position
     /// config - Optional configuration for slippage and iterations
@@ -58,84 +272,212 @@ impl OrcaClient {
     /// let keypair = Keypair::new();
     /// let pool_info = client.get_pool("whirlpool_address").await?;
     ///
-    /// let signature = client.add_liquidity(
+    /// let receipt = client.add_liquidity(
     ///     &keypair,
     ///     &pool_info,
-    ///     1000000, // 1 token A
-    ///     2000000, // 2 token B  
+    ///     (1000000, 2000000), // 1 token A, 2 token B
     ///     -1000,   // lower tick
     ///     1000,    // upper tick
     ///     None,    // use default config
     /// ).await?;
     /// ```
-    pub async fn add_liquidity(
+    #[tracing::instrument(
+        skip(self, signer, pool, config),
+        fields(owner = %signer.pubkey(), pool = %pool.address),
+        err(Debug)
+    )]
+    pub async fn add_liquidity<S: Signer + ?Sized>(
         &self,
-        keypair: &Keypair,
+        signer: &S,
         pool: &PoolInfo,
-        token_a_amount: u64,
-        token_b_amount: u64,
+        (token_a_amount, token_b_amount): (u64, u64),
         lower_tick: i32,
         upper_tick: i32,
         config: Option<AddLiquidityConfig>,
-    ) -> OrcaResult<Signature> {
-        let token_a_mint = Pubkey::from_str(&pool.token_mint_a)
-            .map_err(|e| OrcaError::Error(format!("Invalid token mint A: {}", e)))?;
-        let token_b_mint = Pubkey::from_str(&pool.token_mint_b)
-            .map_err(|e| OrcaError::Error(format!("Invalid token mint B: {}", e)))?;
-        let token_a_account = self.ensure_token_account(keypair, &token_a_mint).await?;
-        let token_b_account = self.ensure_token_account(keypair, &token_b_mint).await?;
-        let pool_pubkey = Pubkey::from_str(&pool.address)
-            .map_err(|e| OrcaError::Error(format!("Invalid pool address: {}", e)))?;
-        let recent_blockhash = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?
-            .get_latest_blockhash()
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get blockhash: {}", e)))?;
-        let position_mint = Keypair::new();
-        let position_token_account =
-            self.get_associated_token_address(&keypair.pubkey(), &position_mint.pubkey());
-        let open_position_instruction = self.build_open_position_instruction(
-            &keypair.pubkey(),
-            &pool_pubkey,
-            &position_mint.pubkey(),
-            &position_token_account,
-            lower_tick,
-            upper_tick,
-        )?;
-        let increase_liquidity_instruction = self.build_increase_liquidity_instruction(
-            &keypair.pubkey(),
-            &pool_pubkey,
-            &position_token_account,
-            &token_a_account,
-            &token_b_account,
-            &token_a_mint,
-            &token_b_mint,
-            &position_mint.pubkey(),
-            token_a_amount,
-            token_b_amount,
-        )?;
-        let message = Message::new(
-            &[open_position_instruction, increase_liquidity_instruction],
-            Some(&keypair.pubkey()),
-        );
-        let transaction = Transaction::new(&[keypair, &position_mint], message, recent_blockhash);
-        self.solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?
-            .send_and_confirm_transaction(&transaction)
+    ) -> OrcaResult<AddLiquidityReceipt> {
+        self.with_timeout("add liquidity", async {
+            validate_tick_range(lower_tick, upper_tick, pool.tick_spacing)?;
+            validate_amount(
+                "token_a_amount + token_b_amount",
+                token_a_amount.saturating_add(token_b_amount),
+            )?;
+            let token_a_mint = parse_address("token_mint_a", &pool.token_mint_a)?;
+            let token_b_mint = parse_address("token_mint_b", &pool.token_mint_b)?;
+            self.check_pool_policy(&pool.address)?;
+            let config = config.unwrap_or_default();
+            self.enforce_pool_freeze_check(config.freeze_check, &signer.pubkey(), pool)
+                .await?;
+            let deposit = self
+                .build_add_liquidity_ixs(
+                    &signer.pubkey(),
+                    pool,
+                    (token_a_amount, token_b_amount),
+                    lower_tick,
+                    upper_tick,
+                    config.slippage_bps,
+                )
+                .await?;
+            let spends = [
+                (pool.token_mint_a.as_str(), deposit.token_max_a),
+                (pool.token_mint_b.as_str(), deposit.token_max_b),
+            ];
+            let reservation = self.reserve_spend(&spends).await?;
+            let mut rent_plan = RentPlan::new()
+                .account("position mint", MINT_ACCOUNT_SIZE)
+                .account("position token account", TOKEN_ACCOUNT_SIZE)
+                .account("position account", POSITION_ACCOUNT_SIZE)
+                .signatures(2);
+            for start in &deposit.initialized_tick_arrays {
+                rent_plan =
+                    rent_plan.account(format!("tick array {}", start), TICK_ARRAY_ACCOUNT_SIZE);
+            }
+            if let Some(tip_lamports) = config.submit_mode.jito_tip() {
+                rent_plan = rent_plan.lamports("Jito tip", tip_lamports);
+            }
+            let rent_plan = self
+                .plan_token_accounts(rent_plan, &signer.pubkey(), &[token_a_mint, token_b_mint])
+                .await?;
+            self.ensure_sol_for_plan(&signer.pubkey(), &rent_plan)
+                .await?;
+            let rent_paid = self.estimate_rent(&rent_plan).await?;
+            let signatures = self
+                .submit_packed(
+                    signer,
+                    &[&deposit.position_mint],
+                    PackedSubmission {
+                        prefix: &self.build_compute_budget_instructions(
+                            config.compute_unit_limit,
+                            config.priority_fee_microlamports,
+                        )?,
+                        groups: &deposit.groups,
+                        packer: &config.effective_packer(),
+                        mode: &config.submit_mode,
+                    },
+                    "add liquidity",
+                    &AuditDetails::new(serde_json::json!({
+                        "pool": pool.address,
+                        "position_mint": deposit.position_mint.pubkey().to_string(),
+                        "token_a_amount": token_a_amount,
+                        "token_b_amount": token_b_amount,
+                        "token_max_a": deposit.token_max_a,
+                        "token_max_b": deposit.token_max_b,
+                        "lower_tick": lower_tick,
+                        "upper_tick": upper_tick,
+                    })),
+                )
+                .await?;
+            let signature = signatures
+                .last()
+                .copied()
+                .ok_or(OrcaError::Error("No transaction was submitted".to_string()))?;
+            reservation.commit();
+            let settlement = self
+                .settle_liquidity(&signature, &signer.pubkey(), &token_a_mint, &token_b_mint)
+                .await;
+            Ok(AddLiquidityReceipt {
+                signature,
+                signatures,
+                pool: parse_address("pool_address", &pool.address)?,
+                position_mint: deposit.position_mint.pubkey(),
+                position_address: deposit.position_address,
+                position_token_account: deposit.position_token_account,
+                lower_tick,
+                upper_tick,
+                token_a_amount: deposit.token_max_a,
+                token_b_amount: deposit.token_max_b,
+                liquidity: deposit.liquidity,
+                rent_paid,
+                settlement,
+            })
+        })
+        .await
+    }
+
+    /// Opens a position between two prices and deposits into it
+    ///
+    /// Prices are decimal-adjusted amounts of token B per token A. They are converted to ticks
+    /// and rounded to the pool's tick spacing with `get_tick_range_for_prices`; the receipt
+    /// reports the ticks that were used.
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool_info = client.get_pool_state_onchain("whirlpool_address").await?;
+    /// let receipt = client
+    ///     .add_liquidity_by_price(&keypair, &pool_info, (1_000_000, 2_000_000), 120.0, 180.0, None)
+    ///     .await?;
+    /// println!("Opened [{}, {}]", receipt.lower_tick, receipt.upper_tick);
+    /// ```
+    pub async fn add_liquidity_by_price<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        pool: &PoolInfo,
+        token_amounts: (u64, u64),
+        lower_price: f64,
+        upper_price: f64,
+        config: Option<AddLiquidityConfig>,
+    ) -> OrcaResult<AddLiquidityReceipt> {
+        let (lower_tick, upper_tick) = self
+            .get_tick_range_for_prices(pool, lower_price, upper_price)
+            .await?;
+        self.add_liquidity(signer, pool, token_amounts, lower_tick, upper_tick, config)
             .await
-            .map_err(|e| OrcaError::Error(format!("Failed to add liquidity: {}", e)))
+    }
+
+    /// Converts a decimal-adjusted price range into ticks aligned to the pool's tick spacing
+    ///
+    /// Each price is mapped to the nearest initializable tick. When both prices round to the
+    /// same tick, the range is widened by one tick spacing so that it stays valid.
+    ///
+    /// # Params
+    /// pool - Pool information
+    /// lower_price - Lower price, token B per token A
+    /// upper_price - Upper price, token B per token A
+    ///
+    /// # Example
+    /// ```rust
+    /// let (lower_tick, upper_tick) = client
+    ///     .get_tick_range_for_prices(&pool_info, 120.0, 180.0)
+    ///     .await?;
+    /// ```
+    pub async fn get_tick_range_for_prices(
+        &self,
+        pool: &PoolInfo,
+        lower_price: f64,
+        upper_price: f64,
+    ) -> OrcaResult<(i32, i32)> {
+        validate_price_range(lower_price, upper_price)?;
+        let mint_infos = self
+            .get_mint_infos(&[
+                parse_address("token_mint_a", &pool.token_mint_a)?,
+                parse_address("token_mint_b", &pool.token_mint_b)?,
+            ])
+            .await?;
+        let (decimals_a, decimals_b) = (mint_infos[0].decimals, mint_infos[1].decimals);
+        let to_tick = |price: f64| {
+            align_tick_index(
+                price_to_tick_index(price, decimals_a, decimals_b),
+                pool.tick_spacing,
+            )
+        };
+        let (mut lower_tick, mut upper_tick) = (to_tick(lower_price), to_tick(upper_price));
+        if lower_tick == upper_tick {
+            let spacing = pool.tick_spacing as i32;
+            if upper_tick + spacing <= MAX_TICK_INDEX {
+                upper_tick += spacing;
+            } else {
+                lower_tick -= spacing;
+            }
+        }
+        validate_tick_range(lower_tick, upper_tick, pool.tick_spacing)?;
+        Ok((lower_tick, upper_tick))
     }
 
     /// Removes liquidity from a position and closes it
     ///
     /// # Params
-    /// keypair - Keypair for transaction signing
+    /// signer - Signs the transaction, e.g. a `Keypair` or a remote signer
     /// position - Liquidity position to remove
+    /// config - Optional configuration for slippage
     ///
     /// # Example
     /// ```rust
@@ -147,51 +489,428 @@ impl OrcaClient {
     /// let positions = client.get_liquidity_positions(&keypair.pubkey()).await?;
     ///
     /// if let Some(position) = positions.first() {
-    ///     let signature = client.remove_liquidity(&keypair, position).await?;
+    ///     let receipt = client.remove_liquidity(&keypair, position, None).await?;
     /// }
     /// ```
-    pub async fn remove_liquidity(
+    #[tracing::instrument(
+        skip(self, signer, position, config),
+        fields(
+            owner = %signer.pubkey(),
+            pool = %position.pool_address,
+            position_mint = %position.position_mint,
+            liquidity = position.lp_token_amount,
+        ),
+        err(Debug)
+    )]
+    pub async fn remove_liquidity<S: Signer + ?Sized>(
         &self,
-        keypair: &Keypair,
+        signer: &S,
         position: &LiquidityPosition,
-    ) -> OrcaResult<Signature> {
-        let recent_blockhash = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?
-            .get_latest_blockhash()
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get blockhash: {}", e)))?;
-        let decrease_liquidity_instruction = self.build_decrease_liquidity_instruction(
-            &keypair.pubkey(),
-            &position.pool_address,
-            &position.position_token_account,
-            &position.position_mint,
-            position.lp_token_amount,
+        config: Option<RemoveLiquidityConfig>,
+    ) -> OrcaResult<RemoveLiquidityReceipt> {
+        self.with_timeout("remove liquidity", async {
+            validate_amount(
+                "liquidity",
+                u64::try_from(position.lp_token_amount).unwrap_or(u64::MAX),
+            )?;
+            let slippage_bps = config.unwrap_or_default().slippage_bps;
+            let pool = self
+                .get_pool_state_onchain(&position.pool_address.to_string())
+                .await?;
+            let token_a_mint = parse_address("token_mint_a", &pool.token_mint_a)?;
+            let token_b_mint = parse_address("token_mint_b", &pool.token_mint_b)?;
+            let rent_refunded = self
+                .estimate_rent(
+                    &RentPlan::new()
+                        .account("position account", POSITION_ACCOUNT_SIZE)
+                        .account("position token account", TOKEN_ACCOUNT_SIZE),
+                )
+                .await?;
+            let recent_blockhash = self.latest_blockhash().await?;
+            let message = Message::new(
+                &self
+                    .build_remove_liquidity_ixs(&signer.pubkey(), position, slippage_bps)
+                    .await?,
+                Some(&signer.pubkey()),
+            );
+            let transaction = Transaction::new(&[signer], message, recent_blockhash);
+            let signature = self
+                .submit_transaction(
+                    &transaction.into(),
+                    "remove liquidity",
+                    &AuditDetails::new(serde_json::json!({
+                        "pool": position.pool_address.to_string(),
+                        "position_mint": position.position_mint.to_string(),
+                        "liquidity": position.lp_token_amount.to_string(),
+                    })),
+                )
+                .await?;
+            let settlement = self
+                .settle_liquidity(&signature, &signer.pubkey(), &token_a_mint, &token_b_mint)
+                .await;
+            Ok(RemoveLiquidityReceipt {
+                signature,
+                pool: position.pool_address,
+                position_mint: position.position_mint,
+                position_address: position.position_address,
+                liquidity: position.lp_token_amount,
+                rent_refunded,
+                settlement,
+            })
+        })
+        .await
+    }
+
+    /// Builds the instructions of `add_liquidity` without signing or sending them
+    ///
+    /// A new position mint is generated and returned; it must co-sign the transaction that
+    /// opens the position. Missing token accounts of the owner are created idempotently, and
+    /// tick arrays of the range that do not exist yet are initialized at the owner's expense. The
+    /// groups can be packed into transactions with `TransactionPacker::pack`.
+    ///
+    /// # Params
+    /// owner - Wallet that pays for and owns the position
+    /// pool - Pool information
+    /// token_amounts - Amounts of token A and token B to deposit
+    /// lower_tick - Lower tick boundary for position
+    /// upper_tick - Upper tick boundary for position
+    /// slippage_bps - Tolerance in basis points added to the expected amounts for the on-chain
+    /// `token_max_a`/`token_max_b` bounds
+    ///
+    /// # Example
+    /// ```rust
+    /// let deposit = client
+    ///     .build_add_liquidity_ixs(&wallet, &pool_info, (1_000_000, 2_000_000), -1000, 1000, 50)
+    ///     .await?;
+    /// let mut transaction = client.build_transaction(&wallet, &deposit.instructions()).await?;
+    /// transaction.partial_sign(&[&deposit.position_mint], transaction.message.recent_blockhash);
+    /// ```
+    pub async fn build_add_liquidity_ixs(
+        &self,
+        owner: &Pubkey,
+        pool: &PoolInfo,
+        (token_a_amount, token_b_amount): (u64, u64),
+        lower_tick: i32,
+        upper_tick: i32,
+        slippage_bps: u16,
+    ) -> OrcaResult<AddLiquidityInstructions> {
+        validate_tick_range(lower_tick, upper_tick, pool.tick_spacing)?;
+        validate_slippage_bps(slippage_bps)?;
+        validate_amount(
+            "token_a_amount + token_b_amount",
+            token_a_amount.saturating_add(token_b_amount),
         )?;
-        let close_position_instruction = self.build_close_position_instruction(
-            &keypair.pubkey(),
-            &position.pool_address,
-            &position.position_token_account,
-            &position.position_mint,
+        let token_a_mint = parse_address("token_mint_a", &pool.token_mint_a)?;
+        let token_b_mint = parse_address("token_mint_b", &pool.token_mint_b)?;
+        let pool_pubkey = parse_address("pool_address", &pool.address)?;
+        let mint_infos = self.get_mint_infos(&[token_a_mint, token_b_mint]).await?;
+        let (token_program_a, token_program_b) =
+            (mint_infos[0].token_program, mint_infos[1].token_program);
+        let token_a_account =
+            self.get_associated_token_address_with_program(owner, &token_a_mint, &token_program_a);
+        let token_b_account =
+            self.get_associated_token_address_with_program(owner, &token_b_mint, &token_program_b);
+        // Transfer fees are withheld before the deposit reaches the vaults
+        let liquidity_amount = liquidity_from_token_amounts(
+            pool.sqrt_price,
+            lower_tick,
+            upper_tick,
+            mint_infos[0].amount_after_transfer_fee(token_a_amount),
+            mint_infos[1].amount_after_transfer_fee(token_b_amount),
+        );
+        validate_amount(
+            "liquidity",
+            u64::try_from(liquidity_amount).unwrap_or(u64::MAX),
         )?;
-        let message = Message::new(
-            &[decrease_liquidity_instruction, close_position_instruction],
-            Some(&keypair.pubkey()),
+        // Amounts the liquidity needs at the current price, grossed up for the transfer fee,
+        // plus the slippage tolerance for price moves before the deposit lands
+        let (expected_a, expected_b) = token_amounts_from_liquidity(
+            liquidity_amount,
+            pool.sqrt_price,
+            lower_tick,
+            upper_tick,
+            true,
         );
-        let transaction = Transaction::new(&[keypair], message, recent_blockhash);
-        self.solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?
-            .send_and_confirm_transaction(&transaction)
+        let token_max_a = max_amount_with_slippage(
+            mint_infos[0].amount_before_transfer_fee(expected_a),
+            slippage_bps,
+        );
+        let token_max_b = max_amount_with_slippage(
+            mint_infos[1].amount_before_transfer_fee(expected_b),
+            slippage_bps,
+        );
+        let mut groups = Vec::new();
+        let missing = self
+            .missing_accounts(&[token_a_account, token_b_account])
+            .await?;
+        for (info, is_missing) in mint_infos.iter().zip(missing) {
+            if is_missing {
+                groups.push(InstructionGroup::new(
+                    vec![create_associated_token_account_idempotent(
+                        owner,
+                        owner,
+                        &info.mint,
+                        &info.token_program,
+                    )],
+                    CREATE_TOKEN_ACCOUNT_COMPUTE_UNITS,
+                ));
+            }
+        }
+        let initialized_tick_arrays = self
+            .get_missing_tick_array_starts(pool, lower_tick, upper_tick)
+            .await?;
+        for start in &initialized_tick_arrays {
+            groups.push(InstructionGroup::new(
+                vec![self.build_initialize_tick_array_instruction(owner, &pool_pubkey, *start)],
+                INITIALIZE_TICK_ARRAY_COMPUTE_UNITS,
+            ));
+        }
+        let position_mint = Keypair::new();
+        let position_token_account =
+            self.get_associated_token_address(owner, &position_mint.pubkey());
+        groups.push(InstructionGroup::new(
+            vec![self.build_open_position_instruction(
+                owner,
+                &pool_pubkey,
+                &position_mint.pubkey(),
+                &position_token_account,
+                lower_tick,
+                upper_tick,
+            )?],
+            OPEN_POSITION_COMPUTE_UNITS,
+        ));
+        groups.push(InstructionGroup::new(
+            vec![self.build_increase_liquidity_instruction(
+                owner,
+                pool,
+                PositionTokenAccounts {
+                    position_mint: &position_mint.pubkey(),
+                    position_token_account: &position_token_account,
+                    token_accounts: (&token_a_account, &token_b_account),
+                    token_programs: (&token_program_a, &token_program_b),
+                },
+                (lower_tick, upper_tick),
+                liquidity_amount,
+                (token_max_a, token_max_b),
+            )?],
+            INCREASE_LIQUIDITY_COMPUTE_UNITS,
+        ));
+        Ok(AddLiquidityInstructions {
+            position_address: self.get_position_pda(&position_mint.pubkey()),
+            position_mint,
+            position_token_account,
+            liquidity: liquidity_amount,
+            token_max_a,
+            token_max_b,
+            initialized_tick_arrays,
+            groups,
+        })
+    }
+
+    /// Simulates `add_liquidity` against current chain state without signing or broadcasting
+    ///
+    /// Returns one outcome per transaction the deposit is packed into, each with logs, compute
+    /// units consumed and the owner's expected token A and B balance changes. Every transaction
+    /// is simulated against current state, so a later one fails when it depends on accounts an
+    /// earlier one creates.
+    ///
+    /// # Example
+    /// ```rust
+    /// let outcomes = client
+    ///     .simulate_add_liquidity(&wallet, &pool_info, (1_000_000, 2_000_000), -1000, 1000, None)
+    ///     .await?;
+    /// if outcomes.iter().all(|outcome| outcome.succeeded()) {
+    ///     println!("Deposit would succeed");
+    /// }
+    /// ```
+    pub async fn simulate_add_liquidity(
+        &self,
+        owner: &Pubkey,
+        pool: &PoolInfo,
+        token_amounts: (u64, u64),
+        lower_tick: i32,
+        upper_tick: i32,
+        config: Option<AddLiquidityConfig>,
+    ) -> OrcaResult<Vec<DryRunOutcome>> {
+        self.with_timeout("simulate add liquidity", async {
+            let config = config.unwrap_or_default();
+            let deposit = self
+                .build_add_liquidity_ixs(
+                    owner,
+                    pool,
+                    token_amounts,
+                    lower_tick,
+                    upper_tick,
+                    config.slippage_bps,
+                )
+                .await?;
+            let prefix = self.build_compute_budget_instructions(
+                config.compute_unit_limit,
+                config.priority_fee_microlamports,
+            )?;
+            let batches =
+                config
+                    .effective_packer()
+                    .pack_with_prefix(owner, &prefix, &deposit.groups)?;
+            let mints = [
+                parse_address("token_mint_a", &pool.token_mint_a)?,
+                parse_address("token_mint_b", &pool.token_mint_b)?,
+            ];
+            let mut outcomes = Vec::with_capacity(batches.len());
+            for (index, instructions) in batches.iter().enumerate() {
+                let operation = if batches.len() > 1 {
+                    format!("add liquidity ({}/{})", index + 1, batches.len())
+                } else {
+                    "add liquidity".to_string()
+                };
+                outcomes.push(
+                    self.simulate_unsigned(
+                        VersionedMessage::Legacy(Message::new(instructions, Some(owner))),
+                        &operation,
+                        owner,
+                        &mints,
+                    )
+                    .await?,
+                );
+            }
+            Ok(outcomes)
+        })
+        .await
+    }
+
+    /// Simulates `remove_liquidity` against current chain state without signing or broadcasting
+    ///
+    /// # Example
+    /// ```rust
+    /// let outcome = client.simulate_remove_liquidity(&wallet, &position, None).await?;
+    /// for change in &outcome.balance_changes {
+    ///     println!("{} would change by {}", change.mint, change.delta());
+    /// }
+    /// ```
+    pub async fn simulate_remove_liquidity(
+        &self,
+        owner: &Pubkey,
+        position: &LiquidityPosition,
+        config: Option<RemoveLiquidityConfig>,
+    ) -> OrcaResult<DryRunOutcome> {
+        self.with_timeout("simulate remove liquidity", async {
+            let slippage_bps = config.unwrap_or_default().slippage_bps;
+            let instructions = self
+                .build_remove_liquidity_ixs(owner, position, slippage_bps)
+                .await?;
+            let pool = self
+                .get_pool_state_onchain(&position.pool_address.to_string())
+                .await?;
+            let mints = [
+                parse_address("token_mint_a", &pool.token_mint_a)?,
+                parse_address("token_mint_b", &pool.token_mint_b)?,
+            ];
+            self.simulate_unsigned(
+                VersionedMessage::Legacy(Message::new(&instructions, Some(owner))),
+                "remove liquidity",
+                owner,
+                &mints,
+            )
             .await
-            .map_err(|e| OrcaError::Error(format!("Failed to remove liquidity: {}", e)))
+        })
+        .await
+    }
+
+    /// Builds the instructions of `remove_liquidity`: withdraw everything, then close
+    ///
+    /// Rewards are claimed first, then the liquidity is withdrawn and the fees collected, so
+    /// that the position is empty when it is closed. Missing token accounts of the owner are
+    /// created idempotently. The withdrawal enforces `token_min_a`/`token_min_b` bounds: the
+    /// amounts expected at the current price, net of transfer fees, less `slippage_bps`.
+    ///
+    /// # Example
+    /// ```rust
+    /// let instructions = client.build_remove_liquidity_ixs(&wallet, &position, 50).await?;
+    /// let transaction = client.build_transaction(&wallet, &instructions).await?;
+    /// ```
+    pub async fn build_remove_liquidity_ixs(
+        &self,
+        owner: &Pubkey,
+        position: &LiquidityPosition,
+        slippage_bps: u16,
+    ) -> OrcaResult<Vec<Instruction>> {
+        validate_amount(
+            "liquidity",
+            u64::try_from(position.lp_token_amount).unwrap_or(u64::MAX),
+        )?;
+        validate_slippage_bps(slippage_bps)?;
+        let pool = self
+            .get_pool_state_onchain(&position.pool_address.to_string())
+            .await?;
+        let mint_infos = self
+            .get_mint_infos(&[
+                parse_address("token_mint_a", &pool.token_mint_a)?,
+                parse_address("token_mint_b", &pool.token_mint_b)?,
+            ])
+            .await?;
+        let token_programs = (&mint_infos[0].token_program, &mint_infos[1].token_program);
+        let token_a_account = self.get_associated_token_address_with_program(
+            owner,
+            &mint_infos[0].mint,
+            token_programs.0,
+        );
+        let token_b_account = self.get_associated_token_address_with_program(
+            owner,
+            &mint_infos[1].mint,
+            token_programs.1,
+        );
+        let mut instructions = self
+            .build_collect_rewards_ixs(owner, position)
+            .await?
+            .instructions;
+        instructions.extend(mint_infos.iter().map(|info| {
+            create_associated_token_account_idempotent(
+                owner,
+                owner,
+                &info.mint,
+                &info.token_program,
+            )
+        }));
+        let (expected_a, expected_b) = token_amounts_from_liquidity(
+            position.lp_token_amount,
+            pool.sqrt_price,
+            position.lower_tick,
+            position.upper_tick,
+            false,
+        );
+        let token_min_a = min_amount_with_slippage(
+            mint_infos[0].amount_after_transfer_fee(expected_a),
+            slippage_bps,
+        );
+        let token_min_b = min_amount_with_slippage(
+            mint_infos[1].amount_after_transfer_fee(expected_b),
+            slippage_bps,
+        );
+        instructions.push(self.build_decrease_liquidity_instruction(
+            owner,
+            &pool,
+            position,
+            (&token_a_account, &token_b_account),
+            token_programs,
+            (token_min_a, token_min_b),
+        )?);
+        instructions.push(self.build_collect_fees_instruction(
+            owner,
+            &pool,
+            position,
+            (&token_a_account, &token_b_account),
+            token_programs,
+        )?);
+        instructions.push(self.build_close_position_instruction(owner, position)?);
+        Ok(instructions)
     }
 
     /// Retrieves all liquidity positions for a given owner
     ///
+    /// Every SPL Token and Token-2022 account of the owner holding exactly one token of a
+    /// zero-decimal mint is a position NFT candidate; candidates are confirmed by decoding their
+    /// position PDAs, fetched in batches with `getMultipleAccounts`.
+    ///
     /// # Params
     /// owner - Public key of the position owner
     ///
@@ -212,172 +931,133 @@ impl OrcaClient {
         &self,
         owner: &Pubkey,
     ) -> OrcaResult<Vec<LiquidityPosition>> {
-        let token_accounts = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?
-            .get_token_accounts_by_owner(
-                owner,
-                solana_client::rpc_request::TokenAccountsFilter::ProgramId(spl_token::id()),
-            )
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get token accounts: {}", e)))?;
-        let mut positions = Vec::new();
-        for account in token_accounts {
-            let account_data_bytes = self.decode_account_data(&account.account.data)?;
-            let token_account = spl_token::state::Account::unpack_from_slice(&account_data_bytes)
-                .map_err(|e| {
-                OrcaError::Error(format!("Failed to unpack token account: {}", e))
-            })?;
-            if token_account.amount > 0 && self.is_position_token(&token_account.mint).await? {
-                let position = LiquidityPosition {
-                    pool_address: Pubkey::default(), // 需要从链上数据解析
-                    token_a_amount: 0,
-                    token_b_amount: 0,
-                    lp_token_amount: token_account.amount,
-                    lower_tick: 0,
-                    upper_tick: 0,
-                    position_mint: token_account.mint,
-                    position_token_account: Pubkey::from_str(&account.pubkey)
-                        .map_err(|e| OrcaError::Error(format!("Invalid account pubkey: {}", e)))?,
-                };
-                positions.push(position);
+        self.with_timeout("get liquidity positions", async {
+            let mut candidates = Vec::new();
+            for token_program in [spl_token::id(), TOKEN_2022_PROGRAM] {
+                let token_accounts = self
+                    .rpc_call("Failed to get token accounts", |client| {
+                        client.get_token_accounts_by_owner(
+                            owner,
+                            solana_client::rpc_request::TokenAccountsFilter::ProgramId(
+                                token_program,
+                            ),
+                        )
+                    })
+                    .await?;
+                candidates.extend(token_accounts.iter().filter_map(position_nft_candidate));
             }
-        }
-        Ok(positions)
+            let addresses: Vec<Pubkey> = candidates
+                .iter()
+                .map(|(position_mint, _)| self.get_position_pda(position_mint))
+                .collect();
+            let accounts = self
+                .get_multiple_accounts_batched(&addresses, "Failed to get position accounts")
+                .await?;
+            let mut positions: Vec<LiquidityPosition> = addresses
+                .iter()
+                .zip(&candidates)
+                .zip(accounts)
+                .filter_map(|((address, (_, token_account)), account)| {
+                    account
+                        .filter(|account| account.owner == self.whirlpool_program_id)
+                        .and_then(|account| {
+                            LiquidityPosition::decode(*address, *token_account, &account.data)
+                        })
+                })
+                .collect();
+            self.fill_position_token_amounts(&mut positions).await?;
+            Ok(positions)
+        })
+        .await
     }
 
-    async fn is_position_token(&self, mint: &Pubkey) -> OrcaResult<bool> {
-        let client = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?;
-        let mint_account = match client.get_account(mint).await {
-            Ok(account) => account,
-            Err(_) => return Ok(false),
-        };
-        let token_data = spl_token::state::Mint::unpack(&mint_account.data)
-            .map_err(|e| OrcaError::Error(format!("Failed to unpack mint data: {}", e)))?;
-        if self
-            .is_position_token_by_metadata(mint, &token_data)
+    /// Fetches and decodes the position of `position_mint`
+    ///
+    /// The position NFT is expected in the owner's associated token account.
+    ///
+    /// # Example
+    /// ```rust
+    /// let position = client.get_position(&wallet, &position_mint).await?;
+    /// println!(
+    ///     "{} liquidity in [{}, {}], fees owed {} / {}",
+    ///     position.lp_token_amount,
+    ///     position.lower_tick,
+    ///     position.upper_tick,
+    ///     position.fee_owed_a,
+    ///     position.fee_owed_b
+    /// );
+    /// ```
+    pub async fn get_position(
+        &self,
+        owner: &Pubkey,
+        position_mint: &Pubkey,
+    ) -> OrcaResult<LiquidityPosition> {
+        let position_token_account = self.get_associated_token_address(owner, position_mint);
+        let mut position = self
+            .fetch_position(position_mint, &position_token_account)
             .await?
-        {
-            return Ok(true);
-        }
-        if self.is_position_token_by_holders(mint).await? {
-            return Ok(true);
-        }
-        if self.is_position_token_by_pool_association(mint).await? {
-            return Ok(true);
-        }
-        Ok(false)
+            .ok_or_else(|| OrcaError::AccountNotFound(self.get_position_pda(position_mint)))?;
+        self.fill_position_token_amounts(std::slice::from_mut(&mut position))
+            .await?;
+        Ok(position)
     }
 
-    async fn is_position_token_by_metadata(
+    /// Sets the token amounts of positions from their liquidity and their pools' sqrt prices
+    async fn fill_position_token_amounts(
         &self,
-        mint: &Pubkey,
-        token_data: &spl_token::state::Mint,
-    ) -> OrcaResult<bool> {
-        let token_name = self.get_token_name(mint).await.unwrap_or_default();
-        let token_symbol = self.get_token_symbol(mint).await.unwrap_or_default();
-        let position_patterns = ["position", "LP", "liquidity", "whirlpool", "concentrated"];
-        for pattern in &position_patterns {
-            if token_name.to_lowercase().contains(pattern)
-                || token_symbol.to_lowercase().contains(pattern)
-            {
-                return Ok(true);
-            }
-        }
-        if token_data.decimals == 6 || token_data.decimals == 9 {
-            return self.verify_position_token(mint).await;
-        }
-        Ok(false)
-    }
-
-    async fn is_position_token_by_holders(&self, mint: &Pubkey) -> OrcaResult<bool> {
-        let client = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?;
-        let token_accounts = client
-            .get_token_accounts_by_owner(
-                &self.whirlpool_program_id,
-                solana_client::rpc_request::TokenAccountsFilter::Mint(*mint),
-            )
-            .await;
-        if let Ok(accounts) = token_accounts {
-            if !accounts.is_empty() {
-                return Ok(true);
-            }
-        }
-        Ok(false)
-    }
-
-    async fn is_position_token_by_pool_association(&self, mint: &Pubkey) -> OrcaResult<bool> {
-        let pools = self.get_all_whirlpools().await?;
-        for pool in pools {
-            if let Ok(pool_info) = self.get_pool_state_onchain(&pool).await {
-                if pool_info.lp_token_mint == mint.to_string() {
-                    return Ok(true);
-                }
+        positions: &mut [LiquidityPosition],
+    ) -> OrcaResult<()> {
+        let mut pools: Vec<Pubkey> = positions.iter().map(|p| p.pool_address).collect();
+        pools.sort();
+        pools.dedup();
+        let accounts = self
+            .get_multiple_accounts_batched(&pools, "Failed to get pool accounts")
+            .await?;
+        let sqrt_prices: HashMap<Pubkey, u128> = pools
+            .iter()
+            .zip(accounts)
+            .filter_map(|(pool, account)| {
+                let info = decode_whirlpool(&account?.data, &pool.to_string())?;
+                Some((*pool, info.sqrt_price))
+            })
+            .collect();
+        for position in positions {
+            if let Some(sqrt_price) = sqrt_prices.get(&position.pool_address) {
+                (position.token_a_amount, position.token_b_amount) = token_amounts_from_liquidity(
+                    position.lp_token_amount,
+                    *sqrt_price,
+                    position.lower_tick,
+                    position.upper_tick,
+                    false,
+                );
             }
         }
-        Ok(false)
+        Ok(())
     }
 
-    async fn get_all_whirlpools(&self) -> OrcaResult<Vec<String>> {
-        let client = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?;
-        let accounts = client
-            .get_program_accounts(&self.whirlpool_program_id)
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get program accounts: {}", e)))?;
-        Ok(accounts
-            .into_iter()
-            .map(|(pubkey, _)| pubkey.to_string())
-            .collect())
-    }
-
-    /// Verifies if a token is a valid Whirlpool position token
-    ///
-    /// # Params
-    /// mint - The position token mint address to verify
-    ///
-    /// # Example
-    /// ```rust
-    /// use orca_rs::client::OrcaClient;
-    /// use solana_sdk::pubkey::Pubkey;
-    ///
-    /// let client = OrcaClient::new("https://api.mainnet-beta.solana.com");
-    /// let mint = Pubkey::new_unique();
-    /// let is_position = client.verify_position_token(&mint).await?;
-    /// println!("Is position token: {}", is_position);
-    /// ```
-    async fn verify_position_token(&self, mint: &Pubkey) -> OrcaResult<bool> {
-        let client = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?;
-        // Method 1: Check if there's a position account for this mint
-        let position_pda = self.get_position_pda(mint);
-        match client.get_account(&position_pda).await {
-            Ok(account) => {
-                // Verify the account is owned by whirlpool program and has data
-                Ok(account.owner == self.whirlpool_program_id && account.data.len() >= 216) // Minimum position account size
-            }
-            Err(_) => Ok(false),
-        }
+    /// Fetches the position PDA of `position_mint`; None when it is missing or not a position
+    async fn fetch_position(
+        &self,
+        position_mint: &Pubkey,
+        position_token_account: &Pubkey,
+    ) -> OrcaResult<Option<LiquidityPosition>> {
+        let position_address = self.get_position_pda(position_mint);
+        let account = self
+            .rpc_call("Failed to get position account", |client| {
+                client.get_multiple_accounts(std::slice::from_ref(&position_address))
+            })
+            .await?
+            .pop()
+            .flatten();
+        Ok(account
+            .filter(|account| account.owner == self.whirlpool_program_id)
+            .and_then(|account| {
+                LiquidityPosition::decode(position_address, *position_token_account, &account.data)
+            }))
     }
 
     /// Derives the position PDA from the position token mint
-    fn get_position_pda(&self, position_mint: &Pubkey) -> Pubkey {
+    pub(crate) fn get_position_pda(&self, position_mint: &Pubkey) -> Pubkey {
         let (pda, _) = Pubkey::find_program_address(
             &[b"position", position_mint.as_ref()],
             &self.whirlpool_program_id,
@@ -385,65 +1065,7 @@ impl OrcaClient {
         pda
     }
 
-    async fn get_token_name(&self, mint: &Pubkey) -> OrcaResult<String> {
-        let client = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?;
-        let metadata_program = Pubkey::from_str(crate::global::TOKEN_METADATA_PROGRAM_ID)
-            .map_err(|e| OrcaError::Error(format!("Invalid metadata program ID: {}", e)))?;
-        let (metadata_address, _) = Pubkey::find_program_address(
-            &[b"metadata", metadata_program.as_ref(), mint.as_ref()],
-            &metadata_program,
-        );
-        match client.get_account(&metadata_address).await {
-            Ok(account) => {
-                if account.data.len() > 120 {
-                    let name_data = &account.data[69..109];
-                    let name = String::from_utf8_lossy(name_data)
-                        .trim_end_matches('\0')
-                        .to_string();
-                    if !name.is_empty() {
-                        return Ok(name);
-                    }
-                }
-                Ok("Unknown".to_string())
-            }
-            Err(_) => Ok("Unknown".to_string()),
-        }
-    }
-
-    async fn get_token_symbol(&self, mint: &Pubkey) -> OrcaResult<String> {
-        let client = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?;
-        let metadata_program = Pubkey::from_str(crate::global::TOKEN_METADATA_PROGRAM_ID)
-            .map_err(|e| OrcaError::Error(format!("Invalid metadata program ID: {}", e)))?;
-        let (metadata_address, _) = Pubkey::find_program_address(
-            &[b"metadata", metadata_program.as_ref(), mint.as_ref()],
-            &metadata_program,
-        );
-        match client.get_account(&metadata_address).await {
-            Ok(account) => {
-                if account.data.len() > 120 {
-                    let symbol_data = &account.data[109..119];
-                    let symbol = String::from_utf8_lossy(symbol_data)
-                        .trim_end_matches('\0')
-                        .to_string();
-                    if !symbol.is_empty() {
-                        return Ok(symbol);
-                    }
-                }
-                Ok("UNK".to_string())
-            }
-            Err(_) => Ok("UNK".to_string()),
-        }
-    }
-
-    fn build_open_position_instruction(
+    pub(crate) fn build_open_position_instruction(
         &self,
         owner: &Pubkey,
         pool: &Pubkey,
@@ -452,17 +1074,24 @@ impl OrcaClient {
         lower_tick: i32,
         upper_tick: i32,
     ) -> OrcaResult<Instruction> {
+        let (position_address, position_bump) = Pubkey::find_program_address(
+            &[b"position", position_mint.as_ref()],
+            &self.whirlpool_program_id,
+        );
         let accounts = vec![
-            AccountMeta::new_readonly(self.whirlpool_program_id, false),
-            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new(position_address, false),
             AccountMeta::new(*position_mint, true),
             AccountMeta::new(*position_token_account, false),
-            AccountMeta::new(*pool, false),
+            AccountMeta::new_readonly(*pool, false),
             AccountMeta::new_readonly(spl_token::id(), false),
             AccountMeta::new_readonly(system_program::id(), false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
         ];
-        let mut data = vec![0x08]; // open_position instruction discriminator
+        let mut data = WHIRLPOOL_OPEN_POSITION_DISCRIMINATOR.to_vec();
+        data.push(position_bump);
         data.extend_from_slice(&lower_tick.to_le_bytes());
         data.extend_from_slice(&upper_tick.to_le_bytes());
         Ok(Instruction {
@@ -472,62 +1101,130 @@ impl OrcaClient {
         })
     }
 
-    fn build_increase_liquidity_instruction(
+    pub(crate) fn build_increase_liquidity_instruction(
         &self,
         owner: &Pubkey,
-        pool: &Pubkey,
-        position_token_account: &Pubkey,
-        token_a_account: &Pubkey,
-        token_b_account: &Pubkey,
-        token_a_mint: &Pubkey,
-        token_b_mint: &Pubkey,
-        position_mint: &Pubkey,
-        token_a_amount: u64,
-        token_b_amount: u64,
+        pool: &PoolInfo,
+        accounts: PositionTokenAccounts,
+        tick_range: (i32, i32),
+        liquidity_amount: u128,
+        (token_max_a, token_max_b): (u64, u64),
     ) -> OrcaResult<Instruction> {
-        let token_vault_a = self.get_associated_token_address(pool, token_a_mint);
-        let token_vault_b = self.get_associated_token_address(pool, token_b_mint);
-        let accounts = vec![
-            AccountMeta::new_readonly(self.whirlpool_program_id, false),
-            AccountMeta::new_readonly(*owner, true),
-            AccountMeta::new(*position_token_account, false),
-            AccountMeta::new(*pool, false),
-            AccountMeta::new(*token_a_account, false),
-            AccountMeta::new(*token_b_account, false),
-            AccountMeta::new(token_vault_a, false),
-            AccountMeta::new(token_vault_b, false),
-            AccountMeta::new(*position_mint, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-            AccountMeta::new_readonly(system_program::id(), false),
-        ];
-        let mut data = vec![0x09]; // increase_liquidity instruction discriminator
-        data.extend_from_slice(&token_a_amount.to_le_bytes());
-        data.extend_from_slice(&token_b_amount.to_le_bytes());
+        let mut data = WHIRLPOOL_INCREASE_LIQUIDITY_V2_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&liquidity_amount.to_le_bytes());
+        data.extend_from_slice(&token_max_a.to_le_bytes());
+        data.extend_from_slice(&token_max_b.to_le_bytes());
+        data.push(0); // no remaining accounts info
         Ok(Instruction {
             program_id: self.whirlpool_program_id,
-            accounts,
+            accounts: self.modify_liquidity_accounts(owner, pool, accounts, tick_range)?,
             data,
         })
     }
 
-    fn build_decrease_liquidity_instruction(
+    /// Withdraws all of the position's liquidity
+    pub(crate) fn build_decrease_liquidity_instruction(
         &self,
         owner: &Pubkey,
-        pool: &Pubkey,
-        position_token_account: &Pubkey,
-        position_mint: &Pubkey,
-        liquidity_amount: u64,
+        pool: &PoolInfo,
+        position: &LiquidityPosition,
+        token_accounts: (&Pubkey, &Pubkey),
+        token_programs: (&Pubkey, &Pubkey),
+        (token_min_a, token_min_b): (u64, u64),
+    ) -> OrcaResult<Instruction> {
+        let mut data = WHIRLPOOL_DECREASE_LIQUIDITY_V2_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&position.lp_token_amount.to_le_bytes());
+        data.extend_from_slice(&token_min_a.to_le_bytes());
+        data.extend_from_slice(&token_min_b.to_le_bytes());
+        data.push(0); // no remaining accounts info
+        Ok(Instruction {
+            program_id: self.whirlpool_program_id,
+            accounts: self.modify_liquidity_accounts(
+                owner,
+                pool,
+                PositionTokenAccounts {
+                    position_mint: &position.position_mint,
+                    position_token_account: &position.position_token_account,
+                    token_accounts,
+                    token_programs,
+                },
+                (position.lower_tick, position.upper_tick),
+            )?,
+            data,
+        })
+    }
+
+    /// Accounts of `increase_liquidity_v2` and `decrease_liquidity_v2`, which share one layout
+    fn modify_liquidity_accounts(
+        &self,
+        owner: &Pubkey,
+        pool: &PoolInfo,
+        accounts: PositionTokenAccounts,
+        (lower_tick, upper_tick): (i32, i32),
+    ) -> OrcaResult<Vec<AccountMeta>> {
+        let PositionTokenAccounts {
+            position_mint,
+            position_token_account,
+            token_accounts: (token_account_a, token_account_b),
+            token_programs: (token_program_a, token_program_b),
+        } = accounts;
+        let whirlpool = parse_address("pool_address", &pool.address)?;
+        Ok(vec![
+            AccountMeta::new(whirlpool, false),
+            AccountMeta::new_readonly(*token_program_a, false),
+            AccountMeta::new_readonly(*token_program_b, false),
+            AccountMeta::new_readonly(MEMO_PROGRAM, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(self.get_position_pda(position_mint), false),
+            AccountMeta::new_readonly(*position_token_account, false),
+            AccountMeta::new_readonly(parse_address("token_mint_a", &pool.token_mint_a)?, false),
+            AccountMeta::new_readonly(parse_address("token_mint_b", &pool.token_mint_b)?, false),
+            AccountMeta::new(*token_account_a, false),
+            AccountMeta::new(*token_account_b, false),
+            AccountMeta::new(parse_address("token_vault_a", &pool.token_vault_a)?, false),
+            AccountMeta::new(parse_address("token_vault_b", &pool.token_vault_b)?, false),
+            AccountMeta::new(
+                self.get_tick_array_address(
+                    &whirlpool,
+                    tick_array_start_index(lower_tick, pool.tick_spacing),
+                ),
+                false,
+            ),
+            AccountMeta::new(
+                self.get_tick_array_address(
+                    &whirlpool,
+                    tick_array_start_index(upper_tick, pool.tick_spacing),
+                ),
+                false,
+            ),
+        ])
+    }
+
+    pub(crate) fn build_collect_fees_instruction(
+        &self,
+        owner: &Pubkey,
+        pool: &PoolInfo,
+        position: &LiquidityPosition,
+        (token_account_a, token_account_b): (&Pubkey, &Pubkey),
+        (token_program_a, token_program_b): (&Pubkey, &Pubkey),
     ) -> OrcaResult<Instruction> {
         let accounts = vec![
-            AccountMeta::new_readonly(self.whirlpool_program_id, false),
+            AccountMeta::new_readonly(position.pool_address, false),
             AccountMeta::new_readonly(*owner, true),
-            AccountMeta::new(*position_token_account, false),
-            AccountMeta::new(*pool, false),
-            AccountMeta::new(*position_mint, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(self.get_position_pda(&position.position_mint), false),
+            AccountMeta::new_readonly(position.position_token_account, false),
+            AccountMeta::new_readonly(parse_address("token_mint_a", &pool.token_mint_a)?, false),
+            AccountMeta::new_readonly(parse_address("token_mint_b", &pool.token_mint_b)?, false),
+            AccountMeta::new(*token_account_a, false),
+            AccountMeta::new(parse_address("token_vault_a", &pool.token_vault_a)?, false),
+            AccountMeta::new(*token_account_b, false),
+            AccountMeta::new(parse_address("token_vault_b", &pool.token_vault_b)?, false),
+            AccountMeta::new_readonly(*token_program_a, false),
+            AccountMeta::new_readonly(*token_program_b, false),
+            AccountMeta::new_readonly(MEMO_PROGRAM, false),
         ];
-        let mut data = vec![0x0A]; // decrease_liquidity instruction discriminator
-        data.extend_from_slice(&liquidity_amount.to_le_bytes());
+        let mut data = WHIRLPOOL_COLLECT_FEES_V2_DISCRIMINATOR.to_vec();
+        data.push(0); // no remaining accounts info
         Ok(Instruction {
             program_id: self.whirlpool_program_id,
             accounts,
@@ -535,26 +1232,107 @@ impl OrcaClient {
         })
     }
 
-    fn build_close_position_instruction(
+    pub(crate) fn build_close_position_instruction(
         &self,
         owner: &Pubkey,
-        pool: &Pubkey,
-        position_token_account: &Pubkey,
-        position_mint: &Pubkey,
+        position: &LiquidityPosition,
     ) -> OrcaResult<Instruction> {
         let accounts = vec![
-            AccountMeta::new_readonly(self.whirlpool_program_id, false),
             AccountMeta::new_readonly(*owner, true),
-            AccountMeta::new(*position_token_account, false),
-            AccountMeta::new(*pool, false),
-            AccountMeta::new(*position_mint, false),
+            AccountMeta::new(*owner, false),
+            AccountMeta::new(self.get_position_pda(&position.position_mint), false),
+            AccountMeta::new(position.position_mint, false),
+            AccountMeta::new(position.position_token_account, false),
             AccountMeta::new_readonly(spl_token::id(), false),
         ];
-        let data = vec![0x0B]; // close_position instruction discriminator
         Ok(Instruction {
             program_id: self.whirlpool_program_id,
             accounts,
-            data,
+            data: WHIRLPOOL_CLOSE_POSITION_DISCRIMINATOR.to_vec(),
         })
     }
 }
+
+/// Returns (mint, token account) when a parsed token account holds exactly one token of a
+/// zero-decimal mint, as position NFTs do
+fn position_nft_candidate(account: &RpcKeyedAccount) -> Option<(Pubkey, Pubkey)> {
+    let UiAccountData::Json(parsed) = &account.account.data else {
+        return None;
+    };
+    let info = parsed.parsed.get("info")?;
+    let token_amount = info.get("tokenAmount")?;
+    if token_amount.get("amount")?.as_str()? != "1" || token_amount.get("decimals")?.as_u64()? != 0
+    {
+        return None;
+    }
+    Some((
+        Pubkey::from_str(info.get("mint")?.as_str()?).ok()?,
+        Pubkey::from_str(&account.pubkey).ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use base64::{Engine, prelude::BASE64_STANDARD};
+
+    use super::*;
+
+    // Mainnet Position and Whirlpool accounts, captured as base64 `getAccountInfo` data
+    const POSITION_ACCOUNT: &str = include_str!("../tests/fixtures/position_account.b64");
+    const WHIRLPOOL_ACCOUNT: &str = include_str!("../tests/fixtures/whirlpool_account.b64");
+
+    fn fixture(data: &str) -> Vec<u8> {
+        BASE64_STANDARD.decode(data.trim()).unwrap()
+    }
+
+    fn pubkey(address: &str) -> Pubkey {
+        Pubkey::from_str(address).unwrap()
+    }
+
+    #[test]
+    fn decodes_position_account() {
+        let position_token_account = Pubkey::new_unique();
+        let position = LiquidityPosition::decode(
+            Pubkey::default(),
+            position_token_account,
+            &fixture(POSITION_ACCOUNT),
+        )
+        .unwrap();
+        assert_eq!(
+            position.pool_address,
+            pubkey("C9U2Ksk6KKWvLEeo5yUQ7Xu46X7NzeBJtd9PBfuXaUSM")
+        );
+        assert_eq!(
+            position.position_mint,
+            pubkey("4t8CquCQLazH6AZHtCdqfNowTvGztBRFdQ5ewimu8bpB")
+        );
+        assert_eq!(position.position_token_account, position_token_account);
+        assert_eq!(position.lp_token_amount, 27713585964);
+        assert_eq!(position.lower_tick, -21264);
+        assert_eq!(position.upper_tick, -19424);
+        assert_eq!(
+            position.fee_growth_checkpoint_a,
+            340282366920938463461883143742666180270
+        );
+        assert_eq!(
+            position.fee_growth_checkpoint_b,
+            340282366920938463463072690694709896731
+        );
+        assert_eq!((position.fee_owed_a, position.fee_owed_b), (0, 0));
+    }
+
+    #[test]
+    fn rejects_non_position_accounts() {
+        assert!(
+            LiquidityPosition::decode(
+                Pubkey::default(),
+                Pubkey::default(),
+                &fixture(WHIRLPOOL_ACCOUNT)
+            )
+            .is_none()
+        );
+        assert!(crate::pool::decode_whirlpool(&fixture(POSITION_ACCOUNT), "pool").is_none());
+    }
+}
@@ -0,0 +1,260 @@
+use solana_address_lookup_table_interface::{
+    instruction::{create_lookup_table, extend_lookup_table},
+    state::AddressLookupTable,
+};
+use solana_sdk::{
+    instruction::Instruction,
+    message::{AddressLookupTableAccount, VersionedMessage, v0},
+};
+
+use super::*;
+use crate::{
+    audit::AuditDetails,
+    global::MAX_SWAP_TICK_ARRAYS,
+    pool::PoolInfo,
+    token::{MEMO_PROGRAM, TOKEN_2022_PROGRAM},
+    types::OrcaResult,
+    validation::parse_address,
+};
+
+// Addresses added per extend instruction, keeping each transaction under the size limit
+const MAX_ADDRESSES_PER_EXTEND: usize = 20;
+
+impl OrcaClient {
+    /// Fetches and decodes address lookup tables in batches of 100
+    ///
+    /// # Example
+    /// ```rust
+    /// let tables = client.get_address_lookup_tables(&[lookup_table_address]).await?;
+    /// println!("{} addresses", tables[0].addresses.len());
+    /// ```
+    pub async fn get_address_lookup_tables(
+        &self,
+        addresses: &[Pubkey],
+    ) -> OrcaResult<Vec<AddressLookupTableAccount>> {
+        let accounts = self
+            .get_multiple_accounts_batched(addresses, "Failed to get lookup tables")
+            .await?;
+        addresses
+            .iter()
+            .zip(accounts)
+            .map(|(address, account)| {
+                let account = account.ok_or(OrcaError::AccountNotFound(*address))?;
+                let table = AddressLookupTable::deserialize(&account.data).map_err(|e| {
+                    OrcaError::InvalidAccountData {
+                        account: *address,
+                        reason: format!("not an address lookup table: {}", e),
+                    }
+                })?;
+                Ok(AddressLookupTableAccount {
+                    key: *address,
+                    addresses: table.addresses.to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    /// Compiles instructions into an unsigned v0 message with a recent blockhash
+    ///
+    /// Accounts found in `lookup_tables` are referenced by index instead of being listed in
+    /// full, which keeps swaps crossing several tick arrays under the transaction size limit.
+    ///
+    /// # Example
+    /// ```rust
+    /// use solana_sdk::transaction::VersionedTransaction;
+    ///
+    /// let swap = client
+    ///     .build_swap_ixs(&keypair.pubkey(), input_mint, output_mint, 1_000_000, true, None)
+    ///     .await?;
+    /// let tables = client.get_address_lookup_tables(&[lookup_table_address]).await?;
+    /// let message = client
+    ///     .build_v0_message(&keypair.pubkey(), &swap.instructions, &tables)
+    ///     .await?;
+    /// let transaction = VersionedTransaction::try_new(message, &[&keypair])?;
+    /// ```
+    pub async fn build_v0_message(
+        &self,
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> OrcaResult<VersionedMessage> {
+        let recent_blockhash = self.latest_blockhash().await?;
+        let message =
+            v0::Message::try_compile(payer, instructions, lookup_tables, recent_blockhash)
+                .map_err(|e| {
+                    OrcaError::TransactionError(format!("Failed to compile message: {}", e))
+                })?;
+        Ok(VersionedMessage::V0(message))
+    }
+
+    /// Builds and signs a v0 transaction using the given lookup tables
+    pub(crate) async fn build_signed_v0_transaction<S: Signer + ?Sized>(
+        &self,
+        payer: &S,
+        instructions: &[Instruction],
+        lookup_table_addresses: &[Pubkey],
+    ) -> OrcaResult<VersionedTransaction> {
+        let lookup_tables = self
+            .get_address_lookup_tables(lookup_table_addresses)
+            .await?;
+        let message = self
+            .build_v0_message(&payer.pubkey(), instructions, &lookup_tables)
+            .await?;
+        VersionedTransaction::try_new(message, &[payer])
+            .map_err(|e| OrcaError::TransactionError(format!("Failed to sign transaction: {}", e)))
+    }
+
+    /// Accounts worth keeping in a lookup table for trading a pool
+    ///
+    /// Includes the pool, its mints, vaults and oracle, the tick arrays a swap in either direction
+    /// starts from, and the programs a swap may invoke. Tick arrays follow the current price, so
+    /// the table may need extending after large price moves.
+    pub fn get_pool_lookup_addresses(&self, pool: &PoolInfo) -> OrcaResult<Vec<Pubkey>> {
+        let whirlpool = parse_address("pool_address", &pool.address)?;
+        let mut addresses = vec![
+            whirlpool,
+            parse_address("token_mint_a", &pool.token_mint_a)?,
+            parse_address("token_mint_b", &pool.token_mint_b)?,
+            parse_address("token_vault_a", &pool.token_vault_a)?,
+            parse_address("token_vault_b", &pool.token_vault_b)?,
+            self.get_oracle_address(&whirlpool),
+            self.whirlpool_program_id,
+            spl_token::id(),
+            TOKEN_2022_PROGRAM,
+            MEMO_PROGRAM,
+        ];
+        for a_to_b in [true, false] {
+            for start in self.get_swap_tick_array_start_indexes(pool, a_to_b, MAX_SWAP_TICK_ARRAYS)
+            {
+                let tick_array = self.get_tick_array_address(&whirlpool, start);
+                if !addresses.contains(&tick_array) {
+                    addresses.push(tick_array);
+                }
+            }
+        }
+        Ok(addresses)
+    }
+
+    /// Creates an address lookup table owned by `signer` holding `addresses`
+    ///
+    /// Returns the table address. Addresses become usable one slot after they are added.
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool = client.get_pool_state_onchain("POOL_ADDRESS_HERE").await?;
+    /// let addresses = client.get_pool_lookup_addresses(&pool)?;
+    /// let table = client.create_address_lookup_table(&keypair, &addresses).await?;
+    /// let config = TradeConfig {
+    ///     lookup_tables: vec![table],
+    ///     ..Default::default()
+    /// };
+    /// ```
+    pub async fn create_address_lookup_table<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        addresses: &[Pubkey],
+    ) -> OrcaResult<Pubkey> {
+        self.with_timeout("create lookup table", async {
+            let recent_slot = self
+                .rpc_call("Failed to get slot", |client| client.get_slot())
+                .await?;
+            let (create_instruction, table) =
+                create_lookup_table(signer.pubkey(), signer.pubkey(), recent_slot);
+            let mut chunks = addresses.chunks(MAX_ADDRESSES_PER_EXTEND);
+            let mut instructions = vec![create_instruction];
+            if let Some(first) = chunks.next() {
+                instructions.push(extend_lookup_table(
+                    table,
+                    signer.pubkey(),
+                    Some(signer.pubkey()),
+                    first.to_vec(),
+                ));
+            }
+            self.submit_lookup_table_instructions(
+                signer,
+                &instructions,
+                &table,
+                "create lookup table",
+            )
+            .await?;
+            for chunk in chunks {
+                self.submit_lookup_table_instructions(
+                    signer,
+                    &[extend_lookup_table(
+                        table,
+                        signer.pubkey(),
+                        Some(signer.pubkey()),
+                        chunk.to_vec(),
+                    )],
+                    &table,
+                    "extend lookup table",
+                )
+                .await?;
+            }
+            Ok(table)
+        })
+        .await
+    }
+
+    /// Adds the addresses a lookup table does not hold yet
+    ///
+    /// Returns the signatures of the extend transactions; empty when nothing was missing.
+    pub async fn extend_address_lookup_table<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        table: &Pubkey,
+        addresses: &[Pubkey],
+    ) -> OrcaResult<Vec<Signature>> {
+        self.with_timeout("extend lookup table", async {
+            let existing = self
+                .get_address_lookup_tables(&[*table])
+                .await?
+                .pop()
+                .map(|table| table.addresses)
+                .unwrap_or_default();
+            let mut missing: Vec<Pubkey> = Vec::new();
+            for address in addresses {
+                if !existing.contains(address) && !missing.contains(address) {
+                    missing.push(*address);
+                }
+            }
+            let mut signatures = Vec::new();
+            for chunk in missing.chunks(MAX_ADDRESSES_PER_EXTEND) {
+                signatures.push(
+                    self.submit_lookup_table_instructions(
+                        signer,
+                        &[extend_lookup_table(
+                            *table,
+                            signer.pubkey(),
+                            Some(signer.pubkey()),
+                            chunk.to_vec(),
+                        )],
+                        table,
+                        "extend lookup table",
+                    )
+                    .await?,
+                );
+            }
+            Ok(signatures)
+        })
+        .await
+    }
+
+    async fn submit_lookup_table_instructions<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        instructions: &[Instruction],
+        table: &Pubkey,
+        operation: &str,
+    ) -> OrcaResult<Signature> {
+        let recent_blockhash = self.latest_blockhash().await?;
+        let message = Message::new(instructions, Some(&signer.pubkey()));
+        let transaction = Transaction::new(&[signer], message, recent_blockhash);
+        self.submit_transaction(
+            &transaction.into(),
+            operation,
+            &AuditDetails::new(serde_json::json!({ "lookup_table": table.to_string() })),
+        )
+        .await
+    }
+}
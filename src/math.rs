@@ -0,0 +1,248 @@
+use crate::{
+    fixed::{
+        amount_a_delta, amount_b_delta, liquidity_from_amount_a, liquidity_from_amount_b,
+        sqrt_price_x64_at_tick,
+    },
+    global::{
+        BPS_DENOMINATOR, MAX_SQRT_PRICE_X64, MAX_TICK_INDEX, MIN_SQRT_PRICE_X64, MIN_TICK_INDEX,
+    },
+};
+
+// 2^64, the scale of Q64.64 numbers
+const Q64: f64 = 18_446_744_073_709_551_616.0;
+// Each tick moves the price by one basis point
+const TICK_BASE: f64 = 1.0001;
+
+/// Factor converting a raw price (B base units per A base unit) into a decimal-adjusted price
+///
+/// Decimal-adjusted prices throughout this module are whole token B per whole token A, with
+/// `decimals_a` and `decimals_b` the decimals of the pool's two mints.
+fn decimals_factor(decimals_a: u8, decimals_b: u8) -> f64 {
+    10f64.powi(decimals_a as i32 - decimals_b as i32)
+}
+
+/// Decimal-adjusted price at a tick
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::math::tick_index_to_price;
+///
+/// // SOL (9 decimals) / USDC (6 decimals)
+/// let price = tick_index_to_price(-18_000, 9, 6);
+/// ```
+pub fn tick_index_to_price(tick_index: i32, decimals_a: u8, decimals_b: u8) -> f64 {
+    TICK_BASE.powi(tick_index) * decimals_factor(decimals_a, decimals_b)
+}
+
+/// Highest tick whose price does not exceed `price`, clamped to the tick bounds
+///
+/// The result is not aligned to any tick spacing; use `tick::align_tick_index` for that.
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::math::price_to_tick_index;
+///
+/// let tick = price_to_tick_index(150.0, 9, 6);
+/// ```
+pub fn price_to_tick_index(price: f64, decimals_a: u8, decimals_b: u8) -> i32 {
+    let raw_price = price / decimals_factor(decimals_a, decimals_b);
+    let tick = (raw_price.ln() / TICK_BASE.ln()).floor();
+    if tick.is_nan() {
+        return MIN_TICK_INDEX;
+    }
+    tick.clamp(MIN_TICK_INDEX as f64, MAX_TICK_INDEX as f64) as i32
+}
+
+/// Decimal-adjusted price of a Q64.64 sqrt price, such as `PoolInfo::sqrt_price`
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::math::sqrt_price_x64_to_price;
+///
+/// let pool = client.get_pool_state_onchain("whirlpool_address").await?;
+/// let price = sqrt_price_x64_to_price(pool.sqrt_price, 9, 6);
+/// ```
+pub fn sqrt_price_x64_to_price(sqrt_price_x64: u128, decimals_a: u8, decimals_b: u8) -> f64 {
+    let sqrt_price = sqrt_price_x64 as f64 / Q64;
+    sqrt_price * sqrt_price * decimals_factor(decimals_a, decimals_b)
+}
+
+/// Q64.64 sqrt price of a decimal-adjusted price, clamped to the Whirlpool sqrt price bounds
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::math::price_to_sqrt_price_x64;
+///
+/// let sqrt_price_limit = price_to_sqrt_price_x64(140.0, 9, 6);
+/// ```
+pub fn price_to_sqrt_price_x64(price: f64, decimals_a: u8, decimals_b: u8) -> u128 {
+    let raw_price = price / decimals_factor(decimals_a, decimals_b);
+    let sqrt_price_x64 = raw_price.sqrt() * Q64;
+    if sqrt_price_x64.is_nan() {
+        return MIN_SQRT_PRICE_X64;
+    }
+    (sqrt_price_x64 as u128).clamp(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64)
+}
+
+/// Q64.64 sqrt price at a tick, exactly as the Whirlpool program computes it
+pub fn tick_index_to_sqrt_price_x64(tick_index: i32) -> u128 {
+    sqrt_price_x64_at_tick(tick_index)
+}
+
+/// Token A and B amounts represented by `liquidity` in a tick range at the current sqrt price
+///
+/// Below the range the position holds only token A, above it only token B. Round up when
+/// sizing a deposit and down when estimating a withdrawal.
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::math::token_amounts_from_liquidity;
+///
+/// let (amount_a, amount_b) =
+///     token_amounts_from_liquidity(1_000_000_000, pool.sqrt_price, -1024, 1024, false);
+/// ```
+pub fn token_amounts_from_liquidity(
+    liquidity: u128,
+    sqrt_price_x64: u128,
+    lower_tick: i32,
+    upper_tick: i32,
+    round_up: bool,
+) -> (u64, u64) {
+    let sqrt_lower = sqrt_price_x64_at_tick(lower_tick);
+    let sqrt_upper = sqrt_price_x64_at_tick(upper_tick);
+    let sqrt_price = sqrt_price_x64.clamp(sqrt_lower, sqrt_upper);
+    (
+        amount_a_delta(sqrt_price, sqrt_upper, liquidity, round_up).unwrap_or(u64::MAX),
+        amount_b_delta(sqrt_lower, sqrt_price, liquidity, round_up).unwrap_or(u64::MAX),
+    )
+}
+
+/// Largest liquidity that `token_a_amount` and `token_b_amount` can mint in a tick range
+///
+/// Inside the range the scarcer token limits the liquidity; the other token is only partly
+/// used. Below the range only token A counts, above it only token B.
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::math::{liquidity_from_token_amounts, token_amounts_from_liquidity};
+///
+/// let liquidity =
+///     liquidity_from_token_amounts(pool.sqrt_price, -1024, 1024, 1_000_000, 2_000_000);
+/// let (used_a, used_b) =
+///     token_amounts_from_liquidity(liquidity, pool.sqrt_price, -1024, 1024, true);
+/// ```
+pub fn liquidity_from_token_amounts(
+    sqrt_price_x64: u128,
+    lower_tick: i32,
+    upper_tick: i32,
+    token_a_amount: u64,
+    token_b_amount: u64,
+) -> u128 {
+    let sqrt_lower = sqrt_price_x64_at_tick(lower_tick);
+    let sqrt_upper = sqrt_price_x64_at_tick(upper_tick);
+    if sqrt_upper <= sqrt_lower {
+        return 0;
+    }
+    let from_a = |sqrt_from: u128| {
+        liquidity_from_amount_a(sqrt_from, sqrt_upper, token_a_amount).unwrap_or(u128::MAX)
+    };
+    let from_b = |sqrt_to: u128| {
+        liquidity_from_amount_b(sqrt_lower, sqrt_to, token_b_amount).unwrap_or(u128::MAX)
+    };
+    if sqrt_price_x64 <= sqrt_lower {
+        from_a(sqrt_lower)
+    } else if sqrt_price_x64 >= sqrt_upper {
+        from_b(sqrt_upper)
+    } else {
+        from_a(sqrt_price_x64).min(from_b(sqrt_price_x64))
+    }
+}
+
+/// Impermanent loss of a concentrated position against holding its entry tokens
+///
+/// The position is opened at `entry_price` with the range `lower_price..upper_price`. The result
+/// is its value at `current_price` divided by the value of the tokens it held at entry, minus
+/// one: -0.05 means the position is worth 5% less than holding, before fees. Outside the range
+/// the position holds a single token, so the loss stops growing. Returns 0 when a price is not
+/// positive or the range is empty.
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::math::impermanent_loss;
+///
+/// // Entered at 150 with a 100..200 range; the price moved to 180
+/// let loss = impermanent_loss(150.0, 180.0, 100.0, 200.0);
+/// println!("IL: {:.2}%", loss * 100.0);
+/// ```
+pub fn impermanent_loss(
+    entry_price: f64,
+    current_price: f64,
+    lower_price: f64,
+    upper_price: f64,
+) -> f64 {
+    let valid = |price: f64| price.is_finite() && price > 0.0;
+    if !(valid(entry_price) && valid(current_price) && valid(lower_price) && valid(upper_price))
+        || lower_price >= upper_price
+    {
+        return 0.0;
+    }
+    let (sqrt_lower, sqrt_upper) = (lower_price.sqrt(), upper_price.sqrt());
+    // Token amounts of one unit of liquidity at a price
+    let amounts = |price: f64| {
+        let sqrt_price = price.sqrt().clamp(sqrt_lower, sqrt_upper);
+        (1.0 / sqrt_price - 1.0 / sqrt_upper, sqrt_price - sqrt_lower)
+    };
+    let (entry_a, entry_b) = amounts(entry_price);
+    let (current_a, current_b) = amounts(current_price);
+    let hold_value = entry_a * current_price + entry_b;
+    if hold_value <= 0.0 {
+        return 0.0;
+    }
+    (current_a * current_price + current_b) / hold_value - 1.0
+}
+
+/// Token amount in whole tokens for a raw amount of a mint with `decimals`
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::math::to_ui_amount;
+///
+/// assert_eq!(to_ui_amount(1_500_000, 6), 1.5);
+/// ```
+pub fn to_ui_amount(amount: u64, decimals: u8) -> f64 {
+    amount as f64 / 10f64.powi(decimals as i32)
+}
+
+/// Raw amount of a mint with `decimals` for an amount in whole tokens, rounded to the nearest unit
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::math::from_ui_amount;
+///
+/// assert_eq!(from_ui_amount(1.5, 6), 1_500_000);
+/// ```
+pub fn from_ui_amount(ui_amount: f64, decimals: u8) -> u64 {
+    (ui_amount * 10f64.powi(decimals as i32)).round() as u64
+}
+
+/// Price impact of a swap in basis points, rounded to the nearest basis point
+///
+/// `spot_output` is the output the fee-free input would buy at the pool's price before the swap,
+/// and `output_amount` the output the swap actually produces. The shortfall of the execution
+/// price against the spot price is `1 - output_amount / spot_output`, clamped to 0..=10_000.
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::math::price_impact_bps;
+///
+/// assert_eq!(price_impact_bps(1_000_000.0, 990_000), 100);
+/// ```
+pub fn price_impact_bps(spot_output: f64, output_amount: u64) -> u16 {
+    if !spot_output.is_finite() || spot_output <= 0.0 {
+        return 0;
+    }
+    let shortfall = 1.0 - output_amount as f64 / spot_output;
+    (shortfall * BPS_DENOMINATOR as f64)
+        .round()
+        .clamp(0.0, BPS_DENOMINATOR as f64) as u16
+}
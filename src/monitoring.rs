@@ -1,38 +1,531 @@
-use solana_commitment_config::CommitmentConfig;
-use solana_transaction_status::UiTransactionEncoding;
+use serde::Serialize;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, option_serializer::OptionSerializer,
+};
+use tokio::sync::mpsc;
 
 use super::*;
-use crate::{pool::PoolInfo, types::OrcaResult};
+use crate::{
+    liquidity::LiquidityPosition,
+    math::{sqrt_price_x64_to_price, to_ui_amount},
+    pool::PoolInfo,
+    types::{InvalidInput, OrcaResult},
+    validation::parse_address,
+};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone)]
+// Triggered alerts buffered before evaluation waits for the consumer
+const TRIGGERED_ALERT_BUFFER: usize = 256;
+// Position range updates buffered before polling waits for the consumer
+const POSITION_UPDATE_BUFFER: usize = 256;
+
+#[derive(Debug, Clone, Serialize)]
 pub struct PriceData {
     pub timestamp: u64,
     pub price: f64,
     pub liquidity: u128,
 }
 
-#[derive(Debug, Clone)]
+/// A price condition on a pool; it fires once and is then removed
+#[derive(Debug, Clone, PartialEq)]
 pub struct PriceAlert {
-    pub token_pair: String,
-    pub target_price: f64,
+    /// Pool whose price is watched, in token B per token A adjusted for decimals
+    pub pool_address: String,
     pub condition: PriceCondition,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PriceCondition {
-    Above,
-    Below,
+    /// The price is at or above the target
+    Above(f64),
+    /// The price is at or below the target
+    Below(f64),
+    /// The price moved by at least this percentage, either way, since the alert was added
+    PercentChange(f64),
 }
 
-#[derive(Debug, Clone)]
+impl PriceCondition {
+    fn is_met(&self, price: f64, reference_price: f64) -> bool {
+        match self {
+            PriceCondition::Above(target) => price >= *target,
+            PriceCondition::Below(target) => price <= *target,
+            PriceCondition::PercentChange(percent) => {
+                reference_price > 0.0
+                    && ((price - reference_price) / reference_price).abs() * 100.0 >= *percent
+            }
+        }
+    }
+}
+
+/// Price alerts registered on a client, evaluated by `OrcaClient::start_price_alerts`
+#[derive(Debug, Clone, Default)]
 pub struct PriceMonitor {
-    alerts: HashMap<String, Vec<PriceAlert>>,
+    alerts: HashMap<u64, ActiveAlert>,
+    next_id: u64,
+}
+
+#[derive(Debug, Clone)]
+struct ActiveAlert {
+    alert: PriceAlert,
+    decimals_a: u8,
+    decimals_b: u8,
+    /// Price when the alert was added, the base of `PercentChange`
+    reference_price: f64,
+}
+
+/// An alert whose condition was met
+#[derive(Debug, Clone)]
+pub struct TriggeredAlert {
+    /// Id returned by `add_price_alert`
+    pub id: u64,
+    pub alert: PriceAlert,
+    /// Price that met the condition
+    pub price: f64,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Triggered alerts delivered by `OrcaClient::start_price_alerts`
+///
+/// Dropping the stream stops the evaluation.
+#[derive(Debug)]
+pub struct PriceAlertStream {
+    alert_rx: mpsc::Receiver<TriggeredAlert>,
+    task_handle: tokio::task::JoinHandle<()>,
+}
+
+impl PriceAlertStream {
+    /// Waits for the next triggered alert
+    pub async fn recv(&mut self) -> Option<TriggeredAlert> {
+        self.alert_rx.recv().await
+    }
+}
+
+impl Drop for PriceAlertStream {
+    fn drop(&mut self) {
+        self.task_handle.abort();
+    }
+}
+
+/// Where a pool's current tick sits relative to a position's range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RangeStatus {
+    /// In range and further from both boundaries than the configured margin
+    InRange,
+    /// In range, but within the configured margin of a boundary
+    NearBoundary,
+    /// Below the lower tick; the position holds only token A and earns no fees
+    BelowRange,
+    /// At or above the upper tick; the position holds only token B and earns no fees
+    AboveRange,
+}
+
+/// Distance of a pool's current tick from a position's range boundaries
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PositionRange {
+    /// Status without a near-boundary margin: `InRange`, `BelowRange` or `AboveRange`
+    pub status: RangeStatus,
+    /// Ticks to the nearest boundary while in range; negative ticks to the boundary the price
+    /// must cross to re-enter the range otherwise
+    pub ticks_to_boundary: i32,
+    /// Price move in percent that reaches that boundary
+    pub price_distance_percent: f64,
+}
+
+impl PositionRange {
+    /// Locates `tick_current_index` relative to `[lower_tick, upper_tick)`
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::monitoring::{PositionRange, RangeStatus};
+    ///
+    /// let range = PositionRange::new(-100, 100, 90);
+    /// assert_eq!(range.status, RangeStatus::InRange);
+    /// assert_eq!(range.ticks_to_boundary, 10);
+    /// ```
+    pub fn new(lower_tick: i32, upper_tick: i32, tick_current_index: i32) -> Self {
+        let (status, boundary) = if tick_current_index < lower_tick {
+            (RangeStatus::BelowRange, lower_tick)
+        } else if tick_current_index >= upper_tick {
+            (RangeStatus::AboveRange, upper_tick)
+        } else if tick_current_index - lower_tick <= upper_tick - tick_current_index {
+            (RangeStatus::InRange, lower_tick)
+        } else {
+            (RangeStatus::InRange, upper_tick)
+        };
+        let distance = (boundary - tick_current_index).abs();
+        let ticks_to_boundary = if status == RangeStatus::InRange {
+            distance
+        } else {
+            -distance
+        };
+        // Each tick moves the price by a factor of 1.0001
+        let price_distance_percent =
+            (1.0001f64.powi(boundary - tick_current_index) - 1.0).abs() * 100.0;
+        Self {
+            status,
+            ticks_to_boundary,
+            price_distance_percent,
+        }
+    }
+}
+
+/// Range status change of one of the owner's positions
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionRangeUpdate {
+    pub position_mint: Pubkey,
+    pub position_address: Pubkey,
+    pub pool_address: Pubkey,
+    pub lower_tick: i32,
+    pub upper_tick: i32,
+    pub tick_current_index: i32,
+    pub status: RangeStatus,
+    /// Status at the previous poll; None the first time the position is seen
+    pub previous_status: Option<RangeStatus>,
+    pub ticks_to_boundary: i32,
+    pub price_distance_percent: f64,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Configuration of `OrcaClient::monitor_positions`
+#[derive(Debug, Clone)]
+pub struct PositionMonitorConfig {
+    pub poll_interval: Duration,
+    /// Price move in percent within which an in-range position counts as `NearBoundary`
+    pub near_boundary_percent: Option<f64>,
+}
+
+impl Default for PositionMonitorConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(30),
+            near_boundary_percent: None,
+        }
+    }
+}
+
+/// Range updates delivered by `OrcaClient::monitor_positions`
+///
+/// Dropping the stream stops the monitoring.
+#[derive(Debug)]
+pub struct PositionRangeStream {
+    update_rx: mpsc::Receiver<PositionRangeUpdate>,
+    task_handle: tokio::task::JoinHandle<()>,
+}
+
+impl PositionRangeStream {
+    /// Waits for the next range update
+    pub async fn recv(&mut self) -> Option<PositionRangeUpdate> {
+        self.update_rx.recv().await
+    }
+}
+
+impl Drop for PositionRangeStream {
+    fn drop(&mut self) {
+        self.task_handle.abort();
+    }
 }
 
 impl OrcaClient {
+    /// Registers a price alert and returns its id
+    ///
+    /// The pool's current price is fetched as the base of `PercentChange` conditions. Alerts
+    /// are evaluated once `start_price_alerts` runs.
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::monitoring::{PriceAlert, PriceCondition};
+    ///
+    /// let id = client
+    ///     .add_price_alert(PriceAlert {
+    ///         pool_address: "POOL_ADDRESS_HERE".to_string(),
+    ///         condition: PriceCondition::Below(120.0),
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn add_price_alert(&self, alert: PriceAlert) -> OrcaResult<u64> {
+        self.with_timeout("add price alert", async {
+            let (PriceCondition::Above(target)
+            | PriceCondition::Below(target)
+            | PriceCondition::PercentChange(target)) = alert.condition;
+            if !target.is_finite() || target <= 0.0 {
+                return Err(OrcaError::InvalidInput(InvalidInput::InvalidThreshold {
+                    field: "condition".to_string(),
+                    value: target,
+                }));
+            }
+            let pool = self.get_pool_state_onchain(&alert.pool_address).await?;
+            let (decimals_a, decimals_b) = self.get_pool_decimals(&pool).await?;
+            let active = ActiveAlert {
+                alert,
+                decimals_a,
+                decimals_b,
+                reference_price: sqrt_price_x64_to_price(pool.sqrt_price, decimals_a, decimals_b),
+            };
+            let mut monitor = self
+                .price_monitor
+                .lock()
+                .map_err(|_| OrcaError::Error("Price monitor lock poisoned".to_string()))?;
+            monitor.next_id += 1;
+            let id = monitor.next_id;
+            monitor.alerts.insert(id, active);
+            Ok(id)
+        })
+        .await
+    }
+
+    /// Removes a price alert; returns false when it already fired or was never added
+    pub fn remove_price_alert(&self, id: u64) -> bool {
+        self.price_monitor
+            .lock()
+            .map(|mut monitor| monitor.alerts.remove(&id).is_some())
+            .unwrap_or(false)
+    }
+
+    /// Returns the registered alerts that have not fired yet, by id
+    pub fn price_alerts(&self) -> Vec<(u64, PriceAlert)> {
+        let Ok(monitor) = self.price_monitor.lock() else {
+            return Vec::new();
+        };
+        let mut alerts: Vec<(u64, PriceAlert)> = monitor
+            .alerts
+            .iter()
+            .map(|(id, active)| (*id, active.alert.clone()))
+            .collect();
+        alerts.sort_by_key(|(id, _)| *id);
+        alerts
+    }
+
+    /// Starts evaluating the registered price alerts against live pool prices
+    ///
+    /// Every `poll_interval` the pools of all alerts are fetched in one batch. Alerts whose
+    /// condition is met are removed and delivered through the returned stream; alerts added
+    /// or removed meanwhile are picked up on the next evaluation.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::{sync::Arc, time::Duration};
+    ///
+    /// let client = Arc::new(OrcaClient::new()?);
+    /// let mut triggered = client.clone().start_price_alerts(Duration::from_secs(5));
+    /// while let Some(alert) = triggered.recv().await {
+    ///     println!("Alert {} fired at {}", alert.id, alert.price);
+    /// }
+    /// ```
+    pub fn start_price_alerts(self: Arc<Self>, poll_interval: Duration) -> PriceAlertStream {
+        let (alert_tx, alert_rx) = mpsc::channel(TRIGGERED_ALERT_BUFFER);
+        let client = self;
+        let task_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(poll_interval);
+            loop {
+                interval.tick().await;
+                if alert_tx.is_closed() {
+                    break;
+                }
+                let triggered = match client.evaluate_price_alerts().await {
+                    Ok(triggered) => triggered,
+                    Err(e) => {
+                        tracing::warn!("Failed to evaluate price alerts: {:?}", e);
+                        continue;
+                    }
+                };
+                for alert in triggered {
+                    if alert_tx.send(alert).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        PriceAlertStream {
+            alert_rx,
+            task_handle,
+        }
+    }
+
+    /// Returns the alerts whose condition is met at the current prices and removes them
+    async fn evaluate_price_alerts(&self) -> OrcaResult<Vec<TriggeredAlert>> {
+        let alerts: Vec<(u64, ActiveAlert)> = match self.price_monitor.lock() {
+            Ok(monitor) => monitor
+                .alerts
+                .iter()
+                .map(|(id, active)| (*id, active.clone()))
+                .collect(),
+            Err(_) => return Ok(Vec::new()),
+        };
+        if alerts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut pool_addresses: Vec<String> = alerts
+            .iter()
+            .map(|(_, active)| active.alert.pool_address.clone())
+            .collect();
+        pool_addresses.sort();
+        pool_addresses.dedup();
+        let sqrt_prices: HashMap<String, u128> = self
+            .get_pool_states(&pool_addresses)
+            .await?
+            .into_iter()
+            .map(|pool| (pool.address, pool.sqrt_price))
+            .collect();
+        let now = chrono::Utc::now();
+        let mut triggered: Vec<TriggeredAlert> = alerts
+            .into_iter()
+            .filter_map(|(id, active)| {
+                let sqrt_price = sqrt_prices.get(&active.alert.pool_address)?;
+                let price =
+                    sqrt_price_x64_to_price(*sqrt_price, active.decimals_a, active.decimals_b);
+                active
+                    .alert
+                    .condition
+                    .is_met(price, active.reference_price)
+                    .then_some(TriggeredAlert {
+                        id,
+                        alert: active.alert,
+                        price,
+                        timestamp: now,
+                    })
+            })
+            .collect();
+        // An alert removed while prices were fetched must not fire
+        if let Ok(mut monitor) = self.price_monitor.lock() {
+            triggered.retain(|alert| monitor.alerts.remove(&alert.id).is_some());
+        }
+        triggered.sort_by_key(|alert| alert.id);
+        Ok(triggered)
+    }
+
+    /// Watches the owner's open positions and reports when their range status changes
+    ///
+    /// Every poll the owner's positions and their pools are fetched. An update is delivered the
+    /// first time a position is seen and whenever its status changes afterwards, e.g. when the
+    /// pool's tick leaves the range and fees stop accruing. Positions opened meanwhile are
+    /// picked up on the next poll; closed and empty positions are skipped.
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::monitoring::{PositionMonitorConfig, RangeStatus};
+    /// use std::sync::Arc;
+    ///
+    /// let client = Arc::new(OrcaClient::new()?);
+    /// let config = PositionMonitorConfig {
+    ///     near_boundary_percent: Some(1.0),
+    ///     ..Default::default()
+    /// };
+    /// let mut updates = client.clone().monitor_positions(owner, config);
+    /// while let Some(update) = updates.recv().await {
+    ///     if update.status != RangeStatus::InRange {
+    ///         println!("{} is {:?}, {:.2}% from its boundary", update.position_mint, update.status, update.price_distance_percent);
+    ///     }
+    /// }
+    /// ```
+    pub fn monitor_positions(
+        self: Arc<Self>,
+        owner: Pubkey,
+        config: PositionMonitorConfig,
+    ) -> PositionRangeStream {
+        let (update_tx, update_rx) = mpsc::channel(POSITION_UPDATE_BUFFER);
+        let client = self;
+        let task_handle = tokio::spawn(async move {
+            let mut statuses: HashMap<Pubkey, RangeStatus> = HashMap::new();
+            let mut interval = tokio::time::interval(config.poll_interval);
+            loop {
+                interval.tick().await;
+                if update_tx.is_closed() {
+                    break;
+                }
+                let updates = match client
+                    .evaluate_positions(&owner, &config, &mut statuses)
+                    .await
+                {
+                    Ok(updates) => updates,
+                    Err(e) => {
+                        tracing::warn!("Failed to evaluate positions of {}: {:?}", owner, e);
+                        continue;
+                    }
+                };
+                for update in updates {
+                    if update_tx.send(update).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        PositionRangeStream {
+            update_rx,
+            task_handle,
+        }
+    }
+
+    /// Returns the positions whose status differs from `statuses`, updating it
+    async fn evaluate_positions(
+        &self,
+        owner: &Pubkey,
+        config: &PositionMonitorConfig,
+        statuses: &mut HashMap<Pubkey, RangeStatus>,
+    ) -> OrcaResult<Vec<PositionRangeUpdate>> {
+        let positions: Vec<LiquidityPosition> = self
+            .get_liquidity_positions(owner)
+            .await?
+            .into_iter()
+            .filter(|position| position.lp_token_amount > 0)
+            .collect();
+        let mut pool_addresses: Vec<String> = positions
+            .iter()
+            .map(|position| position.pool_address.to_string())
+            .collect();
+        pool_addresses.sort();
+        pool_addresses.dedup();
+        let ticks: HashMap<String, i32> = self
+            .get_pool_states(&pool_addresses)
+            .await?
+            .into_iter()
+            .map(|pool| (pool.address, pool.tick_current_index))
+            .collect();
+        statuses.retain(|mint, _| {
+            positions
+                .iter()
+                .any(|position| position.position_mint == *mint)
+        });
+        let now = chrono::Utc::now();
+        let mut updates = Vec::new();
+        for position in positions {
+            let Some(&tick_current_index) = ticks.get(&position.pool_address.to_string()) else {
+                continue;
+            };
+            let range =
+                PositionRange::new(position.lower_tick, position.upper_tick, tick_current_index);
+            let near_boundary = config
+                .near_boundary_percent
+                .is_some_and(|margin| range.price_distance_percent <= margin);
+            let status = if range.status == RangeStatus::InRange && near_boundary {
+                RangeStatus::NearBoundary
+            } else {
+                range.status
+            };
+            let previous_status = statuses.insert(position.position_mint, status);
+            if previous_status == Some(status) {
+                continue;
+            }
+            updates.push(PositionRangeUpdate {
+                position_mint: position.position_mint,
+                position_address: position.position_address,
+                pool_address: position.pool_address,
+                lower_tick: position.lower_tick,
+                upper_tick: position.upper_tick,
+                tick_current_index,
+                status,
+                previous_status,
+                ticks_to_boundary: range.ticks_to_boundary,
+                price_distance_percent: range.price_distance_percent,
+                timestamp: now,
+            });
+        }
+        Ok(updates)
+    }
+
     /// Monitors the health of a liquidity pool by analyzing key metrics.
     ///
+    /// Scores with the default `HealthScoreConfig`; see `monitor_pool_health_with_config`.
+    ///
     /// # Params
     /// pool_address - The address of the pool to monitor
     ///
@@ -50,15 +543,87 @@ impl OrcaClient {
     /// });
     /// ```
     pub async fn monitor_pool_health(&self, pool_address: &str) -> OrcaResult<PoolHealth> {
-        let pool_info = self.get_pool_state_onchain(pool_address).await?;
-        let liquidity = pool_info.liquidity;
-        let volume_24h = self.estimate_24h_volume(&pool_info).await?;
-        let fee_growth = pool_info.fee_growth_global_a + pool_info.fee_growth_global_b;
-        Ok(PoolHealth {
-            liquidity,
-            volume_24h,
-            fee_growth,
-            health_score: self.calculate_health_score(liquidity, volume_24h, fee_growth),
+        self.monitor_pool_health_with_config(pool_address, &HealthScoreConfig::default())
+            .await
+    }
+
+    /// Monitors the health of a liquidity pool, scored with custom weights and caps
+    ///
+    /// Price volatility and vault imbalance are only measured when their weight is above zero,
+    /// since they need the pool's recent price history and its vault balances.
+    ///
+    /// # Example
+    /// ```rust
+    /// let config = HealthScoreConfig {
+    ///     volatility_weight: 0.2,
+    ///     vault_balance_weight: 0.1,
+    ///     ..Default::default()
+    /// };
+    /// let health = client
+    ///     .monitor_pool_health_with_config("whirlpool_address_here", &config)
+    ///     .await?;
+    /// println!("{:.1}: {:?}", health.health_score, health.breakdown);
+    /// ```
+    pub async fn monitor_pool_health_with_config(
+        &self,
+        pool_address: &str,
+        config: &HealthScoreConfig,
+    ) -> OrcaResult<PoolHealth> {
+        self.with_timeout("monitor pool health", async {
+            let pool_info = self.get_pool_state_onchain(pool_address).await?;
+            let liquidity = pool_info.liquidity;
+            let volume_24h = self.estimate_24h_volume(&pool_info).await?;
+            let fee_growth = pool_info.fee_growth_global_a + pool_info.fee_growth_global_b;
+            let volatility_percent = if config.volatility_weight > 0.0 {
+                let since = chrono::Utc::now()
+                    - chrono::Duration::from_std(config.volatility_window)
+                        .unwrap_or(chrono::Duration::days(1));
+                let history = self.get_price_history_since(pool_address, since).await?;
+                Some(price_volatility_percent(&history))
+            } else {
+                None
+            };
+            let vault_imbalance = if config.vault_balance_weight > 0.0 {
+                Some(self.vault_imbalance(&pool_info).await?)
+            } else {
+                None
+            };
+            let breakdown = config.score(
+                liquidity,
+                volume_24h,
+                fee_growth,
+                volatility_percent,
+                vault_imbalance,
+            );
+            Ok(PoolHealth {
+                liquidity,
+                volume_24h,
+                fee_growth,
+                health_score: breakdown.total,
+                breakdown,
+            })
+        })
+        .await
+    }
+
+    /// Share of the pool's value that one vault holds beyond an even split, 0 to 1
+    async fn vault_imbalance(&self, pool: &PoolInfo) -> OrcaResult<f64> {
+        let vault_a = parse_address("token_vault_a", &pool.token_vault_a)?;
+        let vault_b = parse_address("token_vault_b", &pool.token_vault_b)?;
+        let (amount_a, amount_b) = self.get_token_swap_reserves(&vault_a, &vault_b).await?;
+        let mints = [
+            parse_address("token_mint_a", &pool.token_mint_a)?,
+            parse_address("token_mint_b", &pool.token_mint_b)?,
+        ];
+        let decimals = self.get_mint_decimals(&mints).await?;
+        let price = sqrt_price_x64_to_price(pool.sqrt_price, decimals[0], decimals[1]);
+        let value_a = to_ui_amount(amount_a, decimals[0]) * price;
+        let value_b = to_ui_amount(amount_b, decimals[1]);
+        let total = value_a + value_b;
+        Ok(if total > 0.0 {
+            (value_a - value_b).abs() / total
+        } else {
+            0.0
         })
     }
 
@@ -66,13 +631,11 @@ impl OrcaClient {
     ///
     /// Combines fee-based estimation and transaction count analysis for robust volume calculation.
     async fn estimate_24h_volume(&self, pool: &PoolInfo) -> OrcaResult<u64> {
-        let client = self
-            .solana
+        self.solana
             .client
             .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?;
-        let pool_pubkey = Pubkey::from_str(&pool.address)
-            .map_err(|e| OrcaError::Error(format!("Invalid pool address: {}", e)))?;
+            .ok_or(OrcaError::RpcClientUnavailable)?;
+        let pool_pubkey = parse_address("pool_address", &pool.address)?;
         let volume_from_fees = self.estimate_volume_from_fee_growth(pool).await?;
         let volume_from_tx_count = self.estimate_volume_from_tx_count(&pool_pubkey).await?;
         Ok(volume_from_fees.max(volume_from_tx_count))
@@ -85,26 +648,32 @@ impl OrcaClient {
         let total_fee_growth = pool.fee_growth_global_a + pool.fee_growth_global_b;
         const FEE_RATE: f64 = 0.003;
         let estimated_volume = (total_fee_growth as f64 / FEE_RATE) as u64;
-        Ok(estimated_volume.min((u64::MAX as u128).try_into().unwrap()) as u64)
+        Ok(estimated_volume)
     }
 
     /// Estimates trading volume based on transaction count analysis.
     ///
     /// Uses recent transaction samples to extrapolate daily volume.
     async fn estimate_volume_from_tx_count(&self, pool_pubkey: &Pubkey) -> OrcaResult<u64> {
-        let client = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?;
-        let signatures = client
-            .get_signatures_for_address(pool_pubkey)
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get signatures: {}", e)))?;
+        let signatures = self
+            .rpc_call("Failed to get signatures", |client| {
+                client.get_signatures_for_address(pool_pubkey)
+            })
+            .await?;
+        let sample = signatures
+            .iter()
+            .take(20)
+            .filter_map(|sig_info| Signature::from_str(&sig_info.signature).ok())
+            .collect::<Vec<Signature>>();
         let mut total_sample_volume = 0u64;
         let mut sample_count = 0;
-        for sig_info in signatures.iter().take(20) {
-            if let Some(volume) = self.estimate_single_tx_volume(&sig_info.signature).await? {
+        for transaction in self
+            .fetch_transactions(&sample)
+            .await?
+            .into_iter()
+            .flatten()
+        {
+            if let Some(volume) = Self::estimate_volume_from_transaction(&transaction) {
                 total_sample_volume += volume;
                 sample_count += 1;
             }
@@ -117,70 +686,32 @@ impl OrcaClient {
         Ok(avg_tx_volume * estimated_daily_tx_count as u64)
     }
 
-    /// Estimates volume for a single transaction using multiple approaches.
+    /// Estimates volume for a single fetched transaction using multiple approaches.
     ///
     /// # Params
-    /// signature - The transaction signature to analyze
+    /// transaction - The confirmed transaction to analyze
     ///
     /// # Returns
     /// Returns estimated volume if successful, None if transaction cannot be analyzed
-    ///
-    /// # Example
-    /// ```no_run
-    /// use orca_client::OrcaClient;
-    ///
-    /// tokio_test::block_on(async {
-    /// let client = OrcaClient::new();
-    /// let volume = client.estimate_single_tx_volume("SIGNATURE_HERE").await.unwrap();
-    /// println!("Estimated transaction volume: {:?}", volume);
-    /// });
-    /// ```
-    async fn estimate_single_tx_volume(&self, signature: &str) -> OrcaResult<Option<u64>> {
-        let client = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?;
-        let signature = Signature::from_str(signature)
-            .map_err(|e| OrcaError::Error(format!("Invalid signature: {}", e)))?;
-        let transaction = client
-            .get_transaction_with_config(
-                &signature,
-                solana_client::rpc_config::RpcTransactionConfig {
-                    encoding: Some(UiTransactionEncoding::JsonParsed),
-                    commitment: Some(CommitmentConfig::confirmed()),
-                    max_supported_transaction_version: Some(0),
-                },
-            )
-            .await;
-        match transaction {
-            Ok(tx_response) => {
-                if let Some(meta) = &tx_response.transaction.meta {
-                    let fee = meta.fee;
-                    let estimated_volume = (fee as f64 / 0.003) as u64;
-                    return Ok(Some(estimated_volume));
-                }
-                if let Some(logs) = &tx_response
-                    .transaction
-                    .meta
-                    .and_then(|m| Some(m.log_messages))
+    fn estimate_volume_from_transaction(
+        transaction: &EncodedConfirmedTransactionWithStatusMeta,
+    ) -> Option<u64> {
+        let meta = transaction.transaction.meta.as_ref()?;
+        let fee = meta.fee;
+        let estimated_volume = (fee as f64 / 0.003) as u64;
+        if estimated_volume > 0 {
+            return Some(estimated_volume);
+        }
+        if let OptionSerializer::Some(logs) = &meta.log_messages {
+            for log in logs {
+                if (log.contains("swap") || log.contains("amount") || log.contains("Swap"))
+                    && let Some(amount) = Self::extract_amount_from_log(log)
                 {
-                    for log in logs.clone().unwrap() {
-                        if log.contains("swap") || log.contains("amount") || log.contains("Swap") {
-                            if let Some(amount) = Self::extract_amount_from_log(&log) {
-                                return Ok(Some(amount));
-                            }
-                        }
-                    }
+                    return Some(amount);
                 }
-
-                Ok(None)
-            }
-            Err(e) => {
-                log::debug!("Failed to get transaction {}: {}", signature, e);
-                Ok(None)
             }
         }
+        None
     }
 
     /// Extracts numerical amounts from transaction log messages.
@@ -210,18 +741,19 @@ impl OrcaClient {
                 || lower_word.contains("swap")
                 || lower_word.contains("transfer")
             {
-                for j in (i + 1)..words.len().min(i + 4) {
-                    if let Some(amount) = Self::parse_possible_number(words[j]) {
-                        if amount > 100 {
-                            return Some(amount);
-                        }
+                for next in words.iter().skip(i + 1).take(3) {
+                    if let Some(amount) = Self::parse_possible_number(next)
+                        && amount > 100
+                    {
+                        return Some(amount);
                     }
                 }
             }
-            if let Some(amount) = Self::parse_possible_number(word) {
-                if amount > 1000 && amount < 1_000_000_000 {
-                    return Some(amount);
-                }
+            if let Some(amount) = Self::parse_possible_number(word)
+                && amount > 1000
+                && amount < 1_000_000_000
+            {
+                return Some(amount);
             }
         }
         None
@@ -253,36 +785,214 @@ impl OrcaClient {
             None
         }
     }
+}
 
-    /// Calculates a comprehensive health score for a liquidity pool.
-    ///
-    /// Uses logarithmic scaling to normalize metrics and weighted averaging for final score.
+#[derive(Debug, Clone)]
+pub struct PoolHealth {
+    pub liquidity: u128,
+    pub volume_24h: u64,
+    pub fee_growth: u128,
+    pub health_score: f64,
+    /// How each factor contributed to `health_score`
+    pub breakdown: HealthScoreBreakdown,
+}
+
+/// Weights and normalization of the pool health score
+///
+/// Every factor is scored from 0 to 10 and the weighted average is scaled to 0-100. Weights
+/// need not sum to one; a factor with zero weight is not measured. The defaults reproduce the
+/// original score, which leaves out volatility and vault imbalance.
+#[derive(Debug, Clone)]
+pub struct HealthScoreConfig {
+    pub liquidity_weight: f64,
+    pub volume_weight: f64,
+    pub fee_growth_weight: f64,
+    pub volatility_weight: f64,
+    pub vault_balance_weight: f64,
+    /// Liquidity is divided by this before log scaling
+    pub liquidity_scale: f64,
+    /// 24h volume is divided by this before log scaling
+    pub volume_scale: f64,
+    /// Fee growth is divided by this before log scaling
+    pub fee_growth_scale: f64,
+    /// Highest score of the log-scaled liquidity factor
+    pub liquidity_cap: f64,
+    /// Highest score of the log-scaled volume factor
+    pub volume_cap: f64,
+    /// Highest score of the log-scaled fee growth factor
+    pub fee_growth_cap: f64,
+    /// Price volatility, in percent, at which the volatility factor scores zero
+    pub max_volatility_percent: f64,
+    /// Price history the volatility is measured over
+    pub volatility_window: Duration,
+}
+
+impl Default for HealthScoreConfig {
+    fn default() -> Self {
+        Self {
+            liquidity_weight: 0.5,
+            volume_weight: 0.3,
+            fee_growth_weight: 0.2,
+            volatility_weight: 0.0,
+            vault_balance_weight: 0.0,
+            liquidity_scale: 1e6,
+            volume_scale: 1e3,
+            fee_growth_scale: 1e6,
+            liquidity_cap: 10.0,
+            volume_cap: 10.0,
+            fee_growth_cap: 10.0,
+            max_volatility_percent: 10.0,
+            volatility_window: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+impl HealthScoreConfig {
+    /// Scores pool metrics; factors passed as None are left out of the score
     ///
-    /// # Params
-    /// liquidity - Total liquidity in the pool
-    /// volume - 24-hour trading volume
-    /// fee_growth - Total fee growth
+    /// `volatility_percent` is the standard deviation of the price relative to its mean, and
+    /// `vault_imbalance` the share of the pool's value one vault holds beyond an even split.
     ///
     /// # Example
     /// ```rust
-    /// use orca_client::OrcaClient;
-    ///
-    /// let client = OrcaClient::new();
-    /// let score = client.calculate_health_score(1_000_000, 500_000, 100_000);
-    /// assert!(score >= 0.0 && score <= 100.0);
+    /// let breakdown = HealthScoreConfig::default().score(1_000_000, 500_000, 100_000, None, None);
+    /// assert!(breakdown.total >= 0.0 && breakdown.total <= 100.0);
     /// ```
-    fn calculate_health_score(&self, liquidity: u128, volume: u64, fee_growth: u128) -> f64 {
-        let liquidity_score = (liquidity as f64 / 1e6).ln_1p().min(10.0);
-        let volume_score = (volume as f64 / 1e3).ln_1p().min(10.0);
-        let fee_score = (fee_growth as f64 / 1e6).ln_1p().min(10.0);
-        (liquidity_score * 0.5 + volume_score * 0.3 + fee_score * 0.2) * 10.0
+    pub fn score(
+        &self,
+        liquidity: u128,
+        volume: u64,
+        fee_growth: u128,
+        volatility_percent: Option<f64>,
+        vault_imbalance: Option<f64>,
+    ) -> HealthScoreBreakdown {
+        let log_score = |value: f64, scale: f64, cap: f64| {
+            (value / scale.max(f64::MIN_POSITIVE))
+                .ln_1p()
+                .clamp(0.0, cap)
+        };
+        let mut components = [
+            HealthScoreComponent::new(
+                liquidity as f64,
+                log_score(liquidity as f64, self.liquidity_scale, self.liquidity_cap),
+                self.liquidity_weight,
+            ),
+            HealthScoreComponent::new(
+                volume as f64,
+                log_score(volume as f64, self.volume_scale, self.volume_cap),
+                self.volume_weight,
+            ),
+            HealthScoreComponent::new(
+                fee_growth as f64,
+                log_score(
+                    fee_growth as f64,
+                    self.fee_growth_scale,
+                    self.fee_growth_cap,
+                ),
+                self.fee_growth_weight,
+            ),
+        ];
+        let mut volatility = volatility_percent.map(|percent| {
+            let max = self.max_volatility_percent.max(f64::MIN_POSITIVE);
+            HealthScoreComponent::new(
+                percent,
+                10.0 * (1.0 - (percent / max).clamp(0.0, 1.0)),
+                self.volatility_weight,
+            )
+        });
+        let mut vault_balance = vault_imbalance.map(|imbalance| {
+            HealthScoreComponent::new(
+                imbalance,
+                10.0 * (1.0 - imbalance.clamp(0.0, 1.0)),
+                self.vault_balance_weight,
+            )
+        });
+        let total_weight: f64 = components
+            .iter()
+            .chain(volatility.iter())
+            .chain(vault_balance.iter())
+            .map(|component| component.weight.max(0.0))
+            .sum();
+        for component in components
+            .iter_mut()
+            .chain(volatility.iter_mut())
+            .chain(vault_balance.iter_mut())
+        {
+            component.contribution = if total_weight > 0.0 {
+                component.score * component.weight.max(0.0) / total_weight * 10.0
+            } else {
+                0.0
+            };
+        }
+        let total = components
+            .iter()
+            .chain(volatility.iter())
+            .chain(vault_balance.iter())
+            .map(|component| component.contribution)
+            .sum();
+        let [liquidity, volume, fee_growth] = components;
+        HealthScoreBreakdown {
+            liquidity,
+            volume,
+            fee_growth,
+            volatility,
+            vault_balance,
+            total,
+        }
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct PoolHealth {
-    pub liquidity: u128,
-    pub volume_24h: u64,
-    pub fee_growth: u128,
-    pub health_score: f64,
+/// One factor of the pool health score
+#[derive(Debug, Clone, Copy)]
+pub struct HealthScoreComponent {
+    /// Measured value before normalization
+    pub value: f64,
+    /// Normalized score, 0 to 10
+    pub score: f64,
+    pub weight: f64,
+    /// Points added to the 0-100 total
+    pub contribution: f64,
+}
+
+impl HealthScoreComponent {
+    fn new(value: f64, score: f64, weight: f64) -> Self {
+        Self {
+            value,
+            score,
+            weight,
+            contribution: 0.0,
+        }
+    }
+}
+
+/// Per-factor explanation of a pool health score
+#[derive(Debug, Clone, Copy)]
+pub struct HealthScoreBreakdown {
+    pub liquidity: HealthScoreComponent,
+    pub volume: HealthScoreComponent,
+    pub fee_growth: HealthScoreComponent,
+    /// None when volatility was not measured
+    pub volatility: Option<HealthScoreComponent>,
+    /// None when vault imbalance was not measured
+    pub vault_balance: Option<HealthScoreComponent>,
+    /// Sum of the contributions, 0 to 100
+    pub total: f64,
+}
+
+/// Standard deviation of the prices relative to their mean, in percent
+fn price_volatility_percent(history: &[PriceData]) -> f64 {
+    if history.len() < 2 {
+        return 0.0;
+    }
+    let count = history.len() as f64;
+    let mean = history.iter().map(|data| data.price).sum::<f64>() / count;
+    if mean <= 0.0 {
+        return 0.0;
+    }
+    let variance = history
+        .iter()
+        .map(|data| (data.price - mean).powi(2))
+        .sum::<f64>()
+        / count;
+    variance.sqrt() / mean * 100.0
 }
@@ -0,0 +1,78 @@
+use std::{sync::Arc, time::Duration};
+
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_commitment_config::CommitmentConfig;
+use solana_network_sdk::{Solana, types::Mode};
+
+use crate::types::{InvalidInput, OrcaError, OrcaResult};
+
+pub const MAINNET_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+pub const DEVNET_RPC_URL: &str = "https://api.devnet.solana.com";
+pub const LOCALNET_RPC_URL: &str = "http://127.0.0.1:8899";
+
+/// Cluster the client talks to
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Devnet,
+    /// A local validator on the default port
+    Localnet,
+    /// Any other RPC endpoint, e.g. a private provider
+    Custom(String),
+}
+
+impl Network {
+    /// Returns the RPC URL of the network
+    pub fn rpc_url(&self) -> &str {
+        match self {
+            Network::Mainnet => MAINNET_RPC_URL,
+            Network::Devnet => DEVNET_RPC_URL,
+            Network::Localnet => LOCALNET_RPC_URL,
+            Network::Custom(url) => url,
+        }
+    }
+}
+
+/// Derives the WebSocket endpoint served next to an RPC endpoint
+///
+/// `http`/`https` become `ws`/`wss`; a local validator's RPC port 8899 maps to its pubsub
+/// port 8900.
+pub fn websocket_url(rpc_url: &str) -> String {
+    let url = if let Some(rest) = rpc_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        rpc_url.to_string()
+    };
+    url.replacen(":8899", ":8900", 1)
+}
+
+/// Creates an RPC client for `url` with the given commitment
+///
+/// Requests use the RPC client's default timeout unless `request_timeout` is set.
+pub(crate) fn rpc_client(
+    url: &str,
+    commitment: CommitmentConfig,
+    request_timeout: Option<Duration>,
+) -> OrcaResult<Arc<RpcClient>> {
+    if url.is_empty() {
+        return Err(OrcaError::InvalidInput(InvalidInput::EmptyRpcUrl));
+    }
+    let client = match request_timeout {
+        Some(timeout) => {
+            RpcClient::new_with_timeout_and_commitment(url.to_string(), timeout, commitment)
+        }
+        None => RpcClient::new_with_commitment(url.to_string(), commitment),
+    };
+    Ok(Arc::new(client))
+}
+
+/// Creates a Solana handle around an existing RPC client
+pub(crate) fn solana_with_rpc(client: Arc<RpcClient>) -> OrcaResult<Solana> {
+    let mut solana = Solana::new(Mode::MAIN)
+        .map_err(|e| OrcaError::Error(format!("Failed to create Solana client: {}", e)))?;
+    solana.client = Some(client);
+    Ok(solana)
+}
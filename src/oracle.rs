@@ -0,0 +1,229 @@
+use std::time::Duration;
+
+use super::*;
+use crate::{
+    global::{PYTH_PRICE_UPDATE_V2_DISCRIMINATOR, SWITCHBOARD_PULL_FEED_DISCRIMINATOR},
+    layout::AccountReader,
+    math::sqrt_price_x64_to_price,
+    types::OrcaResult,
+};
+
+// Oracle prices older than this are rejected unless the reference overrides it
+const DEFAULT_ORACLE_MAX_AGE: Duration = Duration::from_secs(60);
+// Switchboard feed results are fixed-point with 18 decimals
+const SWITCHBOARD_DECIMALS: i32 = 18;
+// Switchboard PullFeedAccountData: submission slots held before the header fields
+const SWITCHBOARD_SUBMISSIONS: usize = 32;
+const SWITCHBOARD_SUBMISSION_SIZE: usize = 64;
+
+/// On-chain price feed account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OracleSource {
+    /// Pyth pull oracle `PriceUpdateV2` account
+    Pyth(Pubkey),
+    /// Switchboard on-demand `PullFeedAccountData` account
+    Switchboard(Pubkey),
+}
+
+impl OracleSource {
+    pub fn address(&self) -> &Pubkey {
+        match self {
+            OracleSource::Pyth(address) | OracleSource::Switchboard(address) => address,
+        }
+    }
+}
+
+/// Oracle feeds describing the price of a pool's token A in units of token B
+///
+/// With only `base`, the feed must already quote token A in token B (e.g. SOL/USDC with
+/// USDC as token B). With `quote`, the pair price is `base / quote`, so two USD feeds can
+/// price any pair.
+#[derive(Debug, Clone)]
+pub struct OracleReference {
+    pub base: OracleSource,
+    pub quote: Option<OracleSource>,
+    /// Oldest publish time accepted, measured back from now
+    pub max_age: Duration,
+}
+
+impl OracleReference {
+    pub fn new(base: OracleSource) -> Self {
+        Self {
+            base,
+            quote: None,
+            max_age: DEFAULT_ORACLE_MAX_AGE,
+        }
+    }
+
+    pub fn with_quote(mut self, quote: OracleSource) -> Self {
+        self.quote = Some(quote);
+        self
+    }
+
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+}
+
+/// Price read from an oracle feed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OraclePrice {
+    pub price: f64,
+    /// Pyth confidence interval or Switchboard standard deviation, in price units
+    pub confidence: f64,
+    /// Unix timestamp of the last update
+    pub publish_time: i64,
+}
+
+/// Pool price compared against an oracle reference, both as token B per token A
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OracleDeviation {
+    pub pool_price: f64,
+    pub oracle_price: f64,
+    /// Signed deviation of the pool price from the oracle price in percent
+    pub deviation_percent: f64,
+}
+
+impl OrcaClient {
+    /// Reads the latest price of a Pyth or Switchboard feed
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::oracle::OracleSource;
+    ///
+    /// let price = client.get_oracle_price(&OracleSource::Pyth(sol_usd_feed)).await?;
+    /// println!("SOL/USD {} ± {}", price.price, price.confidence);
+    /// ```
+    pub async fn get_oracle_price(&self, source: &OracleSource) -> OrcaResult<OraclePrice> {
+        self.with_timeout("get_oracle_price", async {
+            let account = self
+                .get_existing_account(source.address(), "Failed to get oracle account")
+                .await?;
+            let price = match source {
+                OracleSource::Pyth(_) => parse_pyth_price_update(&account.data),
+                OracleSource::Switchboard(_) => parse_switchboard_pull_feed(&account.data),
+            };
+            price.ok_or_else(|| {
+                OrcaError::ParseError(format!("Invalid oracle account {}", source.address()))
+            })
+        })
+        .await
+    }
+
+    /// Compares a pool's price with an oracle reference
+    ///
+    /// Both prices are decimal-adjusted token B per token A.
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::oracle::{OracleReference, OracleSource};
+    ///
+    /// let reference = OracleReference::new(OracleSource::Pyth(sol_usd_feed));
+    /// let deviation = client.check_oracle_deviation(pool_address, &reference).await?;
+    /// println!("Pool is {:.2}% off the oracle", deviation.deviation_percent);
+    /// ```
+    pub async fn check_oracle_deviation(
+        &self,
+        pool_address: &str,
+        reference: &OracleReference,
+    ) -> OrcaResult<OracleDeviation> {
+        self.with_timeout("check_oracle_deviation", async {
+            let pool = self.get_pool_state_onchain(pool_address).await?;
+            let (decimals_a, decimals_b) = self.get_pool_decimals(&pool).await?;
+            let pool_price = sqrt_price_x64_to_price(pool.sqrt_price, decimals_a, decimals_b);
+            let oracle_price = self.get_oracle_reference_price(reference).await?;
+            Ok(OracleDeviation {
+                pool_price,
+                oracle_price,
+                deviation_percent: (pool_price - oracle_price) / oracle_price * 100.0,
+            })
+        })
+        .await
+    }
+
+    /// Pair price of an oracle reference, rejecting stale or non-positive feeds
+    pub(crate) async fn get_oracle_reference_price(
+        &self,
+        reference: &OracleReference,
+    ) -> OrcaResult<f64> {
+        let base = self.get_fresh_oracle_price(&reference.base, reference.max_age);
+        let price = match &reference.quote {
+            Some(quote) => {
+                let (base, quote) =
+                    tokio::try_join!(base, self.get_fresh_oracle_price(quote, reference.max_age))?;
+                base / quote
+            }
+            None => base.await?,
+        };
+        if !price.is_finite() || price <= 0.0 {
+            return Err(OrcaError::InvalidAccountData {
+                account: *reference.base.address(),
+                reason: format!("invalid pair price {}", price),
+            });
+        }
+        Ok(price)
+    }
+
+    async fn get_fresh_oracle_price(
+        &self,
+        source: &OracleSource,
+        max_age: Duration,
+    ) -> OrcaResult<f64> {
+        let price = self.get_oracle_price(source).await?;
+        let age = chrono::Utc::now().timestamp() - price.publish_time;
+        if age > max_age.as_secs() as i64 {
+            return Err(OrcaError::StaleOracle {
+                oracle: *source.address(),
+                age: Duration::from_secs(age as u64),
+            });
+        }
+        if !price.price.is_finite() || price.price <= 0.0 {
+            return Err(OrcaError::InvalidAccountData {
+                account: *source.address(),
+                reason: format!("invalid price {}", price.price),
+            });
+        }
+        Ok(price.price)
+    }
+}
+
+/// Decodes a Pyth `PriceUpdateV2` account
+fn parse_pyth_price_update(data: &[u8]) -> Option<OraclePrice> {
+    let mut reader = AccountReader::new(data, &PYTH_PRICE_UPDATE_V2_DISCRIMINATOR)?;
+    reader.skip(32)?; // write_authority
+    // VerificationLevel: Partial carries the number of signatures, Full has no payload
+    if reader.u8()? == 0 {
+        reader.skip(1)?;
+    }
+    reader.skip(32)?; // feed_id
+    let price = reader.i64()?;
+    let confidence = reader.u64()?;
+    let exponent = reader.i32()?;
+    let publish_time = reader.i64()?;
+    let scale = 10f64.powi(exponent);
+    Some(OraclePrice {
+        price: price as f64 * scale,
+        confidence: confidence as f64 * scale,
+        publish_time,
+    })
+}
+
+/// Decodes a Switchboard on-demand `PullFeedAccountData` account
+fn parse_switchboard_pull_feed(data: &[u8]) -> Option<OraclePrice> {
+    let mut reader = AccountReader::new(data, &SWITCHBOARD_PULL_FEED_DISCRIMINATOR)?;
+    reader.skip(SWITCHBOARD_SUBMISSIONS * SWITCHBOARD_SUBMISSION_SIZE)?;
+    reader.skip(32 + 32 + 32)?; // authority, queue, feed_hash
+    reader.skip(8 + 8 + 8 + 4)?; // initialized_at, permissions, max_variance, min_responses
+    reader.skip(32 + 4)?; // name, padding and sampling flags
+    let publish_time = reader.i64()?;
+    reader.skip(8 + 32)?; // lut_slot, reserved
+    let value = reader.i128()?;
+    let std_dev = reader.i128()?;
+    let scale = 10f64.powi(-SWITCHBOARD_DECIMALS);
+    Some(OraclePrice {
+        price: value as f64 * scale,
+        confidence: std_dev as f64 * scale,
+        publish_time,
+    })
+}
@@ -0,0 +1,305 @@
+use solana_sdk::{hash::Hash, instruction::Instruction};
+
+use super::*;
+use crate::{
+    audit::AuditDetails,
+    global::{COMPUTE_BUDGET_PROGRAM_ID, PACKET_DATA_SIZE},
+    submit::SubmitMode,
+    types::OrcaResult,
+};
+
+/// Maximum compute units a single transaction may request
+pub const MAX_TRANSACTION_COMPUTE_UNITS: u32 = 1_400_000;
+
+// Compute unit estimates of instructions emitted by the SDK
+pub const CREATE_TOKEN_ACCOUNT_COMPUTE_UNITS: u32 = 30_000;
+pub const INITIALIZE_TICK_ARRAY_COMPUTE_UNITS: u32 = 25_000;
+pub const OPEN_POSITION_COMPUTE_UNITS: u32 = 60_000;
+pub const INCREASE_LIQUIDITY_COMPUTE_UNITS: u32 = 120_000;
+pub const DECREASE_LIQUIDITY_COMPUTE_UNITS: u32 = 120_000;
+pub const COLLECT_FEES_COMPUTE_UNITS: u32 = 40_000;
+pub const COLLECT_REWARD_COMPUTE_UNITS: u32 = 40_000;
+pub const CLOSE_POSITION_COMPUTE_UNITS: u32 = 30_000;
+pub const SWAP_COMPUTE_UNITS: u32 = 150_000;
+pub const JITO_TIP_COMPUTE_UNITS: u32 = 1_000;
+
+// ComputeBudget program instruction indexes
+const SET_COMPUTE_UNIT_LIMIT_INSTRUCTION: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE_INSTRUCTION: u8 = 3;
+
+/// Instruction groups to pack into transactions and how to send them
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PackedSubmission<'a> {
+    /// Instructions placed at the start of every transaction, e.g. compute budget
+    pub(crate) prefix: &'a [Instruction],
+    pub(crate) groups: &'a [InstructionGroup],
+    pub(crate) packer: &'a TransactionPacker,
+    pub(crate) mode: &'a SubmitMode,
+}
+
+/// Instructions that must land in the same transaction, in order
+#[derive(Debug, Clone)]
+pub struct InstructionGroup {
+    pub instructions: Vec<Instruction>,
+    /// Estimated compute units consumed by the whole group
+    pub compute_units: u32,
+}
+
+impl InstructionGroup {
+    pub fn new(instructions: Vec<Instruction>, compute_units: u32) -> Self {
+        Self {
+            instructions,
+            compute_units,
+        }
+    }
+}
+
+/// Splits an ordered list of instruction groups into as few transactions as possible
+///
+/// Groups keep their relative order and are never split, so an instruction may rely on
+/// anything an earlier group created.
+#[derive(Debug, Clone, Copy)]
+pub struct TransactionPacker {
+    /// Serialized size limit of a transaction in bytes
+    pub max_transaction_size: usize,
+    /// Compute unit budget of a transaction
+    pub max_compute_units: u32,
+}
+
+impl Default for TransactionPacker {
+    fn default() -> Self {
+        Self {
+            max_transaction_size: PACKET_DATA_SIZE,
+            max_compute_units: MAX_TRANSACTION_COMPUTE_UNITS,
+        }
+    }
+}
+
+impl TransactionPacker {
+    /// Packs groups into consecutive transactions paid by `payer`
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::packer::{InstructionGroup, TransactionPacker};
+    ///
+    /// let batches = TransactionPacker::default().pack(
+    ///     &payer,
+    ///     &[InstructionGroup::new(vec![create_ata], 30_000), InstructionGroup::new(vec![deposit], 120_000)],
+    /// )?;
+    /// println!("{} transactions", batches.len());
+    /// ```
+    pub fn pack(
+        &self,
+        payer: &Pubkey,
+        groups: &[InstructionGroup],
+    ) -> OrcaResult<Vec<Vec<Instruction>>> {
+        self.pack_with_prefix(payer, &[], groups)
+    }
+
+    /// Packs groups like `pack`, starting every transaction with `prefix`
+    ///
+    /// The prefix (e.g. compute budget instructions) counts towards each transaction's size
+    /// but not its compute units.
+    pub fn pack_with_prefix(
+        &self,
+        payer: &Pubkey,
+        prefix: &[Instruction],
+        groups: &[InstructionGroup],
+    ) -> OrcaResult<Vec<Vec<Instruction>>> {
+        let mut batches = Vec::new();
+        let mut current: Vec<Instruction> = prefix.to_vec();
+        let mut current_units = 0u32;
+        for group in groups {
+            let mut alone = prefix.to_vec();
+            alone.extend(group.instructions.iter().cloned());
+            if group.compute_units > self.max_compute_units
+                || self.transaction_size(payer, &alone)? > self.max_transaction_size
+            {
+                return Err(OrcaError::TransactionError(format!(
+                    "Instruction group of {} instructions does not fit in a single transaction",
+                    group.instructions.len()
+                )));
+            }
+            let mut candidate = current.clone();
+            candidate.extend(group.instructions.iter().cloned());
+            let fits = current_units.saturating_add(group.compute_units) <= self.max_compute_units
+                && self.transaction_size(payer, &candidate)? <= self.max_transaction_size;
+            if fits {
+                current = candidate;
+                current_units += group.compute_units;
+            } else {
+                batches.push(std::mem::replace(&mut current, alone));
+                current_units = group.compute_units;
+            }
+        }
+        if current.len() > prefix.len() {
+            batches.push(current);
+        }
+        Ok(batches)
+    }
+
+    fn transaction_size(&self, payer: &Pubkey, instructions: &[Instruction]) -> OrcaResult<usize> {
+        let message = Message::new(instructions, Some(payer));
+        let transaction = Transaction::new_unsigned(message);
+        bincode::serialized_size(&transaction)
+            .map(|size| size as usize)
+            .map_err(|e| OrcaError::TransactionError(format!("Failed to size transaction: {}", e)))
+    }
+}
+
+impl OrcaClient {
+    /// Packs instruction groups into transactions and submits them in order
+    ///
+    /// Each transaction is signed by the payer plus whichever of `signers` it references.
+    /// Over RPC, transactions are sent one by one and submission stops at the first failure.
+    /// In Jito mode a tip is appended and all transactions go out as one atomic bundle.
+    /// Returns the signatures of all transactions.
+    #[tracing::instrument(
+        skip(self, payer, signers, submission, details),
+        fields(transactions = tracing::field::Empty)
+    )]
+    pub(crate) async fn submit_packed<S: Signer + ?Sized>(
+        &self,
+        payer: &S,
+        signers: &[&Keypair],
+        submission: PackedSubmission<'_>,
+        operation: &str,
+        details: &AuditDetails,
+    ) -> OrcaResult<Vec<Signature>> {
+        let PackedSubmission {
+            prefix,
+            groups,
+            packer,
+            mode,
+        } = submission;
+        let mut groups = groups.to_vec();
+        if let Some(tip_lamports) = mode.jito_tip() {
+            groups.push(InstructionGroup::new(
+                vec![self.build_jito_tip_instruction(&payer.pubkey(), tip_lamports)?],
+                JITO_TIP_COMPUTE_UNITS,
+            ));
+        }
+        let batches = packer.pack_with_prefix(&payer.pubkey(), prefix, &groups)?;
+        tracing::Span::current().record("transactions", batches.len());
+        if let SubmitMode::Jito {
+            block_engine_url, ..
+        } = mode
+        {
+            // A bundle lands atomically, so every transaction shares one blockhash
+            let recent_blockhash = self.latest_blockhash().await?;
+            let transactions: Vec<VersionedTransaction> = batches
+                .iter()
+                .map(|instructions| {
+                    Self::sign_batch(payer, signers, instructions, recent_blockhash).into()
+                })
+                .collect();
+            return self
+                .submit_bundle(&transactions, operation, block_engine_url, details)
+                .await;
+        }
+        let mut signatures = Vec::with_capacity(batches.len());
+        let mut dry_run_outcomes = Vec::new();
+        for (index, instructions) in batches.iter().enumerate() {
+            let recent_blockhash = self.latest_blockhash().await?;
+            let transaction = Self::sign_batch(payer, signers, instructions, recent_blockhash);
+            let batch_operation = if batches.len() > 1 {
+                format!("{} ({}/{})", operation, index + 1, batches.len())
+            } else {
+                operation.to_string()
+            };
+            // In dry-run mode keep simulating the remaining batches and report them together
+            match self
+                .submit_transaction(&transaction.into(), &batch_operation, details)
+                .await
+            {
+                Ok(signature) => signatures.push(signature),
+                Err(OrcaError::DryRun(outcomes)) => dry_run_outcomes.extend(outcomes),
+                Err(e) => return Err(e),
+            }
+        }
+        if !dry_run_outcomes.is_empty() {
+            return Err(OrcaError::DryRun(dry_run_outcomes));
+        }
+        Ok(signatures)
+    }
+
+    /// Signs a packed batch with the payer and whichever of `signers` it references
+    fn sign_batch<S: Signer + ?Sized>(
+        payer: &S,
+        signers: &[&Keypair],
+        instructions: &[Instruction],
+        recent_blockhash: Hash,
+    ) -> Transaction {
+        let message = Message::new(instructions, Some(&payer.pubkey()));
+        let mut transaction_signers: Vec<&dyn Signer> = vec![&payer];
+        transaction_signers.extend(
+            signers
+                .iter()
+                .filter(|signer| {
+                    signer.pubkey() != payer.pubkey()
+                        && message
+                            .account_keys
+                            .iter()
+                            .take(message.header.num_required_signatures as usize)
+                            .any(|key| *key == signer.pubkey())
+                })
+                .map(|signer| *signer as &dyn Signer),
+        );
+        Transaction::new(transaction_signers.as_slice(), message, recent_blockhash)
+    }
+
+    /// Builds ComputeBudget instructions setting the compute unit limit and priority fee
+    ///
+    /// Returns no instructions when neither is set; otherwise they belong at the start of
+    /// the transaction.
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut instructions = client.build_compute_budget_instructions(Some(300_000), Some(50_000))?;
+    /// instructions.extend(swap.instructions);
+    /// ```
+    pub fn build_compute_budget_instructions(
+        &self,
+        compute_unit_limit: Option<u32>,
+        priority_fee_microlamports: Option<u64>,
+    ) -> OrcaResult<Vec<Instruction>> {
+        let program_id = Pubkey::from_str(COMPUTE_BUDGET_PROGRAM_ID)
+            .map_err(|e| OrcaError::Error(format!("Invalid compute budget program ID: {}", e)))?;
+        let mut instructions = Vec::new();
+        if let Some(units) = compute_unit_limit {
+            let mut data = vec![SET_COMPUTE_UNIT_LIMIT_INSTRUCTION];
+            data.extend_from_slice(&units.to_le_bytes());
+            instructions.push(Instruction::new_with_bytes(program_id, &data, vec![]));
+        }
+        if let Some(micro_lamports) = priority_fee_microlamports {
+            let mut data = vec![SET_COMPUTE_UNIT_PRICE_INSTRUCTION];
+            data.extend_from_slice(&micro_lamports.to_le_bytes());
+            instructions.push(Instruction::new_with_bytes(program_id, &data, vec![]));
+        }
+        Ok(instructions)
+    }
+
+    /// Builds an unsigned transaction paid by `payer` with a recent blockhash
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut transaction = client.build_transaction(&payer.pubkey(), &instructions).await?;
+    /// transaction.sign(&[&payer], transaction.message.recent_blockhash);
+    /// ```
+    pub async fn build_transaction(
+        &self,
+        payer: &Pubkey,
+        instructions: &[Instruction],
+    ) -> OrcaResult<Transaction> {
+        let mut transaction = Transaction::new_unsigned(Message::new(instructions, Some(payer)));
+        transaction.message.recent_blockhash = self.latest_blockhash().await?;
+        Ok(transaction)
+    }
+
+    pub(crate) async fn latest_blockhash(&self) -> OrcaResult<Hash> {
+        self.rpc_call("Failed to get blockhash", |client| {
+            client.get_latest_blockhash()
+        })
+        .await
+    }
+}
@@ -0,0 +1,266 @@
+use serde::Serialize;
+
+use super::*;
+use crate::{
+    fees::FeesOwed,
+    liquidity::LiquidityPosition,
+    math::{sqrt_price_x64_to_price, to_ui_amount},
+    pool::PoolInfo,
+    types::OrcaResult,
+    validation::parse_address,
+};
+
+const MAX_PNL_TRANSACTIONS: usize = 1_000;
+
+/// Value and profit of a liquidity position against the tokens deposited into it
+///
+/// Token amounts are raw units. USD figures value every amount at the current token prices,
+/// so the P&L is what the position gained over holding the deposited tokens; they are None
+/// when either token has no USD price. Reward tokens are reported in `fees_owed` only and are
+/// not part of any value.
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionPnl {
+    pub position_mint: Pubkey,
+    pub position_address: Pubkey,
+    pub pool_address: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    /// Token A paid into the pool by the position's transactions
+    pub deposited_a: u64,
+    /// Token B paid into the pool by the position's transactions
+    pub deposited_b: u64,
+    /// Token A paid out of the pool by withdrawals and fee collections
+    pub withdrawn_a: u64,
+    /// Token B paid out of the pool by withdrawals and fee collections
+    pub withdrawn_b: u64,
+    /// Token A currently held by the position's liquidity
+    pub current_a: u64,
+    /// Token B currently held by the position's liquidity
+    pub current_b: u64,
+    /// Fees and rewards the position could collect now
+    pub fees_owed: FeesOwed,
+    /// Number of position transactions that moved pool tokens
+    pub transaction_count: usize,
+    /// Current pool price, token B per token A
+    pub price: f64,
+    /// Liquidity, uncollected fees and withdrawals minus deposits, in token B at `price`
+    pub pnl_token_b: f64,
+    pub cost_basis_usd: Option<f64>,
+    pub withdrawn_usd: Option<f64>,
+    /// Value of the liquidity and the uncollected fees
+    pub value_usd: Option<f64>,
+    /// Gain on what was withdrawn, against its share of the cost basis
+    pub realized_pnl_usd: Option<f64>,
+    /// Gain on what is still in the position, against its share of the cost basis
+    pub unrealized_pnl_usd: Option<f64>,
+    pub pnl_usd: Option<f64>,
+    /// `pnl_usd` relative to the cost basis in percent
+    pub pnl_percent: Option<f64>,
+}
+
+/// Token amounts a position's history moved into and out of its pool
+#[derive(Debug, Clone, Copy, Default)]
+struct PositionFlows {
+    deposited_a: u64,
+    deposited_b: u64,
+    withdrawn_a: u64,
+    withdrawn_b: u64,
+    transaction_count: usize,
+}
+
+impl OrcaClient {
+    /// Estimates the value and P&L of a liquidity position
+    ///
+    /// The cost basis is reconstructed from the confirmed transactions of the position account:
+    /// each one's pool vault balance changes count as deposits when tokens flow into the pool
+    /// and as withdrawals otherwise, so swaps a zap makes in the same pool net out. The current
+    /// value adds the position's token amounts to the fees it could collect now.
+    ///
+    /// Transactions that also touch another position of the same pool, such as a rebalance,
+    /// are attributed to this position as a whole. At most 1,000 transactions are read.
+    ///
+    /// # Example
+    /// ```rust
+    /// for position in client.get_liquidity_positions(&owner).await? {
+    ///     let pnl = client.get_position_pnl(&position).await?;
+    ///     println!("{}: {:?} USD ({:?}%)", pnl.position_mint, pnl.pnl_usd, pnl.pnl_percent);
+    /// }
+    /// ```
+    #[tracing::instrument(
+        skip_all,
+        fields(position = %position.position_address),
+        err(Debug)
+    )]
+    pub async fn get_position_pnl(&self, position: &LiquidityPosition) -> OrcaResult<PositionPnl> {
+        self.with_timeout("get position pnl", async {
+            let pool = self
+                .get_pool_state_onchain(&position.pool_address.to_string())
+                .await?;
+            let mint_a = parse_address("token mint A", &pool.token_mint_a)?;
+            let mint_b = parse_address("token mint B", &pool.token_mint_b)?;
+            let decimals = self.get_mint_decimals(&[mint_a, mint_b]).await?;
+            let (decimals_a, decimals_b) = (decimals[0], decimals[1]);
+            let flows = self.get_position_flows(&pool, position).await?;
+            let fees_owed = self.compute_fees_owed(position).await?;
+            let prices = self
+                .get_token_prices_usd(&[pool.token_mint_a.clone(), pool.token_mint_b.clone()])
+                .await
+                .inspect_err(|e| tracing::warn!(error = ?e, "Failed to price position tokens"))
+                .unwrap_or_default();
+            let price = sqrt_price_x64_to_price(pool.sqrt_price, decimals_a, decimals_b);
+            let current_a = position.token_a_amount;
+            let current_b = position.token_b_amount;
+            let in_b = |amount_a: u64, amount_b: u64| {
+                to_ui_amount(amount_a, decimals_a) * price + to_ui_amount(amount_b, decimals_b)
+            };
+            let held = in_b(
+                current_a.saturating_add(fees_owed.fee_owed_a),
+                current_b.saturating_add(fees_owed.fee_owed_b),
+            );
+            let pnl_token_b = held + in_b(flows.withdrawn_a, flows.withdrawn_b)
+                - in_b(flows.deposited_a, flows.deposited_b);
+            let usd = match (
+                prices.get(&pool.token_mint_a),
+                prices.get(&pool.token_mint_b),
+            ) {
+                (Some(&price_a), Some(&price_b)) => Some(move |amount_a: u64, amount_b: u64| {
+                    to_ui_amount(amount_a, decimals_a) * price_a
+                        + to_ui_amount(amount_b, decimals_b) * price_b
+                }),
+                _ => None,
+            };
+            let cost_basis_usd = usd.map(|usd| usd(flows.deposited_a, flows.deposited_b));
+            let withdrawn_usd = usd.map(|usd| usd(flows.withdrawn_a, flows.withdrawn_b));
+            let value_usd = usd.map(|usd| {
+                usd(
+                    current_a.saturating_add(fees_owed.fee_owed_a),
+                    current_b.saturating_add(fees_owed.fee_owed_b),
+                )
+            });
+            let (realized_pnl_usd, unrealized_pnl_usd) =
+                match (cost_basis_usd, withdrawn_usd, value_usd) {
+                    (Some(cost), Some(withdrawn), Some(value)) => {
+                        let (realized, unrealized) = split_pnl(cost, withdrawn, value);
+                        (Some(realized), Some(unrealized))
+                    }
+                    _ => (None, None),
+                };
+            let pnl_usd = realized_pnl_usd
+                .zip(unrealized_pnl_usd)
+                .map(|(realized, unrealized)| realized + unrealized);
+            let pnl_percent = pnl_usd
+                .zip(cost_basis_usd)
+                .filter(|(_, cost)| *cost > 0.0)
+                .map(|(pnl, cost)| pnl / cost * 100.0);
+            Ok(PositionPnl {
+                position_mint: position.position_mint,
+                position_address: position.position_address,
+                pool_address: position.pool_address,
+                token_mint_a: mint_a,
+                token_mint_b: mint_b,
+                deposited_a: flows.deposited_a,
+                deposited_b: flows.deposited_b,
+                withdrawn_a: flows.withdrawn_a,
+                withdrawn_b: flows.withdrawn_b,
+                current_a,
+                current_b,
+                fees_owed,
+                transaction_count: flows.transaction_count,
+                price,
+                pnl_token_b,
+                cost_basis_usd,
+                withdrawn_usd,
+                value_usd,
+                realized_pnl_usd,
+                unrealized_pnl_usd,
+                pnl_usd,
+                pnl_percent,
+            })
+        })
+        .await
+    }
+
+    /// Estimates the value and P&L of every liquidity position of a wallet
+    ///
+    /// Positions whose P&L cannot be estimated are logged and left out.
+    ///
+    /// # Example
+    /// ```rust
+    /// let pnls = client.get_positions_pnl(&owner).await?;
+    /// let total: f64 = pnls.iter().filter_map(|pnl| pnl.pnl_usd).sum();
+    /// println!("Total P&L: ${:.2}", total);
+    /// ```
+    pub async fn get_positions_pnl(&self, owner: &Pubkey) -> OrcaResult<Vec<PositionPnl>> {
+        let positions = self.get_liquidity_positions(owner).await?;
+        let mut pnls = Vec::with_capacity(positions.len());
+        for position in &positions {
+            match self.get_position_pnl(position).await {
+                Ok(pnl) => pnls.push(pnl),
+                Err(e) => tracing::warn!(
+                    position = %position.position_address,
+                    error = ?e,
+                    "Failed to estimate position P&L"
+                ),
+            }
+        }
+        Ok(pnls)
+    }
+
+    /// Sums the pool vault balance changes of the position account's transactions
+    async fn get_position_flows(
+        &self,
+        pool: &PoolInfo,
+        position: &LiquidityPosition,
+    ) -> OrcaResult<PositionFlows> {
+        let listed = self
+            .get_signatures_paginated(&position.position_address, None, None, MAX_PNL_TRANSACTIONS)
+            .await?;
+        let signatures: Vec<Signature> = listed.iter().map(|(signature, _)| *signature).collect();
+        let mut flows = PositionFlows::default();
+        for transaction in self
+            .fetch_transactions(&signatures)
+            .await?
+            .into_iter()
+            .flatten()
+        {
+            let Some(meta) = transaction.transaction.meta.as_ref() else {
+                continue;
+            };
+            let Some((delta_a, delta_b, _, _)) = Self::extract_vault_deltas(meta, pool) else {
+                continue;
+            };
+            if delta_a == 0 && delta_b == 0 {
+                continue;
+            }
+            flows.transaction_count += 1;
+            for (delta, deposited, withdrawn) in [
+                (delta_a, &mut flows.deposited_a, &mut flows.withdrawn_a),
+                (delta_b, &mut flows.deposited_b, &mut flows.withdrawn_b),
+            ] {
+                let amount = delta.unsigned_abs().min(u64::MAX as u128) as u64;
+                if delta > 0 {
+                    *deposited = deposited.saturating_add(amount);
+                } else {
+                    *withdrawn = withdrawn.saturating_add(amount);
+                }
+            }
+        }
+        Ok(flows)
+    }
+}
+
+/// Splits P&L into realized and unrealized parts
+///
+/// The cost basis is allocated between what was withdrawn and what is still held in
+/// proportion to their value.
+fn split_pnl(cost_basis: f64, withdrawn: f64, value: f64) -> (f64, f64) {
+    let total = withdrawn + value;
+    if total <= 0.0 {
+        return (-cost_basis, 0.0);
+    }
+    let withdrawn_cost = cost_basis * withdrawn / total;
+    (
+        withdrawn - withdrawn_cost,
+        value - (cost_basis - withdrawn_cost),
+    )
+}
@@ -0,0 +1,361 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::PoisonError,
+};
+
+use super::*;
+use crate::{types::OrcaResult, validation::parse_address};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Spending limits and allow/deny lists enforced before any transaction is signed
+///
+/// Token amounts are raw units of the mint; the USD limit uses pool-derived prices.
+#[derive(Debug, Clone, Default)]
+pub struct SpendPolicy {
+    /// When set, only these mints may be traded or deposited
+    pub token_allowlist: Option<HashSet<String>>,
+    pub token_denylist: HashSet<String>,
+    /// When set, only these pools may be used
+    pub pool_allowlist: Option<HashSet<String>>,
+    pub pool_denylist: HashSet<String>,
+    /// Maximum raw amount of a mint spent by a single operation
+    pub per_transaction_limits: HashMap<String, u64>,
+    /// Maximum raw amount of a mint spent per UTC day
+    pub daily_limits: HashMap<String, u64>,
+    /// Maximum USD notional spent per UTC day across all mints
+    pub daily_notional_usd_limit: Option<f64>,
+}
+
+/// Reason an operation was refused by the spend policy
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyViolation {
+    TokenDenied(String),
+    TokenNotAllowed(String),
+    PoolDenied(String),
+    PoolNotAllowed(String),
+    PerTransactionLimitExceeded {
+        mint: String,
+        amount: u64,
+        limit: u64,
+    },
+    DailyLimitExceeded {
+        mint: String,
+        spent: u64,
+        amount: u64,
+        limit: u64,
+    },
+    DailyNotionalLimitExceeded {
+        spent_usd: f64,
+        amount_usd: f64,
+        limit_usd: f64,
+    },
+    /// The USD value of a spend could not be determined while a notional limit is active
+    UnpricedSpend(String),
+}
+
+/// Amounts spent during the current UTC day
+#[derive(Debug, Default)]
+pub(crate) struct SpendLedger {
+    day: i64,
+    spent: HashMap<String, u64>,
+    spent_usd: f64,
+}
+
+impl SpendLedger {
+    fn roll_over(&mut self, today: i64) {
+        if self.day != today {
+            self.day = today;
+            self.spent.clear();
+            self.spent_usd = 0.0;
+        }
+    }
+}
+
+impl OrcaClient {
+    /// Installs or removes the spend policy enforced by mutating APIs
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use orca_sdk::policy::SpendPolicy;
+    ///
+    /// let mut daily_limits = HashMap::new();
+    /// daily_limits.insert("So11111111111111111111111111111111111111112".to_string(), 10_000_000_000);
+    /// client.set_spend_policy(Some(SpendPolicy {
+    ///     daily_limits,
+    ///     daily_notional_usd_limit: Some(5_000.0),
+    ///     ..Default::default()
+    /// }));
+    /// ```
+    pub fn set_spend_policy(&self, policy: Option<SpendPolicy>) {
+        if let Ok(mut current) = self.spend_policy.write() {
+            *current = policy;
+        }
+    }
+
+    /// Returns the active spend policy
+    pub fn spend_policy(&self) -> Option<SpendPolicy> {
+        self.spend_policy
+            .read()
+            .ok()
+            .and_then(|policy| policy.clone())
+    }
+
+    /// Returns the raw amount of a mint spent today under the policy
+    pub fn spent_today(&self, mint: &str) -> u64 {
+        let today = chrono::Utc::now().timestamp() / SECONDS_PER_DAY;
+        match self.spend_ledger.lock() {
+            Ok(mut ledger) => {
+                ledger.roll_over(today);
+                ledger.spent.get(mint).copied().unwrap_or(0)
+            }
+            Err(_) => 0,
+        }
+    }
+
+    /// Checks that a mint may be used at all
+    pub(crate) fn check_token_policy(&self, mint: &str) -> OrcaResult<()> {
+        let Some(policy) = self.spend_policy() else {
+            return Ok(());
+        };
+        if policy.token_denylist.contains(mint) {
+            return Err(OrcaError::PolicyViolation(PolicyViolation::TokenDenied(
+                mint.to_string(),
+            )));
+        }
+        if let Some(allowlist) = &policy.token_allowlist
+            && !allowlist.contains(mint)
+        {
+            return Err(OrcaError::PolicyViolation(
+                PolicyViolation::TokenNotAllowed(mint.to_string()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that a pool may be used at all
+    pub(crate) fn check_pool_policy(&self, pool_address: &str) -> OrcaResult<()> {
+        let Some(policy) = self.spend_policy() else {
+            return Ok(());
+        };
+        if policy.pool_denylist.contains(pool_address) {
+            return Err(OrcaError::PolicyViolation(PolicyViolation::PoolDenied(
+                pool_address.to_string(),
+            )));
+        }
+        if let Some(allowlist) = &policy.pool_allowlist
+            && !allowlist.contains(pool_address)
+        {
+            return Err(OrcaError::PolicyViolation(PolicyViolation::PoolNotAllowed(
+                pool_address.to_string(),
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks per-transaction and daily limits and reserves the spends on the daily ledger
+    ///
+    /// The daily limits are checked and the spends reserved under one ledger lock, so concurrent
+    /// operations cannot pass the limits together. The reservation is released when dropped;
+    /// call `commit` once the operation was sent.
+    pub(crate) async fn reserve_spend(
+        &self,
+        spends: &[(&str, u64)],
+    ) -> OrcaResult<SpendReservation<'_>> {
+        let mut reservation = SpendReservation {
+            client: self,
+            day: 0,
+            spends: Vec::new(),
+            amount_usd: 0.0,
+            committed: false,
+        };
+        let Some(policy) = self.spend_policy() else {
+            return Ok(reservation);
+        };
+        for (mint, amount) in spends {
+            self.check_token_policy(mint)?;
+            if let Some(limit) = policy.per_transaction_limits.get(*mint)
+                && amount > limit
+            {
+                return Err(OrcaError::PolicyViolation(
+                    PolicyViolation::PerTransactionLimitExceeded {
+                        mint: mint.to_string(),
+                        amount: *amount,
+                        limit: *limit,
+                    },
+                ));
+            }
+        }
+        let amount_usd = match policy.daily_notional_usd_limit {
+            Some(_) => self.spend_usd_value(spends).await?,
+            None => 0.0,
+        };
+        let today = chrono::Utc::now().timestamp() / SECONDS_PER_DAY;
+        let mut ledger = self
+            .spend_ledger
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        ledger.roll_over(today);
+        for (mint, amount) in spends {
+            if let Some(limit) = policy.daily_limits.get(*mint) {
+                let spent = ledger.spent.get(*mint).copied().unwrap_or(0);
+                if spent.saturating_add(*amount) > *limit {
+                    return Err(OrcaError::PolicyViolation(
+                        PolicyViolation::DailyLimitExceeded {
+                            mint: mint.to_string(),
+                            spent,
+                            amount: *amount,
+                            limit: *limit,
+                        },
+                    ));
+                }
+            }
+        }
+        if let Some(limit_usd) = policy.daily_notional_usd_limit
+            && ledger.spent_usd + amount_usd > limit_usd
+        {
+            return Err(OrcaError::PolicyViolation(
+                PolicyViolation::DailyNotionalLimitExceeded {
+                    spent_usd: ledger.spent_usd,
+                    amount_usd,
+                    limit_usd,
+                },
+            ));
+        }
+        for (mint, amount) in spends {
+            let spent = ledger.spent.entry(mint.to_string()).or_default();
+            *spent = spent.saturating_add(*amount);
+        }
+        ledger.spent_usd += amount_usd;
+        reservation.day = today;
+        reservation.spends = spends
+            .iter()
+            .map(|(mint, amount)| (mint.to_string(), *amount))
+            .collect();
+        reservation.amount_usd = amount_usd;
+        Ok(reservation)
+    }
+
+    /// USD notional of the given spends; unpriced mints are a policy violation
+    async fn spend_usd_value(&self, spends: &[(&str, u64)]) -> OrcaResult<f64> {
+        let mut amount_usd = 0.0;
+        for (mint, amount) in spends {
+            if *amount == 0 {
+                continue;
+            }
+            let mint_pubkey = parse_address("mint", mint)?;
+            amount_usd += self
+                .estimate_usd_value(&mint_pubkey, *amount)
+                .await
+                .ok_or_else(|| {
+                    OrcaError::PolicyViolation(PolicyViolation::UnpricedSpend(mint.to_string()))
+                })?;
+        }
+        Ok(amount_usd)
+    }
+}
+
+/// Spends held on the daily ledger while an operation is built and sent
+///
+/// Dropping an uncommitted reservation gives the amounts back, so a failed build or send
+/// does not count against the daily limits.
+#[must_use]
+pub(crate) struct SpendReservation<'a> {
+    client: &'a OrcaClient,
+    day: i64,
+    spends: Vec<(String, u64)>,
+    amount_usd: f64,
+    committed: bool,
+}
+
+impl SpendReservation<'_> {
+    /// Keeps the reserved spends on the ledger; call once the operation was sent
+    pub(crate) fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for SpendReservation<'_> {
+    fn drop(&mut self) {
+        if self.committed || self.spends.is_empty() {
+            return;
+        }
+        let mut ledger = self
+            .client
+            .spend_ledger
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Spends of an earlier day were already cleared by the roll-over
+        if ledger.day != self.day {
+            return;
+        }
+        for (mint, amount) in &self.spends {
+            if let Some(spent) = ledger.spent.get_mut(mint) {
+                *spent = spent.saturating_sub(*amount);
+            }
+        }
+        ledger.spent_usd = (ledger.spent_usd - self.amount_usd).max(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::sync::Barrier;
+
+    use super::*;
+    use crate::builder::OrcaClientBuilder;
+
+    const MINT: &str = "So11111111111111111111111111111111111111112";
+
+    fn client_with_daily_limit(limit: u64) -> OrcaClient {
+        let client = OrcaClientBuilder::new().build().unwrap();
+        client.set_spend_policy(Some(SpendPolicy {
+            daily_limits: HashMap::from([(MINT.to_string(), limit)]),
+            ..Default::default()
+        }));
+        client
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn concurrent_spends_cannot_exceed_daily_limit() {
+        let client = Arc::new(client_with_daily_limit(100));
+        let barrier = Arc::new(Barrier::new(2));
+        let tasks = (0..2).map(|_| {
+            let client = client.clone();
+            let barrier = barrier.clone();
+            tokio::spawn(async move {
+                let reservation = client.reserve_spend(&[(MINT, 60)]).await;
+                // Both spends are checked before either one is sent
+                barrier.wait().await;
+                reservation.map(SpendReservation::commit).is_ok()
+            })
+        });
+        let accepted = futures::future::join_all(tasks)
+            .await
+            .into_iter()
+            .filter(|accepted| *accepted.as_ref().unwrap())
+            .count();
+        assert_eq!(accepted, 1);
+        assert_eq!(client.spent_today(MINT), 60);
+    }
+
+    #[tokio::test]
+    async fn dropped_reservation_is_released() {
+        let client = client_with_daily_limit(100);
+        let reservation = client.reserve_spend(&[(MINT, 60)]).await.unwrap();
+        assert_eq!(client.spent_today(MINT), 60);
+        assert!(matches!(
+            client.reserve_spend(&[(MINT, 60)]).await,
+            Err(OrcaError::PolicyViolation(
+                PolicyViolation::DailyLimitExceeded { spent: 60, .. }
+            ))
+        ));
+        drop(reservation);
+        assert_eq!(client.spent_today(MINT), 0);
+        client.reserve_spend(&[(MINT, 60)]).await.unwrap().commit();
+        assert_eq!(client.spent_today(MINT), 60);
+    }
+}
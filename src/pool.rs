@@ -1,37 +1,138 @@
-use solana_account_decoder::UiAccountEncoding;
+use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
 use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
-use solana_client::rpc_filter::RpcFilterType;
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
 use solana_commitment_config::CommitmentConfig;
 
 use super::*;
+use crate::clmm::simulate_swap;
+use crate::fixed::{
+    max_amount_with_slippage, min_amount_with_slippage, tick_index_at_sqrt_price_x64,
+};
 use crate::global::*;
-use crate::types::OrcaResult;
+use crate::layout::AccountReader;
+use crate::math::{price_impact_bps, sqrt_price_x64_to_price, to_ui_amount};
+use crate::resolver::PoolSelection;
+use crate::types::{InvalidInput, OrcaResult};
+use crate::validation::{parse_address, validate_swap_inputs};
 
-#[derive(Debug, Clone)]
+// 2^64, the scale of Q64.64 emission rates
+const Q64: f64 = 18_446_744_073_709_551_616.0;
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+#[derive(Debug, Clone, Default)]
 pub struct PoolInfo {
     pub address: String,
+    /// WhirlpoolsConfig account the pool belongs to
+    pub whirlpools_config: String,
     pub token_mint_a: String,
     pub token_mint_b: String,
     pub token_vault_a: String,
     pub token_vault_b: String,
-    pub lp_token_mint: String,
-    pub fee_account: String,
+    /// Fee rate in hundredths of a basis point, equal to `trade_fee_numerator`
+    pub fee_rate: u16,
+    /// Share of the fee taken by the protocol, in basis points
+    pub protocol_fee_rate: u16,
     pub trade_fee_numerator: u64,
     pub trade_fee_denominator: u64,
     pub tick_spacing: u16,
+    pub tick_current_index: i32,
     pub liquidity: u128,
     pub sqrt_price: u128,
+    pub protocol_fee_owed_a: u64,
+    pub protocol_fee_owed_b: u64,
     pub fee_growth_global_a: u128,
     pub fee_growth_global_b: u128,
+    pub reward_last_updated_timestamp: u64,
+    pub reward_infos: [WhirlpoolRewardInfo; WHIRLPOOL_NUM_REWARDS],
+}
+
+/// Liquidity mining reward slot of a Whirlpool
+///
+/// Uninitialized slots have the default mint.
+#[derive(Debug, Clone, Default)]
+pub struct WhirlpoolRewardInfo {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    /// Reward tokens emitted per second, Q64.64
+    pub emissions_per_second_x64: u128,
+    /// Reward growth per unit of liquidity, Q64.64
+    pub growth_global_x64: u128,
+}
+
+impl WhirlpoolRewardInfo {
+    pub fn is_initialized(&self) -> bool {
+        self.mint != Pubkey::default()
+    }
+
+    /// Reward tokens emitted per day, in raw units
+    pub fn emissions_per_day_raw(&self) -> f64 {
+        self.emissions_per_second_x64 as f64 / Q64 * SECONDS_PER_DAY
+    }
+
+    /// Reward tokens emitted per day, in whole tokens of a reward mint with `decimals`
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool = client.get_pool_state_onchain("whirlpool_address_here").await?;
+    /// let reward = &pool.reward_infos[0];
+    /// let decimals = client.get_mint_decimals(&[reward.mint]).await?[0];
+    /// println!("{} tokens per day", reward.emissions_per_day(decimals));
+    /// ```
+    pub fn emissions_per_day(&self, decimals: u8) -> f64 {
+        self.emissions_per_day_raw() / 10f64.powi(decimals as i32)
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct QuoteResult {
     pub input_amount: u64,
     pub output_amount: u64,
     pub min_output_amount: u64,
-    pub price_impact: f64,
+    /// Maximum input the swap may spend; equals `input_amount` for exact-input quotes
+    pub max_input_amount: u64,
+    /// Shortfall of the execution price against the spot price before the swap, fees excluded,
+    /// in basis points
+    pub price_impact_bps: u16,
     pub fee_amount: u64,
+    /// Token-2022 transfer fee withheld from the input, in input token units
+    pub input_transfer_fee: u64,
+    /// Token-2022 transfer fee withheld from the output, in output token units
+    pub output_transfer_fee: u64,
+    pub input_decimals: u8,
+    pub output_decimals: u8,
+}
+
+impl QuoteResult {
+    /// `input_amount` in whole input tokens
+    pub fn ui_input_amount(&self) -> f64 {
+        to_ui_amount(self.input_amount, self.input_decimals)
+    }
+
+    /// `output_amount` in whole output tokens
+    pub fn ui_output_amount(&self) -> f64 {
+        to_ui_amount(self.output_amount, self.output_decimals)
+    }
+
+    /// `min_output_amount` in whole output tokens
+    pub fn ui_min_output_amount(&self) -> f64 {
+        to_ui_amount(self.min_output_amount, self.output_decimals)
+    }
+
+    /// `max_input_amount` in whole input tokens
+    pub fn ui_max_input_amount(&self) -> f64 {
+        to_ui_amount(self.max_input_amount, self.input_decimals)
+    }
+
+    /// `fee_amount` in whole input tokens
+    pub fn ui_fee_amount(&self) -> f64 {
+        to_ui_amount(self.fee_amount, self.input_decimals)
+    }
+
+    /// Output tokens received per input token spent, in whole tokens
+    pub fn ui_execution_price(&self) -> f64 {
+        self.ui_output_amount() / self.ui_input_amount()
+    }
 }
 
 impl OrcaClient {
@@ -43,140 +144,38 @@ impl OrcaClient {
     /// println!("Pool liquidity: {}", pool_info.liquidity);
     /// ```
     pub async fn get_pool_state_onchain(&self, pool_address: &str) -> OrcaResult<PoolInfo> {
-        let client = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?;
-        let pool_pubkey = Pubkey::from_str(pool_address)
-            .map_err(|e| OrcaError::Error(format!("Invalid pool address: {}", e)))?;
-        let account_data = client
-            .get_account_data(&pool_pubkey)
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get account data: {}", e)))?;
-        self.parse_whirlpool_account_data(&account_data, pool_address)
+        let pool_pubkey = parse_address("pool_address", pool_address)?;
+        let account = self
+            .get_existing_account(&pool_pubkey, "Failed to get pool account")
+            .await?;
+        self.parse_whirlpool_account_data(&account.data, pool_address)
     }
 
-    /// Parses Whirlpool account data into PoolInfo struct
-    fn parse_whirlpool_account_data(
+    /// Decodes Whirlpool account data into PoolInfo
+    ///
+    /// The data must carry the Whirlpool account discriminator and the full account layout.
+    pub(crate) fn parse_whirlpool_account_data(
         &self,
         data: &[u8],
         pool_address: &str,
     ) -> OrcaResult<PoolInfo> {
-        if data.len() < 300 {
-            return Err(OrcaError::Error(
-                "Invalid whirlpool account data length".to_string(),
-            ));
+        if data.len() < WHIRLPOOL_ACCOUNT_SIZE {
+            return Err(OrcaError::InvalidPoolData {
+                pool: pool_address.to_string(),
+                reason: format!("account data is {} bytes", data.len()),
+            });
         }
-        let token_mint_a = Pubkey::new_from_array(
-            data[WHIRLPOOL_TOKEN_MINT_A_OFFSET..WHIRLPOOL_TOKEN_MINT_A_OFFSET + 32]
-                .try_into()
-                .map_err(|_| OrcaError::Error("Failed to parse token mint A".to_string()))?,
-        )
-        .to_string();
-        let token_mint_b = Pubkey::new_from_array(
-            data[WHIRLPOOL_TOKEN_MINT_B_OFFSET..WHIRLPOOL_TOKEN_MINT_B_OFFSET + 32]
-                .try_into()
-                .map_err(|_| OrcaError::Error("Failed to parse token mint B".to_string()))?,
-        )
-        .to_string();
-        let tick_spacing = u16::from_le_bytes(
-            data[WHIRLPOOL_TICK_SPACING_OFFSET..WHIRLPOOL_TICK_SPACING_OFFSET + 2]
-                .try_into()
-                .map_err(|_| OrcaError::Error("Failed to parse tick spacing".to_string()))?,
-        );
-        let fee_rate = u16::from_le_bytes(
-            data[WHIRLPOOL_FEE_RATE_OFFSET..WHIRLPOOL_FEE_RATE_OFFSET + 2]
-                .try_into()
-                .map_err(|_| OrcaError::Error("Failed to parse fee rate".to_string()))?,
-        );
-        let liquidity = u128::from_le_bytes(
-            data[WHIRLPOOL_LIQUIDITY_OFFSET..WHIRLPOOL_LIQUIDITY_OFFSET + 16]
-                .try_into()
-                .map_err(|_| OrcaError::Error("Failed to parse liquidity".to_string()))?,
-        );
-        let sqrt_price = u128::from_le_bytes(
-            data[WHIRLPOOL_SQRT_PRICE_OFFSET..WHIRLPOOL_SQRT_PRICE_OFFSET + 16]
-                .try_into()
-                .map_err(|_| OrcaError::Error("Failed to parse sqrt price".to_string()))?,
-        );
-        let token_vault_a = self.derive_token_vault_address(&token_mint_a, pool_address)?;
-        let token_vault_b = self.derive_token_vault_address(&token_mint_b, pool_address)?;
-        let lp_token_mint = self.derive_lp_token_mint(pool_address)?;
-        let fee_account = self.derive_fee_account(pool_address)?;
-        let fee_growth_global_a = if data.len() >= 248 {
-            u128::from_le_bytes(data[232..248].try_into().unwrap_or([0; 16]))
-        } else {
-            0
-        };
-        let fee_growth_global_b = if data.len() >= 264 {
-            u128::from_le_bytes(data[248..264].try_into().unwrap_or([0; 16]))
-        } else {
-            0
-        };
-        Ok(PoolInfo {
-            address: pool_address.to_string(),
-            token_mint_a,
-            token_mint_b,
-            token_vault_a,
-            token_vault_b,
-            lp_token_mint,
-            fee_account,
-            trade_fee_numerator: fee_rate as u64,
-            trade_fee_denominator: 1_000_000,
-            tick_spacing,
-            liquidity,
-            sqrt_price,
-            fee_growth_global_a,
-            fee_growth_global_b,
+        decode_whirlpool(data, pool_address).ok_or_else(|| OrcaError::InvalidPoolData {
+            pool: pool_address.to_string(),
+            reason: "not a Whirlpool account".to_string(),
         })
     }
 
-    /// Derives token vault address using PDA
-    fn derive_token_vault_address(
-        &self,
-        token_mint: &str,
-        pool_address: &str,
-    ) -> OrcaResult<String> {
-        let token_mint_pubkey = Pubkey::from_str(token_mint)
-            .map_err(|e| OrcaError::Error(format!("Invalid token mint: {}", e)))?;
-        let pool_pubkey = Pubkey::from_str(pool_address)
-            .map_err(|e| OrcaError::Error(format!("Invalid pool address: {}", e)))?;
-        let (vault_address, _) = Pubkey::find_program_address(
-            &[
-                b"token_vault",
-                pool_pubkey.as_ref(),
-                token_mint_pubkey.as_ref(),
-            ],
-            &self.whirlpool_program_id,
-        );
-        Ok(vault_address.to_string())
-    }
-
-    /// Derives LP token mint address using PDA
-    fn derive_lp_token_mint(&self, pool_address: &str) -> OrcaResult<String> {
-        let pool_pubkey = Pubkey::from_str(pool_address)
-            .map_err(|e| OrcaError::Error(format!("Invalid pool address: {}", e)))?;
-        let (lp_mint, _) = Pubkey::find_program_address(
-            &[b"lp_mint", pool_pubkey.as_ref()],
-            &self.whirlpool_program_id,
-        );
-        Ok(lp_mint.to_string())
-    }
-
-    /// Derives fee account address using PDA
-    fn derive_fee_account(&self, pool_address: &str) -> OrcaResult<String> {
-        let pool_pubkey = Pubkey::from_str(pool_address)
-            .map_err(|e| OrcaError::Error(format!("Invalid pool address: {}", e)))?;
-        let (fee_account, _) = Pubkey::find_program_address(
-            &[b"fee_account", pool_pubkey.as_ref()],
-            &self.whirlpool_program_id,
-        );
-        Ok(fee_account.to_string())
-    }
-
     /// Optimized method to find pools containing a specific token
     ///
+    /// Only the two mint fields of each Whirlpool are downloaded during discovery; use
+    /// `get_pool_states_by_token` to fetch the full state of the matches.
+    ///
     /// # Example
     /// ```
     /// let pools = client.find_pools_by_token_onchain_optimized("So11111111111111111111111111111111111111112").await?;
@@ -186,55 +185,77 @@ impl OrcaClient {
         &self,
         token_mint: &str,
     ) -> OrcaResult<Vec<String>> {
-        if let Some(cached_pools) = self.get_cached_pools_for_token(token_mint).await? {
-            return Ok(cached_pools);
-        }
-        let client = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?;
-        let token_pubkey = Pubkey::from_str(token_mint)
-            .map_err(|e| OrcaError::Error(format!("Invalid token mint: {}", e)))?;
-        let filters = vec![RpcFilterType::DataSize(300)];
-        let accounts = client
-            .get_program_accounts_with_config(
-                &self.whirlpool_program_id,
-                RpcProgramAccountsConfig {
-                    filters: Some(filters),
-                    account_config: RpcAccountInfoConfig {
-                        encoding: Some(UiAccountEncoding::Base64),
-                        data_slice: None,
-                        commitment: Some(CommitmentConfig::confirmed()),
-                        min_context_slot: None,
-                    },
-                    with_context: None,
-                    sort_results: None,
-                },
-            )
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get program accounts: {}", e)))?;
+        self.with_timeout("find pools by token", async {
+            if let Some(cached_pools) = self.get_cached_pools_for_token(token_mint).await? {
+                return Ok(cached_pools);
+            }
+            let token_pubkey = parse_address("token_mint", token_mint)?;
+            let pool_addresses = self.scan_pools_by_token(&token_pubkey).await?;
+            self.cache_pools_for_token(token_mint, &pool_addresses)
+                .await?;
+            Ok(pool_addresses)
+        })
+        .await
+    }
+
+    /// Discovers the pools containing a token, then fetches the full state of each in batches
+    ///
+    /// # Example
+    /// ```rust
+    /// let pools = client
+    ///     .get_pool_states_by_token("So11111111111111111111111111111111111111112")
+    ///     .await?;
+    /// for pool in &pools {
+    ///     println!("{}: {}/{}", pool.address, pool.token_mint_a, pool.token_mint_b);
+    /// }
+    /// ```
+    pub async fn get_pool_states_by_token(&self, token_mint: &str) -> OrcaResult<Vec<PoolInfo>> {
+        let addresses = self
+            .find_pools_by_token_onchain_optimized(token_mint)
+            .await?;
+        self.get_pool_states(&addresses).await
+    }
+
+    /// Scans every Whirlpool for `token_mint`, downloading only the mint A through mint B range
+    async fn scan_pools_by_token(&self, token_mint: &Pubkey) -> OrcaResult<Vec<String>> {
+        let slice_length = WHIRLPOOL_TOKEN_MINT_B_OFFSET + 32 - WHIRLPOOL_TOKEN_MINT_A_OFFSET;
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::DataSize(WHIRLPOOL_ACCOUNT_SIZE as u64)]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: Some(UiDataSliceConfig {
+                    offset: WHIRLPOOL_TOKEN_MINT_A_OFFSET,
+                    length: slice_length,
+                }),
+                commitment: Some(CommitmentConfig::confirmed()),
+                min_context_slot: None,
+            },
+            with_context: None,
+            sort_results: None,
+        };
+        let accounts = self
+            .rpc_call("Failed to get program accounts", |client| {
+                client.get_program_accounts_with_config(&self.whirlpool_program_id, config.clone())
+            })
+            .await?;
+        let mint_b_offset = WHIRLPOOL_TOKEN_MINT_B_OFFSET - WHIRLPOOL_TOKEN_MINT_A_OFFSET;
         let mut pool_addresses = Vec::new();
         for (pubkey, account) in accounts {
-            if account.data.len() < WHIRLPOOL_TOKEN_MINT_B_OFFSET + 32 {
+            if account.data.len() != slice_length {
                 continue;
             }
-            let mint_a_bytes: [u8; 32] = account.data
-                [WHIRLPOOL_TOKEN_MINT_A_OFFSET..WHIRLPOOL_TOKEN_MINT_A_OFFSET + 32]
+            let mint_a_bytes: [u8; 32] = account.data[..32]
                 .try_into()
-                .map_err(|_| OrcaError::Error("Failed to convert mint A bytes".to_string()))?;
-            let mint_b_bytes: [u8; 32] = account.data
-                [WHIRLPOOL_TOKEN_MINT_B_OFFSET..WHIRLPOOL_TOKEN_MINT_B_OFFSET + 32]
+                .map_err(|_| invalid_pool_data(&pubkey.to_string(), "mint A"))?;
+            let mint_b_bytes: [u8; 32] = account.data[mint_b_offset..mint_b_offset + 32]
                 .try_into()
-                .map_err(|_| OrcaError::Error("Failed to convert mint B bytes".to_string()))?;
-            let mint_a = Pubkey::new_from_array(mint_a_bytes);
-            let mint_b = Pubkey::new_from_array(mint_b_bytes);
-            if mint_a == token_pubkey || mint_b == token_pubkey {
+                .map_err(|_| invalid_pool_data(&pubkey.to_string(), "mint B"))?;
+            if Pubkey::new_from_array(mint_a_bytes) == *token_mint
+                || Pubkey::new_from_array(mint_b_bytes) == *token_mint
+            {
                 pool_addresses.push(pubkey.to_string());
             }
         }
-        self.cache_pools_for_token(token_mint, &pool_addresses)
-            .await?;
         Ok(pool_addresses)
     }
 
@@ -243,154 +264,426 @@ impl OrcaClient {
         &self,
         token_mint: &str,
     ) -> OrcaResult<Option<Vec<String>>> {
-        todo!();
-        Ok(None)
+        Ok(self.cached_pool_addresses(token_mint))
     }
 
     async fn cache_pools_for_token(&self, token_mint: &str, pools: &[String]) -> OrcaResult<()> {
-        todo!();
+        self.cache_pool_addresses(token_mint, pools);
         Ok(())
     }
 
+    /// Finds the Whirlpools trading a token on either side
+    ///
+    /// A pool holds the token as mint A or as mint B, so one memcmp query is sent per offset
+    /// and the results are merged.
     pub async fn find_pools_by_token_onchain(&self, token_mint: &str) -> OrcaResult<Vec<String>> {
-        let client = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?;
-        let token_pubkey = Pubkey::from_str(token_mint)
-            .map_err(|e| OrcaError::Error(format!("Invalid token mint: {}", e)))?;
-        let filters = vec![
-            solana_client::rpc_filter::RpcFilterType::Memcmp(
-                solana_client::rpc_filter::Memcmp::new_base58_encoded(
-                    WHIRLPOOL_TOKEN_MINT_A_OFFSET,
-                    &token_pubkey.to_bytes(),
-                ),
-            ),
-            solana_client::rpc_filter::RpcFilterType::Memcmp(
-                solana_client::rpc_filter::Memcmp::new_base58_encoded(
-                    WHIRLPOOL_TOKEN_MINT_B_OFFSET,
-                    &token_pubkey.to_bytes(),
-                ),
-            ),
-        ];
-        let accounts = client
-            .get_program_accounts_with_config(
-                &self.whirlpool_program_id,
-                solana_client::rpc_config::RpcProgramAccountsConfig {
-                    filters: Some(filters),
-                    account_config: RpcAccountInfoConfig {
-                        encoding: Some(UiAccountEncoding::Base64),
-                        data_slice: None,
-                        commitment: Some(CommitmentConfig::confirmed()),
-                        min_context_slot: None,
-                    },
-                    with_context: None,
-                    sort_results: None,
-                },
-            )
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get program accounts: {}", e)))?;
-        let pool_addresses: Vec<String> = accounts
+        self.with_timeout("find pools by token", async {
+            let token_pubkey = parse_address("token_mint", token_mint)?;
+            let (as_mint_a, as_mint_b) = tokio::try_join!(
+                self.find_pools_by_mint_at(&token_pubkey, WHIRLPOOL_TOKEN_MINT_A_OFFSET),
+                self.find_pools_by_mint_at(&token_pubkey, WHIRLPOOL_TOKEN_MINT_B_OFFSET),
+            )?;
+            let mut pool_addresses = as_mint_a;
+            for address in as_mint_b {
+                if !pool_addresses.contains(&address) {
+                    pool_addresses.push(address);
+                }
+            }
+            Ok(pool_addresses)
+        })
+        .await
+    }
+
+    /// Addresses of the Whirlpools holding `token_mint` at `offset`
+    async fn find_pools_by_mint_at(
+        &self,
+        token_mint: &Pubkey,
+        offset: usize,
+    ) -> OrcaResult<Vec<String>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                offset,
+                &token_mint.to_bytes(),
+            ))]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                // Only the addresses are used
+                data_slice: Some(UiDataSliceConfig {
+                    offset: 0,
+                    length: 0,
+                }),
+                commitment: Some(CommitmentConfig::confirmed()),
+                min_context_slot: None,
+            },
+            with_context: None,
+            sort_results: None,
+        };
+        let accounts = self
+            .rpc_call("Failed to get program accounts", |client| {
+                client.get_program_accounts_with_config(&self.whirlpool_program_id, config.clone())
+            })
+            .await?;
+        Ok(accounts
             .iter()
             .map(|(pubkey, _account)| pubkey.to_string())
-            .collect();
-        Ok(pool_addresses)
+            .collect())
     }
 
     /// Gets a quote for swapping between two tokens
     ///
+    /// When `pool_address` is given, that pool is quoted directly and pool discovery is skipped.
+    ///
     /// # Example
     /// ```
     /// let quote = client.get_quote_from_pool(
     ///     "So11111111111111111111111111111111111111112",
     ///     "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
     ///     1000000,
-    ///     0.5
+    ///     50,
+    ///     None
     /// ).await?;
     /// println!("Output amount: {}", quote.output_amount);
     /// ```
+    #[tracing::instrument(name = "get_quote", skip(self), fields(pool = tracing::field::Empty), err(Debug))]
     pub async fn get_quote_from_pool(
         &self,
         input_mint: &str,
         output_mint: &str,
         input_amount: u64,
-        slippage: f64,
+        slippage_bps: u16,
+        pool_address: Option<&str>,
     ) -> OrcaResult<QuoteResult> {
-        let pools = self.find_pools_by_token_onchain(input_mint).await?;
-        for pool_address in pools {
-            if let Ok(pool_info) = self.get_pool_state_onchain(&pool_address).await {
-                if (pool_info.token_mint_a == input_mint && pool_info.token_mint_b == output_mint)
-                    || (pool_info.token_mint_a == output_mint
-                        && pool_info.token_mint_b == input_mint)
-                {
-                    return self
-                        .calculate_quote_from_pool_state(
-                            &pool_info,
-                            input_mint,
-                            output_mint,
-                            input_amount,
-                            slippage,
-                        )
-                        .await;
-                }
-            }
-        }
-        Err(OrcaError::Error("No pool found for token pair".to_string()))
+        self.with_timeout("get quote", async {
+            validate_swap_inputs(input_mint, output_mint, input_amount, slippage_bps)?;
+            let selection = match pool_address {
+                Some(address) => PoolSelection::Address(address.to_string()),
+                None => PoolSelection::default(),
+            };
+            let pool_info = self
+                .resolve_pool(input_mint, output_mint, &selection)
+                .await?;
+            tracing::Span::current().record("pool", pool_info.address.as_str());
+            self.calculate_quote_from_pool_state(
+                &pool_info,
+                input_mint,
+                output_mint,
+                input_amount,
+                true,
+                slippage_bps,
+            )
+            .await
+        })
+        .await
     }
 
-    async fn calculate_quote_from_pool_state(
+    /// Gets a quote for receiving exactly `output_amount` of the output token
+    ///
+    /// The returned `input_amount` is the input the swap is expected to spend, and
+    /// `max_input_amount` adds the slippage tolerance on top of it.
+    ///
+    /// # Example
+    /// ```
+    /// let quote = client.get_quote_exact_out(
+    ///     "So11111111111111111111111111111111111111112",
+    ///     "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    ///     10_000_000,
+    ///     50,
+    ///     None
+    /// ).await?;
+    /// println!("Spends at most: {}", quote.max_input_amount);
+    /// ```
+    #[tracing::instrument(skip(self), fields(pool = tracing::field::Empty), err(Debug))]
+    pub async fn get_quote_exact_out(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        output_amount: u64,
+        slippage_bps: u16,
+        pool_address: Option<&str>,
+    ) -> OrcaResult<QuoteResult> {
+        self.with_timeout("get quote", async {
+            validate_swap_inputs(input_mint, output_mint, output_amount, slippage_bps)?;
+            let selection = match pool_address {
+                Some(address) => PoolSelection::Address(address.to_string()),
+                None => PoolSelection::default(),
+            };
+            let pool_info = self
+                .resolve_pool(input_mint, output_mint, &selection)
+                .await?;
+            tracing::Span::current().record("pool", pool_info.address.as_str());
+            self.calculate_quote_from_pool_state(
+                &pool_info,
+                input_mint,
+                output_mint,
+                output_amount,
+                false,
+                slippage_bps,
+            )
+            .await
+        })
+        .await
+    }
+
+    /// Quotes a swap by walking the pool's initialized ticks
+    ///
+    /// `amount` is the exact input when `amount_specified_is_input` is set and the exact output
+    /// otherwise; `slippage_bps` lowers the minimum output (rounded down) or raises the maximum
+    /// input (rounded up) accordingly.
+    /// Token-2022 transfer fees are taken into account: input amounts include the fee the wallet
+    /// pays to transfer into the pool and output amounts exclude the fee withheld on the way out.
+    pub(crate) async fn calculate_quote_from_pool_state(
         &self,
         pool: &PoolInfo,
         input_mint: &str,
         output_mint: &str,
-        input_amount: u64,
-        slippage: f64,
+        amount: u64,
+        amount_specified_is_input: bool,
+        slippage_bps: u16,
     ) -> OrcaResult<QuoteResult> {
-        let is_input_a = input_mint == pool.token_mint_a;
-        let sqrt_price = pool.sqrt_price as f64;
-        let scale_factor = 2f64.powi(64);
-        let price = (sqrt_price * sqrt_price) / scale_factor;
-        let output_amount = if is_input_a {
-            (input_amount as f64 * price) as u64
+        let a_to_b = input_mint == pool.token_mint_a;
+        let trades_pair = if a_to_b {
+            output_mint == pool.token_mint_b
         } else {
-            (input_amount as f64 / price) as u64
+            input_mint == pool.token_mint_b && output_mint == pool.token_mint_a
         };
-        let fee_amount = (input_amount as f64
-            * (pool.trade_fee_numerator as f64 / pool.trade_fee_denominator as f64))
-            as u64;
-        let min_output_amount = (output_amount as f64 * (1.0 - slippage / 100.0)) as u64;
-        let price_impact = self
-            .calculate_price_impact(pool, input_amount, is_input_a)
+        if !trades_pair {
+            let mint = if a_to_b || input_mint == pool.token_mint_b {
+                output_mint
+            } else {
+                input_mint
+            };
+            return Err(OrcaError::InvalidInput(InvalidInput::MintNotInPool {
+                pool: pool.address.clone(),
+                mint: mint.to_string(),
+            }));
+        }
+        let mint_infos = self
+            .get_mint_infos(&[
+                parse_address("input_mint", input_mint)?,
+                parse_address("output_mint", output_mint)?,
+            ])
             .await?;
+        let (input_info, output_info) = (&mint_infos[0], &mint_infos[1]);
+        // The pool sees the input after and the output before the transfer fee
+        let pool_amount = if amount_specified_is_input {
+            input_info.amount_after_transfer_fee(amount)
+        } else {
+            output_info.amount_before_transfer_fee(amount)
+        };
+        let tick_range = self.get_swap_tick_range(pool, a_to_b).await?;
+        let simulation = simulate_swap(
+            pool,
+            &tick_range,
+            pool_amount,
+            amount_specified_is_input,
+            a_to_b,
+        );
+        if !simulation.filled {
+            return Err(OrcaError::InsufficientLiquidity {
+                pool: pool.address.clone(),
+            });
+        }
+        tracing::debug!(
+            ticks_crossed = simulation.ticks_crossed,
+            "Quoted {} -> {}",
+            simulation.amount_in,
+            simulation.amount_out
+        );
+        let (input_amount, output_amount, pool_input, pool_output) = if amount_specified_is_input {
+            (
+                amount,
+                output_info.amount_after_transfer_fee(simulation.amount_out),
+                pool_amount,
+                simulation.amount_out,
+            )
+        } else {
+            (
+                input_info.amount_before_transfer_fee(simulation.amount_in),
+                amount,
+                simulation.amount_in,
+                pool_amount,
+            )
+        };
+        let (min_output_amount, max_input_amount) = if amount_specified_is_input {
+            (
+                min_amount_with_slippage(output_amount, slippage_bps),
+                input_amount,
+            )
+        } else {
+            (
+                output_amount,
+                max_amount_with_slippage(input_amount, slippage_bps),
+            )
+        };
+        let price_impact_bps = Self::calculate_price_impact(
+            pool,
+            simulation.amount_in.saturating_sub(simulation.fee_amount),
+            simulation.amount_out,
+            a_to_b,
+        );
         Ok(QuoteResult {
             input_amount,
             output_amount,
             min_output_amount,
-            price_impact,
-            fee_amount,
+            max_input_amount,
+            price_impact_bps,
+            fee_amount: simulation.fee_amount,
+            input_transfer_fee: input_amount - pool_input,
+            output_transfer_fee: pool_output - output_amount,
+            input_decimals: input_info.decimals,
+            output_decimals: output_info.decimals,
         })
     }
 
-    async fn calculate_price_impact(
-        &self,
+    /// Price impact of a simulated swap in basis points, fees excluded
+    ///
+    /// The pool input after the fee is valued at the spot price before the swap and compared
+    /// with the simulated output, so exact-input and exact-output quotes are measured alike.
+    fn calculate_price_impact(
         pool: &PoolInfo,
-        input_amount: u64,
-        is_input_a: bool,
-    ) -> OrcaResult<f64> {
-        let liquidity = pool.liquidity as f64;
-        let impact = (input_amount as f64) / liquidity * 100.0;
-        Ok(impact.min(100.0))
+        input_after_fee: u64,
+        output_amount: u64,
+        a_to_b: bool,
+    ) -> u16 {
+        // Raw price of token A in token B base units
+        let spot_price = sqrt_price_x64_to_price(pool.sqrt_price, 0, 0);
+        let spot_output = if a_to_b {
+            input_after_fee as f64 * spot_price
+        } else {
+            input_after_fee as f64 / spot_price
+        };
+        price_impact_bps(spot_output, output_amount)
+    }
+
+    /// Derives the address of the Whirlpool of a pair and tick spacing in the main
+    /// WhirlpoolsConfig
+    ///
+    /// The mints may be given in either order; the pool's token A is the lower of the two.
+    ///
+    /// # Example
+    /// ```rust
+    /// use solana_sdk::pubkey;
+    ///
+    /// let sol = pubkey!("So11111111111111111111111111111111111111112");
+    /// let usdc = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+    /// let address = client.get_whirlpool_address(&sol, &usdc, 64);
+    /// ```
+    pub fn get_whirlpool_address(
+        &self,
+        mint_x: &Pubkey,
+        mint_y: &Pubkey,
+        tick_spacing: u16,
+    ) -> Pubkey {
+        self.get_whirlpool_address_with_config(
+            &Pubkey::from_str_const(ORCA_WHIRLPOOLS_CONFIG),
+            mint_x,
+            mint_y,
+            tick_spacing,
+        )
     }
 
+    /// Derives the address of the Whirlpool of a pair and tick spacing in `whirlpools_config`
+    pub fn get_whirlpool_address_with_config(
+        &self,
+        whirlpools_config: &Pubkey,
+        mint_x: &Pubkey,
+        mint_y: &Pubkey,
+        tick_spacing: u16,
+    ) -> Pubkey {
+        let (mint_a, mint_b) = if mint_x < mint_y {
+            (mint_x, mint_y)
+        } else {
+            (mint_y, mint_x)
+        };
+        let (address, _) = Pubkey::find_program_address(
+            &[
+                b"whirlpool",
+                whirlpools_config.as_ref(),
+                mint_a.as_ref(),
+                mint_b.as_ref(),
+                &tick_spacing.to_le_bytes(),
+            ],
+            &self.whirlpool_program_id,
+        );
+        address
+    }
+
+    /// Fetches the Whirlpool of a pair and tick spacing without scanning the program
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool = client
+    ///     .get_pool_for_pair(
+    ///         "So11111111111111111111111111111111111111112",
+    ///         "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    ///         64,
+    ///     )
+    ///     .await?;
+    /// println!("Pool {} liquidity {}", pool.address, pool.liquidity);
+    /// ```
+    pub async fn get_pool_for_pair(
+        &self,
+        mint_x: &str,
+        mint_y: &str,
+        tick_spacing: u16,
+    ) -> OrcaResult<PoolInfo> {
+        let address = self.get_whirlpool_address(
+            &parse_address("mint_x", mint_x)?,
+            &parse_address("mint_y", mint_y)?,
+            tick_spacing,
+        );
+        self.get_pool_state_onchain(&address.to_string()).await
+    }
+
+    /// Fetches the Whirlpools of a pair in every standard fee tier with one RPC call
+    ///
+    /// Addresses are derived for each tick spacing in `WHIRLPOOL_TICK_SPACINGS`; tiers without
+    /// a pool are skipped. Pools of other WhirlpoolsConfigs are not found this way.
+    ///
+    /// # Example
+    /// ```rust
+    /// let pools = client
+    ///     .get_pools_for_pair(
+    ///         "So11111111111111111111111111111111111111112",
+    ///         "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    ///     )
+    ///     .await?;
+    /// for pool in &pools {
+    ///     println!("tick spacing {}: {}", pool.tick_spacing, pool.address);
+    /// }
+    /// ```
+    pub async fn get_pools_for_pair(
+        &self,
+        mint_x: &str,
+        mint_y: &str,
+    ) -> OrcaResult<Vec<PoolInfo>> {
+        let mint_x = parse_address("mint_x", mint_x)?;
+        let mint_y = parse_address("mint_y", mint_y)?;
+        let addresses: Vec<String> = WHIRLPOOL_TICK_SPACINGS
+            .iter()
+            .map(|tick_spacing| {
+                self.get_whirlpool_address(&mint_x, &mint_y, *tick_spacing)
+                    .to_string()
+            })
+            .collect();
+        self.get_pool_states(&addresses).await
+    }
+
+    /// Price of `base_mint` in the pool's other token, adjusted for both mints' decimals
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool = client.get_pool_state_onchain("whirlpool_sol_usdc_address").await?;
+    /// // USDC per SOL, e.g. 150.0
+    /// let price = client.derive_price_from_pool_state(&pool, &pool.token_mint_a).await?;
+    /// ```
     pub async fn derive_price_from_pool_state(
         &self,
         pool: &PoolInfo,
         base_mint: &str,
     ) -> OrcaResult<f64> {
-        let sqrt_price = pool.sqrt_price as f64;
-        let scale_factor = 2f64.powi(64);
-        let price = (sqrt_price * sqrt_price) / scale_factor;
+        let (decimals_a, decimals_b) = self.get_pool_decimals(pool).await?;
+        let price = sqrt_price_x64_to_price(pool.sqrt_price, decimals_a, decimals_b);
         if base_mint == pool.token_mint_a {
             Ok(price)
         } else {
@@ -400,36 +693,83 @@ impl OrcaClient {
 
     /// Gets all pools containing a specific token from on-chain data
     pub async fn get_pools_by_token_onchain(&self, token_mint: &str) -> OrcaResult<Vec<String>> {
-        let client = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?;
-        let token_pubkey = Pubkey::from_str(token_mint)
-            .map_err(|e| OrcaError::Error(format!("Invalid token mint: {}", e)))?;
-        let accounts = client
-            .get_program_accounts(&self.whirlpool_program_id)
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get program accounts: {}", e)))?;
-        let mut pool_addresses = Vec::new();
-        for (pubkey, account) in accounts {
-            if account.data.len() < crate::global::WHIRLPOOL_TOKEN_MINT_B_OFFSET + 32 {
-                continue;
-            }
-            let mint_a_bytes: [u8; 32] = account.data[crate::global::WHIRLPOOL_TOKEN_MINT_A_OFFSET
-                ..crate::global::WHIRLPOOL_TOKEN_MINT_A_OFFSET + 32]
-                .try_into()
-                .map_err(|_| OrcaError::Error("Failed to convert mint A bytes".to_string()))?;
-            let mint_b_bytes: [u8; 32] = account.data[crate::global::WHIRLPOOL_TOKEN_MINT_B_OFFSET
-                ..crate::global::WHIRLPOOL_TOKEN_MINT_B_OFFSET + 32]
-                .try_into()
-                .map_err(|_| OrcaError::Error("Failed to convert mint B bytes".to_string()))?;
-            let mint_a = Pubkey::new_from_array(mint_a_bytes);
-            let mint_b = Pubkey::new_from_array(mint_b_bytes);
-            if mint_a == token_pubkey || mint_b == token_pubkey {
-                pool_addresses.push(pubkey.to_string());
-            }
+        self.with_timeout("get pools by token", async {
+            let token_pubkey = parse_address("token_mint", token_mint)?;
+            self.scan_pools_by_token(&token_pubkey).await
+        })
+        .await
+    }
+}
+
+impl PoolInfo {
+    /// Returns the tick index corresponding to the pool's current sqrt price
+    pub fn current_tick_index(&self) -> i32 {
+        if self.sqrt_price == 0 {
+            return 0;
         }
-        Ok(pool_addresses)
+        tick_index_at_sqrt_price_x64(self.sqrt_price)
+    }
+}
+
+fn invalid_pool_data(pool: &str, field: &str) -> OrcaError {
+    OrcaError::InvalidPoolData {
+        pool: pool.to_string(),
+        reason: format!("failed to parse {}", field),
+    }
+}
+
+pub(crate) fn decode_whirlpool(data: &[u8], pool_address: &str) -> Option<PoolInfo> {
+    let mut reader = AccountReader::new(data, &WHIRLPOOL_DISCRIMINATOR)?;
+    let whirlpools_config = reader.pubkey()?;
+    // whirlpool_bump
+    reader.skip(1)?;
+    let tick_spacing = reader.u16()?;
+    // fee_tier_index_seed
+    reader.skip(2)?;
+    let fee_rate = reader.u16()?;
+    let protocol_fee_rate = reader.u16()?;
+    let liquidity = reader.u128()?;
+    let sqrt_price = reader.u128()?;
+    let tick_current_index = reader.i32()?;
+    let protocol_fee_owed_a = reader.u64()?;
+    let protocol_fee_owed_b = reader.u64()?;
+    let token_mint_a = reader.pubkey()?;
+    let token_vault_a = reader.pubkey()?;
+    let fee_growth_global_a = reader.u128()?;
+    let token_mint_b = reader.pubkey()?;
+    let token_vault_b = reader.pubkey()?;
+    let fee_growth_global_b = reader.u128()?;
+    let reward_last_updated_timestamp = reader.u64()?;
+    let mut reward_infos: [WhirlpoolRewardInfo; WHIRLPOOL_NUM_REWARDS] = Default::default();
+    for reward_info in &mut reward_infos {
+        *reward_info = WhirlpoolRewardInfo {
+            mint: reader.pubkey()?,
+            vault: reader.pubkey()?,
+            authority: reader.pubkey()?,
+            emissions_per_second_x64: reader.u128()?,
+            growth_global_x64: reader.u128()?,
+        };
     }
+    Some(PoolInfo {
+        address: pool_address.to_string(),
+        whirlpools_config: whirlpools_config.to_string(),
+        token_mint_a: token_mint_a.to_string(),
+        token_mint_b: token_mint_b.to_string(),
+        token_vault_a: token_vault_a.to_string(),
+        token_vault_b: token_vault_b.to_string(),
+        fee_rate,
+        protocol_fee_rate,
+        trade_fee_numerator: fee_rate as u64,
+        trade_fee_denominator: FEE_RATE_DENOMINATOR,
+        tick_spacing,
+        tick_current_index,
+        liquidity,
+        sqrt_price,
+        protocol_fee_owed_a,
+        protocol_fee_owed_b,
+        fee_growth_global_a,
+        fee_growth_global_b,
+        reward_last_updated_timestamp,
+        reward_infos,
+    })
 }
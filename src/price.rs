@@ -1,15 +1,45 @@
-use std::time::Duration;
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::Stream;
+use serde::Serialize;
+use tokio::sync::mpsc;
 
 use super::*;
-use crate::{monitoring::PriceData, types::OrcaResult};
+use crate::{
+    events::{SwapEvent, SwapEventStream},
+    global::{SOL_MINT, USDC_MINT},
+    math::sqrt_price_x64_to_price,
+    monitoring::PriceData,
+    resolver::PoolSelection,
+    types::{InvalidInput, OrcaResult},
+    validation::{parse_address, validate_distinct_mints},
+};
 use base64::{Engine, prelude::BASE64_STANDARD};
 use solana_transaction_status::{
-    EncodedTransaction, UiInstruction, UiMessage, UiParsedInstruction, UiTransactionEncoding,
+    EncodedTransaction, UiInstruction, UiMessage, UiParsedInstruction,
 };
 
+// Most recent pool transactions read for a time-bounded price history
+const MAX_PRICE_HISTORY_TRANSACTIONS: usize = 10_000;
+// Transactions kept per pool in the price history cache
+const MAX_CACHED_HISTORY_ENTRIES: usize = 50_000;
+// Most recent pool transactions read for a VWAP
+const MAX_VWAP_TRANSACTIONS: usize = 5_000;
+// Pool transactions read to build klines
+const MAX_KLINE_TRANSACTIONS: usize = 10_000;
+// Candle updates buffered before a kline stream waits for the consumer
+const KLINE_UPDATE_BUFFER: usize = 1_024;
+
 impl OrcaClient {
     /// Get token price from a liquidity pool
     ///
+    /// The price is in whole quote tokens per whole base token, adjusted for both mints'
+    /// decimals.
+    ///
     /// # Arguments
     /// base_mint - Base token mint address
     /// quote_mint - Quote token mint address
@@ -27,26 +57,131 @@ impl OrcaClient {
         base_mint: &str,
         quote_mint: &str,
     ) -> OrcaResult<f64> {
-        let pools = self.get_pools_by_token_onchain(base_mint).await?;
-        for pool_address in pools {
-            if let Ok(pool_info) = self.get_pool_state_onchain(&pool_address).await {
-                if (pool_info.token_mint_a == base_mint && pool_info.token_mint_b == quote_mint)
-                    || (pool_info.token_mint_a == quote_mint && pool_info.token_mint_b == base_mint)
-                {
-                    return self
-                        .derive_price_from_pool_state(&pool_info, base_mint)
-                        .await;
-                }
-            }
-        }
-        Err(OrcaError::Error("No pool found for token pair".to_string()))
+        self.with_timeout("get token price", async {
+            parse_address("base_mint", base_mint)?;
+            parse_address("quote_mint", quote_mint)?;
+            validate_distinct_mints(base_mint, quote_mint)?;
+            let pool_info = self
+                .resolve_pool(base_mint, quote_mint, &PoolSelection::default())
+                .await?;
+            self.derive_price_from_pool_state(&pool_info, base_mint)
+                .await
+        })
+        .await
+    }
+
+    /// Get a token's price in USD
+    ///
+    /// The price is read from the token's deepest USDC pool or, when it has none, from its
+    /// deepest SOL pool combined with the deepest SOL/USDC pool. USDC counts as one dollar.
+    ///
+    /// # Example
+    /// ```rust
+    /// let price = client
+    ///     .get_token_price_usd("orcaEKTdK7LKz57vaAYr9QeNsVEPfiu6QeMU1kektZE")
+    ///     .await?;
+    /// println!("ORCA: ${:.4}", price);
+    /// ```
+    pub async fn get_token_price_usd(&self, mint: &str) -> OrcaResult<f64> {
+        self.get_token_prices_usd(&[mint.to_string()])
+            .await?
+            .remove(mint)
+            .ok_or_else(|| OrcaError::PriceUnavailable(mint.to_string()))
+    }
+
+    /// Get the USD prices of several tokens, keyed by mint
+    ///
+    /// Mint decimals and the SOL/USDC price are fetched once for the whole batch. Tokens
+    /// without a USDC or SOL pool are left out of the result.
+    ///
+    /// # Example
+    /// ```rust
+    /// let prices = client.get_token_prices_usd(&mints).await?;
+    /// for (mint, price) in &prices {
+    ///     println!("{}: ${}", mint, price);
+    /// }
+    /// ```
+    pub async fn get_token_prices_usd(&self, mints: &[String]) -> OrcaResult<HashMap<String, f64>> {
+        self.with_timeout("get token prices usd", async {
+            let mut pubkeys = mints
+                .iter()
+                .map(|mint| parse_address("mint", mint))
+                .collect::<OrcaResult<Vec<_>>>()?;
+            pubkeys.extend([
+                Pubkey::from_str_const(SOL_MINT),
+                Pubkey::from_str_const(USDC_MINT),
+            ]);
+            pubkeys.sort();
+            pubkeys.dedup();
+            let decimals: HashMap<String, u8> = self
+                .get_mint_infos(&pubkeys)
+                .await?
+                .into_iter()
+                .map(|info| (info.mint.to_string(), info.decimals))
+                .collect();
+            let sol_usd = self
+                .deepest_pool_price(SOL_MINT, USDC_MINT, &decimals)
+                .await?;
+            let decimals = &decimals;
+            let prices = futures::future::try_join_all(mints.iter().map(|mint| async move {
+                let price = match mint.as_str() {
+                    USDC_MINT => Some(1.0),
+                    SOL_MINT => sol_usd,
+                    _ => match self.deepest_pool_price(mint, USDC_MINT, decimals).await? {
+                        Some(price) => Some(price),
+                        None => self
+                            .deepest_pool_price(mint, SOL_MINT, decimals)
+                            .await?
+                            .zip(sol_usd)
+                            .map(|(price, sol_usd)| price * sol_usd),
+                    },
+                };
+                Ok::<_, OrcaError>(price.map(|price| (mint.clone(), price)))
+            }))
+            .await?;
+            Ok(prices.into_iter().flatten().collect())
+        })
+        .await
+    }
+
+    /// Decimal-adjusted price of `base_mint` in `quote_mint` from the pair's deepest pool
+    async fn deepest_pool_price(
+        &self,
+        base_mint: &str,
+        quote_mint: &str,
+        decimals: &HashMap<String, u8>,
+    ) -> OrcaResult<Option<f64>> {
+        let addresses = self.find_pools_for_pair(base_mint, quote_mint).await?;
+        let Some(pool) = self
+            .get_pool_states(&addresses)
+            .await?
+            .into_iter()
+            .filter(|pool| pool.liquidity > 0)
+            .max_by_key(|pool| pool.liquidity)
+        else {
+            return Ok(None);
+        };
+        let price = sqrt_price_x64_to_price(
+            pool.sqrt_price,
+            decimals[&pool.token_mint_a],
+            decimals[&pool.token_mint_b],
+        );
+        Ok(Some(if base_mint == pool.token_mint_a {
+            price
+        } else {
+            1.0 / price
+        }))
     }
 
     /// Get price history from on-chain transactions
     ///
+    /// Signatures are paged through beyond the 1000 returned per RPC call, so `limit` may
+    /// exceed that. Parsed transactions are cached per pool, so later calls only fetch
+    /// transactions newer than the cached ones.
+    ///
     /// # Arguments
     /// pool_address - Pool address to get history for
-    /// limit - Maximum number of price points to return
+    /// limit - Maximum number of successful transactions to examine
     ///
     /// # Example
     /// ```rust
@@ -63,42 +198,163 @@ impl OrcaClient {
         pool_address: &str,
         limit: usize,
     ) -> OrcaResult<Vec<PriceData>> {
-        let client = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?;
-        let pool_pubkey = Pubkey::from_str(pool_address)
-            .map_err(|e| OrcaError::Error(format!("Invalid pool address: {}", e)))?;
+        self.with_timeout("get price history", async {
+            self.price_history(pool_address, None, limit).await
+        })
+        .await
+    }
+
+    /// Get price history of all transactions since a point in time
+    ///
+    /// Signatures are paged through until `since`, up to the 10000 most recent transactions,
+    /// so hours or days of history can be fetched from active pools. Shares the per-pool
+    /// cache of `get_price_history_from_chain`.
+    ///
+    /// # Arguments
+    /// pool_address - Pool address to get history for
+    /// since - Oldest block time to include
+    ///
+    /// # Example
+    /// ```rust
+    /// let since = chrono::Utc::now() - chrono::Duration::hours(6);
+    /// let price_history = client
+    ///     .get_price_history_since("whirlpool_address_here", since)
+    ///     .await?;
+    /// println!("{} price points in 6h", price_history.len());
+    /// ```
+    pub async fn get_price_history_since(
+        &self,
+        pool_address: &str,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> OrcaResult<Vec<PriceData>> {
+        self.with_timeout("get price history", async {
+            self.price_history(
+                pool_address,
+                Some(since.timestamp()),
+                MAX_PRICE_HISTORY_TRANSACTIONS,
+            )
+            .await
+        })
+        .await
+    }
+
+    /// Drops the cached price histories of all pools
+    pub fn clear_price_history_cache(&self) {
+        if let Ok(mut cache) = self.price_history_cache.lock() {
+            cache.pools.clear();
+        }
+    }
+
+    /// Price points of a pool's transactions, newest first
+    ///
+    /// A cached series deep enough for the request is topped up with the transactions newer
+    /// than its newest signature; otherwise the series is fetched again and replaces it.
+    async fn price_history(
+        &self,
+        pool_address: &str,
+        since: Option<i64>,
+        max_transactions: usize,
+    ) -> OrcaResult<Vec<PriceData>> {
+        let pool_pubkey = parse_address("pool_address", pool_address)?;
         let base_pool_info = self.get_pool_state_onchain(pool_address).await?;
         let base_liquidity = base_pool_info.liquidity;
-        let signatures = client
-            .get_signatures_for_address(&pool_pubkey)
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get signatures: {}", e)))?;
-        let mut price_history = Vec::new();
-        for sig_info in signatures.iter().take(limit) {
-            let signature = Signature::from_str(&sig_info.signature)
-                .map_err(|e| OrcaError::Error(format!("Invalid signature: {}", e)))?;
-            if let Ok(transaction) = client
-                .get_transaction(&signature, UiTransactionEncoding::Base64)
-                .await
-            {
-                if let Some(block_time) = transaction.block_time {
-                    if let Some(price) = self
-                        .extract_price_from_transaction(&transaction.transaction.transaction)
-                        .await
-                    {
-                        price_history.push(PriceData {
-                            timestamp: block_time as u64,
-                            price,
-                            liquidity: base_liquidity,
-                        });
+        let newest_cached = self.price_history_cache.lock().ok().and_then(|cache| {
+            let history = cache.pools.get(pool_address)?;
+            let covered = match since {
+                Some(since) => history.covered_since <= since,
+                None => {
+                    history.entries.len() >= max_transactions || history.covered_since == i64::MIN
+                }
+            };
+            covered.then(|| history.entries.first().map(|entry| entry.signature))?
+        });
+        let mut refreshed = false;
+        if let Some(newest) = newest_cached {
+            let listed = self
+                .get_signatures_paginated(
+                    &pool_pubkey,
+                    Some(newest),
+                    None,
+                    MAX_PRICE_HISTORY_TRANSACTIONS,
+                )
+                .await?;
+            // A full page set may leave a gap behind the cached series
+            if listed.len() < MAX_PRICE_HISTORY_TRANSACTIONS {
+                let new_entries = self.price_history_entries(&listed).await?;
+                if let Ok(mut cache) = self.price_history_cache.lock()
+                    && let Some(history) = cache.pools.get_mut(pool_address)
+                {
+                    if history.entries.first().map(|entry| entry.signature) == Some(newest) {
+                        history.prepend(new_entries);
                     }
+                    refreshed = true;
                 }
             }
         }
-        Ok(price_history)
+        if !refreshed {
+            let listed = self
+                .get_signatures_paginated(&pool_pubkey, None, since, max_transactions)
+                .await?;
+            let covered_since = match listed.last() {
+                // Transactions sharing the oldest block time may have been cut off
+                Some((_, oldest)) if listed.len() >= max_transactions => oldest + 1,
+                _ => since.unwrap_or(i64::MIN),
+            };
+            let entries = self.price_history_entries(&listed).await?;
+            let mut history = PoolPriceHistory {
+                entries: Vec::new(),
+                covered_since,
+            };
+            history.prepend(entries);
+            if let Ok(mut cache) = self.price_history_cache.lock() {
+                cache.pools.insert(pool_address.to_string(), history);
+            }
+        }
+        let cache = self
+            .price_history_cache
+            .lock()
+            .map_err(|_| OrcaError::Error("Price history cache lock poisoned".to_string()))?;
+        let Some(history) = cache.pools.get(pool_address) else {
+            return Ok(Vec::new());
+        };
+        Ok(history
+            .entries
+            .iter()
+            .take_while(|entry| since.is_none_or(|since| entry.block_time >= since))
+            .take(max_transactions)
+            .filter_map(|entry| {
+                Some(PriceData {
+                    timestamp: entry.block_time as u64,
+                    price: entry.price?,
+                    liquidity: base_liquidity,
+                })
+            })
+            .collect())
+    }
+
+    /// Fetches and parses the listed transactions, keeping their order
+    async fn price_history_entries(
+        &self,
+        listed: &[(Signature, i64)],
+    ) -> OrcaResult<Vec<PriceHistoryEntry>> {
+        let signatures: Vec<Signature> = listed.iter().map(|(signature, _)| *signature).collect();
+        let transactions = self.fetch_transactions(&signatures).await?;
+        let mut entries = Vec::with_capacity(listed.len());
+        for ((signature, block_time), transaction) in listed.iter().zip(transactions) {
+            let price = match transaction {
+                Some(transaction) => {
+                    self.extract_price_from_transaction(&transaction.transaction.transaction)
+                        .await
+                }
+                None => None,
+            };
+            entries.push(PriceHistoryEntry {
+                signature: *signature,
+                block_time: *block_time,
+                price,
+            });
+        }
+        Ok(entries)
     }
 
     async fn extract_price_from_transaction(
@@ -109,7 +365,21 @@ impl OrcaClient {
             EncodedTransaction::Json(encoded_tx) => {
                 self.extract_price_from_message(&encoded_tx.message).await
             }
-            _ => None,
+            _ => {
+                let decoded = transaction.decode()?;
+                let account_keys = decoded.message.static_account_keys();
+                decoded
+                    .message
+                    .instructions()
+                    .iter()
+                    .find_map(|instruction| {
+                        let program_id = account_keys.get(instruction.program_id_index as usize)?;
+                        let (amount_in, amount_out) = self
+                            .swap_instruction_amounts(&program_id.to_string(), &instruction.data)?;
+                        (amount_in > 0 && amount_out > 0)
+                            .then(|| amount_out as f64 / amount_in as f64)
+                    })
+            }
         }
     }
 
@@ -148,20 +418,25 @@ impl OrcaClient {
         message: &solana_transaction_status::UiMessage,
     ) -> Option<f64> {
         match instruction {
+            UiInstruction::Parsed(partial @ UiParsedInstruction::PartiallyDecoded(_)) => {
+                if let Some(amounts) = self.extract_token_amounts_from_instruction(partial)
+                    && amounts.len() >= 2
+                    && amounts[0] > 0.0
+                {
+                    return Some(amounts[1] / amounts[0]);
+                }
+            }
             UiInstruction::Parsed(parsed) => {
-                if let Some(program_name) = Self::get_instruction_program(parsed) {
-                    if program_name.contains("swap")
+                if let Some(program_name) = Self::get_instruction_program(parsed)
+                    && (program_name.contains("swap")
                         || program_name.contains("orca")
                         || program_name.contains("token")
-                        || program_name.contains("amm")
-                    {
-                        if let Some(amounts) = Self::extract_token_amounts_from_instruction(parsed)
-                        {
-                            if amounts.len() >= 2 && amounts[0] > 0.0 {
-                                return Some(amounts[1] / amounts[0]);
-                            }
-                        }
-                    }
+                        || program_name.contains("amm"))
+                    && let Some(amounts) = self.extract_token_amounts_from_instruction(parsed)
+                    && amounts.len() >= 2
+                    && amounts[0] > 0.0
+                {
+                    return Some(amounts[1] / amounts[0]);
                 }
             }
             UiInstruction::Compiled(compiled) => {
@@ -181,32 +456,33 @@ impl OrcaClient {
     }
 
     fn extract_token_amounts_from_instruction(
+        &self,
         instruction: &solana_transaction_status::UiParsedInstruction,
     ) -> Option<Vec<f64>> {
         let mut amounts = Vec::new();
         match instruction {
             solana_transaction_status::UiParsedInstruction::Parsed(parsed) => {
-                if let parsed_data = &parsed.parsed {
-                    if let serde_json::Value::Object(map) = parsed_data {
-                        for (key, value) in map {
-                            if key.contains("amount")
-                                || key.contains("token")
-                                || key.contains("quantity")
-                                || key.contains("value")
-                                || key.contains("source")
-                                || key.contains("destination")
-                            {
-                                if let Some(amount) = Self::parse_amount_from_value(value) {
-                                    amounts.push(amount);
-                                }
-                            }
+                if let serde_json::Value::Object(map) = &parsed.parsed {
+                    for (key, value) in map {
+                        if (key.contains("amount")
+                            || key.contains("token")
+                            || key.contains("quantity")
+                            || key.contains("value")
+                            || key.contains("source")
+                            || key.contains("destination"))
+                            && let Some(amount) = Self::parse_amount_from_value(value)
+                        {
+                            amounts.push(amount);
                         }
                     }
                 }
             }
             solana_transaction_status::UiParsedInstruction::PartiallyDecoded(partial) => {
-                // To be realized
-                todo!();
+                let data = bs58::decode(&partial.data).into_vec().ok()?;
+                let (amount_in, amount_out) =
+                    self.swap_instruction_amounts(&partial.program_id, &data)?;
+                amounts.push(amount_in as f64);
+                amounts.push(amount_out as f64);
             }
         }
         if amounts.is_empty() {
@@ -231,12 +507,11 @@ impl OrcaClient {
     ) -> Option<f64> {
         let program_id = match message {
             solana_transaction_status::UiMessage::Parsed(parsed_msg) => {
-                if let account_keys = &parsed_msg.account_keys {
-                    if let Some(id) = account_keys.get(compiled.program_id_index as usize) {
-                        id.pubkey.clone()
-                    } else {
-                        return None;
-                    }
+                if let Some(id) = parsed_msg
+                    .account_keys
+                    .get(compiled.program_id_index as usize)
+                {
+                    id.pubkey.clone()
                 } else {
                     return None;
                 }
@@ -250,31 +525,30 @@ impl OrcaClient {
                 }
             }
         };
-        let is_swap_program = program_id == crate::global::ORCA_WHIRLPOOLS_PROGRAM_ID
-            || program_id == crate::global::ORCA_STABLE_SWAP_PROGRAM_ID
-            || program_id == crate::global::ORCA_SWAP_PROGRAM_ID_V1
-            || program_id == crate::global::ORCA_SWAP_PROGRAM_ID_V2;
-        if !is_swap_program {
-            return None;
+        // Compiled instruction data is base58 in JSON encodings; base64 is accepted as a fallback
+        let decoded = bs58::decode(&compiled.data)
+            .into_vec()
+            .or_else(|_| BASE64_STANDARD.decode(&compiled.data))
+            .ok()?;
+        let (amount_in, amount_out) = self.swap_instruction_amounts(&program_id, &decoded)?;
+        if amount_in > 0 && amount_out > 0 {
+            return Some(amount_out as f64 / amount_in as f64);
         }
-        if let data = &compiled.data {
-            if let Ok(decoded) = BASE64_STANDARD.decode(data) {
-                if decoded.len() >= 17 {
-                    let amount_in_bytes: [u8; 8] = decoded[1..9].try_into().ok()?;
-                    let amount_out_bytes: [u8; 8] = decoded[9..17].try_into().ok()?;
-                    let amount_in = u64::from_le_bytes(amount_in_bytes);
-                    let amount_out = u64::from_le_bytes(amount_out_bytes);
-
-                    if amount_in > 0 && amount_out > 0 {
-                        return Some(amount_out as f64 / amount_in as f64);
-                    }
-                }
-            }
-        }
-
         None
     }
 
+    /// Decodes swap amounts, treating the configured program IDs like the mainnet Orca programs
+    fn swap_instruction_amounts(&self, program_id: &str, data: &[u8]) -> Option<(u64, u64)> {
+        let program_id = if program_id == self.whirlpool_program_id.to_string() {
+            crate::global::ORCA_WHIRLPOOLS_PROGRAM_ID
+        } else if program_id == self.stable_swap_program_id.to_string() {
+            crate::global::ORCA_STABLE_SWAP_PROGRAM_ID
+        } else {
+            program_id
+        };
+        decode_swap_instruction_amounts(program_id, data)
+    }
+
     /// Calculate moving average price from on-chain data
     ///
     /// # Arguments
@@ -294,15 +568,94 @@ impl OrcaClient {
         pool_address: &str,
         period: usize,
     ) -> OrcaResult<f64> {
-        let prices = self
-            .get_price_history_from_chain(pool_address, period)
-            .await?;
-        if prices.is_empty() {
-            return Err(OrcaError::Error("No price data available".to_string()));
-        }
-        let sum: f64 = prices.iter().map(|p| p.price).sum();
-        let average = sum / prices.len() as f64;
-        Ok(average)
+        self.with_timeout("calculate moving average", async {
+            let prices = self
+                .get_price_history_from_chain(pool_address, period)
+                .await?;
+            if prices.is_empty() {
+                return Err(OrcaError::InsufficientHistory {
+                    pool: pool_address.to_string(),
+                    reason: "no price data available".to_string(),
+                });
+            }
+            let sum: f64 = prices.iter().map(|p| p.price).sum();
+            let average = sum / prices.len() as f64;
+            Ok(average)
+        })
+        .await
+    }
+
+    /// Calculate the time-weighted average price over a recent window
+    ///
+    /// Execution prices (token B per token A, raw units) are taken from the pool's vault
+    /// balance changes and each is weighted by how long it stood, until the next swap or now.
+    /// When no swap happened in the window the current spot price is returned.
+    ///
+    /// # Arguments
+    /// pool_address - Pool address to calculate the TWAP for
+    /// window - How far back from now to average
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// let twap = client
+    ///     .calculate_twap("whirlpool_address_here", Duration::from_secs(3600))
+    ///     .await?;
+    /// println!("1h TWAP: {}", twap);
+    /// ```
+    pub async fn calculate_twap(&self, pool_address: &str, window: Duration) -> OrcaResult<f64> {
+        self.with_timeout("calculate twap", async {
+            let pool_info = self.get_pool_state_onchain(pool_address).await?;
+            self.pool_execution_twap(&pool_info, window).await
+        })
+        .await
+    }
+
+    /// Calculate the volume-weighted average price over a recent window
+    ///
+    /// Swap amounts are read from the pool's vault balance changes in each transaction's
+    /// pre/post token balances, so every swap is weighted by the token A it actually moved.
+    /// The result is token B per token A in raw units.
+    ///
+    /// # Arguments
+    /// pool_address - Pool address to calculate the VWAP for
+    /// window - How far back from now to average
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// let vwap = client
+    ///     .calculate_vwap("whirlpool_address_here", Duration::from_secs(3600))
+    ///     .await?;
+    /// println!("1h VWAP: {}", vwap);
+    /// ```
+    pub async fn calculate_vwap(&self, pool_address: &str, window: Duration) -> OrcaResult<f64> {
+        self.with_timeout("calculate vwap", async {
+            let pool_info = self.get_pool_state_onchain(pool_address).await?;
+            let since = chrono::Utc::now().timestamp() - window.as_secs() as i64;
+            let records = self
+                .fetch_pool_transaction_records(&pool_info, since, MAX_VWAP_TRANSACTIONS)
+                .await?;
+            let (volume_a, volume_b) = records.iter().filter(|record| record.is_swap()).fold(
+                (0u128, 0u128),
+                |(volume_a, volume_b), record| {
+                    (
+                        volume_a + record.delta_a.unsigned_abs(),
+                        volume_b + record.delta_b.unsigned_abs(),
+                    )
+                },
+            );
+            if volume_a == 0 {
+                return Err(OrcaError::InsufficientHistory {
+                    pool: pool_info.address.clone(),
+                    reason: "no swaps in the window".to_string(),
+                });
+            }
+            Ok(volume_b as f64 / volume_a as f64)
+        })
+        .await
     }
 
     pub async fn get_kline_data_production(
@@ -311,39 +664,78 @@ impl OrcaClient {
         timeframe_minutes: u32,
         limit: usize,
     ) -> OrcaResult<Vec<Kline>> {
-        const MAX_RETRIES: u32 = 3;
-        if timeframe_minutes == 0 || timeframe_minutes > 1440 {
-            return Err(OrcaError::Error(
-                "Invalid timeframe: must be between 1 and 1440 minutes".to_string(),
-            ));
-        }
-        if limit > 500 {
-            return Err(OrcaError::Error(
-                "Limit too large: maximum 500 candles".to_string(),
-            ));
-        }
-        let mut retries = 0;
-        loop {
-            match self
+        self.with_timeout("get kline data", async {
+            if timeframe_minutes == 0 || timeframe_minutes > 1440 {
+                return Err(OrcaError::InvalidInput(InvalidInput::ValueOutOfRange {
+                    field: "timeframe_minutes".to_string(),
+                    value: timeframe_minutes as u64,
+                    min: 1,
+                    max: 1440,
+                }));
+            }
+            if limit > 500 {
+                return Err(OrcaError::InvalidInput(InvalidInput::ValueOutOfRange {
+                    field: "limit".to_string(),
+                    value: limit as u64,
+                    min: 0,
+                    max: 500,
+                }));
+            }
+            // Transient RPC failures are already retried per request
+            let kline_data = self
                 .try_get_kline_data(pool_address, timeframe_minutes, limit)
-                .await
-            {
-                Ok(kline_data) => {
-                    if kline_data.is_empty() {
-                        log::warn!("No kline data available for pool: {}", pool_address);
-                    }
-                    return Ok(kline_data);
-                }
-                Err(e) if retries < MAX_RETRIES => {
-                    retries += 1;
-                    let backoff_ms = 1000 * 2u64.pow(retries - 1);
-                    tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
-                }
-                Err(e) => {
-                    return Err(e);
-                }
+                .await?;
+            if kline_data.is_empty() {
+                tracing::warn!("No kline data available for pool: {}", pool_address);
             }
+            Ok(kline_data)
+        })
+        .await
+    }
+
+    /// Builds candles live from a pool's swap events
+    ///
+    /// Swaps arrive through `subscribe_swap_events` and are bucketed by arrival time. Every
+    /// swap yields a `KlineUpdate::Partial` snapshot of the open candle, and a candle is sent
+    /// as `KlineUpdate::Closed` once its timeframe ends. Timeframes without swaps produce no
+    /// candle. Prices and volumes use the same units as `get_kline_data_production`.
+    ///
+    /// # Arguments
+    /// pool_address - Pool to build candles for
+    /// timeframe - Length of a candle, at least one second
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::price::KlineUpdate;
+    /// use std::time::Duration;
+    ///
+    /// let mut candles = client
+    ///     .subscribe_klines("whirlpool_address_here", Duration::from_secs(60))
+    ///     .await?;
+    /// while let Some(update) = candles.recv().await {
+    ///     if let KlineUpdate::Closed(kline) = update {
+    ///         println!("close: {}, volume: {}", kline.close, kline.volume);
+    ///     }
+    /// }
+    /// ```
+    pub async fn subscribe_klines(
+        &self,
+        pool_address: &str,
+        timeframe: Duration,
+    ) -> OrcaResult<KlineStream> {
+        let timeframe_seconds = timeframe.as_secs();
+        if timeframe_seconds == 0 {
+            return Err(OrcaError::InvalidInput(InvalidInput::ZeroAmount(
+                "timeframe".to_string(),
+            )));
         }
+        let swaps = self.subscribe_swap_events(Some(pool_address)).await?;
+        let (update_tx, update_rx) = mpsc::channel(KLINE_UPDATE_BUFFER);
+        let task_handle = tokio::spawn(build_klines(swaps, timeframe_seconds, update_tx));
+        Ok(KlineStream {
+            update_rx,
+            task_handle,
+        })
     }
 
     async fn try_get_kline_data(
@@ -352,60 +744,263 @@ impl OrcaClient {
         timeframe_minutes: u32,
         limit: usize,
     ) -> OrcaResult<Vec<Kline>> {
-        // 获取交易历史作为价格数据源
-        let transactions_needed = limit * 5;
-        let price_history = self
-            .get_price_history_from_chain(pool_address, transactions_needed)
-            .await?;
-        if price_history.is_empty() {
+        if limit == 0 {
             return Ok(Vec::new());
         }
-        let mut sorted_history = price_history;
-        sorted_history.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
-        let timeframe_seconds = (timeframe_minutes * 60) as u64;
-        let mut klines = Vec::with_capacity(limit);
-        let mut current_timeframe_start =
-            sorted_history[0].timestamp / timeframe_seconds * timeframe_seconds;
-        let mut current_kline: Option<Kline> = None;
-        for price_data in sorted_history {
-            let timeframe_start = price_data.timestamp / timeframe_seconds * timeframe_seconds;
-            if timeframe_start != current_timeframe_start {
-                if let Some(kline) = current_kline.take() {
-                    klines.push(kline);
-                    if klines.len() >= limit {
-                        break;
-                    }
+        let pool_info = self.get_pool_state_onchain(pool_address).await?;
+        let timeframe_seconds = timeframe_minutes as i64 * 60;
+        let now = chrono::Utc::now().timestamp();
+        let since =
+            now / timeframe_seconds * timeframe_seconds - (limit as i64 - 1) * timeframe_seconds;
+        // 从池子金库余额变化中获取成交价格和成交量
+        let mut records = self
+            .fetch_pool_transaction_records(&pool_info, since, MAX_KLINE_TRANSACTIONS)
+            .await?;
+        records.sort_by_key(|record| record.timestamp);
+        let mut klines: Vec<Kline> = Vec::with_capacity(limit);
+        for record in &records {
+            let Some(price) = record.execution_price() else {
+                continue;
+            };
+            let timeframe_start = (record.timestamp / timeframe_seconds * timeframe_seconds) as u64;
+            let volume = record.delta_a.unsigned_abs() as f64;
+            let quote_volume = record.delta_b.unsigned_abs() as f64;
+            match klines.last_mut() {
+                Some(kline) if kline.timestamp == timeframe_start => {
+                    kline.high = kline.high.max(price);
+                    kline.low = kline.low.min(price);
+                    kline.close = price;
+                    kline.volume += volume;
+                    kline.quote_volume += quote_volume;
                 }
-                current_timeframe_start = timeframe_start;
-                current_kline = Some(Kline {
+                _ => klines.push(Kline {
                     timestamp: timeframe_start,
-                    open: price_data.price,
-                    high: price_data.price,
-                    low: price_data.price,
-                    close: price_data.price,
-                    volume: 1.0,
-                });
-            } else if let Some(ref mut kline) = current_kline {
-                kline.high = kline.high.max(price_data.price);
-                kline.low = kline.low.min(price_data.price);
-                kline.close = price_data.price;
-                kline.volume += 1.0;
+                    open: price,
+                    high: price,
+                    low: price,
+                    close: price,
+                    volume,
+                    quote_volume,
+                }),
             }
         }
-        if let Some(kline) = current_kline {
-            klines.push(kline);
+        if klines.len() > limit {
+            klines.drain(..klines.len() - limit);
         }
         Ok(klines)
     }
 }
 
-/// K Line data
+/// Parsed transactions of each pool's price history, reused across history calls
+#[derive(Debug, Default)]
+pub(crate) struct PriceHistoryCache {
+    pools: HashMap<String, PoolPriceHistory>,
+}
+
+#[derive(Debug)]
+struct PoolPriceHistory {
+    /// Newest first
+    entries: Vec<PriceHistoryEntry>,
+    /// Every successful transaction from this block time on is in `entries`
+    covered_since: i64,
+}
+
+impl PoolPriceHistory {
+    fn prepend(&mut self, newer: Vec<PriceHistoryEntry>) {
+        self.entries.splice(0..0, newer);
+        if self.entries.len() > MAX_CACHED_HISTORY_ENTRIES {
+            self.entries.truncate(MAX_CACHED_HISTORY_ENTRIES);
+            if let Some(oldest) = self.entries.last() {
+                self.covered_since = self.covered_since.max(oldest.block_time + 1);
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
+struct PriceHistoryEntry {
+    signature: Signature,
+    block_time: i64,
+    /// None when the transaction carries no swap
+    price: Option<f64>,
+}
+
+/// K Line data
+///
+/// Prices are execution prices as token B per token A in raw units.
+#[derive(Debug, Clone, Serialize)]
 pub struct Kline {
     pub timestamp: u64,
     pub open: f64,
     pub high: f64,
     pub low: f64,
     pub close: f64,
+    /// Swapped amount of token A, the base token (raw units)
     pub volume: f64,
+    /// Swapped amount of token B, the quote token (raw units)
+    pub quote_volume: f64,
+}
+
+/// A candle sent by `KlineStream`
+#[derive(Debug, Clone)]
+pub enum KlineUpdate {
+    /// Snapshot of the open candle after a swap
+    Partial(Kline),
+    /// A candle whose timeframe has ended
+    Closed(Kline),
+}
+
+/// Live candles delivered by `OrcaClient::subscribe_klines`
+///
+/// Dropping the stream ends the swap subscription.
+#[derive(Debug)]
+pub struct KlineStream {
+    update_rx: mpsc::Receiver<KlineUpdate>,
+    task_handle: tokio::task::JoinHandle<()>,
+}
+
+impl KlineStream {
+    /// Waits for the next candle update; None once the subscription has ended
+    pub async fn recv(&mut self) -> Option<KlineUpdate> {
+        self.update_rx.recv().await
+    }
+}
+
+impl Stream for KlineStream {
+    type Item = KlineUpdate;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<KlineUpdate>> {
+        self.update_rx.poll_recv(cx)
+    }
+}
+
+impl Drop for KlineStream {
+    fn drop(&mut self) {
+        self.task_handle.abort();
+    }
+}
+
+/// Aggregates swaps into candles until the subscription ends or the consumer goes away
+async fn build_klines(
+    mut swaps: SwapEventStream,
+    timeframe_seconds: u64,
+    update_tx: mpsc::Sender<KlineUpdate>,
+) {
+    let timeframe_millis = timeframe_seconds as i64 * 1_000;
+    let mut candle: Option<Kline> = None;
+    loop {
+        let close_in = candle.as_ref().map(|kline| {
+            let close_at = kline.timestamp as i64 * 1_000 + timeframe_millis;
+            Duration::from_millis((close_at - chrono::Utc::now().timestamp_millis()).max(0) as u64)
+        });
+        let close = async {
+            match close_in {
+                Some(close_in) => tokio::time::sleep(close_in).await,
+                None => std::future::pending().await,
+            }
+        };
+        let update = tokio::select! {
+            swap = swaps.recv() => match swap {
+                Some(swap) => {
+                    let now = chrono::Utc::now().timestamp().max(0) as u64;
+                    let timeframe_start = now / timeframe_seconds * timeframe_seconds;
+                    let previous = candle.take_if(|kline| kline.timestamp != timeframe_start);
+                    if let Some(closed) = previous
+                        && update_tx.send(KlineUpdate::Closed(closed)).await.is_err()
+                    {
+                        return;
+                    }
+                    let Some((price, volume, quote_volume)) = swap_execution(&swap) else {
+                        continue;
+                    };
+                    let kline = candle.get_or_insert(Kline {
+                        timestamp: timeframe_start,
+                        open: price,
+                        high: price,
+                        low: price,
+                        close: price,
+                        volume: 0.0,
+                        quote_volume: 0.0,
+                    });
+                    kline.high = kline.high.max(price);
+                    kline.low = kline.low.min(price);
+                    kline.close = price;
+                    kline.volume += volume;
+                    kline.quote_volume += quote_volume;
+                    KlineUpdate::Partial(kline.clone())
+                }
+                None => {
+                    if let Some(closed) = candle.take() {
+                        let _ = update_tx.send(KlineUpdate::Closed(closed)).await;
+                    }
+                    return;
+                }
+            },
+            _ = close => match candle.take() {
+                Some(closed) => KlineUpdate::Closed(closed),
+                None => continue,
+            },
+        };
+        if update_tx.send(update).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Execution price (token B per token A) and the token A and B amounts of a swap, in raw units
+fn swap_execution(swap: &SwapEvent) -> Option<(f64, f64, f64)> {
+    let (amount_a, amount_b) = if swap.a_to_b {
+        (swap.input_amount, swap.output_amount)
+    } else {
+        (swap.output_amount, swap.input_amount)
+    };
+    if amount_a == 0 || amount_b == 0 {
+        return None;
+    }
+    Some((
+        amount_b as f64 / amount_a as f64,
+        amount_a as f64,
+        amount_b as f64,
+    ))
+}
+
+/// Decodes the input and output amounts of an Orca swap instruction
+///
+/// Whirlpool `swap`/`swap_v2` carry `amount` and `other_amount_threshold`; when the amount is
+/// specified as output the pair is reordered so the result is always (input, output).
+/// Token swap (v1/v2) instructions carry `amount_in` and `minimum_amount_out`.
+/// Returns None for other programs and instructions.
+pub(crate) fn decode_swap_instruction_amounts(program_id: &str, data: &[u8]) -> Option<(u64, u64)> {
+    let read_u64 = |offset: usize| -> Option<u64> {
+        Some(u64::from_le_bytes(
+            data.get(offset..offset + 8)?.try_into().ok()?,
+        ))
+    };
+    if program_id == crate::global::ORCA_WHIRLPOOLS_PROGRAM_ID {
+        let discriminator = data.get(..8)?;
+        if discriminator != crate::global::WHIRLPOOL_SWAP_DISCRIMINATOR
+            && discriminator != crate::global::WHIRLPOOL_SWAP_V2_DISCRIMINATOR
+        {
+            return None;
+        }
+        // amount (8) + other_amount_threshold (8) + sqrt_price_limit (16)
+        let amount = read_u64(8)?;
+        let other_amount_threshold = read_u64(16)?;
+        let amount_specified_is_input = *data.get(40)? != 0;
+        return if amount_specified_is_input {
+            Some((amount, other_amount_threshold))
+        } else {
+            Some((other_amount_threshold, amount))
+        };
+    }
+    if program_id == crate::global::ORCA_SWAP_PROGRAM_ID_V1
+        || program_id == crate::global::ORCA_SWAP_PROGRAM_ID_V2
+        || program_id == crate::global::ORCA_STABLE_SWAP_PROGRAM_ID
+    {
+        if *data.first()? != crate::global::TOKEN_SWAP_SWAP_TAG {
+            return None;
+        }
+        return Some((read_u64(1)?, read_u64(9)?));
+    }
+    None
 }
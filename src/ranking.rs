@@ -0,0 +1,243 @@
+use futures::stream::{self, StreamExt};
+
+use super::*;
+use crate::{
+    math::to_ui_amount, monitoring::HealthScoreConfig, pool::PoolInfo, types::OrcaResult,
+    validation::parse_address,
+};
+
+/// Weights and limits for ranking the pools of a token
+///
+/// Each metric is divided by the best value among the compared pools, so it scores 0 to 1,
+/// and the score is the weighted average of those. Set one weight to sort by that metric only.
+#[derive(Debug, Clone)]
+pub struct PoolRankCriteria {
+    pub tvl_weight: f64,
+    pub volume_weight: f64,
+    pub fee_apr_weight: f64,
+    pub health_weight: f64,
+    /// Pools below this TVL are left out
+    pub min_tvl_usd: f64,
+    /// Only this many pools with the highest TVL are measured and compared
+    pub max_pools: usize,
+    /// Scoring of the health metric
+    pub health: HealthScoreConfig,
+}
+
+impl Default for PoolRankCriteria {
+    fn default() -> Self {
+        Self {
+            tvl_weight: 0.25,
+            volume_weight: 0.25,
+            fee_apr_weight: 0.25,
+            health_weight: 0.25,
+            min_tvl_usd: 0.0,
+            max_pools: 10,
+            health: HealthScoreConfig::default(),
+        }
+    }
+}
+
+/// One row of a pool comparison table
+#[derive(Debug, Clone)]
+pub struct PoolComparison {
+    pub pool_address: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    /// Fee tier, in hundredths of a basis point
+    pub fee_rate: u16,
+    pub tick_spacing: u16,
+    pub tvl_usd: f64,
+    pub volume_24h_usd: f64,
+    /// Fee APR in percent
+    pub fee_apr: f64,
+    /// Health score, 0 to 100
+    pub health_score: f64,
+    /// Weighted score of the row, 0 to 1
+    pub score: f64,
+}
+
+impl OrcaClient {
+    /// Compares the pools containing a token by TVL, 24h volume, fee APR and health
+    ///
+    /// The TVL of every pool is read with batched vault fetches and one price lookup. Pools
+    /// below `min_tvl_usd` or without USD prices are dropped, and only the `max_pools` with
+    /// the highest TVL get their volume, APR and health measured. Volume and APR come from
+    /// fee growth snapshots as in `estimate_pool_yield`; each call records one, and pools
+    /// without an earlier snapshot are left out. Rows are sorted by score, best first.
+    ///
+    /// # Params
+    /// token_mint - Token whose pools are compared
+    /// criteria - Weights and limits of the ranking
+    ///
+    /// # Example
+    /// ```rust
+    /// let criteria = PoolRankCriteria {
+    ///     min_tvl_usd: 10_000.0,
+    ///     ..Default::default()
+    /// };
+    /// for row in client.rank_pools(SOL_MINT, &criteria).await? {
+    ///     println!(
+    ///         "{} fee {}: TVL ${:.0}, volume ${:.0}, APR {:.1}%, health {:.0}, score {:.2}",
+    ///         row.pool_address, row.fee_rate, row.tvl_usd, row.volume_24h_usd,
+    ///         row.fee_apr, row.health_score, row.score
+    ///     );
+    /// }
+    /// ```
+    #[tracing::instrument(skip(self, criteria), err(Debug))]
+    pub async fn rank_pools(
+        &self,
+        token_mint: &str,
+        criteria: &PoolRankCriteria,
+    ) -> OrcaResult<Vec<PoolComparison>> {
+        self.with_timeout("rank pools", async {
+            let pools = self.get_pool_states_by_token(token_mint).await?;
+            let mut candidates: Vec<(PoolInfo, f64)> = self
+                .pool_tvls_usd(&pools)
+                .await?
+                .into_iter()
+                .zip(pools)
+                .filter_map(|(tvl_usd, pool)| Some((pool, tvl_usd?)))
+                .filter(|(_, tvl_usd)| *tvl_usd > 0.0 && *tvl_usd >= criteria.min_tvl_usd)
+                .collect();
+            candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+            candidates.truncate(criteria.max_pools);
+            // Built up front so the returned future stays `Send`
+            let measurements: Vec<_> = candidates
+                .into_iter()
+                .map(|(pool, tvl_usd)| self.measure_pool(pool, tvl_usd, criteria))
+                .collect();
+            let mut rows: Vec<PoolComparison> = stream::iter(measurements)
+                .buffered(self.fetch_concurrency())
+                .collect::<Vec<_>>()
+                .await
+                .into_iter()
+                .filter_map(Result::transpose)
+                .collect::<OrcaResult<_>>()?;
+            score_pools(&mut rows, criteria);
+            rows.sort_by(|a, b| b.score.total_cmp(&a.score));
+            Ok(rows)
+        })
+        .await
+    }
+
+    /// Volume, fee APR and health of one ranked pool; None when its yield cannot be estimated
+    async fn measure_pool(
+        &self,
+        pool: PoolInfo,
+        tvl_usd: f64,
+        criteria: &PoolRankCriteria,
+    ) -> OrcaResult<Option<PoolComparison>> {
+        let (pool_yield, health) = tokio::join!(
+            self.estimate_pool_yield(&pool.address),
+            self.monitor_pool_health_with_config(&pool.address, &criteria.health)
+        );
+        let pool_yield = match pool_yield {
+            Ok(pool_yield) => pool_yield,
+            Err(e) => {
+                tracing::warn!(
+                    pool = %pool.address,
+                    error = ?e,
+                    "Failed to estimate pool yield"
+                );
+                return Ok(None);
+            }
+        };
+        let health_score = health
+            .map(|health| health.health_score)
+            .inspect_err(|e| {
+                tracing::warn!(
+                    pool = %pool.address,
+                    error = ?e,
+                    "Failed to score pool health"
+                )
+            })
+            .unwrap_or(0.0);
+        Ok(Some(PoolComparison {
+            pool_address: pool_yield.pool_address,
+            token_mint_a: parse_address("token_mint_a", &pool.token_mint_a)?,
+            token_mint_b: parse_address("token_mint_b", &pool.token_mint_b)?,
+            fee_rate: pool.fee_rate,
+            tick_spacing: pool.tick_spacing,
+            tvl_usd,
+            volume_24h_usd: pool_yield.volume_24h_usd,
+            fee_apr: pool_yield.fee_apr,
+            health_score,
+            score: 0.0,
+        }))
+    }
+
+    /// USD value of the vaults of each pool; None when a token of the pool has no price
+    async fn pool_tvls_usd(&self, pools: &[PoolInfo]) -> OrcaResult<Vec<Option<f64>>> {
+        let mut vaults = Vec::with_capacity(pools.len() * 2);
+        let mut mints = Vec::with_capacity(pools.len() * 2);
+        for pool in pools {
+            vaults.push(parse_address("token_vault_a", &pool.token_vault_a)?);
+            vaults.push(parse_address("token_vault_b", &pool.token_vault_b)?);
+            mints.push(parse_address("token_mint_a", &pool.token_mint_a)?);
+            mints.push(parse_address("token_mint_b", &pool.token_mint_b)?);
+        }
+        let accounts = self
+            .get_multiple_accounts_batched(&vaults, "Failed to get pool vaults")
+            .await?;
+        let mut unique_mints = mints.clone();
+        unique_mints.sort();
+        unique_mints.dedup();
+        let decimals: HashMap<Pubkey, u8> = unique_mints
+            .iter()
+            .copied()
+            .zip(self.get_mint_decimals(&unique_mints).await?)
+            .collect();
+        let prices = self
+            .get_token_prices_usd(
+                &unique_mints
+                    .iter()
+                    .map(Pubkey::to_string)
+                    .collect::<Vec<_>>(),
+            )
+            .await?;
+        Ok(accounts
+            .chunks(2)
+            .zip(mints.chunks(2))
+            .map(|(accounts, mints)| {
+                let mut tvl_usd = 0.0;
+                for (account, mint) in accounts.iter().zip(mints) {
+                    let amount = Self::unpack_token_account(&account.as_ref()?.data)
+                        .ok()?
+                        .amount;
+                    let price = prices.get(&mint.to_string())?;
+                    tvl_usd += to_ui_amount(amount, *decimals.get(mint)?) * price;
+                }
+                Some(tvl_usd)
+            })
+            .collect())
+    }
+}
+
+/// Reads one metric of a comparison row
+type PoolMetric = fn(&PoolComparison) -> f64;
+
+/// Scores each row against the best value of every metric among the rows
+fn score_pools(rows: &mut [PoolComparison], criteria: &PoolRankCriteria) {
+    let best = |metric: PoolMetric| rows.iter().map(metric).fold(0.0, f64::max);
+    let metrics: [(PoolMetric, f64); 4] = [
+        (|row| row.tvl_usd, criteria.tvl_weight),
+        (|row| row.volume_24h_usd, criteria.volume_weight),
+        (|row| row.fee_apr, criteria.fee_apr_weight),
+        (|row| row.health_score, criteria.health_weight),
+    ];
+    let bests: Vec<f64> = metrics.iter().map(|(metric, _)| best(*metric)).collect();
+    let total_weight: f64 = metrics.iter().map(|(_, weight)| weight.max(0.0)).sum();
+    if total_weight <= 0.0 {
+        return;
+    }
+    for row in rows.iter_mut() {
+        row.score = metrics
+            .iter()
+            .zip(&bests)
+            .filter(|(_, best)| **best > 0.0)
+            .map(|((metric, weight), best)| metric(row).max(0.0) / best * weight.max(0.0))
+            .sum::<f64>()
+            / total_weight;
+    }
+}
@@ -0,0 +1,186 @@
+use super::*;
+use crate::{
+    audit::AuditDetails,
+    liquidity::LiquidityPosition,
+    packer::{PackedSubmission, TransactionPacker},
+    receipt::RebalanceReceipt,
+    rent::{
+        MINT_ACCOUNT_SIZE, POSITION_ACCOUNT_SIZE, RentPlan, TICK_ARRAY_ACCOUNT_SIZE,
+        TOKEN_ACCOUNT_SIZE,
+    },
+    types::{InvalidInput, OrcaResult},
+    validation::{parse_address, validate_amount, validate_tick_range},
+    zap::{ZapConfig, ZapTarget, withdrawal_minimums},
+};
+
+impl OrcaClient {
+    /// Moves a position's liquidity to a new tick range
+    ///
+    /// Withdraws the position, claims its fees and rewards and closes it, then swaps to the
+    /// ratio the new range needs at the post-swap price and opens a fresh position with the
+    /// proceeds. The deposit is sized from the withdrawal minimums plus the fees owed recorded
+    /// on the position, so whatever the withdrawal returns beyond them stays in the wallet.
+    ///
+    /// The steps are packed into as few transactions as fit. In Jito mode they form one atomic
+    /// bundle; otherwise they are sent in order, and when a later transaction fails the
+    /// withdrawn tokens remain in the owner's wallet.
+    ///
+    /// # Example
+    /// ```rust
+    /// let position = client.get_position(&keypair.pubkey(), &position_mint).await?;
+    /// let receipt = client
+    ///     .rebalance_position(&keypair, &position, -512, 512, None)
+    ///     .await?;
+    /// println!("{} -> {}", receipt.old_position_mint, receipt.new_position_mint);
+    /// ```
+    #[tracing::instrument(
+        skip(self, signer, position, config),
+        fields(
+            owner = %signer.pubkey(),
+            pool = %position.pool_address,
+            position_mint = %position.position_mint,
+        ),
+        err(Debug)
+    )]
+    pub async fn rebalance_position<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        position: &LiquidityPosition,
+        lower_tick: i32,
+        upper_tick: i32,
+        config: Option<ZapConfig>,
+    ) -> OrcaResult<RebalanceReceipt> {
+        self.with_timeout("rebalance position", async {
+            let config = config.unwrap_or_default();
+            let owner = signer.pubkey();
+            let pool = self
+                .get_pool_state_onchain(&position.pool_address.to_string())
+                .await?;
+            validate_tick_range(lower_tick, upper_tick, pool.tick_spacing)?;
+            if (lower_tick, upper_tick) == (position.lower_tick, position.upper_tick) {
+                return Err(OrcaError::InvalidInput(InvalidInput::RangeUnchanged {
+                    lower_tick,
+                    upper_tick,
+                }));
+            }
+            self.check_pool_policy(&pool.address)?;
+            let token_a_mint = parse_address("token_mint_a", &pool.token_mint_a)?;
+            let token_b_mint = parse_address("token_mint_b", &pool.token_mint_b)?;
+            let mint_infos = self.get_mint_infos(&[token_a_mint, token_b_mint]).await?;
+            let token_mins = withdrawal_minimums(&pool, position, &mint_infos, config.slippage_bps);
+            let available = (
+                token_mins
+                    .0
+                    .saturating_add(mint_infos[0].amount_after_transfer_fee(position.fee_owed_a)),
+                token_mins
+                    .1
+                    .saturating_add(mint_infos[1].amount_after_transfer_fee(position.fee_owed_b)),
+            );
+            let quote = self
+                .quote_balanced_deposit(
+                    &pool,
+                    Some(position),
+                    available,
+                    lower_tick,
+                    upper_tick,
+                    config.slippage_bps,
+                )
+                .await?;
+            validate_amount(
+                "liquidity",
+                u64::try_from(quote.liquidity).unwrap_or(u64::MAX),
+            )?;
+            let swap_mint = quote.swap_mint.to_string();
+            let spends = [(swap_mint.as_str(), quote.swap_amount)];
+            let reservation = self.reserve_spend(&spends).await?;
+            let mut groups = self
+                .build_withdrawal_groups(&owner, &pool, position, &mint_infos, token_mins, true)
+                .await?;
+            let target = ZapTarget::NewPosition {
+                lower_tick,
+                upper_tick,
+            };
+            let deposit = self
+                .build_zap_in_ixs(&owner, &pool, &quote, &target)
+                .await?;
+            groups.push(deposit.group);
+            let new_position_mint = deposit.new_position_mint.ok_or(OrcaError::Error(
+                "No position mint was generated".to_string(),
+            ))?;
+            let rent_refunded = self
+                .estimate_rent(
+                    &RentPlan::new()
+                        .account("position account", POSITION_ACCOUNT_SIZE)
+                        .account("position token account", TOKEN_ACCOUNT_SIZE),
+                )
+                .await?;
+            let mut rent_plan = RentPlan::new()
+                .account("position mint", MINT_ACCOUNT_SIZE)
+                .account("position token account", TOKEN_ACCOUNT_SIZE)
+                .account("position account", POSITION_ACCOUNT_SIZE)
+                .signatures(groups.len() + 1);
+            for start in &deposit.initialized_tick_arrays {
+                rent_plan =
+                    rent_plan.account(format!("tick array {}", start), TICK_ARRAY_ACCOUNT_SIZE);
+            }
+            if let Some(tip_lamports) = config.submit_mode.jito_tip() {
+                rent_plan = rent_plan.lamports("Jito tip", tip_lamports);
+            }
+            let rent_plan = self
+                .plan_token_accounts(rent_plan, &owner, &[token_a_mint, token_b_mint])
+                .await?;
+            self.ensure_sol_for_plan(&owner, &rent_plan).await?;
+            let rent_paid = self.estimate_rent(&rent_plan).await?;
+            let signatures = self
+                .submit_packed(
+                    signer,
+                    &[&new_position_mint],
+                    PackedSubmission {
+                        prefix: &self.build_compute_budget_instructions(
+                            config.compute_unit_limit,
+                            config.priority_fee_microlamports,
+                        )?,
+                        groups: &groups,
+                        packer: &TransactionPacker::default(),
+                        mode: &config.submit_mode,
+                    },
+                    "rebalance position",
+                    &AuditDetails::new(serde_json::json!({
+                        "pool": pool.address,
+                        "old_position_mint": position.position_mint.to_string(),
+                        "new_position_mint": new_position_mint.pubkey().to_string(),
+                        "old_range": [position.lower_tick, position.upper_tick],
+                        "new_range": [lower_tick, upper_tick],
+                        "swap_amount": quote.swap_amount,
+                        "liquidity": quote.liquidity.to_string(),
+                    })),
+                )
+                .await?;
+            let signature = signatures
+                .last()
+                .copied()
+                .ok_or(OrcaError::Error("No transaction was submitted".to_string()))?;
+            reservation.commit();
+            Ok(RebalanceReceipt {
+                signature,
+                signatures,
+                pool: position.pool_address,
+                old_position_mint: position.position_mint,
+                old_position_address: position.position_address,
+                old_lower_tick: position.lower_tick,
+                old_upper_tick: position.upper_tick,
+                withdrawn_liquidity: position.lp_token_amount,
+                new_position_mint: new_position_mint.pubkey(),
+                new_position_address: deposit.position_address,
+                new_lower_tick: lower_tick,
+                new_upper_tick: upper_tick,
+                swap_mint: quote.swap_mint,
+                swap_amount: quote.swap_amount,
+                liquidity: quote.liquidity,
+                rent_refunded,
+                rent_paid,
+            })
+        })
+        .await
+    }
+}
@@ -0,0 +1,414 @@
+use serde::Serialize;
+use solana_transaction_status::{UiTransactionTokenBalance, option_serializer::OptionSerializer};
+
+use super::*;
+use crate::{math::to_ui_amount, types::OrcaResult};
+
+/// Outcome of a submitted swap
+#[derive(Debug, Clone, Serialize)]
+pub struct SwapReceipt {
+    pub signature: Signature,
+    /// Pool the swap was routed through
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    /// Input amount of the quote; exact for exact-input swaps
+    pub input_amount: u64,
+    /// Maximum input enforced on-chain; equals `input_amount` for exact-input swaps
+    pub max_input_amount: u64,
+    /// Output amount of the quote the transaction was built from
+    pub quoted_output_amount: u64,
+    /// Minimum output enforced on-chain
+    pub min_output_amount: u64,
+    /// Amounts the confirmed transaction actually moved; None when the transaction could not
+    /// be fetched
+    pub settlement: Option<SwapSettlement>,
+}
+
+/// Amounts a confirmed swap moved, read from the owner's pre and post token balances
+#[derive(Debug, Clone, Serialize)]
+pub struct SwapSettlement {
+    /// Input tokens that left the owner's token accounts
+    pub amount_in: u64,
+    /// Output tokens that arrived in the owner's token accounts
+    pub amount_out: u64,
+    /// Transaction fee paid by the fee payer, in lamports
+    pub fee_paid: u64,
+    /// Output tokens received per input token spent, in whole tokens
+    pub price: f64,
+}
+
+/// Outcome of a swap executed along a route
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteReceipt {
+    pub signature: Signature,
+    /// Pools of the route's legs, in execution order
+    pub pools: Vec<Pubkey>,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub input_amount: u64,
+    /// Output amount of the route the transaction was built from
+    pub quoted_output_amount: u64,
+    /// Sum of the minimum outputs enforced on-chain by each leg
+    pub min_output_amount: u64,
+}
+
+/// Outcome of opening a position and depositing into it
+#[derive(Debug, Clone, Serialize)]
+pub struct AddLiquidityReceipt {
+    /// Signature of the transaction that deposited the liquidity
+    pub signature: Signature,
+    /// Signatures of all transactions the operation was split into, in submission order
+    pub signatures: Vec<Signature>,
+    pub pool: Pubkey,
+    /// Mint of the position NFT generated for this deposit
+    pub position_mint: Pubkey,
+    /// Position account (PDA of the position mint)
+    pub position_address: Pubkey,
+    /// Owner's token account holding the position NFT
+    pub position_token_account: Pubkey,
+    pub lower_tick: i32,
+    pub upper_tick: i32,
+    /// Maximum token amounts the deposit was allowed to spend
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    /// Liquidity added to the position
+    pub liquidity: u128,
+    /// Rent-exempt deposits of the accounts the operation created, in lamports
+    pub rent_paid: u64,
+    /// Amounts the confirmed deposit transaction actually moved; None when the transaction
+    /// could not be fetched
+    pub settlement: Option<LiquiditySettlement>,
+}
+
+/// Outcome of withdrawing a position and closing it
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoveLiquidityReceipt {
+    pub signature: Signature,
+    pub pool: Pubkey,
+    pub position_mint: Pubkey,
+    /// Position account (PDA of the position mint)
+    pub position_address: Pubkey,
+    /// Liquidity withdrawn before the position was closed
+    pub liquidity: u128,
+    /// Rent-exempt deposits returned by closing the position accounts, in lamports
+    pub rent_refunded: u64,
+    /// Amounts the confirmed transaction actually moved, collected fees included; None for dry
+    /// runs or when the transaction could not be fetched
+    pub settlement: Option<LiquiditySettlement>,
+}
+
+/// Outcome of depositing a single token into a position
+#[derive(Debug, Clone, Serialize)]
+pub struct ZapInReceipt {
+    /// Signature of the transaction that swapped and deposited
+    pub signature: Signature,
+    pub pool: Pubkey,
+    /// Mint of the position NFT the deposit went to
+    pub position_mint: Pubkey,
+    /// Position account (PDA of the position mint)
+    pub position_address: Pubkey,
+    pub lower_tick: i32,
+    pub upper_tick: i32,
+    pub input_mint: Pubkey,
+    pub input_amount: u64,
+    /// Part of the input swapped into the pool's other token
+    pub swap_amount: u64,
+    /// Smallest swap output the transaction accepted
+    pub min_swap_output: u64,
+    /// Liquidity added to the position
+    pub liquidity: u128,
+    /// Maximum token amounts the deposit was allowed to spend
+    pub token_max_a: u64,
+    pub token_max_b: u64,
+    /// Rent-exempt deposits of the accounts the operation created, in lamports
+    pub rent_paid: u64,
+    /// Net amounts the confirmed transaction moved, swap and deposit combined; None for dry
+    /// runs or when the transaction could not be fetched
+    pub settlement: Option<LiquiditySettlement>,
+}
+
+/// Outcome of withdrawing a position into a single token
+#[derive(Debug, Clone, Serialize)]
+pub struct ZapOutReceipt {
+    /// Signature of the transaction that withdrew and swapped
+    pub signature: Signature,
+    /// Signatures of all transactions the operation was split into, in submission order
+    pub signatures: Vec<Signature>,
+    pub pool: Pubkey,
+    pub position_mint: Pubkey,
+    /// Position account (PDA of the position mint)
+    pub position_address: Pubkey,
+    /// Liquidity withdrawn from the position
+    pub liquidity: u128,
+    pub output_mint: Pubkey,
+    /// Amount of the other token swapped into the output mint
+    pub swap_amount: u64,
+    /// Smallest swap output the transaction accepted
+    pub min_swap_output: u64,
+    /// Least amount of the output mint the operation guaranteed
+    pub min_output_amount: u64,
+    /// Whether the position was closed
+    pub closed: bool,
+    /// Rent-exempt deposits returned by closing the position accounts, in lamports
+    pub rent_refunded: u64,
+    /// Net amounts the confirmed transaction moved, withdrawal and swap combined; None for dry
+    /// runs or when the transaction could not be fetched
+    pub settlement: Option<LiquiditySettlement>,
+}
+
+/// Outcome of compounding a position's fees back into it
+#[derive(Debug, Clone, Serialize)]
+pub struct CompoundReceipt {
+    /// Signature of the transaction that collected the fees
+    pub collect_signature: Signature,
+    /// Signature of the re-deposit; None when the collected amounts were too small
+    pub deposit_signature: Option<Signature>,
+    pub pool: Pubkey,
+    pub position_mint: Pubkey,
+    /// Position account (PDA of the position mint)
+    pub position_address: Pubkey,
+    /// Token A and B the collection moved to the owner
+    pub collected_a: u64,
+    pub collected_b: u64,
+    /// Mint swapped to balance the re-deposit
+    pub swap_mint: Pubkey,
+    pub swap_amount: u64,
+    /// Liquidity added back to the position
+    pub liquidity_added: u128,
+}
+
+/// Outcome of moving a position's liquidity to a new range
+#[derive(Debug, Clone, Serialize)]
+pub struct RebalanceReceipt {
+    /// Signature of the transaction that opened the new position
+    pub signature: Signature,
+    /// Signatures of all transactions the operation was split into, in submission order
+    pub signatures: Vec<Signature>,
+    pub pool: Pubkey,
+    /// Closed position
+    pub old_position_mint: Pubkey,
+    pub old_position_address: Pubkey,
+    pub old_lower_tick: i32,
+    pub old_upper_tick: i32,
+    /// Liquidity withdrawn from the closed position
+    pub withdrawn_liquidity: u128,
+    /// Position opened over the new range
+    pub new_position_mint: Pubkey,
+    pub new_position_address: Pubkey,
+    pub new_lower_tick: i32,
+    pub new_upper_tick: i32,
+    /// Mint swapped to reach the new range's ratio
+    pub swap_mint: Pubkey,
+    pub swap_amount: u64,
+    /// Liquidity deposited into the new position
+    pub liquidity: u128,
+    /// Rent-exempt deposits returned by closing the old position, in lamports
+    pub rent_refunded: u64,
+    /// Rent-exempt deposits of the accounts the new position created, in lamports
+    pub rent_paid: u64,
+}
+
+/// Amounts a confirmed deposit or withdrawal moved, read from the owner's pre and post token
+/// balances
+#[derive(Debug, Clone, Serialize)]
+pub struct LiquiditySettlement {
+    /// Token A that moved between the owner and the pool
+    pub token_a_amount: u64,
+    /// Token B that moved between the owner and the pool
+    pub token_b_amount: u64,
+    /// Transaction fee paid by the fee payer, in lamports
+    pub fee_paid: u64,
+}
+
+/// Outcome of claiming the rewards of a position
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectRewardsReceipt {
+    pub signature: Signature,
+    pub pool: Pubkey,
+    pub position_mint: Pubkey,
+    /// Reward mints that were claimed, in reward index order
+    pub reward_mints: Vec<Pubkey>,
+}
+
+impl OrcaClient {
+    /// Reads the amounts a confirmed swap moved for `owner`
+    ///
+    /// The input and output amounts are the changes of the owner's token balances of the two
+    /// mints, so transfer fees and any difference from the quote are reflected.
+    ///
+    /// # Example
+    /// ```rust
+    /// let settlement = client
+    ///     .get_swap_settlement(&signature, &owner, &input_mint, &output_mint)
+    ///     .await?;
+    /// println!("{} in, {} out at {}", settlement.amount_in, settlement.amount_out, settlement.price);
+    /// ```
+    pub async fn get_swap_settlement(
+        &self,
+        signature: &Signature,
+        owner: &Pubkey,
+        input_mint: &Pubkey,
+        output_mint: &Pubkey,
+    ) -> OrcaResult<SwapSettlement> {
+        self.with_timeout("get swap settlement", async {
+            let changes = self.get_token_balance_changes(signature).await?;
+            let amount_in = changes.decrease(owner, input_mint);
+            let amount_out = changes.increase(owner, output_mint);
+            if amount_in == 0 || amount_out == 0 {
+                return Err(OrcaError::ParseError(format!(
+                    "Transaction {} moved no {} -> {} tokens for {}",
+                    signature, input_mint, output_mint, owner
+                )));
+            }
+            let decimals = self.get_mint_decimals(&[*input_mint, *output_mint]).await?;
+            Ok(SwapSettlement {
+                amount_in,
+                amount_out,
+                fee_paid: changes.fee,
+                price: to_ui_amount(amount_out, decimals[1]) / to_ui_amount(amount_in, decimals[0]),
+            })
+        })
+        .await
+    }
+
+    /// Reads the token amounts a confirmed deposit or withdrawal moved for `owner`
+    ///
+    /// # Example
+    /// ```rust
+    /// let settlement = client
+    ///     .get_liquidity_settlement(&signature, &owner, &token_mint_a, &token_mint_b)
+    ///     .await?;
+    /// println!("{} A, {} B", settlement.token_a_amount, settlement.token_b_amount);
+    /// ```
+    pub async fn get_liquidity_settlement(
+        &self,
+        signature: &Signature,
+        owner: &Pubkey,
+        token_mint_a: &Pubkey,
+        token_mint_b: &Pubkey,
+    ) -> OrcaResult<LiquiditySettlement> {
+        self.with_timeout("get liquidity settlement", async {
+            let changes = self.get_token_balance_changes(signature).await?;
+            Ok(LiquiditySettlement {
+                token_a_amount: changes.change(owner, token_mint_a),
+                token_b_amount: changes.change(owner, token_mint_b),
+                fee_paid: changes.fee,
+            })
+        })
+        .await
+    }
+
+    /// Settlement of a swap this client submitted; failures are logged rather than returned
+    /// since the swap itself already went through
+    pub(crate) async fn settle_swap(
+        &self,
+        signature: &Signature,
+        owner: &Pubkey,
+        input_mint: &Pubkey,
+        output_mint: &Pubkey,
+    ) -> Option<SwapSettlement> {
+        if self.is_dry_run() {
+            return None;
+        }
+        self.get_swap_settlement(signature, owner, input_mint, output_mint)
+            .await
+            .inspect_err(|e| tracing::warn!(error = ?e, "Failed to read swap settlement"))
+            .ok()
+    }
+
+    /// Settlement of a deposit or withdrawal this client submitted, logged like `settle_swap`
+    pub(crate) async fn settle_liquidity(
+        &self,
+        signature: &Signature,
+        owner: &Pubkey,
+        token_mint_a: &Pubkey,
+        token_mint_b: &Pubkey,
+    ) -> Option<LiquiditySettlement> {
+        if self.is_dry_run() {
+            return None;
+        }
+        self.get_liquidity_settlement(signature, owner, token_mint_a, token_mint_b)
+            .await
+            .inspect_err(|e| tracing::warn!(error = ?e, "Failed to read liquidity settlement"))
+            .ok()
+    }
+
+    /// Fetches the token balances before and after a confirmed, successful transaction
+    async fn get_token_balance_changes(
+        &self,
+        signature: &Signature,
+    ) -> OrcaResult<TokenBalanceChanges> {
+        let transaction = self
+            .fetch_transactions(std::slice::from_ref(signature))
+            .await?
+            .pop()
+            .flatten()
+            .ok_or_else(|| {
+                OrcaError::TransactionError(format!("Transaction {} not found", signature))
+            })?;
+        let meta = transaction.transaction.meta.as_ref().ok_or_else(|| {
+            OrcaError::TransactionError(format!("Transaction {} has no status", signature))
+        })?;
+        if let Some(err) = &meta.err {
+            return Err(OrcaError::TransactionError(format!(
+                "Transaction {} failed: {:?}",
+                signature, err
+            )));
+        }
+        let pre: Option<Vec<UiTransactionTokenBalance>> = meta.pre_token_balances.clone().into();
+        let post: Option<Vec<UiTransactionTokenBalance>> = meta.post_token_balances.clone().into();
+        Ok(TokenBalanceChanges {
+            pre: pre.unwrap_or_default(),
+            post: post.unwrap_or_default(),
+            fee: meta.fee,
+        })
+    }
+}
+
+/// Token balances of a transaction's accounts before and after it executed
+struct TokenBalanceChanges {
+    pre: Vec<UiTransactionTokenBalance>,
+    post: Vec<UiTransactionTokenBalance>,
+    fee: u64,
+}
+
+impl TokenBalanceChanges {
+    /// Amount of `mint` that left the token accounts of `owner`
+    fn decrease(&self, owner: &Pubkey, mint: &Pubkey) -> u64 {
+        let (pre, post) = self.balances(owner, mint);
+        pre.saturating_sub(post)
+    }
+
+    /// Amount of `mint` that arrived in the token accounts of `owner`
+    fn increase(&self, owner: &Pubkey, mint: &Pubkey) -> u64 {
+        let (pre, post) = self.balances(owner, mint);
+        post.saturating_sub(pre)
+    }
+
+    /// Amount of `mint` that moved in or out of the token accounts of `owner`
+    fn change(&self, owner: &Pubkey, mint: &Pubkey) -> u64 {
+        let (pre, post) = self.balances(owner, mint);
+        pre.abs_diff(post)
+    }
+
+    fn balances(&self, owner: &Pubkey, mint: &Pubkey) -> (u64, u64) {
+        let (owner, mint) = (owner.to_string(), mint.to_string());
+        (
+            owner_token_balance(&self.pre, &owner, &mint),
+            owner_token_balance(&self.post, &owner, &mint),
+        )
+    }
+}
+
+/// Sum of the raw balances `owner` holds of `mint` across its token accounts
+fn owner_token_balance(balances: &[UiTransactionTokenBalance], owner: &str, mint: &str) -> u64 {
+    balances
+        .iter()
+        .filter(|balance| {
+            balance.mint == mint
+                && matches!(&balance.owner, OptionSerializer::Some(balance_owner) if balance_owner == owner)
+        })
+        .filter_map(|balance| balance.ui_token_amount.amount.parse::<u64>().ok())
+        .sum()
+}
@@ -0,0 +1,167 @@
+use super::*;
+use crate::types::OrcaResult;
+
+// Sizes of accounts created by SDK operations
+pub const TOKEN_ACCOUNT_SIZE: usize = 165;
+// Token-2022 associated token accounts carry the account type and the immutable owner extension
+pub const TOKEN_2022_ACCOUNT_SIZE: usize = 170;
+pub const TRANSFER_FEE_AMOUNT_EXTENSION_SIZE: usize = 12;
+pub const MINT_ACCOUNT_SIZE: usize = 82;
+pub const POSITION_ACCOUNT_SIZE: usize = 216;
+pub const TICK_ARRAY_ACCOUNT_SIZE: usize = 9988;
+// Base fee charged per transaction signature
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// A single line of a SOL requirement breakdown
+#[derive(Debug, Clone, PartialEq)]
+pub struct RentItem {
+    pub description: String,
+    pub lamports: u64,
+}
+
+/// Accounts and fees an operation will pay for
+#[derive(Debug, Clone, Default)]
+pub struct RentPlan {
+    accounts: Vec<(String, usize)>,
+    signatures: usize,
+    transfers: Vec<(String, u64)>,
+}
+
+impl RentPlan {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an account of `size` bytes that the operation will create
+    pub fn account(mut self, description: impl Into<String>, size: usize) -> Self {
+        self.accounts.push((description.into(), size));
+        self
+    }
+
+    /// Adds transaction signatures the fee payer will be charged for
+    pub fn signatures(mut self, count: usize) -> Self {
+        self.signatures += count;
+        self
+    }
+
+    /// Adds lamports the fee payer will transfer, such as a Jito tip
+    pub fn lamports(mut self, description: impl Into<String>, lamports: u64) -> Self {
+        self.transfers.push((description.into(), lamports));
+        self
+    }
+}
+
+impl OrcaClient {
+    /// Computes the itemized SOL requirement of a plan
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::rent::{RentPlan, TOKEN_ACCOUNT_SIZE};
+    ///
+    /// let items = client
+    ///     .estimate_sol_requirement(&RentPlan::new().account("output token account", TOKEN_ACCOUNT_SIZE).signatures(1))
+    ///     .await?;
+    /// let total: u64 = items.iter().map(|item| item.lamports).sum();
+    /// ```
+    pub async fn estimate_sol_requirement(&self, plan: &RentPlan) -> OrcaResult<Vec<RentItem>> {
+        let mut items = Vec::with_capacity(plan.accounts.len() + plan.transfers.len() + 1);
+        for (description, size) in &plan.accounts {
+            let lamports = self
+                .rpc_call("Failed to get rent exemption", |client| {
+                    client.get_minimum_balance_for_rent_exemption(*size)
+                })
+                .await?;
+            items.push(RentItem {
+                description: description.clone(),
+                lamports,
+            });
+        }
+        if plan.signatures > 0 {
+            items.push(RentItem {
+                description: format!("transaction fees ({} signatures)", plan.signatures),
+                lamports: LAMPORTS_PER_SIGNATURE * plan.signatures as u64,
+            });
+        }
+        items.extend(
+            plan.transfers
+                .iter()
+                .map(|(description, lamports)| RentItem {
+                    description: description.clone(),
+                    lamports: *lamports,
+                }),
+        );
+        Ok(items)
+    }
+
+    /// Rent-exempt deposits of the accounts in a plan, in lamports; fees and transfers excluded
+    pub async fn estimate_rent(&self, plan: &RentPlan) -> OrcaResult<u64> {
+        let accounts_only = RentPlan {
+            accounts: plan.accounts.clone(),
+            ..RentPlan::default()
+        };
+        let items = self.estimate_sol_requirement(&accounts_only).await?;
+        Ok(items.iter().map(|item| item.lamports).sum())
+    }
+
+    /// Verifies that the fee payer can cover the fees and rent of a plan
+    ///
+    /// Returns `OrcaError::InsufficientSolForRent` with the itemized breakdown otherwise.
+    pub async fn ensure_sol_for_plan(&self, payer: &Pubkey, plan: &RentPlan) -> OrcaResult<()> {
+        let breakdown = self.estimate_sol_requirement(plan).await?;
+        let required: u64 = breakdown.iter().map(|item| item.lamports).sum();
+        let available = self
+            .rpc_call("Failed to get balance", |client| client.get_balance(payer))
+            .await?;
+        if available < required {
+            return Err(OrcaError::InsufficientSolForRent {
+                required,
+                available,
+                breakdown,
+            });
+        }
+        Ok(())
+    }
+
+    /// Adds the associated token accounts of `owner` that `ensure_token_account` would create
+    ///
+    /// Each missing account is created in its own transaction, so one signature is added per account.
+    pub(crate) async fn plan_token_accounts(
+        &self,
+        plan: RentPlan,
+        owner: &Pubkey,
+        mints: &[Pubkey],
+    ) -> OrcaResult<RentPlan> {
+        let infos = self.get_mint_infos(mints).await?;
+        let addresses: Vec<Pubkey> = infos
+            .iter()
+            .map(|info| {
+                self.get_associated_token_address_with_program(
+                    owner,
+                    &info.mint,
+                    &info.token_program,
+                )
+            })
+            .collect();
+        let missing = self.missing_accounts(&addresses).await?;
+        let mut plan = plan;
+        for (info, is_missing) in infos.iter().zip(missing) {
+            if is_missing {
+                plan = plan
+                    .account(
+                        format!("token account for {}", info.mint),
+                        info.token_account_size(),
+                    )
+                    .signatures(1);
+            }
+        }
+        Ok(plan)
+    }
+
+    /// Returns which of the given accounts do not exist yet
+    pub(crate) async fn missing_accounts(&self, accounts: &[Pubkey]) -> OrcaResult<Vec<bool>> {
+        let fetched = self
+            .get_multiple_accounts_batched(accounts, "Failed to get accounts")
+            .await?;
+        Ok(fetched.iter().map(|account| account.is_none()).collect())
+    }
+}
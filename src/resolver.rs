@@ -0,0 +1,273 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::{
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_commitment_config::CommitmentConfig;
+
+use super::*;
+use crate::{
+    global::{WHIRLPOOL_TOKEN_MINT_A_OFFSET, WHIRLPOOL_TOKEN_MINT_B_OFFSET},
+    pool::PoolInfo,
+    types::{InvalidInput, OrcaResult},
+    validation::{parse_address, validate_distinct_mints},
+};
+
+// How long discovered pool addresses are reused before rediscovery
+const POOL_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// How a pool is chosen when several pools trade the same pair
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum PoolSelection {
+    /// The pool with the most active liquidity
+    #[default]
+    BestLiquidity,
+    /// The pool with the most active liquidity among those charging this fee rate
+    /// (in hundredths of a basis point, as in `PoolInfo::trade_fee_numerator`)
+    FeeRate(u64),
+    /// A specific pool; discovery is skipped
+    Address(String),
+}
+
+/// Discovered pool addresses keyed by token mint or mint pair
+#[derive(Debug, Default)]
+pub(crate) struct PoolAddressCache {
+    entries: HashMap<String, (Instant, Vec<String>)>,
+}
+
+impl PoolAddressCache {
+    pub(crate) fn get(&self, key: &str) -> Option<Vec<String>> {
+        self.entries
+            .get(key)
+            .filter(|(cached_at, _)| cached_at.elapsed() < POOL_CACHE_TTL)
+            .map(|(_, pools)| pools.clone())
+    }
+
+    pub(crate) fn insert(&mut self, key: String, pools: Vec<String>) {
+        self.entries
+            .retain(|_, (cached_at, _)| cached_at.elapsed() < POOL_CACHE_TTL);
+        self.entries.insert(key, (Instant::now(), pools));
+    }
+}
+
+impl OrcaClient {
+    /// Resolves the pool used to trade `input_mint` against `output_mint`
+    ///
+    /// Candidate pools are discovered once per pair and cached; their state is then fetched in a
+    /// single batch. Pools excluded by the spend policy are skipped.
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::resolver::PoolSelection;
+    ///
+    /// let pool = client
+    ///     .resolve_pool(
+    ///         "So11111111111111111111111111111111111111112",
+    ///         "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    ///         &PoolSelection::BestLiquidity,
+    ///     )
+    ///     .await?;
+    /// println!("Trading through {}", pool.address);
+    /// ```
+    #[tracing::instrument(skip(self), fields(candidates = tracing::field::Empty), err(Debug))]
+    pub async fn resolve_pool(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        selection: &PoolSelection,
+    ) -> OrcaResult<PoolInfo> {
+        self.with_timeout("resolve pool", async {
+            parse_address("input_mint", input_mint)?;
+            parse_address("output_mint", output_mint)?;
+            validate_distinct_mints(input_mint, output_mint)?;
+            if let PoolSelection::Address(address) = selection {
+                let pool = self.get_pool_state_onchain(address).await?;
+                if !Self::pool_trades_pair(&pool, input_mint, output_mint) {
+                    let mint = if input_mint == pool.token_mint_a || input_mint == pool.token_mint_b
+                    {
+                        output_mint
+                    } else {
+                        input_mint
+                    };
+                    return Err(OrcaError::InvalidInput(InvalidInput::MintNotInPool {
+                        pool: address.clone(),
+                        mint: mint.to_string(),
+                    }));
+                }
+                return Ok(pool);
+            }
+            let addresses = self.find_pools_for_pair(input_mint, output_mint).await?;
+            let pools = self.get_pool_states(&addresses).await?;
+            tracing::Span::current().record("candidates", pools.len());
+            pools
+                .into_iter()
+                .filter(|pool| Self::pool_trades_pair(pool, input_mint, output_mint))
+                .filter(|pool| self.check_pool_policy(&pool.address).is_ok())
+                .filter(|pool| match selection {
+                    PoolSelection::FeeRate(fee_rate) => pool.trade_fee_numerator == *fee_rate,
+                    _ => true,
+                })
+                .max_by_key(|pool| pool.liquidity)
+                .ok_or_else(|| OrcaError::NoRoute {
+                    input_mint: input_mint.to_string(),
+                    output_mint: output_mint.to_string(),
+                })
+        })
+        .await
+    }
+
+    /// Returns the addresses of all Whirlpools trading the pair, in either mint order
+    pub async fn find_pools_for_pair(&self, mint_x: &str, mint_y: &str) -> OrcaResult<Vec<String>> {
+        self.with_timeout("find pools for pair", async {
+            let cache_key = if mint_x < mint_y {
+                format!("{}:{}", mint_x, mint_y)
+            } else {
+                format!("{}:{}", mint_y, mint_x)
+            };
+            if let Some(pools) = self.cached_pool_addresses(&cache_key) {
+                return Ok(pools);
+            }
+            let mint_x_pubkey = parse_address("mint_x", mint_x)?;
+            let mint_y_pubkey = parse_address("mint_y", mint_y)?;
+            let mut pools = self
+                .find_pools_by_mints(&mint_x_pubkey, &mint_y_pubkey)
+                .await?;
+            pools.extend(
+                self.find_pools_by_mints(&mint_y_pubkey, &mint_x_pubkey)
+                    .await?,
+            );
+            self.cache_pool_addresses(&cache_key, &pools);
+            Ok(pools)
+        })
+        .await
+    }
+
+    /// Fetches and decodes any number of pools in batches of 100, skipping missing accounts
+    ///
+    /// # Example
+    /// ```rust
+    /// let addresses = client.find_pools_for_pair(sol_mint, usdc_mint).await?;
+    /// for pool in client.get_pool_states(&addresses).await? {
+    ///     println!("{}: liquidity {}", pool.address, pool.liquidity);
+    /// }
+    /// ```
+    pub async fn get_pool_states(&self, pool_addresses: &[String]) -> OrcaResult<Vec<PoolInfo>> {
+        self.get_pool_states_with_commitment(pool_addresses, None)
+            .await
+    }
+
+    /// Like `get_pool_states`, reading at `commitment` instead of the client's when set
+    pub(crate) async fn get_pool_states_with_commitment(
+        &self,
+        pool_addresses: &[String],
+        commitment: Option<CommitmentConfig>,
+    ) -> OrcaResult<Vec<PoolInfo>> {
+        let pubkeys = pool_addresses
+            .iter()
+            .map(|address| parse_address("pool_address", address))
+            .collect::<OrcaResult<Vec<Pubkey>>>()?;
+        let accounts = match commitment {
+            Some(commitment) => {
+                self.get_multiple_accounts_batched_with_commitment(
+                    &pubkeys,
+                    commitment,
+                    "Failed to get accounts",
+                )
+                .await?
+            }
+            None => {
+                self.get_multiple_accounts_batched(&pubkeys, "Failed to get accounts")
+                    .await?
+            }
+        };
+        Ok(pool_addresses
+            .iter()
+            .zip(accounts)
+            .filter_map(|(address, account)| {
+                self.parse_whirlpool_account_data(&account?.data, address)
+                    .ok()
+            })
+            .collect())
+    }
+
+    async fn find_pools_by_mints(
+        &self,
+        mint_a: &Pubkey,
+        mint_b: &Pubkey,
+    ) -> OrcaResult<Vec<String>> {
+        let filters = vec![
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                WHIRLPOOL_TOKEN_MINT_A_OFFSET,
+                &mint_a.to_bytes(),
+            )),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                WHIRLPOOL_TOKEN_MINT_B_OFFSET,
+                &mint_b.to_bytes(),
+            )),
+        ];
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                // Only the addresses are used
+                data_slice: Some(UiDataSliceConfig {
+                    offset: 0,
+                    length: 0,
+                }),
+                commitment: Some(CommitmentConfig::confirmed()),
+                min_context_slot: None,
+            },
+            with_context: None,
+            sort_results: None,
+        };
+        let accounts = self
+            .rpc_call("Failed to get program accounts", |client| {
+                client.get_program_accounts_with_config(&self.whirlpool_program_id, config.clone())
+            })
+            .await?;
+        Ok(accounts
+            .iter()
+            .map(|(pubkey, _account)| pubkey.to_string())
+            .collect())
+    }
+
+    /// Pool addresses discovered for `key`, from memory or the persistent cache
+    pub(crate) fn cached_pool_addresses(&self, key: &str) -> Option<Vec<String>> {
+        if let Some(pools) = self.pool_cache.read().ok().and_then(|cache| cache.get(key)) {
+            return Some(pools);
+        }
+        #[cfg(feature = "persistent-cache")]
+        if let Some(pools) = self
+            .persistent_cache
+            .as_ref()
+            .and_then(|cache| cache.get_pools(key))
+        {
+            if let Ok(mut cache) = self.pool_cache.write() {
+                cache.insert(key.to_string(), pools.clone());
+            }
+            return Some(pools);
+        }
+        None
+    }
+
+    /// Remembers the pool addresses discovered for `key`
+    pub(crate) fn cache_pool_addresses(&self, key: &str, pools: &[String]) {
+        if let Ok(mut cache) = self.pool_cache.write() {
+            cache.insert(key.to_string(), pools.to_vec());
+        }
+        #[cfg(feature = "persistent-cache")]
+        if let Some(cache) = &self.persistent_cache {
+            cache.insert_pools(key, pools);
+        }
+    }
+
+    fn pool_trades_pair(pool: &PoolInfo, input_mint: &str, output_mint: &str) -> bool {
+        (pool.token_mint_a == input_mint && pool.token_mint_b == output_mint)
+            || (pool.token_mint_a == output_mint && pool.token_mint_b == input_mint)
+    }
+}
@@ -0,0 +1,232 @@
+use std::{
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    io,
+    time::Duration,
+};
+
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    nonblocking::rpc_client::RpcClient,
+    rpc_custom_error::{
+        JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE,
+        JSON_RPC_SERVER_ERROR_BLOCK_STATUS_NOT_AVAILABLE_YET,
+        JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED, JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY,
+    },
+    rpc_request::RpcError,
+};
+
+use super::*;
+use crate::types::OrcaResult;
+
+// JSON-RPC codes of requests the node could not understand
+const JSON_RPC_INVALID_REQUEST: i64 = -32600;
+const JSON_RPC_METHOD_NOT_FOUND: i64 = -32601;
+const JSON_RPC_INVALID_PARAMS: i64 = -32602;
+
+/// Kind of a failed RPC request, deciding whether it is worth retrying
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcErrorClass {
+    /// HTTP 429 from the node or its provider
+    RateLimited,
+    /// The request did not get a response in time
+    Timeout,
+    /// The connection could not be made or broke off
+    Connection,
+    /// HTTP 5xx from the node or its provider
+    ServerError,
+    /// The node is unhealthy or has not reached the slot the request needs yet
+    NodeBehind,
+    /// Malformed request: invalid params, unknown method or a body the node could not parse
+    InvalidRequest,
+    /// A transaction was rejected, e.g. in preflight
+    Transaction,
+    Other,
+}
+
+impl RpcErrorClass {
+    /// Classifies an error returned by the RPC client
+    pub fn of(error: &ClientError) -> Self {
+        match error.kind() {
+            ClientErrorKind::Reqwest(e) => match e.status().map(|status| status.as_u16()) {
+                Some(429) => Self::RateLimited,
+                Some(500..=599) => Self::ServerError,
+                Some(400..=499) => Self::InvalidRequest,
+                _ if e.is_timeout() => Self::Timeout,
+                _ if e.is_connect() || e.is_request() || e.is_body() => Self::Connection,
+                _ => Self::Other,
+            },
+            ClientErrorKind::Io(e) => match e.kind() {
+                io::ErrorKind::TimedOut => Self::Timeout,
+                io::ErrorKind::ConnectionRefused
+                | io::ErrorKind::ConnectionReset
+                | io::ErrorKind::ConnectionAborted
+                | io::ErrorKind::NotConnected
+                | io::ErrorKind::BrokenPipe
+                | io::ErrorKind::UnexpectedEof => Self::Connection,
+                _ => Self::Other,
+            },
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => match *code {
+                JSON_RPC_INVALID_REQUEST | JSON_RPC_METHOD_NOT_FOUND | JSON_RPC_INVALID_PARAMS => {
+                    Self::InvalidRequest
+                }
+                JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY
+                | JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE
+                | JSON_RPC_SERVER_ERROR_BLOCK_STATUS_NOT_AVAILABLE_YET
+                | JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED => Self::NodeBehind,
+                _ if error.get_transaction_error().is_some() => Self::Transaction,
+                _ => Self::Other,
+            },
+            ClientErrorKind::RpcError(RpcError::RpcRequestError(_)) => Self::Connection,
+            ClientErrorKind::RpcError(RpcError::ParseError(_)) | ClientErrorKind::SerdeJson(_) => {
+                Self::InvalidRequest
+            }
+            ClientErrorKind::TransactionError(_) => Self::Transaction,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// How failed RPC reads are retried
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Attempts after the first one; 0 disables retries
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on every further attempt
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between attempts
+    pub max_backoff: Duration,
+    /// Share of each delay, 0 to 1, that is randomized so clients do not retry in lockstep
+    pub jitter: f64,
+    /// Error classes that are retried; all others fail at once
+    pub retry_on: Vec<RpcErrorClass>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            jitter: 0.5,
+            retry_on: vec![
+                RpcErrorClass::RateLimited,
+                RpcErrorClass::Timeout,
+                RpcErrorClass::Connection,
+                RpcErrorClass::ServerError,
+                RpcErrorClass::NodeBehind,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `attempt` (starting at 1), without jitter
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// `backoff` with its jittered share replaced by a random part of it
+    pub fn backoff_with_jitter(&self, attempt: u32) -> Duration {
+        let backoff = self.backoff(attempt);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        // RandomState is seeded randomly per instance, which is enough for spreading retries
+        let random = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish() as f64
+            / u64::MAX as f64;
+        backoff.mul_f64(1.0 - jitter * random)
+    }
+
+    /// Whether an error of `class` is retried
+    pub fn retries(&self, class: RpcErrorClass) -> bool {
+        self.retry_on.contains(&class)
+    }
+}
+
+impl OrcaClient {
+    /// Returns the retry policy the client was built with
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// The RPC client, or `RpcClientUnavailable` when the client was created without one
+    pub(crate) fn rpc_client(&self) -> OrcaResult<&RpcClient> {
+        self.solana
+            .client
+            .as_deref()
+            .ok_or(OrcaError::RpcClientUnavailable)
+    }
+
+    /// Sends an RPC request, retrying failures the retry policy considers transient
+    ///
+    /// Requests rotate over the client's healthy endpoints. A transient failure moves the
+    /// request to the next healthy endpoint at once; backoff starts when none is left.
+    /// `request` is called again for every attempt. Errors are wrapped with `context` once
+    /// retrying gives up.
+    pub(crate) async fn rpc_call<'a, T, F, Fut>(
+        &'a self,
+        context: &str,
+        request: F,
+    ) -> OrcaResult<T>
+    where
+        F: Fn(&'a RpcClient) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let mut attempt = 0;
+        let mut tried = Vec::new();
+        loop {
+            let index = self.endpoints.pick(&tried, true);
+            match request(self.endpoints.client(index)).await {
+                Ok(value) => {
+                    self.endpoints.record_request(index);
+                    return Ok(value);
+                }
+                Err(e) => {
+                    let class = RpcErrorClass::of(&e);
+                    if !self.retry_policy.retries(class) {
+                        self.endpoints.record_request(index);
+                        return Err(OrcaError::rpc(context, e));
+                    }
+                    self.endpoints.record_failure(index, &e);
+                    tried.push(index);
+                    if self.endpoints.can_fail_over(&tried) {
+                        tracing::debug!(
+                            context,
+                            class = ?class,
+                            error = %e,
+                            "Failing over RPC request"
+                        );
+                        continue;
+                    }
+                    if attempt >= self.retry_policy.max_retries {
+                        return Err(OrcaError::rpc(context, e));
+                    }
+                    attempt += 1;
+                    tried.clear();
+                    let delay = self.retry_policy.backoff_with_jitter(attempt);
+                    tracing::debug!(
+                        context,
+                        attempt,
+                        class = ?class,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %e,
+                        "Retrying RPC request"
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+}
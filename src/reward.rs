@@ -0,0 +1,313 @@
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+use super::*;
+use crate::{
+    audit::AuditDetails,
+    global::{
+        WHIRLPOOL_COLLECT_REWARD_V2_DISCRIMINATOR, WHIRLPOOL_UPDATE_FEES_AND_REWARDS_DISCRIMINATOR,
+    },
+    liquidity::LiquidityPosition,
+    pool::WhirlpoolRewardInfo,
+    receipt::CollectRewardsReceipt,
+    tick::tick_array_start_index,
+    token::MEMO_PROGRAM,
+    types::{InvalidInput, OrcaResult},
+};
+
+/// Instructions that claim the rewards of a position
+#[derive(Debug, Clone)]
+pub struct CollectRewardsInstructions {
+    /// Reward mints claimed, in reward index order
+    pub reward_mints: Vec<Pubkey>,
+    pub instructions: Vec<Instruction>,
+}
+
+/// Reward slot and owner account of a `collect_reward_v2` instruction
+#[derive(Debug, Clone, Copy)]
+pub struct CollectRewardParams<'a> {
+    /// Owner's token account receiving the reward
+    pub reward_owner_account: &'a Pubkey,
+    /// Mint of the reward
+    pub reward_mint: &'a Pubkey,
+    /// Pool vault holding the reward
+    pub reward_vault: &'a Pubkey,
+    /// Token program owning the reward mint
+    pub reward_token_program: &'a Pubkey,
+    /// Reward slot of the pool, 0 to 2
+    pub reward_index: u8,
+}
+
+/// Emission schedule of one initialized reward slot of a pool
+#[derive(Debug, Clone)]
+pub struct RewardEmission {
+    /// Reward slot index, 0 to 2
+    pub index: usize,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    /// Account allowed to change the emissions of the slot
+    pub authority: Pubkey,
+    pub decimals: u8,
+    /// Reward tokens emitted per second, Q64.64 raw units
+    pub emissions_per_second_x64: u128,
+    /// Reward growth per unit of liquidity, Q64.64
+    pub growth_global_x64: u128,
+    /// Reward tokens emitted per day, in whole tokens
+    pub emissions_per_day: f64,
+}
+
+impl OrcaClient {
+    /// Lists the initialized reward slots of a pool with their daily emissions
+    ///
+    /// # Example
+    /// ```rust
+    /// for reward in client.get_reward_emissions("whirlpool_address_here").await? {
+    ///     println!("{}: {} per day", reward.mint, reward.emissions_per_day);
+    /// }
+    /// ```
+    pub async fn get_reward_emissions(
+        &self,
+        pool_address: &str,
+    ) -> OrcaResult<Vec<RewardEmission>> {
+        self.with_timeout("get reward emissions", async {
+            let pool = self.get_pool_state_onchain(pool_address).await?;
+            let rewards: Vec<(usize, &WhirlpoolRewardInfo)> = pool
+                .reward_infos
+                .iter()
+                .enumerate()
+                .filter(|(_, reward)| reward.is_initialized())
+                .collect();
+            let mints: Vec<Pubkey> = rewards.iter().map(|(_, reward)| reward.mint).collect();
+            let decimals = self.get_mint_decimals(&mints).await?;
+            Ok(rewards
+                .into_iter()
+                .zip(decimals)
+                .map(|((index, reward), decimals)| RewardEmission {
+                    index,
+                    mint: reward.mint,
+                    vault: reward.vault,
+                    authority: reward.authority,
+                    decimals,
+                    emissions_per_second_x64: reward.emissions_per_second_x64,
+                    growth_global_x64: reward.growth_global_x64,
+                    emissions_per_day: reward.emissions_per_day(decimals),
+                })
+                .collect())
+        })
+        .await
+    }
+
+    /// Claims every initialized reward of a position into the owner's token accounts
+    ///
+    /// Missing reward token accounts are created first; the position's accrued rewards are
+    /// refreshed before they are claimed.
+    ///
+    /// # Params
+    /// signer - Owner of the position
+    /// position - Position to claim rewards for
+    ///
+    /// # Example
+    /// ```rust
+    /// let positions = client.get_liquidity_positions(&keypair.pubkey()).await?;
+    /// for position in &positions {
+    ///     let receipt = client.collect_rewards(&keypair, position).await?;
+    ///     println!("Claimed {:?} in {}", receipt.reward_mints, receipt.signature);
+    /// }
+    /// ```
+    #[tracing::instrument(
+        skip(self, signer, position),
+        fields(
+            owner = %signer.pubkey(),
+            pool = %position.pool_address,
+            position_mint = %position.position_mint,
+        ),
+        err(Debug)
+    )]
+    pub async fn collect_rewards<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        position: &LiquidityPosition,
+    ) -> OrcaResult<CollectRewardsReceipt> {
+        self.with_timeout("collect rewards", async {
+            let rewards = self
+                .build_collect_rewards_ixs(&signer.pubkey(), position)
+                .await?;
+            if rewards.reward_mints.is_empty() {
+                return Err(OrcaError::InvalidInput(InvalidInput::NoRewards(
+                    position.pool_address.to_string(),
+                )));
+            }
+            let recent_blockhash = self.latest_blockhash().await?;
+            let message = Message::new(&rewards.instructions, Some(&signer.pubkey()));
+            let transaction = Transaction::new(&[signer], message, recent_blockhash);
+            let signature = self
+                .submit_transaction(
+                    &transaction.into(),
+                    "collect rewards",
+                    &AuditDetails::new(serde_json::json!({
+                        "pool": position.pool_address.to_string(),
+                        "position_mint": position.position_mint.to_string(),
+                        "reward_mints": rewards
+                            .reward_mints
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>(),
+                    })),
+                )
+                .await?;
+            Ok(CollectRewardsReceipt {
+                signature,
+                pool: position.pool_address,
+                position_mint: position.position_mint,
+                reward_mints: rewards.reward_mints,
+            })
+        })
+        .await
+    }
+
+    /// Builds the instructions of `collect_rewards` without signing or sending them
+    ///
+    /// Reads the pool's reward infos and adds, for each initialized reward, an idempotent
+    /// token account creation and a `collect_reward_v2` instruction. Returns no instructions
+    /// when the pool has no rewards.
+    ///
+    /// # Example
+    /// ```rust
+    /// let rewards = client.build_collect_rewards_ixs(&wallet, &position).await?;
+    /// let transaction = client.build_transaction(&wallet, &rewards.instructions).await?;
+    /// ```
+    pub async fn build_collect_rewards_ixs(
+        &self,
+        owner: &Pubkey,
+        position: &LiquidityPosition,
+    ) -> OrcaResult<CollectRewardsInstructions> {
+        let pool = self
+            .get_pool_state_onchain(&position.pool_address.to_string())
+            .await?;
+        let rewards: Vec<(u8, Pubkey, Pubkey)> = pool
+            .reward_infos
+            .iter()
+            .enumerate()
+            .filter(|(_, reward)| reward.is_initialized())
+            .map(|(index, reward)| (index as u8, reward.mint, reward.vault))
+            .collect();
+        if rewards.is_empty() {
+            return Ok(CollectRewardsInstructions {
+                reward_mints: Vec::new(),
+                instructions: Vec::new(),
+            });
+        }
+        let reward_mints: Vec<Pubkey> = rewards.iter().map(|(_, mint, _)| *mint).collect();
+        let mint_infos = self.get_mint_infos(&reward_mints).await?;
+        let position_address = self.get_position_pda(&position.position_mint);
+        let mut instructions = Vec::new();
+        if position.lp_token_amount > 0 {
+            instructions.push(self.build_update_fees_and_rewards_instruction(
+                &position.pool_address,
+                &position_address,
+                &self.get_tick_array_address(
+                    &position.pool_address,
+                    tick_array_start_index(position.lower_tick, pool.tick_spacing),
+                ),
+                &self.get_tick_array_address(
+                    &position.pool_address,
+                    tick_array_start_index(position.upper_tick, pool.tick_spacing),
+                ),
+            ));
+        }
+        for ((reward_index, reward_mint, reward_vault), info) in rewards.iter().zip(&mint_infos) {
+            let reward_owner_account = self.get_associated_token_address_with_program(
+                owner,
+                reward_mint,
+                &info.token_program,
+            );
+            instructions.push(create_associated_token_account_idempotent(
+                owner,
+                owner,
+                reward_mint,
+                &info.token_program,
+            ));
+            instructions.push(self.build_collect_reward_instruction(
+                owner,
+                &position.pool_address,
+                &position_address,
+                &position.position_token_account,
+                CollectRewardParams {
+                    reward_owner_account: &reward_owner_account,
+                    reward_mint,
+                    reward_vault,
+                    reward_token_program: &info.token_program,
+                    reward_index: *reward_index,
+                },
+            ));
+        }
+        Ok(CollectRewardsInstructions {
+            reward_mints,
+            instructions,
+        })
+    }
+
+    /// Constructs a `collect_reward_v2` instruction for one reward index of a position
+    ///
+    /// # Arguments
+    /// owner - Position authority
+    /// pool - Whirlpool address
+    /// position - Position account
+    /// position_token_account - Token account holding the position NFT
+    /// params - Reward slot, its mint, vault and token program, and the owner's reward account
+    pub fn build_collect_reward_instruction(
+        &self,
+        owner: &Pubkey,
+        pool: &Pubkey,
+        position: &Pubkey,
+        position_token_account: &Pubkey,
+        params: CollectRewardParams,
+    ) -> Instruction {
+        let CollectRewardParams {
+            reward_owner_account,
+            reward_mint,
+            reward_vault,
+            reward_token_program,
+            reward_index,
+        } = params;
+        let accounts = vec![
+            AccountMeta::new_readonly(*pool, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*position, false),
+            AccountMeta::new_readonly(*position_token_account, false),
+            AccountMeta::new(*reward_owner_account, false),
+            AccountMeta::new_readonly(*reward_mint, false),
+            AccountMeta::new(*reward_vault, false),
+            AccountMeta::new_readonly(*reward_token_program, false),
+            AccountMeta::new_readonly(MEMO_PROGRAM, false),
+        ];
+        let mut data = WHIRLPOOL_COLLECT_REWARD_V2_DISCRIMINATOR.to_vec();
+        data.push(reward_index);
+        data.push(0); // no remaining accounts info
+        Instruction {
+            program_id: self.whirlpool_program_id,
+            accounts,
+            data,
+        }
+    }
+
+    /// Refreshes the fees and rewards a position has accrued
+    pub(crate) fn build_update_fees_and_rewards_instruction(
+        &self,
+        pool: &Pubkey,
+        position: &Pubkey,
+        tick_array_lower: &Pubkey,
+        tick_array_upper: &Pubkey,
+    ) -> Instruction {
+        Instruction {
+            program_id: self.whirlpool_program_id,
+            accounts: vec![
+                AccountMeta::new(*pool, false),
+                AccountMeta::new(*position, false),
+                AccountMeta::new_readonly(*tick_array_lower, false),
+                AccountMeta::new_readonly(*tick_array_upper, false),
+            ],
+            data: WHIRLPOOL_UPDATE_FEES_AND_REWARDS_DISCRIMINATOR.to_vec(),
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use super::*;
+use crate::{
+    global::USDT_MINT, liquidity::LiquidityPosition, math::to_ui_amount, types::OrcaResult,
+};
+
+const USDT_DECIMALS: u8 = 6;
+
+/// Where the pool's current tick sits relative to a position's range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionRangeStatus {
+    InRange,
+    BelowRange,
+    AboveRange,
+    Unknown,
+}
+
+/// Exposure of a portfolio to a single asset
+#[derive(Debug, Clone)]
+pub struct AssetExposure {
+    pub mint: Pubkey,
+    /// Raw amount held directly in token accounts
+    pub wallet_amount: u64,
+    /// Raw amount held inside liquidity positions
+    pub position_amount: u64,
+    /// USD value of the total amount, if a price could be found
+    pub value_usd: Option<f64>,
+    /// Share of the total priced portfolio value in percent
+    pub share_percent: f64,
+}
+
+/// Range status of a single liquidity position
+#[derive(Debug, Clone)]
+pub struct PositionRisk {
+    pub position_mint: Pubkey,
+    pub pool_address: Pubkey,
+    pub lower_tick: i32,
+    pub upper_tick: i32,
+    pub current_tick: Option<i32>,
+    pub liquidity: u128,
+    pub status: PositionRangeStatus,
+}
+
+/// Portfolio-level risk summary for a wallet
+#[derive(Debug, Clone)]
+pub struct PortfolioRisk {
+    pub owner: Pubkey,
+    pub total_value_usd: f64,
+    pub exposures: Vec<AssetExposure>,
+    pub positions: Vec<PositionRisk>,
+    /// Percentage of position liquidity that is currently earning fees
+    pub in_range_liquidity_percent: f64,
+    /// Share of the largest single asset in percent
+    pub largest_exposure_percent: f64,
+    /// Herfindahl-Hirschman index of asset shares (0 to 10000)
+    pub concentration_index: f64,
+}
+
+impl OrcaClient {
+    /// Builds a risk summary combining wallet balances, liquidity positions and USD pricing
+    ///
+    /// # Params
+    /// owner - Wallet to analyze
+    ///
+    /// # Example
+    /// ```rust
+    /// let risk = client.get_portfolio_risk(&owner).await?;
+    /// println!("Total value: ${:.2}", risk.total_value_usd);
+    /// println!("In range: {:.1}%", risk.in_range_liquidity_percent);
+    /// println!("Largest exposure: {:.1}%", risk.largest_exposure_percent);
+    /// ```
+    pub async fn get_portfolio_risk(&self, owner: &Pubkey) -> OrcaResult<PortfolioRisk> {
+        self.with_timeout("get portfolio risk", async {
+            let balances = self.get_all_token_balances(owner).await?;
+            let positions = self.get_liquidity_positions(owner).await?;
+            let position_mints: Vec<Pubkey> = positions.iter().map(|p| p.position_mint).collect();
+            let mut holdings: HashMap<Pubkey, (u64, u64)> = HashMap::new();
+            for (mint, amount) in balances {
+                if position_mints.contains(&mint) {
+                    continue;
+                }
+                holdings.entry(mint).or_default().0 += amount;
+            }
+            let mut position_risks = Vec::with_capacity(positions.len());
+            for position in &positions {
+                let (risk, mints) = self.assess_position_range(position).await;
+                if let Some((mint_a, mint_b)) = mints {
+                    holdings.entry(mint_a).or_default().1 += position.token_a_amount;
+                    holdings.entry(mint_b).or_default().1 += position.token_b_amount;
+                }
+                position_risks.push(risk);
+            }
+            let mut exposures = Vec::with_capacity(holdings.len());
+            for (mint, (wallet_amount, position_amount)) in holdings {
+                let total = wallet_amount.saturating_add(position_amount);
+                let value_usd = self.estimate_usd_value(&mint, total).await;
+                exposures.push(AssetExposure {
+                    mint,
+                    wallet_amount,
+                    position_amount,
+                    value_usd,
+                    share_percent: 0.0,
+                });
+            }
+            let total_value_usd: f64 = exposures.iter().filter_map(|e| e.value_usd).sum();
+            if total_value_usd > 0.0 {
+                for exposure in exposures.iter_mut() {
+                    exposure.share_percent =
+                        exposure.value_usd.unwrap_or(0.0) / total_value_usd * 100.0;
+                }
+            }
+            exposures.sort_by(|a, b| {
+                b.share_percent
+                    .partial_cmp(&a.share_percent)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            let largest_exposure_percent = exposures.first().map_or(0.0, |e| e.share_percent);
+            let concentration_index = exposures
+                .iter()
+                .map(|e| e.share_percent * e.share_percent)
+                .sum();
+            let total_liquidity: u128 = position_risks.iter().map(|p| p.liquidity).sum();
+            let in_range_liquidity: u128 = position_risks
+                .iter()
+                .filter(|p| p.status == PositionRangeStatus::InRange)
+                .map(|p| p.liquidity)
+                .sum();
+            let in_range_liquidity_percent = if total_liquidity > 0 {
+                in_range_liquidity as f64 / total_liquidity as f64 * 100.0
+            } else {
+                0.0
+            };
+            Ok(PortfolioRisk {
+                owner: *owner,
+                total_value_usd,
+                exposures,
+                positions: position_risks,
+                in_range_liquidity_percent,
+                largest_exposure_percent,
+                concentration_index,
+            })
+        })
+        .await
+    }
+
+    /// Determines the range status of a position and the mints of its pool
+    async fn assess_position_range(
+        &self,
+        position: &LiquidityPosition,
+    ) -> (PositionRisk, Option<(Pubkey, Pubkey)>) {
+        let mut risk = PositionRisk {
+            position_mint: position.position_mint,
+            pool_address: position.pool_address,
+            lower_tick: position.lower_tick,
+            upper_tick: position.upper_tick,
+            current_tick: None,
+            liquidity: position.lp_token_amount,
+            status: PositionRangeStatus::Unknown,
+        };
+        if position.pool_address == Pubkey::default() {
+            return (risk, None);
+        }
+        let pool_info = match self
+            .get_pool_state_onchain(&position.pool_address.to_string())
+            .await
+        {
+            Ok(pool_info) => pool_info,
+            Err(_) => return (risk, None),
+        };
+        let current_tick = pool_info.current_tick_index();
+        risk.current_tick = Some(current_tick);
+        risk.status = if current_tick < position.lower_tick {
+            PositionRangeStatus::BelowRange
+        } else if current_tick >= position.upper_tick {
+            PositionRangeStatus::AboveRange
+        } else {
+            PositionRangeStatus::InRange
+        };
+        let mints = match (
+            Pubkey::from_str(&pool_info.token_mint_a),
+            Pubkey::from_str(&pool_info.token_mint_b),
+        ) {
+            (Ok(mint_a), Ok(mint_b)) => Some((mint_a, mint_b)),
+            _ => None,
+        };
+        (risk, mints)
+    }
+
+    /// Values a raw token amount in USD through USDC pools, falling back to a SOL route
+    pub(crate) async fn estimate_usd_value(&self, mint: &Pubkey, amount: u64) -> Option<f64> {
+        let mint_str = mint.to_string();
+        if mint_str == USDT_MINT {
+            return Some(to_ui_amount(amount, USDT_DECIMALS));
+        }
+        let decimals = self.get_mint_decimals(&[*mint]).await.ok()?[0];
+        let price = self.get_token_price_usd(&mint_str).await.ok()?;
+        Some(to_ui_amount(amount, decimals) * price)
+    }
+}
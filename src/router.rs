@@ -0,0 +1,1076 @@
+use std::cmp::Reverse;
+
+use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::{
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{instruction::Instruction, program_pack::Pack};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+use super::*;
+use crate::{
+    audit::AuditDetails,
+    global::{
+        MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64, SOL_MINT, TOKEN_SWAP_ACCOUNT_SIZE,
+        TOKEN_SWAP_TOKEN_MINT_A_OFFSET, TOKEN_SWAP_TOKEN_MINT_B_OFFSET, USDC_MINT, USDT_MINT,
+    },
+    legacy::{LEGACY_SWAP_PROGRAMS, LegacyPool},
+    pool::{PoolInfo, QuoteResult},
+    receipt::RouteReceipt,
+    stable::StablePool,
+    trade::{SwapParams, SwapTokenAccounts, TokenSwapParams},
+    types::OrcaResult,
+    validation::{parse_address, validate_slippage_bps, validate_swap_inputs},
+};
+
+/// Orca program family a venue belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum VenueKind {
+    /// Concentrated liquidity Whirlpools
+    Whirlpool,
+    /// StableSwap pools of the token swap program
+    Stable,
+    /// Legacy constant product pools of the token swap programs v1/v2
+    Legacy,
+}
+
+/// A pool the router can trade through
+#[derive(Debug, Clone)]
+pub enum Venue {
+    Whirlpool(Box<PoolInfo>),
+    Stable(StablePool),
+    Legacy(LegacyPool),
+}
+
+impl Venue {
+    pub fn kind(&self) -> VenueKind {
+        match self {
+            Venue::Whirlpool(_) => VenueKind::Whirlpool,
+            Venue::Stable(_) => VenueKind::Stable,
+            Venue::Legacy(_) => VenueKind::Legacy,
+        }
+    }
+
+    /// View of the venue for the price deviation guard
+    pub(crate) fn price_view(&self) -> PoolInfo {
+        match self {
+            Venue::Whirlpool(pool) => (**pool).clone(),
+            Venue::Stable(pool) => pool.price_view(),
+            Venue::Legacy(pool) => pool.price_view(),
+        }
+    }
+
+    pub fn address(&self) -> String {
+        match self {
+            Venue::Whirlpool(pool) => pool.address.clone(),
+            Venue::Stable(pool) => pool.address.to_string(),
+            Venue::Legacy(pool) => pool.address.to_string(),
+        }
+    }
+
+    /// Total swap fee in hundredths of a basis point, as in `PoolInfo::fee_rate`
+    ///
+    /// For stable and legacy pools the trade and owner trade fees are added up.
+    pub fn fee_rate(&self) -> u32 {
+        let rate = |numerator: u64, denominator: u64| {
+            if denominator == 0 {
+                0
+            } else {
+                (numerator as u128 * 1_000_000 / denominator as u128) as u32
+            }
+        };
+        match self {
+            Venue::Whirlpool(pool) => pool.fee_rate as u32,
+            Venue::Stable(pool) => {
+                rate(pool.trade_fee_numerator, pool.trade_fee_denominator)
+                    + rate(
+                        pool.owner_trade_fee_numerator,
+                        pool.owner_trade_fee_denominator,
+                    )
+            }
+            Venue::Legacy(pool) => {
+                rate(pool.trade_fee_numerator, pool.trade_fee_denominator)
+                    + rate(
+                        pool.owner_trade_fee_numerator,
+                        pool.owner_trade_fee_denominator,
+                    )
+            }
+        }
+    }
+}
+
+/// Quote of one pool of a pair, for comparing venues
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PoolQuote {
+    pub pool: String,
+    pub kind: VenueKind,
+    /// Swap fee in hundredths of a basis point
+    pub fee_rate: u32,
+    /// Tick spacing of Whirlpools; None for stable and legacy pools
+    pub tick_spacing: Option<u16>,
+    pub quote: QuoteResult,
+}
+
+/// Part of a route executed through one venue
+#[derive(Debug, Clone)]
+pub struct RouteLeg {
+    pub venue: Venue,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    /// Exact-input quote of the leg; its minimum output is enforced on-chain
+    pub quote: QuoteResult,
+}
+
+/// Execution plan found by the router
+///
+/// Legs are executed in order in one transaction. A direct route splits the input across legs
+/// that all trade `input_mint` for `output_mint`; a two-hop route first trades into an
+/// intermediate token, then trades that token for `output_mint`.
+#[derive(Debug, Clone)]
+pub struct Route {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub legs: Vec<RouteLeg>,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    /// Sum of the minimum outputs of the legs paying out `output_mint`
+    pub min_output_amount: u64,
+}
+
+impl Route {
+    fn new(input_mint: Pubkey, output_mint: Pubkey, legs: Vec<RouteLeg>) -> Self {
+        let first_hop = || legs.iter().filter(|leg| leg.input_mint == input_mint);
+        let last_hop = || legs.iter().filter(|leg| leg.output_mint == output_mint);
+        Self {
+            input_mint,
+            output_mint,
+            input_amount: first_hop().map(|leg| leg.quote.input_amount).sum(),
+            output_amount: last_hop().map(|leg| leg.quote.output_amount).sum(),
+            min_output_amount: last_hop().map(|leg| leg.quote.min_output_amount).sum(),
+            legs,
+        }
+    }
+
+    /// Chains two routes, the first paying out the token the second spends
+    fn chain(first: Route, second: Route) -> Self {
+        let mut legs = first.legs;
+        legs.extend(second.legs);
+        Self::new(first.input_mint, second.output_mint, legs)
+    }
+
+    /// Tokens the route passes through between its input and output, in order
+    pub fn intermediate_mints(&self) -> Vec<Pubkey> {
+        let mut mints: Vec<Pubkey> = Vec::new();
+        for leg in &self.legs {
+            if leg.output_mint != self.output_mint && !mints.contains(&leg.output_mint) {
+                mints.push(leg.output_mint);
+            }
+        }
+        mints
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RouterConfig {
+    /// Slippage tolerance in basis points (50 = 0.5%), from 0 to 10_000, applied to every leg
+    pub slippage_bps: u16,
+    /// Program families searched for pools
+    pub venues: Vec<VenueKind>,
+    /// Maximum number of venues the input may be split across; 1 disables splitting
+    pub max_splits: usize,
+    /// Number of equal parts the input is divided into when splitting
+    pub split_parts: u32,
+    /// Intermediate tokens tried for two-hop routes; empty disables multi-hop routing
+    pub connectors: Vec<String>,
+}
+
+impl Default for RouterConfig {
+    fn default() -> Self {
+        Self {
+            slippage_bps: 50,
+            venues: vec![VenueKind::Whirlpool, VenueKind::Stable, VenueKind::Legacy],
+            max_splits: 1,
+            split_parts: 10,
+            connectors: vec![
+                SOL_MINT.to_string(),
+                USDC_MINT.to_string(),
+                USDT_MINT.to_string(),
+            ],
+        }
+    }
+}
+
+/// Settings for splitting one order across several Whirlpools of the same pair
+#[derive(Debug, Clone)]
+pub struct SplitConfig {
+    /// Slippage tolerance in basis points (50 = 0.5%), from 0 to 10_000, applied to every leg
+    pub slippage_bps: u16,
+    /// Maximum number of pools the input may be split across
+    pub max_pools: usize,
+    /// Number of equal parts the input is divided into
+    pub split_parts: u32,
+    /// Address lookup tables to compile the swap against; when set, a v0 transaction is sent
+    pub lookup_tables: Vec<Pubkey>,
+    /// Priority fee paid per compute unit, in micro-lamports
+    pub priority_fee_microlamports: Option<u64>,
+    /// Compute unit limit requested for the transaction
+    pub compute_unit_limit: Option<u32>,
+}
+
+impl SplitConfig {
+    fn router_config(&self) -> RouterConfig {
+        RouterConfig {
+            slippage_bps: self.slippage_bps,
+            venues: vec![VenueKind::Whirlpool],
+            max_splits: self.max_pools,
+            split_parts: self.split_parts,
+            connectors: Vec::new(),
+        }
+    }
+}
+
+impl Default for SplitConfig {
+    fn default() -> Self {
+        Self {
+            slippage_bps: 50,
+            max_pools: 3,
+            split_parts: 20,
+            lookup_tables: Vec::new(),
+            priority_fee_microlamports: None,
+            compute_unit_limit: None,
+        }
+    }
+}
+
+impl OrcaClient {
+    /// Finds the pools trading a pair across the requested Orca program families
+    ///
+    /// Whirlpools without active liquidity and pools excluded by the spend policy are skipped.
+    /// Stable and legacy pools come with their current reserves.
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::router::VenueKind;
+    ///
+    /// let venues = client
+    ///     .find_venues(
+    ///         "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    ///         "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+    ///         &[VenueKind::Whirlpool, VenueKind::Stable],
+    ///     )
+    ///     .await?;
+    /// for venue in &venues {
+    ///     println!("{:?} {}", venue.kind(), venue.address());
+    /// }
+    /// ```
+    pub async fn find_venues(
+        &self,
+        mint_x: &str,
+        mint_y: &str,
+        kinds: &[VenueKind],
+    ) -> OrcaResult<Vec<Venue>> {
+        self.with_timeout("find venues", async {
+            let mut venues = Vec::new();
+            if kinds.contains(&VenueKind::Whirlpool) {
+                let addresses = self.find_pools_for_pair(mint_x, mint_y).await?;
+                venues.extend(
+                    self.get_pool_states(&addresses)
+                        .await?
+                        .into_iter()
+                        .filter(|pool| pool.liquidity > 0)
+                        .map(|pool| Venue::Whirlpool(Box::new(pool))),
+                );
+            }
+            if kinds.contains(&VenueKind::Stable) || kinds.contains(&VenueKind::Legacy) {
+                let addresses = self.find_token_swap_pools_for_pair(mint_x, mint_y).await?;
+                venues.extend(
+                    self.get_token_swap_venues(&addresses)
+                        .await?
+                        .into_iter()
+                        .filter(|venue| kinds.contains(&venue.kind())),
+                );
+            }
+            Ok(venues
+                .into_iter()
+                .filter(|venue| self.check_pool_policy(&venue.address()).is_ok())
+                .collect())
+        })
+        .await
+    }
+
+    /// Quotes an exact-input swap of `amount` through a venue
+    pub async fn quote_venue(
+        &self,
+        venue: &Venue,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        slippage_bps: u16,
+    ) -> OrcaResult<QuoteResult> {
+        validate_slippage_bps(slippage_bps)?;
+        match venue {
+            Venue::Whirlpool(pool) => {
+                self.calculate_quote_from_pool_state(
+                    pool,
+                    input_mint,
+                    output_mint,
+                    amount,
+                    true,
+                    slippage_bps,
+                )
+                .await
+            }
+            Venue::Stable(pool) => pool.quote(
+                &parse_address("input_mint", input_mint)?,
+                amount,
+                slippage_bps,
+            ),
+            Venue::Legacy(pool) => pool.quote(
+                &parse_address("input_mint", input_mint)?,
+                amount,
+                slippage_bps,
+            ),
+        }
+    }
+
+    /// Quotes an exact-input swap through every pool of the pair, best output first
+    ///
+    /// Whirlpools, stable and legacy pools are all quoted; pools that cannot fill the amount
+    /// are left out.
+    ///
+    /// # Example
+    /// ```rust
+    /// let quotes = client
+    ///     .get_all_quotes(
+    ///         "So11111111111111111111111111111111111111112",
+    ///         "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    ///         1_000_000_000,
+    ///         50,
+    ///     )
+    ///     .await?;
+    /// for quote in &quotes {
+    ///     println!(
+    ///         "{} {:?} fee {} -> {} ({} bps impact)",
+    ///         quote.pool, quote.kind, quote.fee_rate, quote.quote.output_amount, quote.quote.price_impact_bps
+    ///     );
+    /// }
+    /// ```
+    pub async fn get_all_quotes(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        slippage_bps: u16,
+    ) -> OrcaResult<Vec<PoolQuote>> {
+        self.with_timeout("get all quotes", async {
+            validate_swap_inputs(input_mint, output_mint, amount, slippage_bps)?;
+            let venues = self
+                .find_venues(input_mint, output_mint, &RouterConfig::default().venues)
+                .await?;
+            let quotes = futures::future::join_all(venues.iter().map(|venue| {
+                self.quote_venue(venue, input_mint, output_mint, amount, slippage_bps)
+            }))
+            .await;
+            let mut pool_quotes: Vec<PoolQuote> = venues
+                .iter()
+                .zip(quotes)
+                .filter_map(|(venue, quote)| {
+                    Some(PoolQuote {
+                        pool: venue.address(),
+                        kind: venue.kind(),
+                        fee_rate: venue.fee_rate(),
+                        tick_spacing: match venue {
+                            Venue::Whirlpool(pool) => Some(pool.tick_spacing),
+                            _ => None,
+                        },
+                        quote: quote.ok()?,
+                    })
+                })
+                .collect();
+            pool_quotes.sort_by_key(|candidate| Reverse(candidate.quote.output_amount));
+            Ok(pool_quotes)
+        })
+        .await
+    }
+
+    /// Finds the route giving the most output for an exact input
+    ///
+    /// Every pool of the pair in the configured program families is quoted. When splitting is
+    /// enabled, the input is handed out in `split_parts` equal parts, each to the venue among
+    /// the `max_splits` best whose output grows the most from it; the split is kept only when
+    /// it beats the best single venue.
+    ///
+    /// Two-hop routes through each connector token are quoted as well, so pairs without a
+    /// direct pool can still be traded. The second hop spends the first hop's minimum output,
+    /// which the first hop always delivers; any intermediate tokens received above it stay in
+    /// the owner's token account.
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::router::RouterConfig;
+    ///
+    /// let route = client
+    ///     .find_best_route(
+    ///         "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    ///         "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+    ///         100_000_000_000,
+    ///         Some(RouterConfig { max_splits: 3, ..Default::default() }),
+    ///     )
+    ///     .await?;
+    /// for leg in &route.legs {
+    ///     println!("{:?} {}: {}", leg.venue.kind(), leg.venue.address(), leg.quote.output_amount);
+    /// }
+    /// ```
+    #[tracing::instrument(skip(self, config), err(Debug))]
+    pub async fn find_best_route(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        config: Option<RouterConfig>,
+    ) -> OrcaResult<Route> {
+        self.with_timeout("find route", async {
+            let config = config.unwrap_or_default();
+            validate_swap_inputs(input_mint, output_mint, amount, config.slippage_bps)?;
+            let mut best = self
+                .find_direct_route(input_mint, output_mint, amount, &config)
+                .await
+                .ok();
+            for connector in &config.connectors {
+                if connector == input_mint || connector == output_mint {
+                    continue;
+                }
+                let Ok(first) = self
+                    .find_direct_route(input_mint, connector, amount, &config)
+                    .await
+                else {
+                    continue;
+                };
+                let Ok(second) = self
+                    .find_direct_route(connector, output_mint, first.min_output_amount, &config)
+                    .await
+                else {
+                    continue;
+                };
+                let route = Route::chain(first, second);
+                if best
+                    .as_ref()
+                    .is_none_or(|best| route.output_amount > best.output_amount)
+                {
+                    best = Some(route);
+                }
+            }
+            best.ok_or_else(|| OrcaError::NoRoute {
+                input_mint: input_mint.to_string(),
+                output_mint: output_mint.to_string(),
+            })
+        })
+        .await
+    }
+
+    /// Finds the best single venue or split for a pair traded directly
+    #[tracing::instrument(skip(self, config), fields(venues = tracing::field::Empty), err(Debug))]
+    async fn find_direct_route(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        config: &RouterConfig,
+    ) -> OrcaResult<Route> {
+        let input_mint_pubkey = parse_address("input_mint", input_mint)?;
+        let output_mint_pubkey = parse_address("output_mint", output_mint)?;
+        let venues = self
+            .find_venues(input_mint, output_mint, &config.venues)
+            .await?;
+        let quotes = futures::future::join_all(venues.iter().map(|venue| {
+            self.quote_venue(venue, input_mint, output_mint, amount, config.slippage_bps)
+        }))
+        .await;
+        let mut candidates: Vec<RouteLeg> = venues
+            .into_iter()
+            .zip(quotes)
+            .filter_map(|(venue, quote)| {
+                Some(RouteLeg {
+                    venue,
+                    input_mint: input_mint_pubkey,
+                    output_mint: output_mint_pubkey,
+                    quote: quote.ok()?,
+                })
+            })
+            .collect();
+        tracing::Span::current().record("venues", candidates.len());
+        candidates.sort_by_key(|candidate| Reverse(candidate.quote.output_amount));
+        candidates.truncate(config.max_splits.max(1));
+        let best = candidates
+            .first()
+            .cloned()
+            .ok_or_else(|| OrcaError::NoRoute {
+                input_mint: input_mint.to_string(),
+                output_mint: output_mint.to_string(),
+            })?;
+        let mut route = Route::new(input_mint_pubkey, output_mint_pubkey, vec![best]);
+        if candidates.len() > 1 {
+            let legs = self
+                .split_route(candidates, input_mint, output_mint, amount, config)
+                .await;
+            let split = Route::new(input_mint_pubkey, output_mint_pubkey, legs);
+            if split.output_amount > route.output_amount {
+                route = split;
+            }
+        }
+        Ok(route)
+    }
+
+    /// Builds the instructions of a route, ready to be signed by `owner`
+    ///
+    /// The output token account and the token accounts of intermediate tokens are created
+    /// idempotently ahead of the legs.
+    pub async fn build_route_ixs(
+        &self,
+        owner: &Pubkey,
+        route: &Route,
+    ) -> OrcaResult<Vec<Instruction>> {
+        let mut mints = vec![route.input_mint];
+        mints.extend(route.intermediate_mints());
+        mints.push(route.output_mint);
+        let mint_infos = self.get_mint_infos(&mints).await?;
+        let token_program = |mint: &Pubkey| {
+            mint_infos
+                .iter()
+                .find(|info| info.mint == *mint)
+                .map(|info| info.token_program)
+                .ok_or(OrcaError::AccountNotFound(*mint))
+        };
+        let mut instructions = Vec::new();
+        for info in &mint_infos[1..] {
+            instructions.push(create_associated_token_account_idempotent(
+                owner,
+                owner,
+                &info.mint,
+                &info.token_program,
+            ));
+        }
+        for leg in &route.legs {
+            let input_token_program = token_program(&leg.input_mint)?;
+            let output_token_program = token_program(&leg.output_mint)?;
+            let source = self.get_associated_token_address_with_program(
+                owner,
+                &leg.input_mint,
+                &input_token_program,
+            );
+            let destination = self.get_associated_token_address_with_program(
+                owner,
+                &leg.output_mint,
+                &output_token_program,
+            );
+            let (amount, minimum_amount_out) =
+                (leg.quote.input_amount, leg.quote.min_output_amount);
+            instructions.push(match &leg.venue {
+                Venue::Whirlpool(pool) => {
+                    let a_to_b = pool.token_mint_a == leg.input_mint.to_string();
+                    self.build_swap_instruction(
+                        owner,
+                        pool,
+                        SwapTokenAccounts {
+                            input_token_account: &source,
+                            output_token_account: &destination,
+                            input_token_program: &input_token_program,
+                            output_token_program: &output_token_program,
+                        },
+                        SwapParams {
+                            amount,
+                            other_amount_threshold: minimum_amount_out,
+                            sqrt_price_limit: if a_to_b {
+                                MIN_SQRT_PRICE_X64
+                            } else {
+                                MAX_SQRT_PRICE_X64
+                            },
+                            amount_specified_is_input: true,
+                            a_to_b,
+                        },
+                    )?
+                }
+                Venue::Stable(pool) => self.build_stable_swap_instruction(
+                    owner,
+                    pool,
+                    TokenSwapParams {
+                        source: &source,
+                        destination: &destination,
+                        a_to_b: leg.input_mint == pool.token_mint_a,
+                        amount_in: amount,
+                        minimum_amount_out,
+                    },
+                ),
+                Venue::Legacy(pool) => self.build_legacy_swap_instruction(
+                    owner,
+                    pool,
+                    TokenSwapParams {
+                        source: &source,
+                        destination: &destination,
+                        a_to_b: leg.input_mint == pool.token_mint_a,
+                        amount_in: amount,
+                        minimum_amount_out,
+                    },
+                ),
+            });
+        }
+        Ok(instructions)
+    }
+
+    /// Signs and submits a route found by `find_best_route`
+    ///
+    /// All legs execute in one transaction; each fails the whole swap when its output falls
+    /// below the leg's minimum. Intermediate tokens of two-hop routes must pass the token
+    /// policy like the output token.
+    ///
+    /// # Example
+    /// ```rust
+    /// let route = client.find_best_route(input_mint, output_mint, 1_000_000, None).await?;
+    /// let receipt = client.execute_route(&keypair, &route).await?;
+    /// println!("Swapped through {:?} in {}", receipt.pools, receipt.signature);
+    /// ```
+    #[tracing::instrument(skip(self, signer, route), fields(owner = %signer.pubkey()), err(Debug))]
+    pub async fn execute_route<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        route: &Route,
+    ) -> OrcaResult<RouteReceipt> {
+        self.submit_route(signer, route, Vec::new(), &[]).await
+    }
+
+    /// Checks policies and balance, then signs and submits a route
+    ///
+    /// `prefix` instructions, such as compute budget settings, run ahead of the route; a v0
+    /// transaction is sent when `lookup_tables` is not empty.
+    async fn submit_route<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        route: &Route,
+        prefix: Vec<Instruction>,
+        lookup_tables: &[Pubkey],
+    ) -> OrcaResult<RouteReceipt> {
+        self.with_timeout("route swap", async {
+            let owner = signer.pubkey();
+            let (input_mint, output_mint) =
+                (route.input_mint.to_string(), route.output_mint.to_string());
+            self.check_token_policy(&output_mint)?;
+            for mint in route.intermediate_mints() {
+                self.check_token_policy(&mint.to_string())?;
+            }
+            let pools = route
+                .legs
+                .iter()
+                .map(|leg| parse_address("pool_address", &leg.venue.address()))
+                .collect::<OrcaResult<Vec<Pubkey>>>()?;
+            for pool in &pools {
+                self.check_pool_policy(&pool.to_string())?;
+            }
+            let mut hops: Vec<(Pubkey, Pubkey)> = Vec::new();
+            for leg in &route.legs {
+                if !hops.contains(&(leg.input_mint, leg.output_mint)) {
+                    hops.push((leg.input_mint, leg.output_mint));
+                }
+            }
+            for (hop_input, hop_output) in hops {
+                self.enforce_token_safety(&hop_input.to_string(), &hop_output.to_string(), None)
+                    .await?;
+            }
+            for leg in &route.legs {
+                self.enforce_price_guard(
+                    None,
+                    &leg.venue.price_view(),
+                    &leg.input_mint.to_string(),
+                    leg.quote.input_amount,
+                    leg.quote.output_amount,
+                )
+                .await?;
+            }
+            let spends = [(input_mint.as_str(), route.input_amount)];
+            let reservation = self.reserve_spend(&spends).await?;
+            let available = self.get_token_balance(&owner, &route.input_mint).await?;
+            if available < route.input_amount {
+                return Err(OrcaError::InsufficientBalance {
+                    mint: route.input_mint,
+                    required: route.input_amount,
+                    available,
+                });
+            }
+            let mut instructions = prefix;
+            instructions.extend(self.build_route_ixs(&owner, route).await?);
+            let transaction = if lookup_tables.is_empty() {
+                let recent_blockhash = self.latest_blockhash().await?;
+                let message = Message::new(&instructions, Some(&owner));
+                Transaction::new(&[signer], message, recent_blockhash).into()
+            } else {
+                self.build_signed_v0_transaction(signer, &instructions, lookup_tables)
+                    .await?
+            };
+            let signature = self
+                .submit_transaction(
+                    &transaction,
+                    "route swap",
+                    &AuditDetails::new(serde_json::json!({
+                        "pools": pools.iter().map(|pool| pool.to_string()).collect::<Vec<_>>(),
+                        "input_mint": input_mint,
+                        "output_mint": output_mint,
+                        "amount": route.input_amount,
+                        "quoted_output_amount": route.output_amount,
+                        "min_output_amount": route.min_output_amount,
+                    })),
+                )
+                .await?;
+            reservation.commit();
+            Ok(RouteReceipt {
+                signature,
+                pools,
+                input_mint: route.input_mint,
+                output_mint: route.output_mint,
+                input_amount: route.input_amount,
+                quoted_output_amount: route.output_amount,
+                min_output_amount: route.min_output_amount,
+            })
+        })
+        .await
+    }
+
+    /// Finds the best route for an exact input and executes it
+    ///
+    /// # Example
+    /// ```rust
+    /// let receipt = client
+    ///     .swap_best_route(&keypair, input_mint, output_mint, 1_000_000, None)
+    ///     .await?;
+    /// ```
+    pub async fn swap_best_route<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        config: Option<RouterConfig>,
+    ) -> OrcaResult<RouteReceipt> {
+        let route = self
+            .find_best_route(input_mint, output_mint, amount, config)
+            .await?;
+        self.execute_route(signer, &route).await
+    }
+
+    /// Quotes an exact-input order split across the Whirlpools of a pair
+    ///
+    /// Pools of the same pair with different tick spacings and fee tiers are quoted together and
+    /// the input is spread over up to `max_pools` of them; when one pool gives the most output
+    /// the route has a single leg.
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::router::SplitConfig;
+    ///
+    /// let route = client
+    ///     .quote_split(sol_mint, usdc_mint, 5_000_000_000_000, Some(SplitConfig::default()))
+    ///     .await?;
+    /// for leg in &route.legs {
+    ///     println!("{}: {} -> {}", leg.venue.address(), leg.quote.input_amount, leg.quote.output_amount);
+    /// }
+    /// ```
+    pub async fn quote_split(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        config: Option<SplitConfig>,
+    ) -> OrcaResult<Route> {
+        let config = config.unwrap_or_default();
+        self.find_best_route(
+            input_mint,
+            output_mint,
+            amount,
+            Some(config.router_config()),
+        )
+        .await
+    }
+
+    /// Splits an exact-input order across the Whirlpools of a pair and executes it atomically
+    ///
+    /// All legs are sent in one transaction. Three or more Whirlpool legs usually exceed the
+    /// legacy transaction size; pass lookup tables holding the pools' accounts in that case.
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::router::SplitConfig;
+    ///
+    /// let config = SplitConfig {
+    ///     max_pools: 3,
+    ///     lookup_tables: vec![lookup_table],
+    ///     ..Default::default()
+    /// };
+    /// let receipt = client
+    ///     .swap_split(&keypair, sol_mint, usdc_mint, 5_000_000_000_000, Some(config))
+    ///     .await?;
+    /// println!("Split across {:?}", receipt.pools);
+    /// ```
+    #[tracing::instrument(skip(self, signer, config), fields(owner = %signer.pubkey()), err(Debug))]
+    pub async fn swap_split<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        config: Option<SplitConfig>,
+    ) -> OrcaResult<RouteReceipt> {
+        let config = config.unwrap_or_default();
+        let route = self
+            .find_best_route(
+                input_mint,
+                output_mint,
+                amount,
+                Some(config.router_config()),
+            )
+            .await?;
+        let prefix = self.build_compute_budget_instructions(
+            config.compute_unit_limit,
+            config.priority_fee_microlamports,
+        )?;
+        self.submit_route(signer, &route, prefix, &config.lookup_tables)
+            .await
+    }
+
+    /// Returns the addresses of all stable and legacy pools trading the pair, in either mint order
+    pub async fn find_token_swap_pools_for_pair(
+        &self,
+        mint_x: &str,
+        mint_y: &str,
+    ) -> OrcaResult<Vec<String>> {
+        let cache_key = if mint_x < mint_y {
+            format!("token_swap:{}:{}", mint_x, mint_y)
+        } else {
+            format!("token_swap:{}:{}", mint_y, mint_x)
+        };
+        if let Some(pools) = self.cached_pool_addresses(&cache_key) {
+            return Ok(pools);
+        }
+        let mint_x_pubkey = parse_address("mint_x", mint_x)?;
+        let mint_y_pubkey = parse_address("mint_y", mint_y)?;
+        let mut programs = vec![self.stable_swap_program_id];
+        programs.extend(
+            LEGACY_SWAP_PROGRAMS
+                .iter()
+                .filter(|program| **program != self.stable_swap_program_id),
+        );
+        let mut pools = Vec::new();
+        for program in &programs {
+            for (mint_a, mint_b) in [
+                (&mint_x_pubkey, &mint_y_pubkey),
+                (&mint_y_pubkey, &mint_x_pubkey),
+            ] {
+                let filters = vec![
+                    RpcFilterType::DataSize(TOKEN_SWAP_ACCOUNT_SIZE as u64),
+                    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                        TOKEN_SWAP_TOKEN_MINT_A_OFFSET,
+                        &mint_a.to_bytes(),
+                    )),
+                    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                        TOKEN_SWAP_TOKEN_MINT_B_OFFSET,
+                        &mint_b.to_bytes(),
+                    )),
+                ];
+                let config = RpcProgramAccountsConfig {
+                    filters: Some(filters),
+                    account_config: RpcAccountInfoConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        // Only the addresses are used
+                        data_slice: Some(UiDataSliceConfig {
+                            offset: 0,
+                            length: 0,
+                        }),
+                        commitment: Some(CommitmentConfig::confirmed()),
+                        min_context_slot: None,
+                    },
+                    with_context: None,
+                    sort_results: None,
+                };
+                let accounts = self
+                    .rpc_call("Failed to get program accounts", |client| {
+                        client.get_program_accounts_with_config(program, config.clone())
+                    })
+                    .await?;
+                pools.extend(accounts.iter().map(|(pubkey, _account)| pubkey.to_string()));
+            }
+        }
+        self.cache_pool_addresses(&cache_key, &pools);
+        Ok(pools)
+    }
+
+    /// Fetches stable and legacy pools with their reserves, skipping accounts that are neither
+    ///
+    /// Pool accounts, then all vaults and LP mints, are each fetched in batches of 100.
+    async fn get_token_swap_venues(&self, pool_addresses: &[String]) -> OrcaResult<Vec<Venue>> {
+        if pool_addresses.is_empty() {
+            return Ok(Vec::new());
+        }
+        let pubkeys = pool_addresses
+            .iter()
+            .map(|address| parse_address("pool_address", address))
+            .collect::<OrcaResult<Vec<Pubkey>>>()?;
+        let accounts = self
+            .get_multiple_accounts_batched(&pubkeys, "Failed to get accounts")
+            .await?;
+        let venues: Vec<Venue> = pubkeys
+            .into_iter()
+            .zip(accounts)
+            .filter_map(|(address, account)| {
+                let account = account?;
+                // The stable program is also legacy v2; the curve type tells the pools apart
+                let stable = if account.owner == self.stable_swap_program_id {
+                    StablePool::decode(address, &account.data, &account.owner)
+                } else {
+                    None
+                };
+                if let Some(pool) = stable {
+                    return Some(Venue::Stable(pool));
+                }
+                if !LEGACY_SWAP_PROGRAMS.contains(&account.owner) {
+                    return None;
+                }
+                LegacyPool::decode(address, &account.data, &account.owner).map(Venue::Legacy)
+            })
+            .collect();
+        let state_accounts: Vec<Pubkey> = venues
+            .iter()
+            .flat_map(|venue| match venue {
+                Venue::Stable(pool) => [pool.token_vault_a, pool.token_vault_b, pool.pool_mint],
+                Venue::Legacy(pool) => [pool.token_vault_a, pool.token_vault_b, pool.pool_mint],
+                Venue::Whirlpool(_) => unreachable!("only token swap pools are decoded"),
+            })
+            .collect();
+        let states = self
+            .get_multiple_accounts_batched(&state_accounts, "Failed to get pool vaults")
+            .await?;
+        let mut loaded = Vec::with_capacity(venues.len());
+        for (mut venue, state) in venues.into_iter().zip(states.chunks(3)) {
+            let [Some(vault_a), Some(vault_b), Some(pool_mint)] = state else {
+                continue;
+            };
+            let reserve_a = Self::unpack_token_account(&vault_a.data)?.amount;
+            let reserve_b = Self::unpack_token_account(&vault_b.data)?.amount;
+            match &mut venue {
+                Venue::Stable(pool) => (pool.reserve_a, pool.reserve_b) = (reserve_a, reserve_b),
+                Venue::Legacy(pool) => {
+                    (pool.reserve_a, pool.reserve_b) = (reserve_a, reserve_b);
+                    pool.pool_token_supply = pool_mint
+                        .data
+                        .get(..spl_token::state::Mint::LEN)
+                        .and_then(|data| spl_token::state::Mint::unpack_from_slice(data).ok())
+                        .map_or(0, |mint| mint.supply);
+                }
+                Venue::Whirlpool(_) => {}
+            }
+            loaded.push(venue);
+        }
+        let mints: Vec<Pubkey> = loaded
+            .iter()
+            .flat_map(|venue| match venue {
+                Venue::Stable(pool) => [pool.token_mint_a, pool.token_mint_b],
+                Venue::Legacy(pool) => [pool.token_mint_a, pool.token_mint_b],
+                Venue::Whirlpool(_) => unreachable!("only token swap pools are decoded"),
+            })
+            .collect();
+        let decimals = self.get_mint_decimals(&mints).await?;
+        for (venue, decimals) in loaded.iter_mut().zip(decimals.chunks(2)) {
+            match venue {
+                Venue::Stable(pool) => {
+                    (pool.decimals_a, pool.decimals_b) = (decimals[0], decimals[1])
+                }
+                Venue::Legacy(pool) => {
+                    (pool.decimals_a, pool.decimals_b) = (decimals[0], decimals[1])
+                }
+                Venue::Whirlpool(_) => {}
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Greedily splits `amount` across the candidate venues in equal parts
+    ///
+    /// Each part goes to the venue whose output grows the most from it, so only the venue that
+    /// received the previous part is re-quoted. Returns no legs when a part cannot be placed.
+    async fn split_route(
+        &self,
+        candidates: Vec<RouteLeg>,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        config: &RouterConfig,
+    ) -> Vec<RouteLeg> {
+        let parts = (config.split_parts.max(2) as u64).min(amount);
+        let part_amount = |count: u64| (amount as u128 * count as u128 / parts as u128) as u64;
+        let mut allocations = vec![0u64; candidates.len()];
+        let mut current: Vec<Option<QuoteResult>> = vec![None; candidates.len()];
+        let mut next: Vec<Option<Option<QuoteResult>>> = vec![None; candidates.len()];
+        for _ in 0..parts {
+            let mut best: Option<(usize, u64)> = None;
+            for (index, candidate) in candidates.iter().enumerate() {
+                if next[index].is_none() {
+                    let quote = self
+                        .quote_venue(
+                            &candidate.venue,
+                            input_mint,
+                            output_mint,
+                            part_amount(allocations[index] + 1),
+                            config.slippage_bps,
+                        )
+                        .await;
+                    next[index] = Some(quote.ok());
+                }
+                if let Some(Some(quote)) = &next[index] {
+                    let gain = quote
+                        .output_amount
+                        .saturating_sub(current[index].as_ref().map_or(0, |q| q.output_amount));
+                    if best.is_none_or(|(_, best_gain)| gain > best_gain) {
+                        best = Some((index, gain));
+                    }
+                }
+            }
+            let Some((index, _)) = best else {
+                return Vec::new();
+            };
+            allocations[index] += 1;
+            current[index] = next[index].take().flatten();
+        }
+        // Parts are rounded down; the largest leg takes the remainder
+        let allocated: u64 = allocations.iter().map(|count| part_amount(*count)).sum();
+        let remainder = amount - allocated;
+        let largest = (0..candidates.len())
+            .max_by_key(|index| allocations[*index])
+            .unwrap_or_default();
+        let mut legs = Vec::new();
+        for (index, candidate) in candidates.into_iter().enumerate() {
+            if allocations[index] == 0 {
+                continue;
+            }
+            let quote = if index == largest && remainder > 0 {
+                match self
+                    .quote_venue(
+                        &candidate.venue,
+                        input_mint,
+                        output_mint,
+                        part_amount(allocations[index]) + remainder,
+                        config.slippage_bps,
+                    )
+                    .await
+                {
+                    Ok(quote) => quote,
+                    Err(_) => return Vec::new(),
+                }
+            } else {
+                match current[index].take() {
+                    Some(quote) => quote,
+                    None => return Vec::new(),
+                }
+            };
+            legs.push(RouteLeg { quote, ..candidate });
+        }
+        legs
+    }
+}
@@ -0,0 +1,301 @@
+use solana_sdk::program_pack::Pack;
+
+use super::*;
+use crate::{
+    global::{SOL_MINT, USDC_MINT},
+    token::{EXTENSION_TRANSFER_FEE_CONFIG, TOKEN_2022_PROGRAM, token_2022_mint_extensions},
+    types::OrcaResult,
+    validation::parse_address,
+};
+
+// Token-2022 extension type discriminators
+const EXTENSION_MINT_CLOSE_AUTHORITY: u16 = 3;
+const EXTENSION_DEFAULT_ACCOUNT_STATE: u16 = 6;
+const EXTENSION_NON_TRANSFERABLE: u16 = 9;
+const EXTENSION_PERMANENT_DELEGATE: u16 = 12;
+const EXTENSION_TRANSFER_HOOK: u16 = 14;
+const EXTENSION_PAUSABLE: u16 = 26;
+const ACCOUNT_STATE_FROZEN: u8 = 2;
+// Amount used to probe for sell-back liquidity
+const SELL_BACK_PROBE_AMOUNT: u64 = 1_000;
+
+/// Individual findings of a token safety inspection
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenRiskFlag {
+    /// New supply can still be minted
+    MintAuthorityActive(Pubkey),
+    /// Token accounts can be frozen by an authority
+    FreezeAuthorityActive(Pubkey),
+    /// Transfers invoke an external program
+    TransferHook(Pubkey),
+    /// A delegate can move or burn tokens from any account
+    PermanentDelegate(Pubkey),
+    /// New token accounts start frozen
+    DefaultAccountFrozen,
+    /// Tokens cannot be transferred at all
+    NonTransferable,
+    /// Transfers are charged a fee
+    TransferFee { basis_points: u16 },
+    /// An authority can pause all transfers
+    Pausable,
+    /// The mint account can be closed
+    MintCloseAuthority(Pubkey),
+    /// No pool was found to sell the token back
+    NoExitLiquidity,
+}
+
+impl TokenRiskFlag {
+    /// Severity contributed by this finding
+    pub fn level(&self) -> TokenRiskLevel {
+        match self {
+            TokenRiskFlag::TransferHook(_)
+            | TokenRiskFlag::PermanentDelegate(_)
+            | TokenRiskFlag::DefaultAccountFrozen
+            | TokenRiskFlag::NonTransferable
+            | TokenRiskFlag::Pausable
+            | TokenRiskFlag::NoExitLiquidity => TokenRiskLevel::High,
+            TokenRiskFlag::MintAuthorityActive(_)
+            | TokenRiskFlag::FreezeAuthorityActive(_)
+            | TokenRiskFlag::TransferFee { .. }
+            | TokenRiskFlag::MintCloseAuthority(_) => TokenRiskLevel::Medium,
+        }
+    }
+}
+
+/// Overall risk classification of a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TokenRiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+/// Options for a token safety inspection
+#[derive(Debug, Clone, Default)]
+pub struct TokenSafetyOptions {
+    /// Verify that a pool exists to sell the token back into this mint
+    pub sell_back_mint: Option<String>,
+}
+
+/// Result of inspecting a token mint
+#[derive(Debug, Clone)]
+pub struct TokenSafetyReport {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub decimals: u8,
+    pub supply: u64,
+    pub mint_authority: Option<Pubkey>,
+    pub freeze_authority: Option<Pubkey>,
+    pub flags: Vec<TokenRiskFlag>,
+    pub risk_level: TokenRiskLevel,
+}
+
+impl TokenSafetyReport {
+    /// Returns true when the risk level does not exceed `max_level`
+    pub fn is_acceptable(&self, max_level: TokenRiskLevel) -> bool {
+        self.risk_level <= max_level
+    }
+}
+
+impl OrcaClient {
+    /// Inspects a mint for authorities and Token-2022 extensions that put holders at risk
+    ///
+    /// # Params
+    /// mint - Token mint address
+    ///
+    /// # Example
+    /// ```rust
+    /// let report = client.check_token_safety("MintAddressHere").await?;
+    /// for flag in &report.flags {
+    ///     println!("{:?}", flag);
+    /// }
+    /// println!("Risk level: {:?}", report.risk_level);
+    /// ```
+    pub async fn check_token_safety(&self, mint: &str) -> OrcaResult<TokenSafetyReport> {
+        self.check_token_safety_with_options(mint, &TokenSafetyOptions::default())
+            .await
+    }
+
+    /// Inspects a mint and optionally verifies that it can be sold back through a pool
+    ///
+    /// # Params
+    /// mint - Token mint address
+    /// options - Inspection options such as the sell-back mint
+    ///
+    /// # Example
+    /// ```rust
+    /// let options = TokenSafetyOptions {
+    ///     sell_back_mint: Some("So11111111111111111111111111111111111111112".to_string()),
+    /// };
+    /// let report = client.check_token_safety_with_options("MintAddressHere", &options).await?;
+    /// ```
+    pub async fn check_token_safety_with_options(
+        &self,
+        mint: &str,
+        options: &TokenSafetyOptions,
+    ) -> OrcaResult<TokenSafetyReport> {
+        self.with_timeout("check token safety", async {
+            let mint_pubkey = parse_address("mint", mint)?;
+            let account = self
+                .get_existing_account(&mint_pubkey, "Failed to get mint account")
+                .await?;
+            let mint_data = account
+                .data
+                .get(..spl_token::state::Mint::LEN)
+                .and_then(|data| spl_token::state::Mint::unpack_from_slice(data).ok())
+                .ok_or_else(|| OrcaError::InvalidAccountData {
+                    account: mint_pubkey,
+                    reason: "not an SPL token mint".to_string(),
+                })?;
+            let mint_authority: Option<Pubkey> = mint_data.mint_authority.into();
+            let freeze_authority: Option<Pubkey> = mint_data.freeze_authority.into();
+            let mut flags = Vec::new();
+            if let Some(authority) = mint_authority {
+                flags.push(TokenRiskFlag::MintAuthorityActive(authority));
+            }
+            if let Some(authority) = freeze_authority {
+                flags.push(TokenRiskFlag::FreezeAuthorityActive(authority));
+            }
+            if account.owner == TOKEN_2022_PROGRAM {
+                flags.extend(Self::parse_token_2022_extension_flags(&account.data));
+            }
+            if let Some(sell_back_mint) = &options.sell_back_mint
+                && sell_back_mint != mint
+                && !self.has_exit_liquidity(mint, sell_back_mint).await
+            {
+                flags.push(TokenRiskFlag::NoExitLiquidity);
+            }
+            let risk_level = flags
+                .iter()
+                .map(TokenRiskFlag::level)
+                .max()
+                .unwrap_or(TokenRiskLevel::Low);
+            Ok(TokenSafetyReport {
+                mint: mint_pubkey,
+                token_program: account.owner,
+                decimals: mint_data.decimals,
+                supply: mint_data.supply,
+                mint_authority,
+                freeze_authority,
+                flags,
+                risk_level,
+            })
+        })
+        .await
+    }
+
+    /// Sets the client-wide token risk limit applied to the output token of every swap
+    ///
+    /// Covers Whirlpool, route, stable, legacy and Jupiter swaps as well as the swap legs of
+    /// zaps. A level passed through `TradeConfig::max_token_risk` takes precedence for that
+    /// call.
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::safety::TokenRiskLevel;
+    ///
+    /// client.set_max_token_risk(Some(TokenRiskLevel::Medium));
+    /// ```
+    pub fn set_max_token_risk(&self, max_level: Option<TokenRiskLevel>) {
+        if let Ok(mut current) = self.max_token_risk.write() {
+            *current = max_level;
+        }
+    }
+
+    /// Returns the client-wide token risk limit
+    pub fn max_token_risk(&self) -> Option<TokenRiskLevel> {
+        self.max_token_risk.read().ok().and_then(|level| *level)
+    }
+
+    /// Rejects a swap output token whose risk exceeds `max_level`, or the client-wide limit
+    /// when no level is given
+    ///
+    /// The honeypot heuristic checks that the output can be sold back into the input mint.
+    /// Every swap entry point calls this before building its transaction.
+    pub(crate) async fn enforce_token_safety(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        max_level: Option<TokenRiskLevel>,
+    ) -> OrcaResult<()> {
+        let Some(max_level) = max_level.or_else(|| self.max_token_risk()) else {
+            return Ok(());
+        };
+        let report = self
+            .check_token_safety_with_options(
+                output_mint,
+                &TokenSafetyOptions {
+                    sell_back_mint: Some(input_mint.to_string()),
+                },
+            )
+            .await?;
+        if !report.is_acceptable(max_level) {
+            return Err(OrcaError::UnsafeToken(Box::new(report)));
+        }
+        Ok(())
+    }
+
+    /// Checks whether a small amount of `mint` can be quoted back into `exit_mint`
+    async fn has_exit_liquidity(&self, mint: &str, exit_mint: &str) -> bool {
+        for candidate in [exit_mint, SOL_MINT, USDC_MINT] {
+            if candidate == mint {
+                continue;
+            }
+            if let Ok(quote) = self
+                .get_quote_from_pool(mint, candidate, SELL_BACK_PROBE_AMOUNT, 10_000, None)
+                .await
+                && quote.output_amount > 0
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Maps the Token-2022 extensions of a mint account to risk flags
+    fn parse_token_2022_extension_flags(data: &[u8]) -> Vec<TokenRiskFlag> {
+        let mut flags = Vec::new();
+        for (extension_type, value) in token_2022_mint_extensions(data) {
+            let optional_pubkey = |at: usize| -> Option<Pubkey> {
+                let bytes: [u8; 32] = value.get(at..at + 32)?.try_into().ok()?;
+                let pubkey = Pubkey::new_from_array(bytes);
+                (pubkey != Pubkey::default()).then_some(pubkey)
+            };
+            match extension_type {
+                EXTENSION_TRANSFER_FEE_CONFIG => {
+                    // authority(32) + withdraw authority(32) + withheld(8) + older fee(18) + newer fee(18);
+                    // each fee is epoch(8) + maximum_fee(8) + basis_points(2)
+                    if let Some(bytes) = value.get(106..108) {
+                        let basis_points = u16::from_le_bytes([bytes[0], bytes[1]]);
+                        if basis_points > 0 {
+                            flags.push(TokenRiskFlag::TransferFee { basis_points });
+                        }
+                    }
+                }
+                EXTENSION_MINT_CLOSE_AUTHORITY => {
+                    if let Some(authority) = optional_pubkey(0) {
+                        flags.push(TokenRiskFlag::MintCloseAuthority(authority));
+                    }
+                }
+                EXTENSION_DEFAULT_ACCOUNT_STATE if value.first() == Some(&ACCOUNT_STATE_FROZEN) => {
+                    flags.push(TokenRiskFlag::DefaultAccountFrozen);
+                }
+                EXTENSION_NON_TRANSFERABLE => flags.push(TokenRiskFlag::NonTransferable),
+                EXTENSION_PERMANENT_DELEGATE => {
+                    if let Some(delegate) = optional_pubkey(0) {
+                        flags.push(TokenRiskFlag::PermanentDelegate(delegate));
+                    }
+                }
+                EXTENSION_TRANSFER_HOOK => {
+                    // authority(32) + program_id(32)
+                    if let Some(program_id) = optional_pubkey(32) {
+                        flags.push(TokenRiskFlag::TransferHook(program_id));
+                    }
+                }
+                EXTENSION_PAUSABLE => flags.push(TokenRiskFlag::Pausable),
+                _ => {}
+            }
+        }
+        flags
+    }
+}
@@ -0,0 +1,566 @@
+use solana_sdk::instruction::Instruction;
+
+use super::*;
+use crate::{
+    fixed::min_amount_with_slippage,
+    global::TOKEN_SWAP_CURVE_STABLE,
+    math::price_impact_bps,
+    pool::{PoolInfo, QuoteResult},
+    receipt::SwapReceipt,
+    token_swap::{
+        TokenSwapAccount, TokenSwapAccounts, TokenSwapOrder, token_swap_fee,
+        token_swap_instruction, token_swap_price_view,
+    },
+    trade::TokenSwapParams,
+    types::{InvalidInput, OrcaResult},
+    validation::{parse_address, validate_slippage_bps},
+};
+
+// The stable curve of the token swap program is specialised for two-token pools
+const N_COINS: u128 = 2;
+// Newton iterations allowed when solving the invariant
+const MAX_ITERATIONS: usize = 32;
+
+/// An Orca stable pool, a token swap pool priced by the StableSwap (amplified) curve
+#[derive(Debug, Clone)]
+pub struct StablePool {
+    pub address: Pubkey,
+    /// PDA of the pool that owns the vaults
+    pub authority: Pubkey,
+    pub token_program: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_vault_b: Pubkey,
+    /// Mint of the pool's LP token
+    pub pool_mint: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    /// LP token account receiving the owner trade fee
+    pub fee_account: Pubkey,
+    pub trade_fee_numerator: u64,
+    pub trade_fee_denominator: u64,
+    pub owner_trade_fee_numerator: u64,
+    pub owner_trade_fee_denominator: u64,
+    /// Amplification coefficient; higher values keep the price closer to 1:1
+    pub amplification: u64,
+    /// Token A held by the pool vault
+    pub reserve_a: u64,
+    /// Token B held by the pool vault
+    pub reserve_b: u64,
+    pub decimals_a: u8,
+    pub decimals_b: u8,
+}
+
+impl StablePool {
+    /// Decodes a stable pool account owned by `program_id`
+    ///
+    /// Returns None when the data is not an initialized token swap account with a stable curve.
+    /// Reserves and decimals are left at zero; `get_stable_pool` fills them.
+    pub fn decode(address: Pubkey, data: &[u8], program_id: &Pubkey) -> Option<Self> {
+        let account = TokenSwapAccount::decode(&address, data, program_id)?;
+        if account.curve_type != TOKEN_SWAP_CURVE_STABLE {
+            return None;
+        }
+        Some(Self {
+            address,
+            authority: account.authority,
+            token_program: account.token_program,
+            token_vault_a: account.token_vault_a,
+            token_vault_b: account.token_vault_b,
+            pool_mint: account.pool_mint,
+            token_mint_a: account.token_mint_a,
+            token_mint_b: account.token_mint_b,
+            fee_account: account.fee_account,
+            trade_fee_numerator: account.trade_fee_numerator,
+            trade_fee_denominator: account.trade_fee_denominator,
+            owner_trade_fee_numerator: account.owner_trade_fee_numerator,
+            owner_trade_fee_denominator: account.owner_trade_fee_denominator,
+            amplification: u64::from_le_bytes(account.curve_parameters[..8].try_into().ok()?),
+            reserve_a: 0,
+            reserve_b: 0,
+            decimals_a: 0,
+            decimals_b: 0,
+        })
+    }
+
+    /// View of the pool for the price deviation guard
+    pub(crate) fn price_view(&self) -> PoolInfo {
+        token_swap_price_view(
+            &self.authority,
+            (&self.token_mint_a, &self.token_mint_b),
+            (&self.token_vault_a, &self.token_vault_b),
+            (self.reserve_a, self.reserve_b),
+        )
+    }
+
+    /// Quotes an exact-input swap of `amount` of `input_mint` against the pool's reserves
+    ///
+    /// Trade and owner fees are taken from the input before it is priced on the curve; the
+    /// minimum output applies `slippage_bps` in basis points.
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool = client.get_stable_pool("stable_pool_address").await?;
+    /// let quote = pool.quote(&pool.token_mint_a, 1_000_000, 10)?;
+    /// println!("{} -> {}", quote.input_amount, quote.output_amount);
+    /// ```
+    pub fn quote(
+        &self,
+        input_mint: &Pubkey,
+        amount: u64,
+        slippage_bps: u16,
+    ) -> OrcaResult<QuoteResult> {
+        validate_slippage_bps(slippage_bps)?;
+        let (reserve_in, reserve_out, input_decimals, output_decimals) =
+            if *input_mint == self.token_mint_a {
+                (
+                    self.reserve_a,
+                    self.reserve_b,
+                    self.decimals_a,
+                    self.decimals_b,
+                )
+            } else if *input_mint == self.token_mint_b {
+                (
+                    self.reserve_b,
+                    self.reserve_a,
+                    self.decimals_b,
+                    self.decimals_a,
+                )
+            } else {
+                return Err(OrcaError::InvalidInput(InvalidInput::MintNotInPool {
+                    pool: self.address.to_string(),
+                    mint: input_mint.to_string(),
+                }));
+            };
+        let fee_amount =
+            token_swap_fee(amount, self.trade_fee_numerator, self.trade_fee_denominator)
+                .saturating_add(token_swap_fee(
+                    amount,
+                    self.owner_trade_fee_numerator,
+                    self.owner_trade_fee_denominator,
+                ));
+        let input_after_fee = amount.saturating_sub(fee_amount);
+        let insufficient = || OrcaError::InsufficientLiquidity {
+            pool: self.address.to_string(),
+        };
+        let output_amount =
+            stable_swap_output(self.amplification, reserve_in, reserve_out, input_after_fee)
+                .ok_or_else(insufficient)?;
+        if output_amount == 0 || output_amount >= reserve_out {
+            return Err(insufficient());
+        }
+        // Marginal price from a swap of 0.01% of the input reserve
+        let probe = (reserve_in / 10_000).max(1);
+        let spot_price = stable_swap_output(self.amplification, reserve_in, reserve_out, probe)
+            .map_or(0.0, |out| out as f64 / probe as f64);
+        let spot_output = input_after_fee as f64 * spot_price;
+        Ok(QuoteResult {
+            input_amount: amount,
+            output_amount,
+            min_output_amount: min_amount_with_slippage(output_amount, slippage_bps),
+            max_input_amount: amount,
+            price_impact_bps: price_impact_bps(spot_output, output_amount),
+            fee_amount,
+            input_transfer_fee: 0,
+            output_transfer_fee: 0,
+            input_decimals,
+            output_decimals,
+        })
+    }
+
+    /// Marginal price of token A in token B, decimal adjusted
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool = client.get_stable_pool("stable_pool_address").await?;
+    /// println!("1 A = {:.6} B", pool.spot_price());
+    /// ```
+    pub fn spot_price(&self) -> f64 {
+        stable_spot_price(self.amplification, self.reserve_a, self.reserve_b)
+            * 10f64.powi(self.decimals_a as i32 - self.decimals_b as i32)
+    }
+
+    /// Input (fees excluded) and output of a swap moving `spot_price` to `target_price`
+    ///
+    /// A higher target buys token A with token B, a lower one sells token A. Returns None when
+    /// the reserves cannot reach the target.
+    pub(crate) fn swap_to_price(&self, target_price: f64) -> Option<(u64, u64)> {
+        let current = self.spot_price();
+        if !target_price.is_finite() || target_price <= 0.0 || current <= 0.0 {
+            return None;
+        }
+        let buy_a = target_price > current;
+        let (reserve_in, reserve_out) = if buy_a {
+            (self.reserve_b, self.reserve_a)
+        } else {
+            (self.reserve_a, self.reserve_b)
+        };
+        let raw_target = target_price / 10f64.powi(self.decimals_a as i32 - self.decimals_b as i32);
+        // Whether swapping `amount_in` moves the price at least to the target
+        let reaches = |amount_in: u64| -> Option<bool> {
+            let output =
+                stable_swap_output(self.amplification, reserve_in, reserve_out, amount_in)?;
+            let (reserve_a, reserve_b) = if buy_a {
+                (
+                    reserve_out.checked_sub(output)?,
+                    reserve_in.checked_add(amount_in)?,
+                )
+            } else {
+                (
+                    reserve_in.checked_add(amount_in)?,
+                    reserve_out.checked_sub(output)?,
+                )
+            };
+            let price = stable_spot_price(self.amplification, reserve_a, reserve_b);
+            Some(if buy_a {
+                price >= raw_target
+            } else {
+                price <= raw_target
+            })
+        };
+        let mut high = (reserve_in / 1_000).max(1);
+        while !reaches(high)? {
+            high = high.checked_mul(2)?;
+        }
+        let mut low = 0;
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if reaches(mid)? {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        Some((
+            high,
+            stable_swap_output(self.amplification, reserve_in, reserve_out, high)?,
+        ))
+    }
+}
+
+impl OrcaClient {
+    /// Fetches a stable pool and the current balances of its vaults
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool = client.get_stable_pool("stable_pool_address").await?;
+    /// println!("A={} B={} amp={}", pool.reserve_a, pool.reserve_b, pool.amplification);
+    /// ```
+    pub async fn get_stable_pool(&self, pool_address: &str) -> OrcaResult<StablePool> {
+        let address = parse_address("pool_address", pool_address)?;
+        let account = self
+            .get_existing_account(&address, "Failed to get stable pool account")
+            .await?;
+        if account.owner != self.stable_swap_program_id {
+            return Err(OrcaError::InvalidPoolData {
+                pool: pool_address.to_string(),
+                reason: format!("owned by {}, not the stable swap program", account.owner),
+            });
+        }
+        let mut pool = StablePool::decode(address, &account.data, &self.stable_swap_program_id)
+            .ok_or_else(|| OrcaError::InvalidPoolData {
+                pool: pool_address.to_string(),
+                reason: "not an initialized stable curve pool".to_string(),
+            })?;
+        (pool.reserve_a, pool.reserve_b) = self
+            .get_token_swap_reserves(&pool.token_vault_a, &pool.token_vault_b)
+            .await?;
+        let decimals = self
+            .get_mint_decimals(&[pool.token_mint_a, pool.token_mint_b])
+            .await?;
+        (pool.decimals_a, pool.decimals_b) = (decimals[0], decimals[1]);
+        Ok(pool)
+    }
+
+    /// Swaps an exact input through an Orca stable pool
+    ///
+    /// The output token account is created when missing; the minimum output is derived from
+    /// the quote and `slippage_bps` in basis points.
+    ///
+    /// # Example
+    /// ```rust
+    /// // USDC -> USDT
+    /// let receipt = client
+    ///     .swap_stable(
+    ///         &keypair,
+    ///         "stable_pool_address",
+    ///         "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    ///         1_000_000,
+    ///         10,
+    ///     )
+    ///     .await?;
+    /// println!("Swapped in {}", receipt.signature);
+    /// ```
+    #[tracing::instrument(skip(self, signer), fields(owner = %signer.pubkey()), err(Debug))]
+    pub async fn swap_stable<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        pool_address: &str,
+        input_mint: &str,
+        amount: u64,
+        slippage_bps: u16,
+    ) -> OrcaResult<SwapReceipt> {
+        self.with_timeout("stable swap", async {
+            let pool = self.get_stable_pool(pool_address).await?;
+            let input_mint = parse_address("input_mint", input_mint)?;
+            let quote = pool.quote(&input_mint, amount, slippage_bps)?;
+            let a_to_b = input_mint == pool.token_mint_a;
+            self.submit_token_swap(
+                signer,
+                "stable swap",
+                TokenSwapOrder {
+                    pool: &pool.address,
+                    token_mint_a: &pool.token_mint_a,
+                    token_mint_b: &pool.token_mint_b,
+                    token_program: &pool.token_program,
+                    a_to_b,
+                    amount,
+                    slippage_bps,
+                    price_view: pool.price_view(),
+                },
+                quote,
+                |source, destination, minimum_amount_out| {
+                    self.build_stable_swap_instruction(
+                        &signer.pubkey(),
+                        &pool,
+                        TokenSwapParams {
+                            source,
+                            destination,
+                            a_to_b,
+                            amount_in: amount,
+                            minimum_amount_out,
+                        },
+                    )
+                },
+            )
+            .await
+        })
+        .await
+    }
+
+    /// Constructs a token swap `Swap` instruction for a stable pool
+    ///
+    /// # Arguments
+    /// owner - Owner of the token accounts
+    /// pool - Stable pool
+    /// params - Owner's token accounts, direction and amounts of the swap
+    pub fn build_stable_swap_instruction(
+        &self,
+        owner: &Pubkey,
+        pool: &StablePool,
+        params: TokenSwapParams,
+    ) -> Instruction {
+        let (swap_source, swap_destination) = if params.a_to_b {
+            (pool.token_vault_a, pool.token_vault_b)
+        } else {
+            (pool.token_vault_b, pool.token_vault_a)
+        };
+        token_swap_instruction(
+            &self.stable_swap_program_id,
+            TokenSwapAccounts {
+                pool: &pool.address,
+                authority: &pool.authority,
+                owner,
+                source: params.source,
+                swap_source: &swap_source,
+                swap_destination: &swap_destination,
+                destination: params.destination,
+                pool_mint: &pool.pool_mint,
+                fee_account: &pool.fee_account,
+                token_program: &pool.token_program,
+            },
+            params.amount_in,
+            params.minimum_amount_out,
+        )
+    }
+}
+
+/// Output of swapping `amount_in` (fees already removed) on the StableSwap curve
+fn stable_swap_output(
+    amplification: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    amount_in: u64,
+) -> Option<u64> {
+    if reserve_in == 0 || reserve_out == 0 {
+        return None;
+    }
+    let leverage = (amplification as u128).checked_mul(N_COINS)?;
+    let d = compute_d(leverage, reserve_in as u128, reserve_out as u128)?;
+    let new_reserve_in = (reserve_in as u128).checked_add(amount_in as u128)?;
+    let new_reserve_out = compute_y(leverage, new_reserve_in, d)?;
+    u64::try_from((reserve_out as u128).checked_sub(new_reserve_out)?).ok()
+}
+
+/// Raw token B paid for a unit of token A at the margin, from a swap of 0.01% of the A reserve
+fn stable_spot_price(amplification: u64, reserve_a: u64, reserve_b: u64) -> f64 {
+    let probe = (reserve_a / 10_000).max(1);
+    stable_swap_output(amplification, reserve_a, reserve_b, probe)
+        .map_or(0.0, |out| out as f64 / probe as f64)
+}
+
+/// StableSwap invariant D of two reserves, found by Newton's method
+fn compute_d(leverage: u128, amount_a: u128, amount_b: u128) -> Option<u128> {
+    let sum = amount_a.checked_add(amount_b)?;
+    if sum == 0 {
+        return Some(0);
+    }
+    let mut d = sum;
+    for _ in 0..MAX_ITERATIONS {
+        let mut d_product = d;
+        d_product = d_product.checked_mul(d)?.checked_div(amount_a * N_COINS)?;
+        d_product = d_product.checked_mul(d)?.checked_div(amount_b * N_COINS)?;
+        let previous = d;
+        let numerator = leverage
+            .checked_mul(sum)?
+            .checked_add(d_product.checked_mul(N_COINS)?)?
+            .checked_mul(d)?;
+        let denominator = d
+            .checked_mul(leverage.checked_sub(1)?)?
+            .checked_add(d_product.checked_mul(N_COINS + 1)?)?;
+        d = numerator.checked_div(denominator)?;
+        if d.abs_diff(previous) <= 1 {
+            break;
+        }
+    }
+    Some(d)
+}
+
+/// Reserve of the other token that keeps the invariant `d` once one reserve is `new_amount`
+fn compute_y(leverage: u128, new_amount: u128, d: u128) -> Option<u128> {
+    // c = D^3 / (n^2 * x * A*n), computed in two steps to stay within u128
+    let c = d
+        .checked_mul(d)?
+        .checked_div(new_amount.checked_mul(N_COINS)?)?
+        .checked_mul(d)?
+        .checked_div(leverage.checked_mul(N_COINS)?)?;
+    let b = new_amount.checked_add(d.checked_div(leverage)?)?;
+    let mut y = d;
+    for _ in 0..MAX_ITERATIONS {
+        let previous = y;
+        let numerator = y.checked_mul(y)?.checked_add(c)?;
+        let denominator = y.checked_mul(2)?.checked_add(b)?.checked_sub(d)?;
+        y = numerator.checked_div(denominator)?;
+        if y.abs_diff(previous) <= 1 {
+            break;
+        }
+    }
+    Some(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESERVE: u128 = 1_000_000_000_000;
+    const DEPOSIT: u128 = 1_000_000_000;
+
+    /// Invariant D and the out reserve after `DEPOSIT` is added to the in reserve, solved
+    /// exactly offline for each amplification and reserve pair
+    const CASES: [(u64, u128, u128, u128, u128); 5] = [
+        (100, RESERVE, RESERVE, 2_000_000_000_000, 999_000_009_900),
+        (
+            100,
+            RESERVE,
+            500_000_000_000,
+            1_499_073_492_619,
+            499_008_306_845,
+        ),
+        (1, RESERVE, 10_000_000_000, 401_533_031_107, 9_977_951_720),
+        (
+            10_000,
+            RESERVE,
+            100_000_000_000,
+            1_099_888_669_944,
+            99_001_509_649,
+        ),
+        (
+            1_000_000,
+            RESERVE,
+            100_000_000_000,
+            1_099_998_886_254,
+            99_000_015_126,
+        ),
+    ];
+
+    fn leverage(amplification: u64) -> u128 {
+        amplification as u128 * N_COINS
+    }
+
+    #[test]
+    fn invariant_of_balanced_pool_is_reserve_sum() {
+        for amplification in [1, 100, 1_000_000] {
+            assert_eq!(
+                compute_d(leverage(amplification), RESERVE, RESERVE),
+                Some(2 * RESERVE)
+            );
+        }
+        assert_eq!(compute_d(leverage(100), 0, 0), Some(0));
+    }
+
+    #[test]
+    fn invariant_matches_known_values() {
+        for (amplification, reserve_a, reserve_b, expected_d, _) in CASES {
+            let d = compute_d(leverage(amplification), reserve_a, reserve_b).unwrap();
+            assert!(
+                d.abs_diff(expected_d) <= 1,
+                "amp {}: D {} != {}",
+                amplification,
+                d,
+                expected_d
+            );
+            // Up to rounding, the invariant does not depend on which reserve comes first
+            let swapped = compute_d(leverage(amplification), reserve_b, reserve_a).unwrap();
+            assert!(swapped.abs_diff(expected_d) <= 1);
+        }
+    }
+
+    #[test]
+    fn compute_y_matches_known_values() {
+        for (amplification, reserve_in, reserve_out, _, expected_y) in CASES {
+            let leverage = leverage(amplification);
+            let d = compute_d(leverage, reserve_in, reserve_out).unwrap();
+            let y = compute_y(leverage, reserve_in + DEPOSIT, d).unwrap();
+            assert!(
+                y.abs_diff(expected_y) <= 2,
+                "amp {}: y {} != {}",
+                amplification,
+                y,
+                expected_y
+            );
+        }
+    }
+
+    #[test]
+    fn compute_y_recovers_reserve_at_unchanged_balance() {
+        for (amplification, reserve_in, reserve_out, _, _) in CASES {
+            let leverage = leverage(amplification);
+            let d = compute_d(leverage, reserve_in, reserve_out).unwrap();
+            let y = compute_y(leverage, reserve_in, d).unwrap();
+            assert!(
+                y.abs_diff(reserve_out) <= 2,
+                "amp {}: y {}",
+                amplification,
+                y
+            );
+        }
+    }
+
+    #[test]
+    fn newton_converges_for_extreme_imbalance() {
+        // Exact D is 1_999_333_334.02; flooring in the D^3 term leaves it slightly above
+        let d = compute_d(leverage(1), RESERVE, 1_000).unwrap();
+        assert!(d.abs_diff(1_999_333_334) * 100_000_000 <= 1_999_333_334);
+        assert!(compute_y(leverage(1), RESERVE + DEPOSIT, d).unwrap() <= 1_000);
+    }
+
+    #[test]
+    fn swap_output_follows_amplification() {
+        let reserve = RESERVE as u64;
+        let amount_in = DEPOSIT as u64;
+        let flat = stable_swap_output(1_000_000, reserve, reserve, amount_in).unwrap();
+        let curved = stable_swap_output(1, reserve, reserve, amount_in).unwrap();
+        assert!(flat <= amount_in && flat > amount_in - 10);
+        assert!(curved < flat);
+        assert_eq!(stable_swap_output(100, 0, reserve, amount_in), None);
+    }
+}
@@ -0,0 +1,848 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiMessage,
+    UiTransactionStatusMeta, UiTransactionTokenBalance, option_serializer::OptionSerializer,
+};
+
+use super::*;
+use crate::{
+    fixed::mul_div_floor,
+    global::{BPS_DENOMINATOR, FEE_RATE_DENOMINATOR},
+    math::to_ui_amount,
+    pool::PoolInfo,
+    types::{InvalidInput, OrcaResult},
+    validation::parse_address,
+};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+const DAYS_PER_YEAR: f64 = 365.0;
+const MAX_STATS_DAYS: u32 = 90;
+const MAX_STATS_TRANSACTIONS: usize = 10_000;
+const MAX_FEE_GROWTH_SNAPSHOTS: usize = 1_000;
+// Growth values are Q64.64 per unit of liquidity
+const GROWTH_SCALE: u128 = 1 << 64;
+
+/// Aggregated statistics of a pool for one UTC day
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PoolDailyStats {
+    /// Unix timestamp of the start of the UTC day
+    pub day_start: u64,
+    /// Swap volume denominated in token A (raw units)
+    pub volume_a: u64,
+    /// Swap volume denominated in token B (raw units)
+    pub volume_b: u64,
+    /// Trading fees paid in token A (raw units)
+    pub fees_a: u64,
+    /// Trading fees paid in token B (raw units)
+    pub fees_b: u64,
+    /// Vault A balance at the last observed transaction of the day
+    pub tvl_a: u64,
+    /// Vault B balance at the last observed transaction of the day
+    pub tvl_b: u64,
+    /// Number of distinct fee payers that swapped in the pool
+    pub unique_traders: usize,
+    /// Number of swaps observed
+    pub swap_count: usize,
+    /// Lowest execution price (token B per token A, raw units)
+    pub price_low: f64,
+    /// Highest execution price (token B per token A, raw units)
+    pub price_high: f64,
+    /// True when the transaction limit was reached before the start of the day, so the day
+    /// is only partly covered or not at all
+    pub truncated: bool,
+}
+
+/// Vault balance changes of a pool caused by a single confirmed transaction
+#[derive(Debug, Clone)]
+pub struct PoolTransactionRecord {
+    pub signature: String,
+    pub timestamp: i64,
+    pub fee_payer: Option<String>,
+    /// Change of the token A vault balance (positive when tokens flow into the pool)
+    pub delta_a: i128,
+    /// Change of the token B vault balance (positive when tokens flow into the pool)
+    pub delta_b: i128,
+    pub vault_balance_a: u64,
+    pub vault_balance_b: u64,
+}
+
+impl PoolTransactionRecord {
+    /// Returns true when one vault received tokens while the other paid out
+    pub fn is_swap(&self) -> bool {
+        (self.delta_a > 0 && self.delta_b < 0) || (self.delta_a < 0 && self.delta_b > 0)
+    }
+
+    /// Execution price of a swap as token B per token A (raw units)
+    pub fn execution_price(&self) -> Option<f64> {
+        if !self.is_swap() {
+            return None;
+        }
+        Some(self.delta_b.unsigned_abs() as f64 / self.delta_a.unsigned_abs() as f64)
+    }
+}
+
+/// Token balances of a pool's vaults and their USD value
+#[derive(Debug, Clone)]
+pub struct PoolTvl {
+    pub pool_address: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_vault_b: Pubkey,
+    pub decimals_a: u8,
+    pub decimals_b: u8,
+    /// Token A held by the vault (raw units)
+    pub amount_a: u64,
+    /// Token B held by the vault (raw units)
+    pub amount_b: u64,
+    pub ui_amount_a: f64,
+    pub ui_amount_b: f64,
+    /// None when no USDC or SOL route prices the token
+    pub price_a_usd: Option<f64>,
+    pub price_b_usd: Option<f64>,
+    pub value_a_usd: Option<f64>,
+    pub value_b_usd: Option<f64>,
+    /// Value of both vaults; None unless both tokens are priced
+    pub tvl_usd: Option<f64>,
+}
+
+/// Estimated yield of liquidity provided across a whole pool
+///
+/// Rates are in percent. APRs are simple annualizations of the last 24 hours; the APY compounds
+/// the total APR daily.
+#[derive(Debug, Clone)]
+pub struct PoolYield {
+    pub pool_address: Pubkey,
+    /// Fee tier of the pool, in hundredths of a basis point
+    pub fee_rate: u16,
+    pub tick_spacing: u16,
+    /// Token A fees credited to liquidity providers over the last 24 hours (raw units)
+    pub fees_24h_a: u64,
+    /// Token B fees credited to liquidity providers over the last 24 hours (raw units)
+    pub fees_24h_b: u64,
+    pub fees_24h_usd: f64,
+    /// USD value of the tokens sold into the pool over the last 24 hours, implied by the fee
+    /// growth and the fee rate
+    pub volume_24h_usd: f64,
+    /// USD value of one day of reward emissions; rewards without a price count as zero
+    pub rewards_per_day_usd: f64,
+    /// USD value of both vaults
+    pub tvl_usd: f64,
+    pub fee_apr: f64,
+    pub reward_apr: f64,
+    pub total_apr: f64,
+    pub total_apy: f64,
+}
+
+/// A pool's global fee growth and liquidity at one point in time
+///
+/// Snapshots can be persisted and handed back to `import_fee_growth_snapshots` after a
+/// restart, since past account state cannot be read from RPC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeGrowthSnapshot {
+    pub pool_address: Pubkey,
+    /// Unix timestamp the pool state was read at
+    pub timestamp: i64,
+    /// Q64.64 fee growth per unit of liquidity
+    pub fee_growth_global_a: u128,
+    pub fee_growth_global_b: u128,
+    pub liquidity: u128,
+}
+
+impl FeeGrowthSnapshot {
+    pub fn from_pool(pool: &PoolInfo, timestamp: i64) -> OrcaResult<Self> {
+        Ok(Self {
+            pool_address: parse_address("pool_address", &pool.address)?,
+            timestamp,
+            fee_growth_global_a: pool.fee_growth_global_a,
+            fee_growth_global_b: pool.fee_growth_global_b,
+            liquidity: pool.liquidity,
+        })
+    }
+}
+
+/// Fee revenue and implied volume of a pool between two fee growth snapshots
+#[derive(Debug, Clone)]
+pub struct FeeGrowthVolume {
+    pub pool_address: Pubkey,
+    /// Timestamp of the snapshot the window starts at
+    pub window_start: i64,
+    pub window_end: i64,
+    /// Number of snapshots the window was measured with
+    pub snapshot_count: usize,
+    /// Token A fees credited to liquidity providers (raw units)
+    pub lp_fees_a: u64,
+    /// Token B fees credited to liquidity providers (raw units)
+    pub lp_fees_b: u64,
+    /// Token A fees including the protocol's share (raw units)
+    pub fees_a: u64,
+    /// Token B fees including the protocol's share (raw units)
+    pub fees_b: u64,
+    /// Token A sold into the pool, implied by the fees and the fee rate (raw units)
+    pub volume_a: u64,
+    /// Token B sold into the pool, implied by the fees and the fee rate (raw units)
+    pub volume_b: u64,
+}
+
+/// Fee growth snapshots kept per pool, oldest first
+#[derive(Debug, Default)]
+pub(crate) struct FeeGrowthSnapshots {
+    pools: HashMap<Pubkey, VecDeque<FeeGrowthSnapshot>>,
+}
+
+impl FeeGrowthSnapshots {
+    fn insert(&mut self, snapshot: FeeGrowthSnapshot) {
+        let snapshots = self.pools.entry(snapshot.pool_address).or_default();
+        let position = snapshots.partition_point(|s| s.timestamp <= snapshot.timestamp);
+        snapshots.insert(position, snapshot);
+        if snapshots.len() > MAX_FEE_GROWTH_SNAPSHOTS {
+            snapshots.pop_front();
+        }
+    }
+}
+
+/// Finished days of pool statistics, so their transactions are aggregated only once
+#[derive(Debug, Default)]
+pub(crate) struct DailyStatsStore {
+    pools: HashMap<String, BTreeMap<i64, PoolDailyStats>>,
+}
+
+impl OrcaClient {
+    /// Returns per-day volume, fees, TVL, unique traders and price range for a pool
+    ///
+    /// Days are UTC calendar days, most recent first; days without transactions are returned
+    /// as zero rows. Finished days are stored once aggregated, in memory and in the persistent
+    /// cache when one is configured, and read back from there. Transactions are only fetched
+    /// for today and the days the store does not cover. At most 10,000 transactions are read,
+    /// and days reaching past the oldest of them on a busy pool are marked `truncated`;
+    /// truncated days are not stored.
+    ///
+    /// # Params
+    /// pool_address - Whirlpool address
+    /// days - Number of days to aggregate (1 to 90)
+    ///
+    /// # Example
+    /// ```rust
+    /// let stats = client.get_pool_daily_stats("whirlpool_address_here", 7).await?;
+    /// for day in stats {
+    ///     println!("{}: volume A {}, fees A {}, traders {}", day.day_start, day.volume_a, day.fees_a, day.unique_traders);
+    /// }
+    /// ```
+    pub async fn get_pool_daily_stats(
+        &self,
+        pool_address: &str,
+        days: u32,
+    ) -> OrcaResult<Vec<PoolDailyStats>> {
+        self.with_timeout("get pool daily stats", async {
+            if days == 0 || days > MAX_STATS_DAYS {
+                return Err(OrcaError::InvalidInput(InvalidInput::ValueOutOfRange {
+                    field: "days".to_string(),
+                    value: days as u64,
+                    min: 1,
+                    max: MAX_STATS_DAYS as u64,
+                }));
+            }
+            let pool_info = self.get_pool_state_onchain(pool_address).await?;
+            let now = chrono::Utc::now().timestamp();
+            let today_start = now / SECONDS_PER_DAY * SECONDS_PER_DAY;
+            let since = today_start - (days as i64 - 1) * SECONDS_PER_DAY;
+            let mut stats =
+                self.stored_daily_stats(&pool_info.address, (since, today_start - SECONDS_PER_DAY));
+            // Today is never stored, so transactions are read back to the oldest missing day
+            let fetch_from = (since..=today_start)
+                .step_by(SECONDS_PER_DAY as usize)
+                .find(|day_start| !stats.contains_key(day_start))
+                .unwrap_or(today_start);
+            let records = self
+                .fetch_pool_transaction_records(&pool_info, fetch_from, MAX_STATS_TRANSACTIONS)
+                .await?;
+            // Signatures are listed newest first, so a full page may stop short of `fetch_from`
+            let covered_from = (records.len() >= MAX_STATS_TRANSACTIONS)
+                .then(|| records.iter().map(|record| record.timestamp).min())
+                .flatten();
+            let fetched = Self::aggregate_daily_stats(
+                &pool_info,
+                &records,
+                (fetch_from, today_start),
+                covered_from,
+            );
+            self.store_daily_stats(&pool_info.address, &fetched, today_start);
+            for day in fetched {
+                stats.entry(day.day_start as i64).or_insert(day);
+            }
+            Ok(stats.into_values().rev().collect())
+        })
+        .await
+    }
+
+    /// Stored days of a pool from `day_range.0` to `day_range.1`, both inclusive
+    fn stored_daily_stats(
+        &self,
+        pool_address: &str,
+        day_range: (i64, i64),
+    ) -> BTreeMap<i64, PoolDailyStats> {
+        let stored: BTreeMap<i64, PoolDailyStats> = self
+            .daily_stats
+            .lock()
+            .ok()
+            .and_then(|store| {
+                store.pools.get(pool_address).map(|days| {
+                    days.range(day_range.0..=day_range.1)
+                        .map(|(day_start, stats)| (*day_start, stats.clone()))
+                        .collect()
+                })
+            })
+            .unwrap_or_default();
+        #[cfg(feature = "persistent-cache")]
+        let stored = match &self.persistent_cache {
+            Some(cache) => {
+                let mut stored = stored;
+                for day_start in (day_range.0..=day_range.1).step_by(SECONDS_PER_DAY as usize) {
+                    if !stored.contains_key(&day_start)
+                        && let Some(stats) = cache.get_daily_stats(pool_address, day_start)
+                    {
+                        stored.insert(day_start, stats);
+                    }
+                }
+                stored
+            }
+            None => stored,
+        };
+        stored
+    }
+
+    /// Keeps the finished, fully covered days of freshly aggregated stats
+    fn store_daily_stats(&self, pool_address: &str, days: &[PoolDailyStats], today_start: i64) {
+        let finished: Vec<&PoolDailyStats> = days
+            .iter()
+            .filter(|stats| (stats.day_start as i64) < today_start && !stats.truncated)
+            .collect();
+        if let Ok(mut store) = self.daily_stats.lock() {
+            let stored = store.pools.entry(pool_address.to_string()).or_default();
+            for stats in &finished {
+                stored.insert(stats.day_start as i64, (*stats).clone());
+            }
+            while stored.len() > MAX_STATS_DAYS as usize {
+                stored.pop_first();
+            }
+        }
+        #[cfg(feature = "persistent-cache")]
+        if let Some(cache) = &self.persistent_cache {
+            for stats in &finished {
+                cache.insert_daily_stats(pool_address, stats);
+            }
+        }
+    }
+
+    /// Returns the total value locked in a pool, read from its vault accounts
+    ///
+    /// Vault balances include the protocol fees owed, which are not yet withdrawn. Each side is
+    /// valued in USD through its USDC or SOL pools.
+    ///
+    /// # Example
+    /// ```rust
+    /// let tvl = client.get_pool_tvl("whirlpool_address_here").await?;
+    /// println!("{} A + {} B = ${:?}", tvl.ui_amount_a, tvl.ui_amount_b, tvl.tvl_usd);
+    /// ```
+    #[tracing::instrument(skip(self), err(Debug))]
+    pub async fn get_pool_tvl(&self, pool_address: &str) -> OrcaResult<PoolTvl> {
+        self.with_timeout("get pool tvl", async {
+            let pool = self.get_pool_state_onchain(pool_address).await?;
+            self.pool_tvl(&pool).await
+        })
+        .await
+    }
+
+    /// Reads the vault balances of a decoded pool and values them in USD
+    pub(crate) async fn pool_tvl(&self, pool: &PoolInfo) -> OrcaResult<PoolTvl> {
+        let mint_a = parse_address("token_mint_a", &pool.token_mint_a)?;
+        let mint_b = parse_address("token_mint_b", &pool.token_mint_b)?;
+        let vault_a = parse_address("token_vault_a", &pool.token_vault_a)?;
+        let vault_b = parse_address("token_vault_b", &pool.token_vault_b)?;
+        let (amount_a, amount_b) = self.get_token_swap_reserves(&vault_a, &vault_b).await?;
+        let decimals = self.get_mint_decimals(&[mint_a, mint_b]).await?;
+        let prices = self
+            .get_token_prices_usd(&[pool.token_mint_a.clone(), pool.token_mint_b.clone()])
+            .await?;
+        let ui_amount_a = to_ui_amount(amount_a, decimals[0]);
+        let ui_amount_b = to_ui_amount(amount_b, decimals[1]);
+        let price_a_usd = prices.get(&pool.token_mint_a).copied();
+        let price_b_usd = prices.get(&pool.token_mint_b).copied();
+        let value_a_usd = price_a_usd.map(|price| ui_amount_a * price);
+        let value_b_usd = price_b_usd.map(|price| ui_amount_b * price);
+        Ok(PoolTvl {
+            pool_address: parse_address("pool_address", &pool.address)?,
+            token_mint_a: mint_a,
+            token_mint_b: mint_b,
+            token_vault_a: vault_a,
+            token_vault_b: vault_b,
+            decimals_a: decimals[0],
+            decimals_b: decimals[1],
+            amount_a,
+            amount_b,
+            ui_amount_a,
+            ui_amount_b,
+            price_a_usd,
+            price_b_usd,
+            value_a_usd,
+            value_b_usd,
+            tvl_usd: value_a_usd.zip(value_b_usd).map(|(a, b)| a + b),
+        })
+    }
+
+    /// Estimates the fee APR, reward APR and total APY of a pool against its TVL
+    ///
+    /// Fee revenue is what the pool's fee growth credited to liquidity providers over the last
+    /// 24 hours, measured with fee growth snapshots like `get_fee_growth_volume`. The pool is
+    /// recorded as the newest snapshot, and when the stored snapshots span more or less than
+    /// 24 hours the revenue is scaled to one day. Fails when no earlier snapshot exists, so
+    /// record snapshots with `record_fee_growth_snapshot` first. Reward revenue is one day of
+    /// the emissions of the initialized reward slots. Both are valued in USD at current prices
+    /// and divided by the USD value of the vaults.
+    ///
+    /// # Example
+    /// ```rust
+    /// client.record_fee_growth_snapshot("whirlpool_address_here").await?;
+    /// // ...some hours of snapshots later
+    /// let pool_yield = client.estimate_pool_yield("whirlpool_address_here").await?;
+    /// println!(
+    ///     "Fee tier {}: fee APR {:.2}%, reward APR {:.2}%, APY {:.2}%",
+    ///     pool_yield.fee_rate, pool_yield.fee_apr, pool_yield.reward_apr, pool_yield.total_apy
+    /// );
+    /// ```
+    #[tracing::instrument(skip(self), err(Debug))]
+    pub async fn estimate_pool_yield(&self, pool_address: &str) -> OrcaResult<PoolYield> {
+        self.with_timeout("estimate pool yield", async {
+            let pool = self.get_pool_state_onchain(pool_address).await?;
+            let tvl = self.pool_tvl(&pool).await?;
+            let growth = self.pool_fee_growth_volume(&pool, SECONDS_PER_DAY)?;
+            let scale = SECONDS_PER_DAY as f64 / (growth.window_end - growth.window_start) as f64;
+            let rewards: Vec<_> = pool
+                .reward_infos
+                .iter()
+                .filter(|reward| reward.is_initialized())
+                .collect();
+            let price_a = tvl
+                .price_a_usd
+                .ok_or_else(|| OrcaError::PriceUnavailable(pool.token_mint_a.clone()))?;
+            let price_b = tvl
+                .price_b_usd
+                .ok_or_else(|| OrcaError::PriceUnavailable(pool.token_mint_b.clone()))?;
+            let tvl_usd = tvl.tvl_usd.unwrap_or(0.0);
+            if tvl_usd <= 0.0 {
+                return Err(OrcaError::InsufficientLiquidity {
+                    pool: pool.address.clone(),
+                });
+            }
+            let reward_mints: Vec<Pubkey> = rewards.iter().map(|reward| reward.mint).collect();
+            let reward_decimals = self.get_mint_decimals(&reward_mints).await?;
+            let reward_prices = if reward_mints.is_empty() {
+                HashMap::new()
+            } else {
+                self.get_token_prices_usd(
+                    &reward_mints
+                        .iter()
+                        .map(Pubkey::to_string)
+                        .collect::<Vec<_>>(),
+                )
+                .await?
+            };
+            let usd = |amount_a: u64, amount_b: u64| {
+                (to_ui_amount(amount_a, tvl.decimals_a) * price_a
+                    + to_ui_amount(amount_b, tvl.decimals_b) * price_b)
+                    * scale
+            };
+            let fees_24h_usd = usd(growth.lp_fees_a, growth.lp_fees_b);
+            let volume_24h_usd = usd(growth.volume_a, growth.volume_b);
+            let rewards_per_day_usd: f64 = rewards
+                .iter()
+                .zip(reward_decimals)
+                .filter_map(|(reward, decimals)| {
+                    reward_prices
+                        .get(&reward.mint.to_string())
+                        .map(|price| reward.emissions_per_day(decimals) * price)
+                })
+                .sum();
+            let fee_apr = fees_24h_usd * DAYS_PER_YEAR / tvl_usd * 100.0;
+            let reward_apr = rewards_per_day_usd * DAYS_PER_YEAR / tvl_usd * 100.0;
+            let total_apr = fee_apr + reward_apr;
+            let total_apy =
+                ((1.0 + total_apr / 100.0 / DAYS_PER_YEAR).powf(DAYS_PER_YEAR) - 1.0) * 100.0;
+            Ok(PoolYield {
+                pool_address: tvl.pool_address,
+                fee_rate: pool.fee_rate,
+                tick_spacing: pool.tick_spacing,
+                fees_24h_a: (growth.lp_fees_a as f64 * scale) as u64,
+                fees_24h_b: (growth.lp_fees_b as f64 * scale) as u64,
+                fees_24h_usd,
+                volume_24h_usd,
+                rewards_per_day_usd,
+                tvl_usd,
+                fee_apr,
+                reward_apr,
+                total_apr,
+                total_apy,
+            })
+        })
+        .await
+    }
+
+    /// Reads a pool and keeps its fee growth as a snapshot for `get_fee_growth_volume`
+    ///
+    /// Up to 1,000 snapshots are kept per pool, dropping the oldest. Record snapshots on a
+    /// schedule, e.g. hourly, to measure volume over any past window.
+    ///
+    /// # Example
+    /// ```rust
+    /// let snapshot = client.record_fee_growth_snapshot("whirlpool_address_here").await?;
+    /// store.save(&serde_json::to_string(&snapshot)?)?;
+    /// ```
+    pub async fn record_fee_growth_snapshot(
+        &self,
+        pool_address: &str,
+    ) -> OrcaResult<FeeGrowthSnapshot> {
+        let pool = self.get_pool_state_onchain(pool_address).await?;
+        let snapshot = FeeGrowthSnapshot::from_pool(&pool, chrono::Utc::now().timestamp())?;
+        self.import_fee_growth_snapshots(&[snapshot]);
+        Ok(snapshot)
+    }
+
+    /// Adds previously recorded fee growth snapshots, e.g. loaded from storage after a restart
+    pub fn import_fee_growth_snapshots(&self, snapshots: &[FeeGrowthSnapshot]) {
+        if let Ok(mut stored) = self.fee_growth_snapshots.lock() {
+            for snapshot in snapshots {
+                stored.insert(*snapshot);
+            }
+        }
+    }
+
+    /// Measures fee revenue and implied volume of a pool from fee growth snapshots
+    ///
+    /// The pool is read and recorded as the newest snapshot. The window starts at the newest
+    /// snapshot at least `window` old, or at the oldest one when none is, so check
+    /// `window_start` for the span actually measured. Fails when no earlier snapshot exists.
+    /// See [`fee_growth_volume`] for how the snapshots are turned into volume.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// client.record_fee_growth_snapshot("whirlpool_address_here").await?;
+    /// // ...a day of hourly snapshots later
+    /// let volume = client
+    ///     .get_fee_growth_volume("whirlpool_address_here", Duration::from_secs(86_400))
+    ///     .await?;
+    /// println!("{} A and {} B sold into the pool", volume.volume_a, volume.volume_b);
+    /// ```
+    #[tracing::instrument(skip(self), err(Debug))]
+    pub async fn get_fee_growth_volume(
+        &self,
+        pool_address: &str,
+        window: Duration,
+    ) -> OrcaResult<FeeGrowthVolume> {
+        self.with_timeout("get fee growth volume", async {
+            let pool = self.get_pool_state_onchain(pool_address).await?;
+            self.pool_fee_growth_volume(&pool, window.as_secs() as i64)
+        })
+        .await
+    }
+
+    /// Records a decoded pool as the newest snapshot and measures the last `window` seconds
+    fn pool_fee_growth_volume(&self, pool: &PoolInfo, window: i64) -> OrcaResult<FeeGrowthVolume> {
+        let current = FeeGrowthSnapshot::from_pool(pool, chrono::Utc::now().timestamp())?;
+        self.import_fee_growth_snapshots(&[current]);
+        let window_start = current.timestamp - window;
+        let snapshots: Vec<FeeGrowthSnapshot> = self
+            .fee_growth_snapshots
+            .lock()
+            .ok()
+            .and_then(|stored| {
+                stored.pools.get(&current.pool_address).map(|snapshots| {
+                    let first = snapshots
+                        .partition_point(|s| s.timestamp <= window_start)
+                        .saturating_sub(1);
+                    snapshots.range(first..).copied().collect()
+                })
+            })
+            .unwrap_or_default();
+        fee_growth_volume(pool, &snapshots).ok_or_else(|| OrcaError::InsufficientHistory {
+            pool: pool.address.clone(),
+            reason: "no earlier fee growth snapshot; record one first".to_string(),
+        })
+    }
+
+    /// Buckets records into the UTC days from `day_range.0` to `day_range.1`, both inclusive
+    ///
+    /// `covered_from` is the timestamp of the oldest record when the records were cut off by
+    /// the transaction limit; days starting at or before it are marked truncated.
+    fn aggregate_daily_stats(
+        pool: &PoolInfo,
+        records: &[PoolTransactionRecord],
+        day_range: (i64, i64),
+        covered_from: Option<i64>,
+    ) -> Vec<PoolDailyStats> {
+        let empty_day = |day_start: i64| {
+            (
+                PoolDailyStats {
+                    day_start: day_start as u64,
+                    price_low: f64::MAX,
+                    truncated: covered_from.is_some_and(|oldest| day_start <= oldest),
+                    ..Default::default()
+                },
+                HashSet::new(),
+                i64::MIN,
+            )
+        };
+        let mut days: BTreeMap<i64, (PoolDailyStats, HashSet<String>, i64)> = (day_range.0
+            ..=day_range.1)
+            .step_by(SECONDS_PER_DAY as usize)
+            .map(|day_start| (day_start, empty_day(day_start)))
+            .collect();
+        for record in records {
+            let day_start = record.timestamp / SECONDS_PER_DAY * SECONDS_PER_DAY;
+            let (stats, traders, last_seen) = days
+                .entry(day_start)
+                .or_insert_with(|| empty_day(day_start));
+            if record.timestamp >= *last_seen {
+                *last_seen = record.timestamp;
+                stats.tvl_a = record.vault_balance_a;
+                stats.tvl_b = record.vault_balance_b;
+            }
+            if !record.is_swap() {
+                continue;
+            }
+            stats.swap_count += 1;
+            if record.delta_a > 0 {
+                let amount_in = record.delta_a as u64;
+                stats.volume_a = stats.volume_a.saturating_add(amount_in);
+                stats.volume_b = stats
+                    .volume_b
+                    .saturating_add(record.delta_b.unsigned_abs() as u64);
+                stats.fees_a = stats
+                    .fees_a
+                    .saturating_add(Self::fee_for_amount(pool, amount_in));
+            } else {
+                let amount_in = record.delta_b as u64;
+                stats.volume_b = stats.volume_b.saturating_add(amount_in);
+                stats.volume_a = stats
+                    .volume_a
+                    .saturating_add(record.delta_a.unsigned_abs() as u64);
+                stats.fees_b = stats
+                    .fees_b
+                    .saturating_add(Self::fee_for_amount(pool, amount_in));
+            }
+            if let Some(price) = record.execution_price() {
+                stats.price_low = stats.price_low.min(price);
+                stats.price_high = stats.price_high.max(price);
+            }
+            if let Some(fee_payer) = &record.fee_payer {
+                traders.insert(fee_payer.clone());
+            }
+        }
+        days.into_iter()
+            .rev()
+            .map(|(_, (mut stats, traders, _))| {
+                stats.unique_traders = traders.len();
+                if stats.swap_count == 0 {
+                    stats.price_low = 0.0;
+                }
+                stats
+            })
+            .collect()
+    }
+
+    fn fee_for_amount(pool: &PoolInfo, amount: u64) -> u64 {
+        if pool.trade_fee_denominator == 0 {
+            return 0;
+        }
+        (amount as u128 * pool.trade_fee_numerator as u128 / pool.trade_fee_denominator as u128)
+            as u64
+    }
+
+    /// Fetches confirmed pool transactions newer than `since` and extracts vault balance changes
+    ///
+    /// Signatures are paginated backwards until the cutoff time or `max_transactions` is reached.
+    pub(crate) async fn fetch_pool_transaction_records(
+        &self,
+        pool: &PoolInfo,
+        since: i64,
+        max_transactions: usize,
+    ) -> OrcaResult<Vec<PoolTransactionRecord>> {
+        let pool_pubkey = parse_address("pool_address", &pool.address)?;
+        let listed = self
+            .get_signatures_paginated(&pool_pubkey, None, Some(since), max_transactions)
+            .await?;
+        let signatures: Vec<Signature> = listed.iter().map(|(signature, _)| *signature).collect();
+        let transactions = self.fetch_transactions(&signatures).await?;
+        let records = listed
+            .into_iter()
+            .zip(transactions)
+            .filter_map(|((signature, block_time), transaction)| {
+                Self::pool_transaction_record(pool, &signature, block_time, &*transaction?)
+            })
+            .collect();
+        Ok(records)
+    }
+
+    fn pool_transaction_record(
+        pool: &PoolInfo,
+        signature: &Signature,
+        block_time: i64,
+        transaction: &EncodedConfirmedTransactionWithStatusMeta,
+    ) -> Option<PoolTransactionRecord> {
+        let meta = transaction.transaction.meta.as_ref()?;
+        let fee_payer = Self::fee_payer_of(&transaction.transaction.transaction);
+        Self::extract_vault_deltas(meta, pool).map(|(delta_a, delta_b, balance_a, balance_b)| {
+            PoolTransactionRecord {
+                signature: signature.to_string(),
+                timestamp: block_time,
+                fee_payer,
+                delta_a,
+                delta_b,
+                vault_balance_a: balance_a,
+                vault_balance_b: balance_b,
+            }
+        })
+    }
+
+    fn fee_payer_of(transaction: &EncodedTransaction) -> Option<String> {
+        match transaction {
+            EncodedTransaction::Json(ui_transaction) => match &ui_transaction.message {
+                UiMessage::Parsed(parsed) => parsed.account_keys.first().map(|k| k.pubkey.clone()),
+                UiMessage::Raw(raw) => raw.account_keys.first().cloned(),
+            },
+            _ => None,
+        }
+    }
+
+    /// Computes pool vault balance changes from pre/post token balances
+    ///
+    /// Whirlpool vaults are token accounts whose owner is the pool itself, so they are
+    /// identified by owner and mint. Returns (delta_a, delta_b, post_balance_a, post_balance_b).
+    pub(crate) fn extract_vault_deltas(
+        meta: &UiTransactionStatusMeta,
+        pool: &PoolInfo,
+    ) -> Option<(i128, i128, u64, u64)> {
+        let pre_balances: Option<Vec<UiTransactionTokenBalance>> =
+            meta.pre_token_balances.clone().into();
+        let post_balances: Option<Vec<UiTransactionTokenBalance>> =
+            meta.post_token_balances.clone().into();
+        let pre_balances = pre_balances.unwrap_or_default();
+        let post_balances = post_balances?;
+        let vault_balance = |balances: &[UiTransactionTokenBalance], mint: &str| -> Option<u64> {
+            balances
+                .iter()
+                .find(|balance| {
+                    balance.mint == mint
+                        && matches!(&balance.owner, OptionSerializer::Some(owner) if *owner == pool.address)
+                })
+                .and_then(|balance| balance.ui_token_amount.amount.parse::<u64>().ok())
+        };
+        let post_a = vault_balance(&post_balances, &pool.token_mint_a)?;
+        let post_b = vault_balance(&post_balances, &pool.token_mint_b)?;
+        let pre_a = vault_balance(&pre_balances, &pool.token_mint_a).unwrap_or(post_a);
+        let pre_b = vault_balance(&pre_balances, &pool.token_mint_b).unwrap_or(post_b);
+        Some((
+            post_a as i128 - pre_a as i128,
+            post_b as i128 - pre_b as i128,
+            post_a,
+            post_b,
+        ))
+    }
+}
+
+/// Fee revenue and implied volume of a pool over consecutive fee growth snapshots
+///
+/// Between two snapshots, the growth of `fee_growth_global` times the liquidity it accrued on
+/// is what liquidity providers earned; growth wraps like on-chain. Liquidity is taken as the
+/// average of both ends, so denser snapshots track liquidity changes more closely. The
+/// protocol's share is added back to get the fees swappers paid, and dividing by the pool's
+/// fee rate gives the volume. Returns None with fewer than two snapshots or a zero span.
+///
+/// # Example
+/// ```rust
+/// use orca_sdk::stats::fee_growth_volume;
+///
+/// let volume = fee_growth_volume(&pool, &stored_snapshots);
+/// ```
+pub fn fee_growth_volume(
+    pool: &PoolInfo,
+    snapshots: &[FeeGrowthSnapshot],
+) -> Option<FeeGrowthVolume> {
+    let (first, last) = (snapshots.first()?, snapshots.last()?);
+    if last.timestamp <= first.timestamp {
+        return None;
+    }
+    let (mut lp_fees_a, mut lp_fees_b) = (0u128, 0u128);
+    for pair in snapshots.windows(2) {
+        let liquidity = pair[0].liquidity / 2 + pair[1].liquidity / 2;
+        let earned = |start: u128, end: u128| {
+            mul_div_floor(end.wrapping_sub(start), liquidity, GROWTH_SCALE).unwrap_or(u128::MAX)
+        };
+        lp_fees_a = lp_fees_a.saturating_add(earned(
+            pair[0].fee_growth_global_a,
+            pair[1].fee_growth_global_a,
+        ));
+        lp_fees_b = lp_fees_b.saturating_add(earned(
+            pair[0].fee_growth_global_b,
+            pair[1].fee_growth_global_b,
+        ));
+    }
+    let lp_share = (BPS_DENOMINATOR - pool.protocol_fee_rate.min(BPS_DENOMINATOR)) as u128;
+    let total = |lp_fees: u128| {
+        if lp_share == 0 {
+            return lp_fees;
+        }
+        mul_div_floor(lp_fees, BPS_DENOMINATOR as u128, lp_share).unwrap_or(u128::MAX)
+    };
+    let volume = |fees: u128| {
+        if pool.fee_rate == 0 {
+            return 0;
+        }
+        mul_div_floor(fees, FEE_RATE_DENOMINATOR as u128, pool.fee_rate as u128)
+            .unwrap_or(u128::MAX)
+    };
+    let to_u64 = |amount: u128| amount.min(u64::MAX as u128) as u64;
+    let (fees_a, fees_b) = (total(lp_fees_a), total(lp_fees_b));
+    Some(FeeGrowthVolume {
+        pool_address: last.pool_address,
+        window_start: first.timestamp,
+        window_end: last.timestamp,
+        snapshot_count: snapshots.len(),
+        lp_fees_a: to_u64(lp_fees_a),
+        lp_fees_b: to_u64(lp_fees_b),
+        fees_a: to_u64(fees_a),
+        fees_b: to_u64(fees_b),
+        volume_a: to_u64(volume(fees_a)),
+        volume_b: to_u64(volume(fees_b)),
+    })
+}
+
+/// Time-weighted average of `(timestamp, price)` observations up to `end`
+///
+/// Each price is weighted by the time until the next observation; the last one
+/// is held until `end`. Falls back to the arithmetic mean when all observations
+/// share one timestamp.
+pub(crate) fn time_weighted_average(observations: &[(i64, f64)], end: i64) -> Option<f64> {
+    if observations.is_empty() {
+        return None;
+    }
+    let mut sorted = observations.to_vec();
+    sorted.sort_by_key(|(timestamp, _)| *timestamp);
+    let mut weighted_sum = 0.0;
+    let mut total_weight = 0.0;
+    for (i, (timestamp, price)) in sorted.iter().enumerate() {
+        let next = sorted.get(i + 1).map_or(end, |(next, _)| *next);
+        let weight = (next - timestamp).max(0) as f64;
+        weighted_sum += price * weight;
+        total_weight += weight;
+    }
+    if total_weight > 0.0 {
+        Some(weighted_sum / total_weight)
+    } else {
+        Some(sorted.iter().map(|(_, price)| price).sum::<f64>() / sorted.len() as f64)
+    }
+}
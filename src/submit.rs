@@ -0,0 +1,580 @@
+use std::{sync::atomic::Ordering, time::Instant};
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{
+        RpcSendTransactionConfig, RpcSimulateTransactionAccountsConfig,
+        RpcSimulateTransactionConfig,
+    },
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    message::VersionedMessage,
+    program_pack::Pack,
+};
+use spl_token::state::Account as TokenAccount;
+
+use super::*;
+use crate::{
+    audit::{AuditDetails, AuditOutcome},
+    global::{JITO_TIP_ACCOUNTS, SYSTEM_PROGRAM_ID},
+    types::OrcaResult,
+};
+
+// System program transfer instruction index
+const SYSTEM_TRANSFER_INSTRUCTION: u32 = 2;
+// Jito accepts at most this many transactions per bundle
+const MAX_BUNDLE_TRANSACTIONS: usize = 5;
+
+/// Private submission path that keeps a transaction out of the public mempool
+#[derive(Debug, Clone)]
+pub enum PrivateRelay {
+    /// Jito block engine; a tip transfer is appended to the transaction
+    Jito {
+        block_engine_url: String,
+        tip_lamports: u64,
+    },
+    /// A trusted RPC endpoint, sent with preflight skipped
+    Rpc { url: String },
+}
+
+/// Backend used to send the transactions of an operation
+#[derive(Debug, Clone, Default)]
+pub enum SubmitMode {
+    /// The client's RPC endpoint, one transaction at a time
+    #[default]
+    Rpc,
+    /// A Jito block engine; all transactions go out as one bundle with a tip transfer appended
+    Jito {
+        block_engine_url: String,
+        tip_lamports: u64,
+    },
+}
+
+impl SubmitMode {
+    /// Tip the operation must pay, if any
+    pub fn jito_tip(&self) -> Option<u64> {
+        match self {
+            SubmitMode::Rpc => None,
+            SubmitMode::Jito { tip_lamports, .. } => Some(*tip_lamports),
+        }
+    }
+}
+
+/// Result of a transaction that was built and simulated instead of broadcast
+#[derive(Debug, Clone)]
+pub struct DryRunOutcome {
+    /// Operation that produced the transaction, e.g. "swap"
+    pub operation: String,
+    /// Signature the transaction would have had if it were sent
+    pub signature: Signature,
+    /// Simulation error, if the transaction would have failed
+    pub error: Option<String>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    /// Expected token balance changes of the owner; only reported by the `simulate_*` calls
+    pub balance_changes: Vec<BalanceChange>,
+}
+
+/// Expected change of one of the owner's token balances
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceChange {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub before: u64,
+    pub after: u64,
+}
+
+impl BalanceChange {
+    /// Signed change in raw token units
+    pub fn delta(&self) -> i128 {
+        self.after as i128 - self.before as i128
+    }
+}
+
+impl DryRunOutcome {
+    /// Returns true when the simulation reported no error
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl OrcaClient {
+    /// Enables or disables dry-run mode
+    ///
+    /// In dry-run mode every mutating API builds and signs its transactions and simulates
+    /// them instead of broadcasting. The call then fails with `OrcaError::DryRun`, which
+    /// carries the simulation result of each transaction.
+    ///
+    /// # Example
+    /// ```rust
+    /// client.set_dry_run(true);
+    /// match client.swap(&keypair, input_mint, output_mint, amount, None).await {
+    ///     Err(OrcaError::DryRun(outcomes)) => {
+    ///         for outcome in outcomes {
+    ///             println!("{} {} -> {:?}", outcome.operation, outcome.signature, outcome.error);
+    ///         }
+    ///     }
+    ///     other => println!("{:?}", other),
+    /// }
+    /// ```
+    pub fn set_dry_run(&self, enabled: bool) {
+        self.dry_run.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Returns true when dry-run mode is enabled
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run.load(Ordering::SeqCst)
+    }
+
+    /// Sends a signed transaction, or simulates it when dry-run mode is enabled
+    ///
+    /// All mutating APIs submit through this method. In dry-run mode it returns
+    /// `OrcaError::DryRun` with the simulation result.
+    #[tracing::instrument(
+        skip(self, transaction, details),
+        fields(signature = tracing::field::Empty, elapsed_ms = tracing::field::Empty)
+    )]
+    pub(crate) async fn submit_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        operation: &str,
+        details: &AuditDetails,
+    ) -> OrcaResult<Signature> {
+        self.rpc_client()?;
+        if self.is_dry_run() {
+            return Err(OrcaError::DryRun(vec![
+                self.simulate_dry_run(transaction, operation, details)
+                    .await?,
+            ]));
+        }
+        let started = Instant::now();
+        let result = self.send_with_failover(transaction, operation).await;
+        self.record_submission(&result, started);
+        self.audit_result(transaction, operation, details, &result);
+        result
+    }
+
+    /// Simulates a transaction in place of sending it and audits the result
+    async fn simulate_dry_run(
+        &self,
+        transaction: &VersionedTransaction,
+        operation: &str,
+        details: &AuditDetails,
+    ) -> OrcaResult<DryRunOutcome> {
+        let outcome = self
+            .simulate_signed_transaction(transaction, operation)
+            .await?;
+        self.audit_transaction(
+            transaction,
+            operation,
+            details,
+            AuditOutcome::Simulated(outcome.error.clone()),
+        );
+        Ok(outcome)
+    }
+
+    fn audit_result(
+        &self,
+        transaction: &VersionedTransaction,
+        operation: &str,
+        details: &AuditDetails,
+        result: &OrcaResult<Signature>,
+    ) {
+        let outcome = match result {
+            Ok(_) => AuditOutcome::Confirmed,
+            Err(e) => AuditOutcome::Failed(format!("{:?}", e)),
+        };
+        self.audit_transaction(transaction, operation, details, outcome);
+    }
+
+    /// Simulates a signed transaction and captures logs and compute usage
+    pub(crate) async fn simulate_signed_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        operation: &str,
+    ) -> OrcaResult<DryRunOutcome> {
+        let signature = transaction.signatures.first().copied().unwrap_or_default();
+        let simulation = self
+            .rpc_call(&format!("Failed to simulate {}", operation), |client| {
+                client.simulate_transaction(transaction)
+            })
+            .await?
+            .value;
+        Ok(DryRunOutcome {
+            operation: operation.to_string(),
+            signature,
+            error: simulation.err.map(|e| format!("{:?}", e)),
+            logs: simulation.logs.unwrap_or_default(),
+            units_consumed: simulation.units_consumed,
+            balance_changes: Vec::new(),
+        })
+    }
+
+    /// Simulates an unsigned message and reports the owner's token balance changes for `mints`
+    ///
+    /// Signature verification is skipped and the blockhash replaced, so no keys are needed.
+    pub(crate) async fn simulate_unsigned(
+        &self,
+        message: VersionedMessage,
+        operation: &str,
+        owner: &Pubkey,
+        mints: &[Pubkey],
+    ) -> OrcaResult<DryRunOutcome> {
+        let transaction = VersionedTransaction {
+            signatures: vec![
+                Signature::default();
+                message.header().num_required_signatures as usize
+            ],
+            message,
+        };
+        let token_accounts = self.get_associated_token_addresses(owner, mints).await?;
+        let before = self
+            .rpc_call("Failed to get accounts", |client| {
+                client.get_multiple_accounts(&token_accounts)
+            })
+            .await?;
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            accounts: Some(RpcSimulateTransactionAccountsConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                addresses: token_accounts.iter().map(ToString::to_string).collect(),
+            }),
+            ..Default::default()
+        };
+        let simulation = self
+            .rpc_call(&format!("Failed to simulate {}", operation), |client| {
+                client.simulate_transaction_with_config(&transaction, config.clone())
+            })
+            .await?
+            .value;
+        let after = simulation.accounts.unwrap_or_default();
+        let balance_changes = mints
+            .iter()
+            .zip(&token_accounts)
+            .enumerate()
+            .map(|(index, (mint, token_account))| BalanceChange {
+                mint: *mint,
+                token_account: *token_account,
+                before: before
+                    .get(index)
+                    .and_then(Option::as_ref)
+                    .and_then(|account| token_amount(&account.data))
+                    .unwrap_or(0),
+                after: after
+                    .get(index)
+                    .and_then(Option::as_ref)
+                    .and_then(|account| account.data.decode())
+                    .and_then(|data| token_amount(&data))
+                    .unwrap_or(0),
+            })
+            .collect();
+        Ok(DryRunOutcome {
+            operation: operation.to_string(),
+            signature: Signature::default(),
+            error: simulation.err.map(|e| format!("{:?}", e)),
+            logs: simulation.logs.unwrap_or_default(),
+            units_consumed: simulation.units_consumed,
+            balance_changes,
+        })
+    }
+
+    /// Sends a signed transaction through a private relay and waits for confirmation
+    ///
+    /// Honors dry-run mode like `submit_transaction`.
+    #[tracing::instrument(
+        skip(self, transaction, relay, details),
+        fields(signature = tracing::field::Empty, elapsed_ms = tracing::field::Empty)
+    )]
+    pub(crate) async fn submit_transaction_private(
+        &self,
+        transaction: &VersionedTransaction,
+        operation: &str,
+        relay: &PrivateRelay,
+        details: &AuditDetails,
+    ) -> OrcaResult<Signature> {
+        if self.is_dry_run() {
+            return Err(OrcaError::DryRun(vec![
+                self.simulate_dry_run(transaction, operation, details)
+                    .await?,
+            ]));
+        }
+        let started = Instant::now();
+        let result = self.send_via_relay(transaction, operation, relay).await;
+        self.record_submission(&result, started);
+        self.audit_result(transaction, operation, details, &result);
+        result
+    }
+
+    /// Records the signature and RPC round-trip time of a submission on the current span
+    fn record_submission(&self, result: &OrcaResult<Signature>, started: Instant) {
+        let span = tracing::Span::current();
+        span.record("elapsed_ms", started.elapsed().as_millis() as u64);
+        match result {
+            Ok(signature) => {
+                span.record("signature", tracing::field::display(signature));
+            }
+            Err(e) => tracing::warn!(error = ?e, "Transaction submission failed"),
+        }
+    }
+
+    async fn send_via_relay(
+        &self,
+        transaction: &VersionedTransaction,
+        operation: &str,
+        relay: &PrivateRelay,
+    ) -> OrcaResult<Signature> {
+        let signature = match relay {
+            PrivateRelay::Jito {
+                block_engine_url, ..
+            } => {
+                self.send_via_jito(transaction, block_engine_url, operation)
+                    .await?
+            }
+            PrivateRelay::Rpc { url } => RpcClient::new(url.clone())
+                .send_transaction_with_config(
+                    transaction,
+                    RpcSendTransactionConfig {
+                        skip_preflight: true,
+                        ..Default::default()
+                    },
+                )
+                .await
+                .map_err(|e| {
+                    OrcaError::from_send_error(
+                        format!("Failed to {}", operation),
+                        transaction.signatures.first().copied(),
+                        e,
+                    )
+                })?,
+        };
+        self.wait_for_confirmation(&signature, operation).await?;
+        Ok(signature)
+    }
+
+    async fn send_via_jito(
+        &self,
+        transaction: &VersionedTransaction,
+        block_engine_url: &str,
+        operation: &str,
+    ) -> OrcaResult<Signature> {
+        self.jito_request(
+            block_engine_url,
+            "transactions",
+            "sendTransaction",
+            serde_json::json!([encode_transaction(transaction)?, { "encoding": "base64" }]),
+            operation,
+        )
+        .await?;
+        transaction
+            .signatures
+            .first()
+            .copied()
+            .ok_or(OrcaError::TransactionError(
+                "Transaction is not signed".to_string(),
+            ))
+    }
+
+    /// Sends signed transactions as one Jito bundle and waits until all of them confirm
+    ///
+    /// The bundle lands atomically and in order; its last transaction must carry the tip.
+    /// Honors dry-run mode like `submit_transaction`, simulating each transaction on its own.
+    #[tracing::instrument(
+        skip(self, transactions, details),
+        fields(bundle_id = tracing::field::Empty, elapsed_ms = tracing::field::Empty)
+    )]
+    pub(crate) async fn submit_bundle(
+        &self,
+        transactions: &[VersionedTransaction],
+        operation: &str,
+        block_engine_url: &str,
+        details: &AuditDetails,
+    ) -> OrcaResult<Vec<Signature>> {
+        if transactions.len() > MAX_BUNDLE_TRANSACTIONS {
+            return Err(OrcaError::TransactionError(format!(
+                "Failed to {}: {} transactions exceed the Jito bundle limit of {}",
+                operation,
+                transactions.len(),
+                MAX_BUNDLE_TRANSACTIONS
+            )));
+        }
+        if self.is_dry_run() {
+            let mut outcomes = Vec::with_capacity(transactions.len());
+            for transaction in transactions {
+                outcomes.push(
+                    self.simulate_dry_run(transaction, operation, details)
+                        .await?,
+                );
+            }
+            return Err(OrcaError::DryRun(outcomes));
+        }
+        let started = Instant::now();
+        let result = self
+            .send_jito_bundle(transactions, block_engine_url, operation)
+            .await;
+        let span = tracing::Span::current();
+        span.record("elapsed_ms", started.elapsed().as_millis() as u64);
+        let outcome = match &result {
+            Ok(_) => AuditOutcome::Confirmed,
+            Err(e) => {
+                tracing::warn!(error = ?e, "Bundle submission failed");
+                AuditOutcome::Failed(format!("{:?}", e))
+            }
+        };
+        for transaction in transactions {
+            self.audit_transaction(transaction, operation, details, outcome.clone());
+        }
+        result
+    }
+
+    async fn send_jito_bundle(
+        &self,
+        transactions: &[VersionedTransaction],
+        block_engine_url: &str,
+        operation: &str,
+    ) -> OrcaResult<Vec<Signature>> {
+        let encoded = transactions
+            .iter()
+            .map(encode_transaction)
+            .collect::<OrcaResult<Vec<String>>>()?;
+        let bundle_id = self
+            .jito_request(
+                block_engine_url,
+                "bundles",
+                "sendBundle",
+                serde_json::json!([encoded, { "encoding": "base64" }]),
+                operation,
+            )
+            .await?;
+        tracing::Span::current().record("bundle_id", tracing::field::display(&bundle_id));
+        let mut signatures = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            let signature =
+                transaction
+                    .signatures
+                    .first()
+                    .copied()
+                    .ok_or(OrcaError::TransactionError(
+                        "Transaction is not signed".to_string(),
+                    ))?;
+            self.wait_for_confirmation(&signature, operation).await?;
+            signatures.push(signature);
+        }
+        Ok(signatures)
+    }
+
+    /// Calls a Jito block engine JSON-RPC method and returns its result
+    async fn jito_request(
+        &self,
+        block_engine_url: &str,
+        path: &str,
+        method: &str,
+        params: serde_json::Value,
+        operation: &str,
+    ) -> OrcaResult<serde_json::Value> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let response: serde_json::Value = reqwest::Client::new()
+            .post(format!(
+                "{}/api/v1/{}",
+                block_engine_url.trim_end_matches('/'),
+                path
+            ))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| OrcaError::NetworkError(format!("Failed to reach Jito: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| OrcaError::NetworkError(format!("Invalid Jito response: {}", e)))?;
+        if let Some(error) = response.get("error") {
+            return Err(OrcaError::TransactionError(format!(
+                "Failed to {}: Jito rejected {}: {}",
+                operation, method, error
+            )));
+        }
+        Ok(response
+            .get("result")
+            .cloned()
+            .unwrap_or(serde_json::Value::Null))
+    }
+
+    /// Polls until the signature is confirmed and reports on-chain failures
+    pub(crate) async fn wait_for_confirmation(
+        &self,
+        signature: &Signature,
+        operation: &str,
+    ) -> OrcaResult<()> {
+        let client = self
+            .solana
+            .client
+            .as_ref()
+            .ok_or(OrcaError::RpcClientUnavailable)?;
+        client
+            .poll_for_signature_with_commitment(signature, CommitmentConfig::confirmed())
+            .await
+            .map_err(|e| OrcaError::rpc(format!("Failed to confirm {}", operation), e))?;
+        if let Ok(Some(Err(e))) = client
+            .get_signature_status_with_commitment(signature, CommitmentConfig::confirmed())
+            .await
+        {
+            let logs = self.transaction_logs(signature).await;
+            return Err(OrcaError::transaction_failed(Some(*signature), e, logs));
+        }
+        Ok(())
+    }
+
+    /// Returns the program logs of a confirmed transaction, or nothing if they are unavailable
+    async fn transaction_logs(&self, signature: &Signature) -> Vec<String> {
+        self.fetch_transactions(&[*signature])
+            .await
+            .ok()
+            .and_then(|mut transactions| transactions.pop().flatten())
+            .and_then(|transaction| transaction.transaction.meta.clone())
+            .and_then(|meta| Option::<Vec<String>>::from(meta.log_messages))
+            .unwrap_or_default()
+    }
+
+    /// Builds a SOL transfer to a Jito tip account
+    pub(crate) fn build_jito_tip_instruction(
+        &self,
+        payer: &Pubkey,
+        tip_lamports: u64,
+    ) -> OrcaResult<Instruction> {
+        let index = chrono::Utc::now().timestamp_subsec_nanos() as usize % JITO_TIP_ACCOUNTS.len();
+        let tip_account = Pubkey::from_str(JITO_TIP_ACCOUNTS[index])
+            .map_err(|e| OrcaError::Error(format!("Invalid Jito tip account: {}", e)))?;
+        let system_program = Pubkey::from_str(SYSTEM_PROGRAM_ID)
+            .map_err(|e| OrcaError::Error(format!("Invalid system program ID: {}", e)))?;
+        let mut data = SYSTEM_TRANSFER_INSTRUCTION.to_le_bytes().to_vec();
+        data.extend_from_slice(&tip_lamports.to_le_bytes());
+        Ok(Instruction {
+            program_id: system_program,
+            accounts: vec![
+                AccountMeta::new(*payer, true),
+                AccountMeta::new(tip_account, false),
+            ],
+            data,
+        })
+    }
+}
+
+fn encode_transaction(transaction: &VersionedTransaction) -> OrcaResult<String> {
+    bincode::serialize(transaction)
+        .map(|serialized| BASE64_STANDARD.encode(serialized))
+        .map_err(|e| OrcaError::TransactionError(format!("Failed to serialize transaction: {}", e)))
+}
+
+fn token_amount(data: &[u8]) -> Option<u64> {
+    TokenAccount::unpack_from_slice(data.get(..TokenAccount::LEN)?)
+        .ok()
+        .map(|account| account.amount)
+}
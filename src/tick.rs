@@ -0,0 +1,358 @@
+use std::cmp::Reverse;
+
+use solana_sdk::instruction::{AccountMeta, Instruction};
+
+use super::*;
+use crate::{
+    global::{
+        MAX_SWAP_TICK_ARRAYS, MAX_TICK_INDEX, MIN_TICK_INDEX, SYSTEM_PROGRAM_ID,
+        TICK_ARRAY_ACCOUNT_SIZE, TICK_ARRAY_DISCRIMINATOR, TICK_ARRAY_SIZE,
+        WHIRLPOOL_INITIALIZE_TICK_ARRAY_DISCRIMINATOR, WHIRLPOOL_NUM_REWARDS,
+    },
+    layout::AccountReader,
+    pool::PoolInfo,
+    types::OrcaResult,
+    validation::parse_address,
+};
+
+/// A single tick of a Whirlpool
+#[derive(Debug, Clone, Default)]
+pub struct Tick {
+    /// Tick index (not the offset inside its array)
+    pub index: i32,
+    pub initialized: bool,
+    /// Liquidity added when the price crosses this tick moving up
+    pub liquidity_net: i128,
+    /// Total liquidity referencing this tick
+    pub liquidity_gross: u128,
+    pub fee_growth_outside_a: u128,
+    pub fee_growth_outside_b: u128,
+    pub reward_growths_outside: [u128; WHIRLPOOL_NUM_REWARDS],
+}
+
+/// Decoded TickArray account holding `TICK_ARRAY_SIZE` consecutive ticks
+#[derive(Debug, Clone)]
+pub struct TickArray {
+    pub address: Pubkey,
+    pub whirlpool: Pubkey,
+    pub start_tick_index: i32,
+    pub ticks: Vec<Tick>,
+}
+
+impl TickArray {
+    /// Decodes TickArray account data, returning None for other accounts
+    pub fn decode(address: Pubkey, data: &[u8], tick_spacing: u16) -> Option<Self> {
+        if data.len() < TICK_ARRAY_ACCOUNT_SIZE {
+            return None;
+        }
+        let mut reader = AccountReader::new(data, &TICK_ARRAY_DISCRIMINATOR)?;
+        let start_tick_index = reader.i32()?;
+        let mut ticks = Vec::with_capacity(TICK_ARRAY_SIZE as usize);
+        for offset in 0..TICK_ARRAY_SIZE {
+            let mut tick = Tick {
+                index: start_tick_index + offset * tick_spacing as i32,
+                initialized: reader.bool()?,
+                liquidity_net: reader.i128()?,
+                liquidity_gross: reader.u128()?,
+                fee_growth_outside_a: reader.u128()?,
+                fee_growth_outside_b: reader.u128()?,
+                ..Default::default()
+            };
+            for growth in &mut tick.reward_growths_outside {
+                *growth = reader.u128()?;
+            }
+            ticks.push(tick);
+        }
+        let whirlpool = reader.pubkey()?;
+        Some(Self {
+            address,
+            whirlpool,
+            start_tick_index,
+            ticks,
+        })
+    }
+
+    /// Ticks that currently hold liquidity
+    pub fn initialized_ticks(&self) -> impl Iterator<Item = &Tick> {
+        self.ticks.iter().filter(|tick| tick.initialized)
+    }
+
+    /// Returns the tick at `tick_index` if it falls inside this array
+    pub fn tick(&self, tick_index: i32, tick_spacing: u16) -> Option<&Tick> {
+        let offset = tick_index - self.start_tick_index;
+        if offset < 0 || offset % tick_spacing as i32 != 0 {
+            return None;
+        }
+        self.ticks.get((offset / tick_spacing as i32) as usize)
+    }
+}
+
+/// Initialized ticks a swap may cross, ordered in the swap direction
+#[derive(Debug, Clone)]
+pub(crate) struct SwapTickRange {
+    pub ticks: Vec<Tick>,
+    /// Furthest tick the swap can reach with the tick arrays that exist on-chain
+    pub boundary_tick_index: i32,
+}
+
+/// Start index of the tick array containing `tick_index`
+pub fn tick_array_start_index(tick_index: i32, tick_spacing: u16) -> i32 {
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    tick_index.div_euclid(ticks_in_array) * ticks_in_array
+}
+
+/// Rounds `tick_index` to the nearest multiple of `tick_spacing` within the tick bounds
+pub fn align_tick_index(tick_index: i32, tick_spacing: u16) -> i32 {
+    let spacing = tick_spacing.max(1) as i32;
+    let min = -(MIN_TICK_INDEX.abs() / spacing * spacing);
+    let max = MAX_TICK_INDEX / spacing * spacing;
+    let aligned = (tick_index as f64 / spacing as f64).round() as i32 * spacing;
+    aligned.clamp(min, max)
+}
+
+impl OrcaClient {
+    /// Derives the address of the tick array starting at `start_tick_index`
+    pub fn get_tick_array_address(&self, whirlpool: &Pubkey, start_tick_index: i32) -> Pubkey {
+        let (address, _) = Pubkey::find_program_address(
+            &[
+                b"tick_array",
+                whirlpool.as_ref(),
+                start_tick_index.to_string().as_bytes(),
+            ],
+            &self.whirlpool_program_id,
+        );
+        address
+    }
+
+    /// Start indexes of the tick arrays holding `lower_tick` and `upper_tick` that do not exist yet
+    ///
+    /// A position can only be opened once both of its tick arrays are initialized.
+    ///
+    /// # Example
+    /// ```rust
+    /// for start in client.get_missing_tick_array_starts(&pool_info, -1024, 1024).await? {
+    ///     println!("tick array {} must be initialized", start);
+    /// }
+    /// ```
+    pub async fn get_missing_tick_array_starts(
+        &self,
+        pool: &PoolInfo,
+        lower_tick: i32,
+        upper_tick: i32,
+    ) -> OrcaResult<Vec<i32>> {
+        let whirlpool = parse_address("pool_address", &pool.address)?;
+        let mut starts = vec![
+            tick_array_start_index(lower_tick, pool.tick_spacing),
+            tick_array_start_index(upper_tick, pool.tick_spacing),
+        ];
+        starts.dedup();
+        let addresses: Vec<Pubkey> = starts
+            .iter()
+            .map(|start| self.get_tick_array_address(&whirlpool, *start))
+            .collect();
+        let missing = self.missing_accounts(&addresses).await?;
+        Ok(starts
+            .into_iter()
+            .zip(missing)
+            .filter_map(|(start, is_missing)| is_missing.then_some(start))
+            .collect())
+    }
+
+    /// Constructs an `initialize_tick_array` instruction for the array at `start_tick_index`
+    ///
+    /// # Arguments
+    /// funder - Wallet paying rent for the tick array
+    /// whirlpool - Whirlpool address
+    /// start_tick_index - First tick of the array, a multiple of `TICK_ARRAY_SIZE * tick_spacing`
+    pub fn build_initialize_tick_array_instruction(
+        &self,
+        funder: &Pubkey,
+        whirlpool: &Pubkey,
+        start_tick_index: i32,
+    ) -> Instruction {
+        let mut data = WHIRLPOOL_INITIALIZE_TICK_ARRAY_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&start_tick_index.to_le_bytes());
+        Instruction {
+            program_id: self.whirlpool_program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(*whirlpool, false),
+                AccountMeta::new(*funder, true),
+                AccountMeta::new(
+                    self.get_tick_array_address(whirlpool, start_tick_index),
+                    false,
+                ),
+                AccountMeta::new_readonly(Pubkey::from_str_const(SYSTEM_PROGRAM_ID), false),
+            ],
+            data,
+        }
+    }
+
+    /// Start indexes of the tick arrays a swap walks through, beginning at the current tick
+    ///
+    /// Arrays beyond the tick bounds are omitted, so fewer than `count` may be returned.
+    pub fn get_swap_tick_array_start_indexes(
+        &self,
+        pool: &PoolInfo,
+        a_to_b: bool,
+        count: usize,
+    ) -> Vec<i32> {
+        let ticks_in_array = TICK_ARRAY_SIZE * pool.tick_spacing as i32;
+        let min_start = tick_array_start_index(MIN_TICK_INDEX, pool.tick_spacing);
+        let max_start = tick_array_start_index(MAX_TICK_INDEX, pool.tick_spacing);
+        let current = tick_array_start_index(pool.tick_current_index, pool.tick_spacing);
+        (0..count as i32)
+            .map(|step| {
+                if a_to_b {
+                    current - step * ticks_in_array
+                } else {
+                    current + step * ticks_in_array
+                }
+            })
+            .take_while(|start| (min_start..=max_start).contains(start))
+            .collect()
+    }
+
+    /// Fetches and decodes tick arrays of a pool with one RPC call
+    ///
+    /// Returns one entry per start index; arrays that were never initialized on-chain are None.
+    ///
+    /// # Params
+    /// pool - The pool the tick arrays belong to
+    /// start_tick_indexes - Start indexes of the arrays to fetch
+    ///
+    /// # Example
+    /// ```rust
+    /// let pool = client.get_pool_state_onchain("POOL_ADDRESS_HERE").await?;
+    /// let starts = client.get_swap_tick_array_start_indexes(&pool, true, 3);
+    /// for tick_array in client.get_tick_arrays(&pool, &starts).await?.into_iter().flatten() {
+    ///     for tick in tick_array.initialized_ticks() {
+    ///         println!("tick {} net {}", tick.index, tick.liquidity_net);
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_tick_arrays(
+        &self,
+        pool: &PoolInfo,
+        start_tick_indexes: &[i32],
+    ) -> OrcaResult<Vec<Option<TickArray>>> {
+        let whirlpool = parse_address("pool_address", &pool.address)?;
+        let addresses: Vec<Pubkey> = start_tick_indexes
+            .iter()
+            .map(|start| self.get_tick_array_address(&whirlpool, *start))
+            .collect();
+        let accounts = self
+            .rpc_call("Failed to get tick arrays", |client| {
+                client.get_multiple_accounts(&addresses)
+            })
+            .await?;
+        Ok(addresses
+            .into_iter()
+            .zip(accounts)
+            .map(|(address, account)| {
+                let account = account?;
+                if account.owner != self.whirlpool_program_id {
+                    return None;
+                }
+                TickArray::decode(address, &account.data, pool.tick_spacing)
+            })
+            .collect())
+    }
+
+    /// Returns initialized ticks within `array_radius` tick arrays on each side of the current tick
+    ///
+    /// Ticks are sorted by index.
+    pub async fn get_initialized_ticks(
+        &self,
+        pool: &PoolInfo,
+        array_radius: usize,
+    ) -> OrcaResult<Vec<Tick>> {
+        let mut start_indexes =
+            self.get_swap_tick_array_start_indexes(pool, true, array_radius + 1);
+        start_indexes.reverse();
+        start_indexes.extend(
+            self.get_swap_tick_array_start_indexes(pool, false, array_radius + 1)
+                .into_iter()
+                .skip(1),
+        );
+        let tick_arrays = self.get_tick_arrays(pool, &start_indexes).await?;
+        Ok(tick_arrays
+            .iter()
+            .flatten()
+            .flat_map(|tick_array| tick_array.initialized_ticks().cloned())
+            .collect())
+    }
+
+    /// Loads the initialized ticks a swap in the given direction can cross
+    ///
+    /// Only the first `MAX_SWAP_TICK_ARRAYS` arrays are considered, and the range stops at the
+    /// first array that was never initialized, mirroring what one swap instruction can reach.
+    pub(crate) async fn get_swap_tick_range(
+        &self,
+        pool: &PoolInfo,
+        a_to_b: bool,
+    ) -> OrcaResult<SwapTickRange> {
+        let start_indexes =
+            self.get_swap_tick_array_start_indexes(pool, a_to_b, MAX_SWAP_TICK_ARRAYS);
+        let tick_arrays = self.get_tick_arrays(pool, &start_indexes).await?;
+        let ticks_in_array = TICK_ARRAY_SIZE * pool.tick_spacing as i32;
+        let mut ticks = Vec::new();
+        let mut boundary_tick_index = pool.tick_current_index;
+        for tick_array in tick_arrays.iter().map_while(Option::as_ref) {
+            ticks.extend(tick_array.initialized_ticks().cloned());
+            boundary_tick_index = if a_to_b {
+                tick_array.start_tick_index
+            } else {
+                tick_array.start_tick_index + ticks_in_array - 1
+            };
+        }
+        if a_to_b {
+            ticks.retain(|tick| tick.index <= pool.tick_current_index);
+            ticks.sort_by_key(|tick| Reverse(tick.index));
+        } else {
+            ticks.retain(|tick| tick.index > pool.tick_current_index);
+            ticks.sort_by_key(|tick| tick.index);
+        }
+        Ok(SwapTickRange {
+            ticks,
+            boundary_tick_index: boundary_tick_index.clamp(MIN_TICK_INDEX, MAX_TICK_INDEX),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use base64::{Engine, prelude::BASE64_STANDARD};
+
+    use super::*;
+
+    // Mainnet TickArray account, captured as base64 `getAccountInfo` data
+    const TICK_ARRAY_ACCOUNT: &str = include_str!("../tests/fixtures/tick_array_account.b64");
+
+    fn fixture() -> Vec<u8> {
+        BASE64_STANDARD.decode(TICK_ARRAY_ACCOUNT.trim()).unwrap()
+    }
+
+    #[test]
+    fn decodes_tick_array_account() {
+        let tick_array = TickArray::decode(Pubkey::default(), &fixture(), 32896).unwrap();
+        assert_eq!(tick_array.start_tick_index, -2894848);
+        assert_eq!(
+            tick_array.whirlpool,
+            Pubkey::from_str("CGGNcohZdLdeDBdhmQRGmUH1Viv1p4d1ds2aPLoiVWaR").unwrap()
+        );
+        assert_eq!(tick_array.ticks.len(), 88);
+        let initialized: Vec<_> = tick_array.initialized_ticks().collect();
+        assert_eq!(initialized.len(), 1);
+        assert_eq!(initialized[0].index, -2894848 + 75 * 32896);
+        assert_eq!(initialized[0].liquidity_net, 141421356);
+        assert_eq!(initialized[0].liquidity_gross, 141421356);
+    }
+
+    #[test]
+    fn rejects_truncated_tick_array() {
+        let data = fixture();
+        assert!(TickArray::decode(Pubkey::default(), &data[..data.len() - 1], 32896).is_none());
+    }
+}
@@ -0,0 +1,57 @@
+use std::future::Future;
+
+use super::*;
+use crate::types::OrcaResult;
+
+impl OrcaClient {
+    /// Sets a deadline applied to every long-running operation, or removes it with `None`
+    ///
+    /// Operations such as pool discovery, history backfills, quotes and swaps return
+    /// `OrcaError::Timeout` once the deadline passes. Dropping the returned future of any
+    /// operation cancels it; no background work is left running.
+    ///
+    /// A timed-out swap or liquidity operation may still land if its transaction was
+    /// already sent; check the audit log or the chain before retrying.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// client.set_operation_timeout(Some(Duration::from_secs(10)));
+    /// match client.find_pools_by_token_onchain_optimized(mint).await {
+    ///     Err(OrcaError::Timeout { operation, .. }) => println!("{} timed out", operation),
+    ///     result => println!("{:?}", result),
+    /// }
+    /// ```
+    pub fn set_operation_timeout(&self, timeout: Option<Duration>) {
+        if let Ok(mut current) = self.operation_timeout.write() {
+            *current = timeout;
+        }
+    }
+
+    /// Returns the deadline applied to long-running operations
+    pub fn operation_timeout(&self) -> Option<Duration> {
+        self.operation_timeout
+            .read()
+            .ok()
+            .and_then(|timeout| *timeout)
+    }
+
+    /// Runs an operation under the configured deadline
+    pub(crate) async fn with_timeout<T, F>(&self, operation: &str, future: F) -> OrcaResult<T>
+    where
+        F: Future<Output = OrcaResult<T>>,
+    {
+        match self.operation_timeout() {
+            Some(timeout) => tokio::time::timeout(timeout, future)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(OrcaError::Timeout {
+                        operation: operation.to_string(),
+                        timeout,
+                    })
+                }),
+            None => future.await,
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+
+use solana_sdk::program_pack::Pack;
+
+use super::*;
+use crate::{
+    global::{MEMO_PROGRAM_ID, TOKEN_2022_PROGRAM_ID},
+    pool::PoolInfo,
+    rent::{TOKEN_2022_ACCOUNT_SIZE, TOKEN_ACCOUNT_SIZE, TRANSFER_FEE_AMOUNT_EXTENSION_SIZE},
+    types::OrcaResult,
+    validation::parse_address,
+};
+
+/// Token-2022 (Token Extensions) program
+pub const TOKEN_2022_PROGRAM: Pubkey = Pubkey::from_str_const(TOKEN_2022_PROGRAM_ID);
+/// SPL Memo program, required by the Whirlpool `*_v2` instructions
+pub const MEMO_PROGRAM: Pubkey = Pubkey::from_str_const(MEMO_PROGRAM_ID);
+
+// Token-2022 account layout: base mint padded to the token account size, then account type
+const TOKEN_2022_ACCOUNT_TYPE_OFFSET: usize = 165;
+const TOKEN_2022_MINT_ACCOUNT_TYPE: u8 = 1;
+// Token-2022 extension type discriminators
+pub(crate) const EXTENSION_TRANSFER_FEE_CONFIG: u16 = 1;
+// Transfer fee rates are expressed in basis points
+const MAX_FEE_BASIS_POINTS: u128 = 10_000;
+
+/// Transfer fee a Token-2022 mint withholds on every transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TransferFee {
+    /// First epoch the fee applies to
+    pub epoch: u64,
+    pub maximum_fee: u64,
+    pub basis_points: u16,
+}
+
+impl TransferFee {
+    /// Fee withheld when `amount` is transferred
+    pub fn fee(&self, amount: u64) -> u64 {
+        if self.basis_points == 0 || amount == 0 {
+            return 0;
+        }
+        let fee = (amount as u128 * self.basis_points as u128).div_ceil(MAX_FEE_BASIS_POINTS);
+        fee.min(self.maximum_fee as u128) as u64
+    }
+
+    /// Amount that arrives when `amount` is transferred
+    pub fn amount_after_fee(&self, amount: u64) -> u64 {
+        amount.saturating_sub(self.fee(amount))
+    }
+
+    /// Amount to transfer so that `net_amount` arrives
+    pub fn amount_before_fee(&self, net_amount: u64) -> u64 {
+        if self.basis_points == 0 || net_amount == 0 {
+            return net_amount;
+        }
+        let basis_points = self.basis_points as u128;
+        if basis_points >= MAX_FEE_BASIS_POINTS {
+            return net_amount.saturating_add(self.maximum_fee);
+        }
+        let gross = (net_amount as u128 * MAX_FEE_BASIS_POINTS)
+            .div_ceil(MAX_FEE_BASIS_POINTS - basis_points);
+        if gross - net_amount as u128 >= self.maximum_fee as u128 {
+            net_amount.saturating_add(self.maximum_fee)
+        } else {
+            gross.min(u64::MAX as u128) as u64
+        }
+    }
+}
+
+/// Token program and transfer fee of a mint
+#[derive(Debug, Clone, PartialEq)]
+pub struct MintInfo {
+    pub mint: Pubkey,
+    /// Program that owns the mint, SPL Token or Token-2022
+    pub token_program: Pubkey,
+    pub decimals: u8,
+    /// Transfer fee in effect for the current epoch; set for every mint with a fee config
+    pub transfer_fee: Option<TransferFee>,
+}
+
+impl MintInfo {
+    /// Returns true when the mint belongs to the Token-2022 program
+    pub fn is_token_2022(&self) -> bool {
+        self.token_program == TOKEN_2022_PROGRAM
+    }
+
+    /// Amount that arrives when `amount` is transferred
+    pub fn amount_after_transfer_fee(&self, amount: u64) -> u64 {
+        self.transfer_fee
+            .map_or(amount, |fee| fee.amount_after_fee(amount))
+    }
+
+    /// Amount to transfer so that `net_amount` arrives
+    pub fn amount_before_transfer_fee(&self, net_amount: u64) -> u64 {
+        self.transfer_fee
+            .map_or(net_amount, |fee| fee.amount_before_fee(net_amount))
+    }
+
+    /// Size of an associated token account for this mint
+    pub(crate) fn token_account_size(&self) -> usize {
+        match (self.is_token_2022(), self.transfer_fee) {
+            (false, _) => TOKEN_ACCOUNT_SIZE,
+            (true, None) => TOKEN_2022_ACCOUNT_SIZE,
+            (true, Some(_)) => TOKEN_2022_ACCOUNT_SIZE + TRANSFER_FEE_AMOUNT_EXTENSION_SIZE,
+        }
+    }
+}
+
+impl OrcaClient {
+    /// Fetches the token program, decimals and transfer fee of a mint
+    ///
+    /// # Example
+    /// ```rust
+    /// let info = client.get_mint_info(&mint).await?;
+    /// if let Some(fee) = info.transfer_fee {
+    ///     println!("{} bps transfer fee", fee.basis_points);
+    /// }
+    /// ```
+    pub async fn get_mint_info(&self, mint: &Pubkey) -> OrcaResult<MintInfo> {
+        self.get_mint_infos(&[*mint])
+            .await?
+            .pop()
+            .ok_or(OrcaError::AccountNotFound(*mint))
+    }
+
+    /// Fetches the token program, decimals and transfer fee of several mints in batches of 100
+    ///
+    /// Fails when a mint does not exist or is not owned by SPL Token or Token-2022.
+    pub async fn get_mint_infos(&self, mints: &[Pubkey]) -> OrcaResult<Vec<MintInfo>> {
+        #[cfg(feature = "persistent-cache")]
+        if let Some(infos) = self
+            .persistent_cache
+            .as_ref()
+            .and_then(|cache| cache.get_mint_infos(mints))
+        {
+            return Ok(infos);
+        }
+        let accounts = self
+            .get_multiple_accounts_batched(mints, "Failed to get mint accounts")
+            .await?;
+        let mut infos = Vec::with_capacity(mints.len());
+        let mut fee_configs = Vec::with_capacity(mints.len());
+        for (mint, account) in mints.iter().zip(accounts) {
+            let account = account.ok_or(OrcaError::AccountNotFound(*mint))?;
+            if account.owner != spl_token::id() && account.owner != TOKEN_2022_PROGRAM {
+                return Err(OrcaError::InvalidAccountData {
+                    account: *mint,
+                    reason: format!("owned by {}, not a token program", account.owner),
+                });
+            }
+            let mint_data = account
+                .data
+                .get(..spl_token::state::Mint::LEN)
+                .and_then(|data| spl_token::state::Mint::unpack_from_slice(data).ok())
+                .ok_or_else(|| OrcaError::InvalidAccountData {
+                    account: *mint,
+                    reason: "not an SPL token mint".to_string(),
+                })?;
+            fee_configs.push(
+                token_2022_mint_extensions(&account.data)
+                    .into_iter()
+                    .find(|(extension_type, _)| *extension_type == EXTENSION_TRANSFER_FEE_CONFIG)
+                    .and_then(|(_, value)| parse_transfer_fee_config(value)),
+            );
+            infos.push(MintInfo {
+                mint: *mint,
+                token_program: account.owner,
+                decimals: mint_data.decimals,
+                transfer_fee: None,
+            });
+        }
+        if fee_configs.iter().any(Option::is_some) {
+            let epoch = self
+                .rpc_call("Failed to get epoch info", |client| client.get_epoch_info())
+                .await?
+                .epoch;
+            for (info, config) in infos.iter_mut().zip(fee_configs) {
+                info.transfer_fee =
+                    config.map(|(older, newer)| if epoch >= newer.epoch { newer } else { older });
+            }
+        }
+        #[cfg(feature = "persistent-cache")]
+        if let Some(cache) = &self.persistent_cache {
+            cache.insert_mint_infos(&infos);
+        }
+        if let Ok(mut decimals) = self.mint_decimals.write() {
+            decimals.extend(infos.iter().map(|info| (info.mint, info.decimals)));
+        }
+        Ok(infos)
+    }
+
+    /// Decimals of several mints, in order
+    ///
+    /// Decimals never change, so they are kept in memory once fetched and later calls only
+    /// request the mints not seen before.
+    ///
+    /// # Example
+    /// ```rust
+    /// let decimals = client.get_mint_decimals(&[sol_mint, usdc_mint]).await?;
+    /// assert_eq!(decimals, vec![9, 6]);
+    /// ```
+    pub async fn get_mint_decimals(&self, mints: &[Pubkey]) -> OrcaResult<Vec<u8>> {
+        let cached = |mint: &Pubkey| {
+            self.mint_decimals
+                .read()
+                .ok()
+                .and_then(|decimals| decimals.get(mint).copied())
+        };
+        let mut missing: Vec<Pubkey> = mints
+            .iter()
+            .filter(|mint| cached(mint).is_none())
+            .copied()
+            .collect();
+        missing.sort();
+        missing.dedup();
+        let fetched: HashMap<Pubkey, u8> = if missing.is_empty() {
+            HashMap::new()
+        } else {
+            self.get_mint_infos(&missing)
+                .await?
+                .into_iter()
+                .map(|info| (info.mint, info.decimals))
+                .collect()
+        };
+        mints
+            .iter()
+            .map(|mint| {
+                fetched
+                    .get(mint)
+                    .copied()
+                    .or_else(|| cached(mint))
+                    .ok_or(OrcaError::AccountNotFound(*mint))
+            })
+            .collect()
+    }
+
+    /// Associated token accounts of `owner` for `mints`, derived under each mint's token program
+    pub async fn get_associated_token_addresses(
+        &self,
+        owner: &Pubkey,
+        mints: &[Pubkey],
+    ) -> OrcaResult<Vec<Pubkey>> {
+        Ok(self
+            .get_mint_infos(mints)
+            .await?
+            .iter()
+            .map(|info| {
+                self.get_associated_token_address_with_program(
+                    owner,
+                    &info.mint,
+                    &info.token_program,
+                )
+            })
+            .collect())
+    }
+
+    /// Decimals of a pool's token A and token B mints
+    pub(crate) async fn get_pool_decimals(&self, pool: &PoolInfo) -> OrcaResult<(u8, u8)> {
+        let decimals = self
+            .get_mint_decimals(&[
+                parse_address("token_mint_a", &pool.token_mint_a)?,
+                parse_address("token_mint_b", &pool.token_mint_b)?,
+            ])
+            .await?;
+        Ok((decimals[0], decimals[1]))
+    }
+}
+
+/// Splits the Token-2022 TLV extension area of a mint account into (type, value) entries
+///
+/// Returns nothing for SPL Token mints and Token-2022 mints without extensions.
+pub(crate) fn token_2022_mint_extensions(data: &[u8]) -> Vec<(u16, &[u8])> {
+    let mut extensions = Vec::new();
+    if data.len() <= TOKEN_2022_ACCOUNT_TYPE_OFFSET
+        || data[TOKEN_2022_ACCOUNT_TYPE_OFFSET] != TOKEN_2022_MINT_ACCOUNT_TYPE
+    {
+        return extensions;
+    }
+    let mut offset = TOKEN_2022_ACCOUNT_TYPE_OFFSET + 1;
+    while offset + 4 <= data.len() {
+        let extension_type = u16::from_le_bytes([data[offset], data[offset + 1]]);
+        let length = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+        let start = offset + 4;
+        let end = start + length;
+        if extension_type == 0 || end > data.len() {
+            break;
+        }
+        extensions.push((extension_type, &data[start..end]));
+        offset = end;
+    }
+    extensions
+}
+
+/// Decodes the older and newer fee of a transfer fee config extension
+///
+/// Layout: authority(32) + withdraw authority(32) + withheld(8) + older fee(18) + newer fee(18);
+/// each fee is epoch(8) + maximum_fee(8) + basis_points(2).
+fn parse_transfer_fee_config(value: &[u8]) -> Option<(TransferFee, TransferFee)> {
+    let fee_at = |at: usize| -> Option<TransferFee> {
+        let bytes = value.get(at..at + 18)?;
+        Some(TransferFee {
+            epoch: u64::from_le_bytes(bytes[..8].try_into().ok()?),
+            maximum_fee: u64::from_le_bytes(bytes[8..16].try_into().ok()?),
+            basis_points: u16::from_le_bytes(bytes[16..18].try_into().ok()?),
+        })
+    };
+    Some((fee_at(72)?, fee_at(90)?))
+}
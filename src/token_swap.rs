@@ -0,0 +1,303 @@
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+use super::*;
+use crate::{
+    audit::AuditDetails,
+    global::{TOKEN_SWAP_ACCOUNT_SIZE, TOKEN_SWAP_SWAP_TAG},
+    layout::AccountReader,
+    math::price_to_sqrt_price_x64,
+    pool::{PoolInfo, QuoteResult},
+    receipt::SwapReceipt,
+    types::OrcaResult,
+    validation::validate_swap_inputs,
+};
+
+/// Pool account of the SPL token swap program, shared by Orca legacy and stable pools
+#[derive(Debug, Clone)]
+pub(crate) struct TokenSwapAccount {
+    /// PDA of the pool that owns the vaults
+    pub authority: Pubkey,
+    pub token_program: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_vault_b: Pubkey,
+    pub pool_mint: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub fee_account: Pubkey,
+    pub trade_fee_numerator: u64,
+    pub trade_fee_denominator: u64,
+    pub owner_trade_fee_numerator: u64,
+    pub owner_trade_fee_denominator: u64,
+    pub owner_withdraw_fee_numerator: u64,
+    pub owner_withdraw_fee_denominator: u64,
+    pub curve_type: u8,
+    /// Curve calculator state; the amplification coefficient for stable curves
+    pub curve_parameters: [u8; 32],
+}
+
+impl TokenSwapAccount {
+    /// Decodes an initialized token swap account owned by `program_id`
+    pub(crate) fn decode(address: &Pubkey, data: &[u8], program_id: &Pubkey) -> Option<Self> {
+        if data.len() != TOKEN_SWAP_ACCOUNT_SIZE {
+            return None;
+        }
+        let mut reader = AccountReader::unprefixed(data);
+        // version
+        reader.skip(1)?;
+        if !reader.bool()? {
+            return None;
+        }
+        let bump_seed = reader.u8()?;
+        let token_program = reader.pubkey()?;
+        let token_vault_a = reader.pubkey()?;
+        let token_vault_b = reader.pubkey()?;
+        let pool_mint = reader.pubkey()?;
+        let token_mint_a = reader.pubkey()?;
+        let token_mint_b = reader.pubkey()?;
+        let fee_account = reader.pubkey()?;
+        let trade_fee_numerator = reader.u64()?;
+        let trade_fee_denominator = reader.u64()?;
+        let owner_trade_fee_numerator = reader.u64()?;
+        let owner_trade_fee_denominator = reader.u64()?;
+        let owner_withdraw_fee_numerator = reader.u64()?;
+        let owner_withdraw_fee_denominator = reader.u64()?;
+        // host fee
+        reader.skip(16)?;
+        let curve_type = reader.u8()?;
+        let curve_parameters = reader.bytes(32)?.try_into().ok()?;
+        let authority =
+            Pubkey::create_program_address(&[address.as_ref(), &[bump_seed]], program_id).ok()?;
+        Some(Self {
+            authority,
+            token_program,
+            token_vault_a,
+            token_vault_b,
+            pool_mint,
+            token_mint_a,
+            token_mint_b,
+            fee_account,
+            trade_fee_numerator,
+            trade_fee_denominator,
+            owner_trade_fee_numerator,
+            owner_trade_fee_denominator,
+            owner_withdraw_fee_numerator,
+            owner_withdraw_fee_denominator,
+            curve_type,
+            curve_parameters,
+        })
+    }
+}
+
+/// Accounts of a token swap `Swap` instruction, in instruction order
+pub(crate) struct TokenSwapAccounts<'a> {
+    pub pool: &'a Pubkey,
+    pub authority: &'a Pubkey,
+    pub owner: &'a Pubkey,
+    pub source: &'a Pubkey,
+    pub swap_source: &'a Pubkey,
+    pub swap_destination: &'a Pubkey,
+    pub destination: &'a Pubkey,
+    pub pool_mint: &'a Pubkey,
+    pub fee_account: &'a Pubkey,
+    pub token_program: &'a Pubkey,
+}
+
+/// Constructs a token swap `Swap` instruction
+pub(crate) fn token_swap_instruction(
+    program_id: &Pubkey,
+    accounts: TokenSwapAccounts,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Instruction {
+    let mut data = vec![TOKEN_SWAP_SWAP_TAG];
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*accounts.pool, false),
+            AccountMeta::new_readonly(*accounts.authority, false),
+            AccountMeta::new_readonly(*accounts.owner, true),
+            AccountMeta::new(*accounts.source, false),
+            AccountMeta::new(*accounts.swap_source, false),
+            AccountMeta::new(*accounts.swap_destination, false),
+            AccountMeta::new(*accounts.destination, false),
+            AccountMeta::new(*accounts.pool_mint, false),
+            AccountMeta::new(*accounts.fee_account, false),
+            AccountMeta::new_readonly(*accounts.token_program, false),
+        ],
+        data,
+    }
+}
+
+/// Exact-input swap through a token swap pool
+pub(crate) struct TokenSwapOrder<'a> {
+    pub pool: &'a Pubkey,
+    pub token_mint_a: &'a Pubkey,
+    pub token_mint_b: &'a Pubkey,
+    pub token_program: &'a Pubkey,
+    pub a_to_b: bool,
+    pub amount: u64,
+    /// Slippage tolerance in basis points, recorded in the audit trail
+    pub slippage_bps: u16,
+    /// View of the pool the price deviation guard checks the quote against
+    pub price_view: PoolInfo,
+}
+
+/// Whirlpool-shaped view of a token swap pool for the price deviation guard
+///
+/// The pool authority owns the vaults, so it stands in for the pool address and the guard
+/// finds the pool's swaps in the authority's history. The reserve ratio serves as spot price.
+pub(crate) fn token_swap_price_view(
+    authority: &Pubkey,
+    token_mints: (&Pubkey, &Pubkey),
+    token_vaults: (&Pubkey, &Pubkey),
+    reserves: (u64, u64),
+) -> PoolInfo {
+    let price = if reserves.0 == 0 {
+        0.0
+    } else {
+        reserves.1 as f64 / reserves.0 as f64
+    };
+    PoolInfo {
+        address: authority.to_string(),
+        token_mint_a: token_mints.0.to_string(),
+        token_mint_b: token_mints.1.to_string(),
+        token_vault_a: token_vaults.0.to_string(),
+        token_vault_b: token_vaults.1.to_string(),
+        sqrt_price: price_to_sqrt_price_x64(price, 0, 0),
+        ..Default::default()
+    }
+}
+
+/// Fee the token swap program charges on `amount`; any non-zero rate charges at least one unit
+pub(crate) fn token_swap_fee(amount: u64, numerator: u64, denominator: u64) -> u64 {
+    if numerator == 0 || denominator == 0 || amount == 0 {
+        return 0;
+    }
+    ((amount as u128 * numerator as u128 / denominator as u128) as u64).max(1)
+}
+
+impl OrcaClient {
+    /// Fetches the balances of a token swap pool's two vaults
+    pub(crate) async fn get_token_swap_reserves(
+        &self,
+        token_vault_a: &Pubkey,
+        token_vault_b: &Pubkey,
+    ) -> OrcaResult<(u64, u64)> {
+        let vaults = [*token_vault_a, *token_vault_b];
+        let accounts = self
+            .rpc_call("Failed to get pool vaults", |client| {
+                client.get_multiple_accounts(&vaults)
+            })
+            .await?;
+        let mut reserves = [0u64; 2];
+        for ((reserve, vault), account) in reserves.iter_mut().zip(&vaults).zip(accounts) {
+            let account = account.ok_or(OrcaError::AccountNotFound(*vault))?;
+            *reserve = Self::unpack_token_account(&account.data)?.amount;
+        }
+        Ok((reserves[0], reserves[1]))
+    }
+
+    /// Checks policies and balance, then signs and submits an already quoted token swap
+    ///
+    /// The output token account is created idempotently; `build` receives the owner's source
+    /// and destination token accounts and the minimum output of `quote`.
+    pub(crate) async fn submit_token_swap<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        operation: &str,
+        order: TokenSwapOrder<'_>,
+        quote: QuoteResult,
+        build: impl FnOnce(&Pubkey, &Pubkey, u64) -> Instruction,
+    ) -> OrcaResult<SwapReceipt> {
+        let (input_mint, output_mint) = if order.a_to_b {
+            (*order.token_mint_a, *order.token_mint_b)
+        } else {
+            (*order.token_mint_b, *order.token_mint_a)
+        };
+        let (input_mint_str, output_mint_str) = (input_mint.to_string(), output_mint.to_string());
+        validate_swap_inputs(
+            &input_mint_str,
+            &output_mint_str,
+            order.amount,
+            order.slippage_bps,
+        )?;
+        self.check_token_policy(&output_mint_str)?;
+        self.check_pool_policy(&order.pool.to_string())?;
+        self.enforce_token_safety(&input_mint_str, &output_mint_str, None)
+            .await?;
+        self.enforce_price_guard(
+            None,
+            &order.price_view,
+            &input_mint_str,
+            order.amount,
+            quote.output_amount,
+        )
+        .await?;
+        let spends = [(input_mint_str.as_str(), order.amount)];
+        let reservation = self.reserve_spend(&spends).await?;
+        let owner = signer.pubkey();
+        let available = self.get_token_balance(&owner, &input_mint).await?;
+        if available < order.amount {
+            return Err(OrcaError::InsufficientBalance {
+                mint: input_mint,
+                required: order.amount,
+                available,
+            });
+        }
+        let source = self.get_associated_token_address_with_program(
+            &owner,
+            &input_mint,
+            order.token_program,
+        );
+        let destination = self.get_associated_token_address_with_program(
+            &owner,
+            &output_mint,
+            order.token_program,
+        );
+        let instructions = vec![
+            create_associated_token_account_idempotent(
+                &owner,
+                &owner,
+                &output_mint,
+                order.token_program,
+            ),
+            build(&source, &destination, quote.min_output_amount),
+        ];
+        let recent_blockhash = self.latest_blockhash().await?;
+        let message = Message::new(&instructions, Some(&owner));
+        let transaction = Transaction::new(&[signer], message, recent_blockhash);
+        let signature = self
+            .submit_transaction(
+                &transaction.into(),
+                operation,
+                &AuditDetails::new(serde_json::json!({
+                    "pool": order.pool.to_string(),
+                    "input_mint": input_mint_str,
+                    "output_mint": output_mint_str,
+                    "amount": order.amount,
+                    "slippage_bps": order.slippage_bps,
+                    "min_output_amount": quote.min_output_amount,
+                }))
+                .with_quote(&quote),
+            )
+            .await?;
+        reservation.commit();
+        let settlement = self
+            .settle_swap(&signature, &owner, &input_mint, &output_mint)
+            .await;
+        Ok(SwapReceipt {
+            signature,
+            pool: *order.pool,
+            input_mint,
+            output_mint,
+            input_amount: order.amount,
+            max_input_amount: order.amount,
+            quoted_output_amount: quote.output_amount,
+            min_output_amount: quote.min_output_amount,
+            settlement,
+        })
+    }
+}
@@ -1,19 +1,159 @@
 use super::*;
-use crate::types::OrcaResult;
-use solana_sdk::message::{AccountMeta, Instruction};
-use std::str::FromStr;
+use crate::{
+    audit::AuditDetails,
+    fixed::mul_div_floor,
+    freeze::FreezeCheckMode,
+    global::{
+        MAX_SQRT_PRICE_X64, MAX_SWAP_TICK_ARRAYS, MIN_SQRT_PRICE_X64,
+        WHIRLPOOL_SWAP_V2_DISCRIMINATOR,
+    },
+    guard::PriceDeviationGuard,
+    pool::{PoolInfo, QuoteResult},
+    receipt::SwapReceipt,
+    rent::RentPlan,
+    resolver::PoolSelection,
+    safety::TokenRiskLevel,
+    submit::{DryRunOutcome, PrivateRelay, SubmitMode},
+    token::MEMO_PROGRAM,
+    types::OrcaResult,
+    validation::{parse_address, validate_swap_inputs},
+};
+use solana_sdk::message::{AccountMeta, Instruction, VersionedMessage};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
 
 #[derive(Debug, Clone)]
 pub struct TradeConfig {
-    pub slippage: f64,
+    /// Slippage tolerance in basis points (50 = 0.5%), from 0 to 10_000
+    ///
+    /// The minimum output is rounded down and the maximum input rounded up, so the bound
+    /// enforced on-chain is never looser than the tolerance.
+    pub slippage_bps: u16,
     pub max_iterations: u8,
+    /// Reject the swap when the output token's safety report exceeds this level, overriding
+    /// the client-wide limit
+    pub max_token_risk: Option<TokenRiskLevel>,
+    /// Per-call price deviation guard, overriding the client-wide guard
+    pub price_guard: Option<PriceDeviationGuard>,
+    /// Sandwich protection: tight price limit, refreshed minimum output and private submission
+    pub mev_protection: Option<MevProtection>,
+    /// How the pool is chosen when several pools trade the pair
+    pub pool_selection: PoolSelection,
+    /// Handling of frozen token accounts and freezable mints on either side of the pool
+    pub freeze_check: FreezeCheckMode,
+    /// Address lookup tables to compile the swap against; when set, a v0 transaction is sent
+    pub lookup_tables: Vec<Pubkey>,
+    /// Priority fee paid per compute unit, in micro-lamports
+    pub priority_fee_microlamports: Option<u64>,
+    /// Compute unit limit requested for the transaction
+    pub compute_unit_limit: Option<u32>,
+    /// How the transaction is sent; ignored when `mev_protection` is set
+    pub submit_mode: SubmitMode,
+}
+
+/// Anti-MEV settings for a swap
+#[derive(Debug, Clone)]
+pub struct MevProtection {
+    /// Maximum pool price movement the swap may cause or tolerate, in percent
+    pub max_price_move_percent: f64,
+    /// Private submission path used instead of the public RPC
+    pub relay: PrivateRelay,
+}
+
+/// Instructions of a swap, ready to be signed by the owner
+#[derive(Debug, Clone)]
+pub struct SwapInstructions {
+    pub pool: Pubkey,
+    pub quote: QuoteResult,
+    /// Minimum output enforced on-chain
+    pub min_output_amount: u64,
+    /// Maximum input enforced on-chain
+    pub max_input_amount: u64,
+    /// Compute budget settings, creation of missing token accounts, then the swap
+    pub instructions: Vec<Instruction>,
+}
+
+/// Owner token accounts of a Whirlpool swap and the token programs of their mints
+#[derive(Debug, Clone, Copy)]
+pub struct SwapTokenAccounts<'a> {
+    pub input_token_account: &'a Pubkey,
+    pub output_token_account: &'a Pubkey,
+    /// Token program owning the input mint
+    pub input_token_program: &'a Pubkey,
+    /// Token program owning the output mint
+    pub output_token_program: &'a Pubkey,
+}
+
+/// Amounts, price limit and direction of a Whirlpool `swap_v2` instruction
+#[derive(Debug, Clone, Copy)]
+pub struct SwapParams {
+    /// Exact input, or exact output when `amount_specified_is_input` is false
+    pub amount: u64,
+    /// Minimum output for exact-input swaps, maximum input otherwise
+    pub other_amount_threshold: u64,
+    /// Q64.64 sqrt price at which the swap stops
+    pub sqrt_price_limit: u128,
+    /// Whether `amount` is the input or the output
+    pub amount_specified_is_input: bool,
+    /// Whether the swap sells token A for token B
+    pub a_to_b: bool,
+}
+
+/// Owner token accounts, direction and amounts of a stable or legacy pool swap
+#[derive(Debug, Clone, Copy)]
+pub struct TokenSwapParams<'a> {
+    /// Owner's input token account
+    pub source: &'a Pubkey,
+    /// Owner's output token account
+    pub destination: &'a Pubkey,
+    /// Whether the swap sells token A for token B
+    pub a_to_b: bool,
+    /// Exact input amount
+    pub amount_in: u64,
+    /// Output below which the swap fails
+    pub minimum_amount_out: u64,
+}
+
+impl TradeConfig {
+    /// Trades through the given pool, skipping pool discovery
+    ///
+    /// # Example
+    /// ```rust
+    /// let config = TradeConfig::default().with_pool_address("whirlpool_address_here");
+    /// let signature = client.swap(&keypair, input_mint, output_mint, amount, Some(config)).await?;
+    /// ```
+    pub fn with_pool_address(mut self, pool_address: impl Into<String>) -> Self {
+        self.pool_selection = PoolSelection::Address(pool_address.into());
+        self
+    }
+
+    /// Jito tip the swap pays; a private relay from MEV protection takes precedence over the
+    /// submit mode
+    fn jito_tip_lamports(&self) -> Option<u64> {
+        match &self.mev_protection {
+            Some(MevProtection {
+                relay: PrivateRelay::Jito { tip_lamports, .. },
+                ..
+            }) => Some(*tip_lamports),
+            Some(_) => None,
+            None => self.submit_mode.jito_tip(),
+        }
+    }
 }
 
 impl Default for TradeConfig {
     fn default() -> Self {
         Self {
-            slippage: 0.5,
+            slippage_bps: 50,
             max_iterations: 3,
+            max_token_risk: None,
+            price_guard: None,
+            mev_protection: None,
+            pool_selection: PoolSelection::default(),
+            freeze_check: FreezeCheckMode::default(),
+            lookup_tables: Vec::new(),
+            priority_fee_microlamports: None,
+            compute_unit_limit: None,
+            submit_mode: SubmitMode::default(),
         }
     }
 }
@@ -22,14 +162,14 @@ impl OrcaClient {
     /// Executes a token swap between specified input and output mints
     ///
     /// # Arguments
-    /// keypair - Keypair for signing the transaction
+    /// signer - Signs the transaction, e.g. a `Keypair` or a remote signer
     /// input_mint - Mint address of the input token
     /// output_mint - Mint address of the output token
     /// amount - Amount of input tokens to swap
     /// config - Optional trade configuration parameters
     ///
     /// # Returns
-    /// Transaction signature if successful
+    /// Receipt with the signature, pool and amounts of the swap
     ///
     /// # Examples
     /// ```rust
@@ -42,148 +182,629 @@ impl OrcaClient {
     /// let output_mint = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
     /// let amount = 1_000_000; // 1 SOL
     ///
-    /// let signature = client.swap(&keypair, input_mint, output_mint, amount, None).await?;
-    /// println!("Swap completed with signature: {}", signature);
+    /// let receipt = client.swap(&keypair, input_mint, output_mint, amount, None).await?;
+    /// println!("Swap completed with signature: {}", receipt.signature);
     /// ```
-    pub async fn swap(
+    #[tracing::instrument(
+        skip(self, signer, config),
+        fields(owner = %signer.pubkey(), pool = tracing::field::Empty),
+        err(Debug)
+    )]
+    pub async fn swap<S: Signer + ?Sized>(
         &self,
-        keypair: &Keypair,
+        signer: &S,
         input_mint: &str,
         output_mint: &str,
         amount: u64,
         config: Option<TradeConfig>,
-    ) -> OrcaResult<Signature> {
-        let config = config.unwrap_or_default();
-        let quote = self
-            .get_quote_from_pool(input_mint, output_mint, amount, config.slippage)
-            .await?;
-        let input_mint_pubkey = Pubkey::from_str(input_mint)
-            .map_err(|e| OrcaError::Error(format!("Invalid input mint: {}", e)))?;
-        let output_mint_pubkey = Pubkey::from_str(output_mint)
-            .map_err(|e| OrcaError::Error(format!("Invalid output mint: {}", e)))?;
-        let input_token_account = self
-            .ensure_token_account(keypair, &input_mint_pubkey)
+    ) -> OrcaResult<SwapReceipt> {
+        self.execute_swap(signer, input_mint, output_mint, amount, true, config)
+            .await
+    }
+
+    /// Swaps for exactly `output_amount` of the output token
+    ///
+    /// The input is quoted from the pool, and the transaction spends at most the quoted input
+    /// plus the configured slippage; the swap fails on-chain if more would be needed.
+    ///
+    /// # Arguments
+    /// signer - Signs the transaction, e.g. a `Keypair` or a remote signer
+    /// input_mint - Mint address of the input token
+    /// output_mint - Mint address of the output token
+    /// output_amount - Amount of output tokens to receive
+    /// config - Optional trade configuration parameters
+    ///
+    /// # Examples
+    /// ```rust
+    /// let input_mint = "So11111111111111111111111111111111111111112";
+    /// let output_mint = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    /// let output_amount = 10_000_000; // 10 USDC
+    ///
+    /// let receipt = client
+    ///     .swap_exact_out(&keypair, input_mint, output_mint, output_amount, None)
+    ///     .await?;
+    /// println!("Spent at most {} lamports", receipt.max_input_amount);
+    /// ```
+    #[tracing::instrument(
+        skip(self, signer, config),
+        fields(owner = %signer.pubkey(), pool = tracing::field::Empty),
+        err(Debug)
+    )]
+    pub async fn swap_exact_out<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        input_mint: &str,
+        output_mint: &str,
+        output_amount: u64,
+        config: Option<TradeConfig>,
+    ) -> OrcaResult<SwapReceipt> {
+        self.execute_swap(
+            signer,
+            input_mint,
+            output_mint,
+            output_amount,
+            false,
+            config,
+        )
+        .await
+    }
+
+    /// Builds the instructions of a swap without signing or sending them
+    ///
+    /// Missing token accounts of the owner are created idempotently ahead of the swap. No
+    /// balance, policy or safety checks are run; those only apply to `swap` and
+    /// `swap_exact_out`.
+    ///
+    /// # Arguments
+    /// owner - Wallet that signs the swap and owns the token accounts
+    /// input_mint - Mint address of the input token
+    /// output_mint - Mint address of the output token
+    /// amount - Exact input, or exact output when `amount_specified_is_input` is false
+    /// amount_specified_is_input - Whether `amount` is the input or the output
+    /// config - Optional trade configuration parameters
+    ///
+    /// # Examples
+    /// ```rust
+    /// let swap = client
+    ///     .build_swap_ixs(&wallet, input_mint, output_mint, 1_000_000, true, None)
+    ///     .await?;
+    /// let mut instructions = vec![my_memo_instruction];
+    /// instructions.extend(swap.instructions);
+    /// ```
+    pub async fn build_swap_ixs(
+        &self,
+        owner: &Pubkey,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        amount_specified_is_input: bool,
+        config: Option<TradeConfig>,
+    ) -> OrcaResult<SwapInstructions> {
+        self.with_timeout("build swap", async {
+            let config = config.unwrap_or_default();
+            validate_swap_inputs(input_mint, output_mint, amount, config.slippage_bps)?;
+            let pool = self
+                .resolve_pool(input_mint, output_mint, &config.pool_selection)
+                .await?;
+            let quote = self
+                .calculate_quote_from_pool_state(
+                    &pool,
+                    input_mint,
+                    output_mint,
+                    amount,
+                    amount_specified_is_input,
+                    config.slippage_bps,
+                )
+                .await?;
+            self.swap_instructions(
+                owner,
+                &pool,
+                input_mint,
+                amount_specified_is_input,
+                quote,
+                &config,
+            )
+            .await
+        })
+        .await
+    }
+
+    /// Builds an unsigned swap transaction paid by `owner`
+    ///
+    /// The transaction carries a recent blockhash; sign it with the owner's key and submit it
+    /// through any RPC.
+    ///
+    /// # Examples
+    /// ```rust
+    /// let mut transaction = client
+    ///     .build_swap_tx(&wallet, input_mint, output_mint, 1_000_000, true, None)
+    ///     .await?;
+    /// transaction.sign(&[&keypair], transaction.message.recent_blockhash);
+    /// ```
+    pub async fn build_swap_tx(
+        &self,
+        owner: &Pubkey,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        amount_specified_is_input: bool,
+        config: Option<TradeConfig>,
+    ) -> OrcaResult<Transaction> {
+        let swap = self
+            .build_swap_ixs(
+                owner,
+                input_mint,
+                output_mint,
+                amount,
+                amount_specified_is_input,
+                config,
+            )
             .await?;
-        let output_token_account = self
-            .ensure_token_account(keypair, &output_mint_pubkey)
+        self.build_transaction(owner, &swap.instructions).await
+    }
+
+    /// Simulates a swap against current chain state without signing or broadcasting it
+    ///
+    /// Builds the same instructions as `build_swap_ixs` and reports program logs, compute units
+    /// consumed and the owner's expected input and output token balance changes.
+    ///
+    /// # Examples
+    /// ```rust
+    /// let outcome = client
+    ///     .simulate_swap(&wallet, input_mint, output_mint, 1_000_000, true, None)
+    ///     .await?;
+    /// for change in &outcome.balance_changes {
+    ///     println!("{}: {:+}", change.mint, change.delta());
+    /// }
+    /// println!("{:?} CU, error: {:?}", outcome.units_consumed, outcome.error);
+    /// ```
+    pub async fn simulate_swap(
+        &self,
+        owner: &Pubkey,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        amount_specified_is_input: bool,
+        config: Option<TradeConfig>,
+    ) -> OrcaResult<DryRunOutcome> {
+        self.with_timeout("simulate swap", async {
+            let config = config.unwrap_or_default();
+            let lookup_tables = config.lookup_tables.clone();
+            let swap = self
+                .build_swap_ixs(
+                    owner,
+                    input_mint,
+                    output_mint,
+                    amount,
+                    amount_specified_is_input,
+                    Some(config),
+                )
+                .await?;
+            let message = if lookup_tables.is_empty() {
+                VersionedMessage::Legacy(Message::new(&swap.instructions, Some(owner)))
+            } else {
+                let tables = self.get_address_lookup_tables(&lookup_tables).await?;
+                self.build_v0_message(owner, &swap.instructions, &tables)
+                    .await?
+            };
+            let mints = [
+                parse_address("input_mint", input_mint)?,
+                parse_address("output_mint", output_mint)?,
+            ];
+            self.simulate_unsigned(message, "swap", owner, &mints).await
+        })
+        .await
+    }
+
+    async fn execute_swap<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        amount_specified_is_input: bool,
+        config: Option<TradeConfig>,
+    ) -> OrcaResult<SwapReceipt> {
+        self.with_timeout("swap", async {
+            let config = config.unwrap_or_default();
+            let (input_mint_pubkey, output_mint_pubkey) =
+                validate_swap_inputs(input_mint, output_mint, amount, config.slippage_bps)?;
+            self.check_token_policy(output_mint)?;
+            self.enforce_token_safety(input_mint, output_mint, config.max_token_risk)
+                .await?;
+            let target_pool = self
+                .resolve_pool(input_mint, output_mint, &config.pool_selection)
+                .await?;
+            tracing::Span::current().record("pool", target_pool.address.as_str());
+            let quote = self
+                .calculate_quote_from_pool_state(
+                    &target_pool,
+                    input_mint,
+                    output_mint,
+                    amount,
+                    amount_specified_is_input,
+                    config.slippage_bps,
+                )
+                .await?;
+            let spends = [(input_mint, quote.max_input_amount)];
+            let reservation = self.reserve_spend(&spends).await?;
+            self.enforce_price_guard(
+                config.price_guard.as_ref(),
+                &target_pool,
+                input_mint,
+                quote.input_amount,
+                quote.output_amount,
+            )
             .await?;
-        let pools = self.find_pools_by_token_onchain(input_mint).await?;
-        let target_pool = {
-            let mut found_pool = None;
-            for pool in pools {
-                if let Ok(pool_info) = self.get_pool_state_onchain(&pool).await {
-                    if (pool_info.token_mint_a == input_mint
-                        && pool_info.token_mint_b == output_mint)
-                        || (pool_info.token_mint_a == output_mint
-                            && pool_info.token_mint_b == input_mint)
-                    {
-                        found_pool = Some(pool.clone());
-                        break;
-                    }
-                }
+            self.check_pool_policy(&target_pool.address)?;
+            self.enforce_pool_freeze_check(config.freeze_check, &signer.pubkey(), &target_pool)
+                .await?;
+            let available = self
+                .get_token_balance(&signer.pubkey(), &input_mint_pubkey)
+                .await?;
+            if available < quote.max_input_amount {
+                return Err(OrcaError::InsufficientBalance {
+                    mint: input_mint_pubkey,
+                    required: quote.max_input_amount,
+                    available,
+                });
             }
-            found_pool
-        }
-        .ok_or(OrcaError::Error("No suitable pool found".to_string()))?;
-        let pool_pubkey = Pubkey::from_str(&target_pool)
-            .map_err(|e| OrcaError::Error(format!("Invalid pool address: {}", e)))?;
-        let recent_blockhash = self
-            .solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?
-            .get_latest_blockhash()
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to get blockhash: {}", e)))?;
-        let swap_instruction = self.build_swap_instruction(
-            &keypair.pubkey(),
-            &pool_pubkey,
-            &input_token_account,
-            &output_token_account,
+            let mut rent_plan = RentPlan::new().signatures(1);
+            if let Some(tip_lamports) = config.jito_tip_lamports() {
+                rent_plan = rent_plan.lamports("Jito tip", tip_lamports);
+            }
+            let rent_plan = self
+                .plan_token_accounts(
+                    rent_plan,
+                    &signer.pubkey(),
+                    &[input_mint_pubkey, output_mint_pubkey],
+                )
+                .await?;
+            self.ensure_sol_for_plan(&signer.pubkey(), &rent_plan)
+                .await?;
+            let mut signed_quote = quote.clone();
+            let mut refreshed_pool = None;
+            if config.mev_protection.is_some() {
+                // Re-quote right before signing so the thresholds and tick arrays reflect
+                // current state
+                let pool = self.get_pool_state_onchain(&target_pool.address).await?;
+                let refreshed = self
+                    .calculate_quote_from_pool_state(
+                        &pool,
+                        input_mint,
+                        output_mint,
+                        amount,
+                        amount_specified_is_input,
+                        config.slippage_bps,
+                    )
+                    .await?;
+                signed_quote.min_output_amount =
+                    refreshed.min_output_amount.max(quote.min_output_amount);
+                signed_quote.max_input_amount =
+                    refreshed.max_input_amount.min(quote.max_input_amount);
+                refreshed_pool = Some(pool);
+            }
+            let swap = self
+                .swap_instructions(
+                    &signer.pubkey(),
+                    refreshed_pool.as_ref().unwrap_or(&target_pool),
+                    input_mint,
+                    amount_specified_is_input,
+                    signed_quote,
+                    &config,
+                )
+                .await?;
+            let transaction = if config.lookup_tables.is_empty() {
+                let recent_blockhash = self.latest_blockhash().await?;
+                let message = Message::new(&swap.instructions, Some(&signer.pubkey()));
+                Transaction::new(&[signer], message, recent_blockhash).into()
+            } else {
+                self.build_signed_v0_transaction(signer, &swap.instructions, &config.lookup_tables)
+                    .await?
+            };
+            let audit_details = AuditDetails::new(serde_json::json!({
+                "pool": target_pool.address,
+                "input_mint": input_mint,
+                "output_mint": output_mint,
+                "amount": amount,
+                "amount_specified_is_input": amount_specified_is_input,
+                "slippage_bps": config.slippage_bps,
+                "min_output_amount": swap.min_output_amount,
+                "max_input_amount": swap.max_input_amount,
+            }))
+            .with_quote(&quote);
+            let signature = match (&config.mev_protection, &config.submit_mode) {
+                (Some(protection), _) => {
+                    self.submit_transaction_private(
+                        &transaction,
+                        "execute swap",
+                        &protection.relay,
+                        &audit_details,
+                    )
+                    .await?
+                }
+                (
+                    None,
+                    SubmitMode::Jito {
+                        block_engine_url, ..
+                    },
+                ) => self
+                    .submit_bundle(
+                        std::slice::from_ref(&transaction),
+                        "execute swap",
+                        block_engine_url,
+                        &audit_details,
+                    )
+                    .await?
+                    .pop()
+                    .ok_or(OrcaError::Error("No transaction was submitted".to_string()))?,
+                (None, SubmitMode::Rpc) => {
+                    self.submit_transaction(&transaction, "execute swap", &audit_details)
+                        .await?
+                }
+            };
+            let mut receipt = SwapReceipt {
+                signature,
+                pool: swap.pool,
+                input_mint: input_mint_pubkey,
+                output_mint: output_mint_pubkey,
+                input_amount: quote.input_amount,
+                max_input_amount: swap.max_input_amount,
+                quoted_output_amount: quote.output_amount,
+                min_output_amount: swap.min_output_amount,
+                settlement: None,
+            };
+            reservation.commit();
+            receipt.settlement = self
+                .settle_swap(
+                    &signature,
+                    &signer.pubkey(),
+                    &input_mint_pubkey,
+                    &output_mint_pubkey,
+                )
+                .await;
+            Ok(receipt)
+        })
+        .await
+    }
+
+    /// Assembles token account creation, swap and tip instructions for an already quoted swap
+    ///
+    /// The specified amount and the on-chain thresholds are taken from `quote`.
+    async fn swap_instructions(
+        &self,
+        owner: &Pubkey,
+        pool: &PoolInfo,
+        input_mint: &str,
+        amount_specified_is_input: bool,
+        quote: QuoteResult,
+        config: &TradeConfig,
+    ) -> OrcaResult<SwapInstructions> {
+        let a_to_b = input_mint == pool.token_mint_a;
+        let (input_mint_pubkey, output_mint_pubkey) = if a_to_b {
+            (
+                parse_address("token_mint_a", &pool.token_mint_a)?,
+                parse_address("token_mint_b", &pool.token_mint_b)?,
+            )
+        } else {
+            (
+                parse_address("token_mint_b", &pool.token_mint_b)?,
+                parse_address("token_mint_a", &pool.token_mint_a)?,
+            )
+        };
+        let pool_pubkey = parse_address("pool_address", &pool.address)?;
+        let mint_infos = self
+            .get_mint_infos(&[input_mint_pubkey, output_mint_pubkey])
+            .await?;
+        let (input_token_program, output_token_program) =
+            (mint_infos[0].token_program, mint_infos[1].token_program);
+        let input_token_account = self.get_associated_token_address_with_program(
+            owner,
             &input_mint_pubkey,
+            &input_token_program,
+        );
+        let output_token_account = self.get_associated_token_address_with_program(
+            owner,
             &output_mint_pubkey,
-            amount,
-            quote.min_output_amount,
+            &output_token_program,
+        );
+        let missing = self
+            .missing_accounts(&[input_token_account, output_token_account])
+            .await?;
+        let mut instructions = self.build_compute_budget_instructions(
+            config.compute_unit_limit,
+            config.priority_fee_microlamports,
         )?;
-        let message = Message::new(&[swap_instruction], Some(&keypair.pubkey()));
-        let transaction = Transaction::new(&[keypair], message, recent_blockhash);
-        self.solana
-            .client
-            .as_ref()
-            .ok_or(OrcaError::Error("RPC client not available".to_string()))?
-            .send_and_confirm_transaction(&transaction)
-            .await
-            .map_err(|e| OrcaError::Error(format!("Failed to execute swap: {}", e)))
+        instructions.extend(
+            mint_infos
+                .iter()
+                .zip(missing)
+                .filter(|(_, is_missing)| *is_missing)
+                .map(|(info, _)| {
+                    create_associated_token_account_idempotent(
+                        owner,
+                        owner,
+                        &info.mint,
+                        &info.token_program,
+                    )
+                }),
+        );
+        let sqrt_price_limit = match &config.mev_protection {
+            Some(protection) => Self::sqrt_price_limit_for_move(
+                pool.sqrt_price,
+                a_to_b,
+                protection.max_price_move_percent,
+            ),
+            None if a_to_b => MIN_SQRT_PRICE_X64,
+            None => MAX_SQRT_PRICE_X64,
+        };
+        let (amount, other_amount_threshold) = if amount_specified_is_input {
+            (quote.input_amount, quote.min_output_amount)
+        } else {
+            (quote.output_amount, quote.max_input_amount)
+        };
+        instructions.push(self.build_swap_instruction(
+            owner,
+            pool,
+            SwapTokenAccounts {
+                input_token_account: &input_token_account,
+                output_token_account: &output_token_account,
+                input_token_program: &input_token_program,
+                output_token_program: &output_token_program,
+            },
+            SwapParams {
+                amount,
+                other_amount_threshold,
+                sqrt_price_limit,
+                amount_specified_is_input,
+                a_to_b,
+            },
+        )?);
+        if let Some(tip_lamports) = config.jito_tip_lamports() {
+            instructions.push(self.build_jito_tip_instruction(owner, tip_lamports)?);
+        }
+        Ok(SwapInstructions {
+            pool: pool_pubkey,
+            min_output_amount: quote.min_output_amount,
+            max_input_amount: quote.max_input_amount,
+            quote,
+            instructions,
+        })
     }
 
-    /// Constructs a swap instruction for the Whirlpool program
+    /// Constructs a `swap_v2` instruction for the Whirlpool program
+    ///
+    /// The pool's own vaults are used, together with the three tick arrays the swap walks
+    /// through from the current tick in the swap direction and the pool's oracle PDA. Near the
+    /// tick bounds, where fewer arrays exist, the last one is repeated. The token program of
+    /// each mint and the memo program are passed so that Token-2022 mints can be traded.
     ///
     /// # Arguments
     /// owner - Owner of the token accounts
-    /// pool - Whirlpool address
-    /// input_token_account - Input token account
-    /// output_token_account - Output token account
-    /// input_mint - Input token mint
-    /// output_mint - Output token mint
-    /// input_amount - Amount of input tokens
-    /// min_output_amount - Minimum amount of output tokens to receive
+    /// pool - Pool information
+    /// accounts - Owner's input and output token accounts and their token programs
+    /// params - Amount, threshold, sqrt price limit and direction of the swap
     ///
     /// # Examples
     /// ```rust
     /// use orca_sdk::client::OrcaClient;
+    /// use orca_sdk::trade::{SwapParams, SwapTokenAccounts};
     /// use solana_sdk::pubkey::Pubkey;
     ///
     /// let client = OrcaClient::new_with_defaults();
     /// let owner = Pubkey::new_unique();
-    /// let pool = Pubkey::new_unique();
+    /// let pool_info = client.get_pool_state_onchain("whirlpool_address").await?;
     /// let input_token_account = Pubkey::new_unique();
     /// let output_token_account = Pubkey::new_unique();
-    /// let input_mint = Pubkey::new_unique();
-    /// let output_mint = Pubkey::new_unique();
-    /// let input_amount = 1_000_000;
-    /// let min_output_amount = 500_000;
     ///
     /// let instruction = client.build_swap_instruction(
     ///     &owner,
-    ///     &pool,
-    ///     &input_token_account,
-    ///     &output_token_account,
-    ///     &input_mint,
-    ///     &output_mint,
-    ///     input_amount,
-    ///     min_output_amount,
+    ///     &pool_info,
+    ///     SwapTokenAccounts {
+    ///         input_token_account: &input_token_account,
+    ///         output_token_account: &output_token_account,
+    ///         input_token_program: &spl_token::id(),
+    ///         output_token_program: &spl_token::id(),
+    ///     },
+    ///     SwapParams {
+    ///         amount: 1_000_000,
+    ///         other_amount_threshold: 500_000,
+    ///         sqrt_price_limit: orca_sdk::global::MIN_SQRT_PRICE_X64,
+    ///         amount_specified_is_input: true,
+    ///         a_to_b: true,
+    ///     },
     /// )?;
     /// ```
-    fn build_swap_instruction(
+    pub fn build_swap_instruction(
         &self,
         owner: &Pubkey,
-        pool: &Pubkey,
-        input_token_account: &Pubkey,
-        output_token_account: &Pubkey,
-        input_mint: &Pubkey,
-        output_mint: &Pubkey,
-        input_amount: u64,
-        min_output_amount: u64,
+        pool: &PoolInfo,
+        accounts: SwapTokenAccounts,
+        params: SwapParams,
     ) -> OrcaResult<Instruction> {
-        let token_vault_a = self.get_associated_token_address(pool, input_mint);
-        let token_vault_b = self.get_associated_token_address(pool, output_mint);
-        let accounts = vec![
-            AccountMeta::new_readonly(self.whirlpool_program_id, false),
+        let SwapParams {
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+        } = params;
+        let whirlpool = parse_address("pool_address", &pool.address)?;
+        let input = (accounts.input_token_program, accounts.input_token_account);
+        let output = (accounts.output_token_program, accounts.output_token_account);
+        let ((token_program_a, token_owner_account_a), (token_program_b, token_owner_account_b)) =
+            if a_to_b {
+                (input, output)
+            } else {
+                (output, input)
+            };
+        let mut tick_arrays: Vec<Pubkey> = self
+            .get_swap_tick_array_start_indexes(pool, a_to_b, MAX_SWAP_TICK_ARRAYS)
+            .into_iter()
+            .map(|start| self.get_tick_array_address(&whirlpool, start))
+            .collect();
+        let last_tick_array = *tick_arrays
+            .last()
+            .ok_or_else(|| OrcaError::InvalidPoolData {
+                pool: pool.address.clone(),
+                reason: "no tick array holds the current tick".to_string(),
+            })?;
+        tick_arrays.resize(MAX_SWAP_TICK_ARRAYS, last_tick_array);
+        let mut accounts = vec![
+            AccountMeta::new_readonly(*token_program_a, false),
+            AccountMeta::new_readonly(*token_program_b, false),
+            AccountMeta::new_readonly(MEMO_PROGRAM, false),
             AccountMeta::new_readonly(*owner, true),
-            AccountMeta::new(*pool, false),
-            AccountMeta::new(*input_token_account, false),
-            AccountMeta::new(*output_token_account, false),
-            AccountMeta::new(token_vault_a, false),
-            AccountMeta::new(token_vault_b, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(whirlpool, false),
+            AccountMeta::new_readonly(parse_address("token_mint_a", &pool.token_mint_a)?, false),
+            AccountMeta::new_readonly(parse_address("token_mint_b", &pool.token_mint_b)?, false),
+            AccountMeta::new(*token_owner_account_a, false),
+            AccountMeta::new(parse_address("token_vault_a", &pool.token_vault_a)?, false),
+            AccountMeta::new(*token_owner_account_b, false),
+            AccountMeta::new(parse_address("token_vault_b", &pool.token_vault_b)?, false),
         ];
-        let mut data = vec![0x01]; // swap instruction discriminator
-        data.extend_from_slice(&input_amount.to_le_bytes());
-        data.extend_from_slice(&min_output_amount.to_le_bytes());
+        accounts.extend(
+            tick_arrays
+                .into_iter()
+                .map(|tick_array| AccountMeta::new(tick_array, false)),
+        );
+        accounts.push(AccountMeta::new(self.get_oracle_address(&whirlpool), false));
+        let mut data = WHIRLPOOL_SWAP_V2_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&other_amount_threshold.to_le_bytes());
+        data.extend_from_slice(&sqrt_price_limit.to_le_bytes());
+        data.push(amount_specified_is_input as u8);
+        data.push(a_to_b as u8);
+        data.push(0); // no remaining accounts info
         Ok(Instruction {
             program_id: self.whirlpool_program_id,
             accounts,
             data,
         })
     }
+
+    /// Derives the oracle PDA of a Whirlpool, required by every swap
+    pub fn get_oracle_address(&self, whirlpool: &Pubkey) -> Pubkey {
+        let (address, _) = Pubkey::find_program_address(
+            &[b"oracle", whirlpool.as_ref()],
+            &self.whirlpool_program_id,
+        );
+        address
+    }
+
+    /// Computes the sqrt price limit that caps the pool price move of a swap
+    ///
+    /// A to B swaps push the price down, B to A swaps push it up.
+    fn sqrt_price_limit_for_move(sqrt_price: u128, a_to_b: bool, max_move_percent: f64) -> u128 {
+        let move_fraction = (max_move_percent / 100.0).clamp(0.0, 1.0);
+        let factor = if a_to_b {
+            (1.0 - move_fraction).sqrt()
+        } else {
+            (1.0 + move_fraction).sqrt()
+        };
+        // Scale by the factor in Q64.64 so large sqrt prices keep their precision
+        let factor_x64 = (factor * 18_446_744_073_709_551_616.0) as u128;
+        let limit = mul_div_floor(sqrt_price, factor_x64, 1 << 64).unwrap_or(MAX_SQRT_PRICE_X64);
+        limit.clamp(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64)
+    }
 }
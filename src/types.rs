@@ -1,9 +1,337 @@
+use std::{fmt, time::Duration};
+
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_request::{RpcError, RpcResponseErrorData},
+};
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signature::Signature,
+    transaction::TransactionError,
+};
+
+use crate::{
+    freeze::FreezeRisk,
+    global::{
+        WHIRLPOOL_AMOUNT_IN_ABOVE_MAXIMUM, WHIRLPOOL_AMOUNT_OUT_BELOW_MINIMUM,
+        WHIRLPOOL_TOKEN_MAX_EXCEEDED, WHIRLPOOL_TOKEN_MIN_SUBCEEDED,
+    },
+    policy::PolicyViolation,
+    rent::RentItem,
+    safety::TokenSafetyReport,
+    submit::DryRunOutcome,
+};
+
 #[derive(Debug)]
 pub enum OrcaError {
+    /// Failures that fit no other variant, such as broken internal invariants
     Error(String),
     NetworkError(String),
     TransactionError(String),
     ParseError(String),
+    InvalidInput(InvalidInput),
+    UnsafeToken(Box<TokenSafetyReport>),
+    PriceDeviationExceeded {
+        reference_price: f64,
+        execution_price: f64,
+        deviation_percent: f64,
+        max_deviation_percent: f64,
+    },
+    PolicyViolation(PolicyViolation),
+    /// The fee payer cannot cover transaction fees plus rent of accounts being created
+    InsufficientSolForRent {
+        required: u64,
+        available: u64,
+        breakdown: Vec<RentItem>,
+    },
+    /// Frozen token accounts or freezable mints involved in the operation
+    FreezeRisk(Vec<FreezeRisk>),
+    /// The operation did not finish within the configured deadline
+    Timeout {
+        operation: String,
+        timeout: Duration,
+    },
+    /// The client was created without an RPC connection
+    RpcClientUnavailable,
+    /// An RPC request failed; `context` names what was being requested
+    RpcError {
+        context: String,
+        source: Box<ClientError>,
+    },
+    /// An account the operation depends on does not exist
+    AccountNotFound(Pubkey),
+    /// A pool account exists but could not be decoded
+    InvalidPoolData {
+        pool: String,
+        reason: String,
+    },
+    /// The on-chain price moved past the slippage bound before the swap or deposit executed
+    SlippageExceeded {
+        signature: Option<Signature>,
+    },
+    /// The pool cannot fill the amount within the tick arrays one swap can traverse
+    InsufficientLiquidity {
+        pool: String,
+    },
+    /// The owner holds less of a token than the operation needs
+    InsufficientBalance {
+        mint: Pubkey,
+        required: u64,
+        available: u64,
+    },
+    /// A transaction was rejected during preflight or failed on-chain
+    TransactionFailed {
+        signature: Option<Signature>,
+        error: TransactionError,
+        logs: Vec<String>,
+    },
+    /// Dry-run mode is enabled: the operation's transactions were simulated, not sent
+    DryRun(Vec<DryRunOutcome>),
+    /// An account exists but does not hold the expected data
+    InvalidAccountData {
+        account: Pubkey,
+        reason: String,
+    },
+    /// No pool or route connects the two mints
+    NoRoute {
+        input_mint: String,
+        output_mint: String,
+    },
+    /// No USDC or SOL pool, or no usable reference, prices the mint
+    PriceUnavailable(String),
+    /// Not enough recorded snapshots or transactions of the pool to compute the result
+    InsufficientHistory {
+        pool: String,
+        reason: String,
+    },
+    /// An oracle price was published longer ago than the accepted age
+    StaleOracle {
+        oracle: Pubkey,
+        age: Duration,
+    },
+    /// Reading or writing a local file or database failed
+    IoError(String),
+}
+
+impl OrcaError {
+    /// Wraps a failed RPC request
+    pub(crate) fn rpc(context: impl Into<String>, source: ClientError) -> Self {
+        OrcaError::RpcError {
+            context: context.into(),
+            source: Box::new(source),
+        }
+    }
+
+    /// Classifies a failed send: transaction errors keep their logs, the rest are RPC errors
+    pub(crate) fn from_send_error(
+        context: impl Into<String>,
+        signature: Option<Signature>,
+        source: ClientError,
+    ) -> Self {
+        match source.get_transaction_error() {
+            Some(error) => {
+                let logs = match source.kind() {
+                    ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                        data: RpcResponseErrorData::SendTransactionPreflightFailure(simulation),
+                        ..
+                    }) => simulation.logs.clone().unwrap_or_default(),
+                    _ => Vec::new(),
+                };
+                Self::transaction_failed(signature, error, logs)
+            }
+            None => Self::rpc(context, source),
+        }
+    }
+
+    /// Maps Whirlpool slippage errors to `SlippageExceeded`, everything else to `TransactionFailed`
+    pub(crate) fn transaction_failed(
+        signature: Option<Signature>,
+        error: TransactionError,
+        logs: Vec<String>,
+    ) -> Self {
+        match error {
+            TransactionError::InstructionError(
+                _,
+                InstructionError::Custom(
+                    WHIRLPOOL_AMOUNT_OUT_BELOW_MINIMUM
+                    | WHIRLPOOL_AMOUNT_IN_ABOVE_MAXIMUM
+                    | WHIRLPOOL_TOKEN_MAX_EXCEEDED
+                    | WHIRLPOOL_TOKEN_MIN_SUBCEEDED,
+                ),
+            ) => OrcaError::SlippageExceeded { signature },
+            error => OrcaError::TransactionFailed {
+                signature,
+                error,
+                logs,
+            },
+        }
+    }
+
+    /// Returns true for transient failures worth retrying: timeouts, connection errors,
+    /// rate limiting and unhealthy nodes. User errors such as invalid input, missing
+    /// accounts, failed transactions or policy violations return false.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            OrcaError::NetworkError(_) | OrcaError::Timeout { .. } => true,
+            OrcaError::RpcError { source, .. } => match source.kind() {
+                ClientErrorKind::Io(_) => true,
+                ClientErrorKind::Reqwest(e) => {
+                    e.is_timeout()
+                        || e.is_connect()
+                        || e.status().is_some_and(|status| {
+                            status.as_u16() == 429 || status.is_server_error()
+                        })
+                }
+                ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                    data: RpcResponseErrorData::NodeUnhealthy { .. },
+                    ..
+                }) => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for OrcaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrcaError::Error(message)
+            | OrcaError::NetworkError(message)
+            | OrcaError::TransactionError(message)
+            | OrcaError::ParseError(message)
+            | OrcaError::IoError(message) => write!(f, "{}", message),
+            OrcaError::InvalidInput(reason) => write!(f, "Invalid input: {:?}", reason),
+            OrcaError::UnsafeToken(report) => write!(
+                f,
+                "Token {} exceeds the allowed risk level: {:?}",
+                report.mint, report.risk_level
+            ),
+            OrcaError::PriceDeviationExceeded {
+                deviation_percent,
+                max_deviation_percent,
+                ..
+            } => write!(
+                f,
+                "Execution price deviates {:.2}% from the reference price (max {:.2}%)",
+                deviation_percent, max_deviation_percent
+            ),
+            OrcaError::PolicyViolation(violation) => {
+                write!(f, "Policy violation: {:?}", violation)
+            }
+            OrcaError::InsufficientSolForRent {
+                required,
+                available,
+                ..
+            } => write!(
+                f,
+                "Insufficient SOL for fees and rent: {} lamports required, {} available",
+                required, available
+            ),
+            OrcaError::FreezeRisk(risks) => write!(f, "Freeze risk: {:?}", risks),
+            OrcaError::Timeout { operation, timeout } => {
+                write!(f, "{} timed out after {:?}", operation, timeout)
+            }
+            OrcaError::RpcClientUnavailable => write!(f, "RPC client not available"),
+            OrcaError::RpcError { context, source } => write!(f, "{}: {}", context, source),
+            OrcaError::AccountNotFound(address) => write!(f, "Account {} not found", address),
+            OrcaError::InvalidPoolData { pool, reason } => {
+                write!(f, "Invalid pool data for {}: {}", pool, reason)
+            }
+            OrcaError::SlippageExceeded { .. } => write!(f, "Slippage tolerance exceeded"),
+            OrcaError::InsufficientLiquidity { pool } => {
+                write!(f, "Insufficient liquidity in pool {}", pool)
+            }
+            OrcaError::InsufficientBalance {
+                mint,
+                required,
+                available,
+            } => write!(
+                f,
+                "Insufficient balance of {}: {} required, {} available",
+                mint, required, available
+            ),
+            OrcaError::TransactionFailed { error, .. } => {
+                write!(f, "Transaction failed: {}", error)
+            }
+            OrcaError::DryRun(outcomes) => write!(
+                f,
+                "Dry run: {} transaction(s) simulated, none sent",
+                outcomes.len()
+            ),
+            OrcaError::InvalidAccountData { account, reason } => {
+                write!(f, "Invalid account data for {}: {}", account, reason)
+            }
+            OrcaError::NoRoute {
+                input_mint,
+                output_mint,
+            } => write!(f, "No route found from {} to {}", input_mint, output_mint),
+            OrcaError::PriceUnavailable(mint) => write!(f, "No USD price found for {}", mint),
+            OrcaError::InsufficientHistory { pool, reason } => {
+                write!(f, "Insufficient history of pool {}: {}", pool, reason)
+            }
+            OrcaError::StaleOracle { oracle, age } => {
+                write!(
+                    f,
+                    "Oracle price from {} is stale: published {:?} ago",
+                    oracle, age
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for OrcaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OrcaError::RpcError { source, .. } => Some(source.as_ref()),
+            OrcaError::TransactionFailed { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Reasons an input was rejected before any RPC call or signing happened
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidInput {
+    /// A tick index lies outside the Whirlpool tick bounds
+    TickOutOfBounds { tick: i32, min: i32, max: i32 },
+    /// A tick index is not a multiple of the pool's tick spacing
+    TickNotAligned { tick: i32, tick_spacing: u16 },
+    /// The lower tick is not strictly below the upper tick
+    InvalidTickRange { lower_tick: i32, upper_tick: i32 },
+    /// A zero amount was supplied for the named parameter
+    ZeroAmount(String),
+    /// Input and output (or A and B) mints are the same
+    IdenticalMints(String),
+    /// Slippage tolerance in basis points is above 10_000
+    SlippageBpsOutOfRange(u16),
+    /// A string could not be parsed as a public key
+    InvalidAddress { field: String, value: String },
+    /// Prices are not positive and finite, or the lower price is not below the upper price
+    InvalidPriceRange { lower_price: f64, upper_price: f64 },
+    /// A count or size parameter lies outside its accepted range
+    ValueOutOfRange {
+        field: String,
+        value: u64,
+        min: u64,
+        max: u64,
+    },
+    /// A percentage or price threshold is not finite or outside its accepted range
+    InvalidThreshold { field: String, value: f64 },
+    /// A keypair could not be read from its source or parsed
+    InvalidKeypair { source: String, reason: String },
+    /// The pool does not trade the given mint
+    MintNotInPool { pool: String, mint: String },
+    /// The position belongs to a different pool
+    PositionNotInPool { position: Pubkey, pool: String },
+    /// The requested tick range is the one the position already spans
+    RangeUnchanged { lower_tick: i32, upper_tick: i32 },
+    /// The pool kind does not support the operation
+    UnsupportedPool(String),
+    /// The pool has no initialized reward slots
+    NoRewards(String),
+    /// An RPC URL is empty
+    EmptyRpcUrl,
 }
 
 pub type OrcaResult<T> = Result<T, OrcaError>;
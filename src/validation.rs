@@ -0,0 +1,110 @@
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+use crate::{
+    global::{BPS_DENOMINATOR, MAX_TICK_INDEX, MIN_TICK_INDEX},
+    types::{InvalidInput, OrcaError, OrcaResult},
+};
+
+/// Parses a base58 address, naming the offending parameter on failure
+///
+/// # Example
+/// ```rust
+/// let pool = orca_sdk::validation::parse_address("pool_address", "whirlpool_address_here")?;
+/// ```
+pub fn parse_address(field: &str, value: &str) -> OrcaResult<Pubkey> {
+    Pubkey::from_str(value).map_err(|_| {
+        OrcaError::InvalidInput(InvalidInput::InvalidAddress {
+            field: field.to_string(),
+            value: value.to_string(),
+        })
+    })
+}
+
+/// Rejects zero amounts for the named parameter
+pub fn validate_amount(field: &str, amount: u64) -> OrcaResult<()> {
+    if amount == 0 {
+        return Err(OrcaError::InvalidInput(InvalidInput::ZeroAmount(
+            field.to_string(),
+        )));
+    }
+    Ok(())
+}
+
+/// Rejects identical mints for a pair
+pub fn validate_distinct_mints(mint_a: &str, mint_b: &str) -> OrcaResult<()> {
+    if mint_a == mint_b {
+        return Err(OrcaError::InvalidInput(InvalidInput::IdenticalMints(
+            mint_a.to_string(),
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that a slippage tolerance in basis points lies within 0 to 10_000
+pub fn validate_slippage_bps(slippage_bps: u16) -> OrcaResult<()> {
+    if slippage_bps > BPS_DENOMINATOR {
+        return Err(OrcaError::InvalidInput(
+            InvalidInput::SlippageBpsOutOfRange(slippage_bps),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that a tick lies within the Whirlpool bounds and is aligned to the spacing
+pub fn validate_tick(tick: i32, tick_spacing: u16) -> OrcaResult<()> {
+    if !(MIN_TICK_INDEX..=MAX_TICK_INDEX).contains(&tick) {
+        return Err(OrcaError::InvalidInput(InvalidInput::TickOutOfBounds {
+            tick,
+            min: MIN_TICK_INDEX,
+            max: MAX_TICK_INDEX,
+        }));
+    }
+    if tick_spacing == 0 || tick % tick_spacing as i32 != 0 {
+        return Err(OrcaError::InvalidInput(InvalidInput::TickNotAligned {
+            tick,
+            tick_spacing,
+        }));
+    }
+    Ok(())
+}
+
+/// Validates both bounds of a position range and their ordering
+pub fn validate_tick_range(lower_tick: i32, upper_tick: i32, tick_spacing: u16) -> OrcaResult<()> {
+    validate_tick(lower_tick, tick_spacing)?;
+    validate_tick(upper_tick, tick_spacing)?;
+    if lower_tick >= upper_tick {
+        return Err(OrcaError::InvalidInput(InvalidInput::InvalidTickRange {
+            lower_tick,
+            upper_tick,
+        }));
+    }
+    Ok(())
+}
+
+/// Validates a position price range: both prices positive and finite, lower below upper
+pub fn validate_price_range(lower_price: f64, upper_price: f64) -> OrcaResult<()> {
+    let valid = |price: f64| price.is_finite() && price > 0.0;
+    if !valid(lower_price) || !valid(upper_price) || lower_price >= upper_price {
+        return Err(OrcaError::InvalidInput(InvalidInput::InvalidPriceRange {
+            lower_price,
+            upper_price,
+        }));
+    }
+    Ok(())
+}
+
+/// Validates a swap pair and amount before any pool discovery happens
+pub fn validate_swap_inputs(
+    input_mint: &str,
+    output_mint: &str,
+    amount: u64,
+    slippage_bps: u16,
+) -> OrcaResult<(Pubkey, Pubkey)> {
+    let input = parse_address("input_mint", input_mint)?;
+    let output = parse_address("output_mint", output_mint)?;
+    validate_distinct_mints(input_mint, output_mint)?;
+    validate_amount("amount", amount)?;
+    validate_slippage_bps(slippage_bps)?;
+    Ok((input, output))
+}
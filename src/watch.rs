@@ -0,0 +1,128 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::sync::watch;
+
+use crate::{OrcaClient, pool::PoolInfo, types::OrcaResult};
+
+/// Default interval between pool state refreshes
+pub const DEFAULT_POOL_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Pool state snapshot held by a `WatchedPool`
+#[derive(Debug, Clone)]
+pub struct PoolSnapshot {
+    pub pool: PoolInfo,
+    /// When the state was fetched
+    pub fetched_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl OrcaClient {
+    /// Starts refreshing a pool's state in the background
+    ///
+    /// The initial state is fetched before this returns, so `latest()` is always available.
+    /// Refresh failures are logged and the previous state is kept. With the `yellowstone`
+    /// feature and a Geyser endpoint configured, account updates are streamed over gRPC and
+    /// `refresh_interval` is unused.
+    ///
+    /// # Params
+    /// pool_address - The address of the pool to watch
+    /// refresh_interval - Time between refreshes
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use orca_sdk::watch::DEFAULT_POOL_REFRESH_INTERVAL;
+    ///
+    /// let client = Arc::new(OrcaClient::new()?);
+    /// let mut watched = client
+    ///     .watch_pool("POOL_ADDRESS_HERE", DEFAULT_POOL_REFRESH_INTERVAL)
+    ///     .await?;
+    /// loop {
+    ///     let pool = watched.latest();
+    ///     println!("sqrt price: {}", pool.sqrt_price);
+    ///     watched.changed().await;
+    /// }
+    /// ```
+    pub async fn watch_pool(
+        self: Arc<Self>,
+        pool_address: &str,
+        refresh_interval: Duration,
+    ) -> OrcaResult<WatchedPool> {
+        let initial = self.get_pool_state_onchain(pool_address).await?;
+        let (state_tx, state_rx) = watch::channel(PoolSnapshot {
+            pool: initial,
+            fetched_at: chrono::Utc::now(),
+        });
+        #[cfg(feature = "yellowstone")]
+        if let Some(geyser) = &self.geyser {
+            let pool = crate::validation::parse_address("pool_address", pool_address)?;
+            let task_handle = tokio::spawn(crate::geyser::stream_pool_updates(
+                geyser.clone(),
+                pool,
+                state_tx,
+            ));
+            return Ok(WatchedPool {
+                state_rx,
+                task_handle,
+            });
+        }
+        let client = self;
+        let pool_address = pool_address.to_string();
+        let task_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(refresh_interval);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if state_tx.is_closed() {
+                    break;
+                }
+                match client.get_pool_state_onchain(&pool_address).await {
+                    Ok(pool) => {
+                        let _ = state_tx.send(PoolSnapshot {
+                            pool,
+                            fetched_at: chrono::Utc::now(),
+                        });
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to refresh watched pool {}: {:?}", pool_address, e);
+                    }
+                }
+            }
+        });
+        Ok(WatchedPool {
+            state_rx,
+            task_handle,
+        })
+    }
+}
+
+/// Handle to a pool whose state is refreshed in the background
+///
+/// Dropping the handle stops the refresh task.
+#[derive(Debug)]
+pub struct WatchedPool {
+    state_rx: watch::Receiver<PoolSnapshot>,
+    task_handle: tokio::task::JoinHandle<()>,
+}
+
+impl WatchedPool {
+    /// Returns the most recently fetched pool state
+    pub fn latest(&self) -> PoolInfo {
+        self.state_rx.borrow().pool.clone()
+    }
+
+    /// Returns the most recent state together with its fetch time
+    pub fn snapshot(&self) -> PoolSnapshot {
+        self.state_rx.borrow().clone()
+    }
+
+    /// Waits until a newer state than the one last observed through this method is available
+    pub async fn changed(&mut self) {
+        let _ = self.state_rx.changed().await;
+    }
+}
+
+impl Drop for WatchedPool {
+    fn drop(&mut self) {
+        self.task_handle.abort();
+    }
+}
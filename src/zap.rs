@@ -0,0 +1,1059 @@
+use super::*;
+use crate::{
+    audit::AuditDetails,
+    clmm::simulate_swap,
+    fixed::{
+        liquidity_from_amount_a, liquidity_from_amount_b, min_amount_with_slippage,
+        sqrt_price_x64_at_tick,
+    },
+    global::{MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64},
+    liquidity::{LiquidityPosition, PositionTokenAccounts},
+    math::{liquidity_from_token_amounts, token_amounts_from_liquidity},
+    packer::{
+        CLOSE_POSITION_COMPUTE_UNITS, COLLECT_FEES_COMPUTE_UNITS, COLLECT_REWARD_COMPUTE_UNITS,
+        CREATE_TOKEN_ACCOUNT_COMPUTE_UNITS, DECREASE_LIQUIDITY_COMPUTE_UNITS,
+        INCREASE_LIQUIDITY_COMPUTE_UNITS, INITIALIZE_TICK_ARRAY_COMPUTE_UNITS, InstructionGroup,
+        OPEN_POSITION_COMPUTE_UNITS, PackedSubmission, SWAP_COMPUTE_UNITS, TransactionPacker,
+    },
+    pool::PoolInfo,
+    receipt::{ZapInReceipt, ZapOutReceipt},
+    rent::{
+        MINT_ACCOUNT_SIZE, POSITION_ACCOUNT_SIZE, RentPlan, TICK_ARRAY_ACCOUNT_SIZE,
+        TOKEN_ACCOUNT_SIZE,
+    },
+    submit::SubmitMode,
+    tick::SwapTickRange,
+    token::MintInfo,
+    trade::{SwapParams, SwapTokenAccounts},
+    types::{InvalidInput, OrcaResult},
+    validation::{parse_address, validate_amount, validate_slippage_bps, validate_tick_range},
+};
+use solana_sdk::instruction::Instruction;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+/// Position a zap deposits into
+#[derive(Debug, Clone)]
+pub enum ZapTarget {
+    /// Opens a new position over the tick range
+    NewPosition { lower_tick: i32, upper_tick: i32 },
+    /// Adds to a position the owner already holds
+    Position(Box<LiquidityPosition>),
+}
+
+impl ZapTarget {
+    pub fn tick_range(&self) -> (i32, i32) {
+        match self {
+            ZapTarget::NewPosition {
+                lower_tick,
+                upper_tick,
+            } => (*lower_tick, *upper_tick),
+            ZapTarget::Position(position) => (position.lower_tick, position.upper_tick),
+        }
+    }
+}
+
+/// Configuration for zapping into or out of a position
+#[derive(Debug, Clone)]
+pub struct ZapConfig {
+    /// Slippage tolerance in basis points, applied to the swap output and to the deposit
+    pub slippage_bps: u16,
+    /// Priority fee paid per compute unit, in micro-lamports
+    pub priority_fee_microlamports: Option<u64>,
+    /// Compute unit limit requested for the transaction
+    pub compute_unit_limit: Option<u32>,
+    /// How the transaction is sent
+    pub submit_mode: SubmitMode,
+}
+
+impl Default for ZapConfig {
+    fn default() -> Self {
+        Self {
+            slippage_bps: 50,
+            priority_fee_microlamports: None,
+            compute_unit_limit: None,
+            submit_mode: SubmitMode::default(),
+        }
+    }
+}
+
+/// Split of a single-token deposit between a swap and the position
+#[derive(Debug, Clone)]
+pub struct ZapInQuote {
+    /// Mint swapped into the pool's other token
+    pub swap_mint: Pubkey,
+    /// Amount of `swap_mint` swapped
+    pub swap_amount: u64,
+    /// Expected swap output reaching the owner, net of transfer fees
+    pub swap_output: u64,
+    /// Smallest swap output the transaction accepts
+    pub min_swap_output: u64,
+    /// Pool sqrt price expected once the swap completed, Q64.64
+    pub sqrt_price_after_swap: u128,
+    /// Liquidity the deposit adds to the position
+    pub liquidity: u128,
+    /// Most token A the deposit may spend
+    pub token_max_a: u64,
+    /// Most token B the deposit may spend
+    pub token_max_b: u64,
+}
+
+/// Instructions swapping part of the input and depositing the rest, ready to be signed
+#[derive(Debug)]
+pub struct ZapInInstructions {
+    /// Mint of the position NFT the deposit goes to
+    pub position_mint: Pubkey,
+    /// Freshly generated position mint when the zap opens a position; it must co-sign
+    pub new_position_mint: Option<Keypair>,
+    /// Position account (PDA of the position mint)
+    pub position_address: Pubkey,
+    /// Owner's token account holding the position NFT
+    pub position_token_account: Pubkey,
+    /// Start indexes of the tick arrays the deposit initializes because they did not exist
+    pub initialized_tick_arrays: Vec<i32>,
+    /// Instructions that must land in one transaction, in order
+    pub group: InstructionGroup,
+}
+
+/// Withdrawal of a position converted into a single token
+#[derive(Debug, Clone)]
+pub struct ZapOutQuote {
+    pub output_mint: Pubkey,
+    /// Least token A the withdrawal returns, net of transfer fees
+    pub token_min_a: u64,
+    /// Least token B the withdrawal returns, net of transfer fees
+    pub token_min_b: u64,
+    /// Amount of the other token swapped into the output mint: its withdrawal minimum plus the
+    /// fees owed
+    pub swap_amount: u64,
+    /// Expected swap output reaching the owner, net of transfer fees
+    pub swap_output: u64,
+    /// Smallest swap output the transaction accepts
+    pub min_swap_output: u64,
+    /// Least amount of the output mint the owner receives, withdrawal, fees and swap combined
+    pub min_output_amount: u64,
+}
+
+impl OrcaClient {
+    /// Deposits a single token into a position
+    ///
+    /// Swaps the part of `amount` that balances the deposit at the post-swap price into the
+    /// pool's other token, then deposits both into `target`. The swap, the position opening and
+    /// the deposit land in one transaction, so either all of them execute or none do.
+    ///
+    /// # Example
+    /// ```rust
+    /// use orca_sdk::zap::ZapTarget;
+    ///
+    /// let pool_info = client.get_pool_state_onchain("whirlpool_address").await?;
+    /// let target = ZapTarget::NewPosition { lower_tick: -1024, upper_tick: 1024 };
+    /// let receipt = client
+    ///     .zap_in(&keypair, &pool_info, &pool_info.token_mint_a, 1_000_000, target, None)
+    ///     .await?;
+    /// println!("Swapped {} and added {} liquidity", receipt.swap_amount, receipt.liquidity);
+    /// ```
+    #[tracing::instrument(
+        skip(self, signer, pool, target, config),
+        fields(owner = %signer.pubkey(), pool = %pool.address),
+        err(Debug)
+    )]
+    pub async fn zap_in<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        pool: &PoolInfo,
+        input_mint: &str,
+        amount: u64,
+        target: ZapTarget,
+        config: Option<ZapConfig>,
+    ) -> OrcaResult<ZapInReceipt> {
+        self.with_timeout("zap in", async {
+            let config = config.unwrap_or_default();
+            let owner = signer.pubkey();
+            if let ZapTarget::Position(position) = &target
+                && position.pool_address.to_string() != pool.address
+            {
+                return Err(OrcaError::InvalidInput(InvalidInput::PositionNotInPool {
+                    position: position.position_address,
+                    pool: pool.address.clone(),
+                }));
+            }
+            self.check_pool_policy(&pool.address)?;
+            let (lower_tick, upper_tick) = target.tick_range();
+            let quote = self
+                .quote_zap_in(
+                    pool,
+                    input_mint,
+                    amount,
+                    lower_tick,
+                    upper_tick,
+                    config.slippage_bps,
+                )
+                .await?;
+            if quote.swap_amount > 0 {
+                let swap_output_mint = if input_mint == pool.token_mint_a {
+                    &pool.token_mint_b
+                } else {
+                    &pool.token_mint_a
+                };
+                self.enforce_token_safety(input_mint, swap_output_mint, None)
+                    .await?;
+                self.enforce_price_guard(
+                    None,
+                    pool,
+                    input_mint,
+                    quote.swap_amount,
+                    quote.swap_output,
+                )
+                .await?;
+            }
+            let input_mint_pubkey = parse_address("input_mint", input_mint)?;
+            let spends = [(input_mint, amount)];
+            let reservation = self.reserve_spend(&spends).await?;
+            let available = self.get_token_balance(&owner, &input_mint_pubkey).await?;
+            if available < amount {
+                return Err(OrcaError::InsufficientBalance {
+                    mint: input_mint_pubkey,
+                    required: amount,
+                    available,
+                });
+            }
+            let zap = self.build_zap_in_ixs(&owner, pool, &quote, &target).await?;
+            let token_a_mint = parse_address("token_mint_a", &pool.token_mint_a)?;
+            let token_b_mint = parse_address("token_mint_b", &pool.token_mint_b)?;
+            let mut rent_plan = RentPlan::new().signatures(1);
+            if zap.new_position_mint.is_some() {
+                rent_plan = rent_plan
+                    .account("position mint", MINT_ACCOUNT_SIZE)
+                    .account("position token account", TOKEN_ACCOUNT_SIZE)
+                    .account("position account", POSITION_ACCOUNT_SIZE)
+                    .signatures(1);
+            }
+            for start in &zap.initialized_tick_arrays {
+                rent_plan =
+                    rent_plan.account(format!("tick array {}", start), TICK_ARRAY_ACCOUNT_SIZE);
+            }
+            if let Some(tip_lamports) = config.submit_mode.jito_tip() {
+                rent_plan = rent_plan.lamports("Jito tip", tip_lamports);
+            }
+            let rent_plan = self
+                .plan_token_accounts(rent_plan, &owner, &[token_a_mint, token_b_mint])
+                .await?;
+            self.ensure_sol_for_plan(&owner, &rent_plan).await?;
+            let rent_paid = self.estimate_rent(&rent_plan).await?;
+            let signers: Vec<&Keypair> = zap.new_position_mint.iter().collect();
+            let signatures = self
+                .submit_packed(
+                    signer,
+                    &signers,
+                    PackedSubmission {
+                        prefix: &self.build_compute_budget_instructions(
+                            config.compute_unit_limit,
+                            config.priority_fee_microlamports,
+                        )?,
+                        groups: std::slice::from_ref(&zap.group),
+                        packer: &TransactionPacker::default(),
+                        mode: &config.submit_mode,
+                    },
+                    "zap in",
+                    &AuditDetails::new(serde_json::json!({
+                        "pool": pool.address,
+                        "position_address": zap.position_address.to_string(),
+                        "input_mint": input_mint,
+                        "input_amount": amount,
+                        "swap_amount": quote.swap_amount,
+                        "min_swap_output": quote.min_swap_output,
+                        "liquidity": quote.liquidity.to_string(),
+                        "token_max_a": quote.token_max_a,
+                        "token_max_b": quote.token_max_b,
+                        "lower_tick": lower_tick,
+                        "upper_tick": upper_tick,
+                    })),
+                )
+                .await?;
+            let signature = signatures
+                .last()
+                .copied()
+                .ok_or(OrcaError::Error("No transaction was submitted".to_string()))?;
+            reservation.commit();
+            let settlement = self
+                .settle_liquidity(&signature, &owner, &token_a_mint, &token_b_mint)
+                .await;
+            Ok(ZapInReceipt {
+                signature,
+                pool: parse_address("pool_address", &pool.address)?,
+                position_mint: zap.position_mint,
+                position_address: zap.position_address,
+                lower_tick,
+                upper_tick,
+                input_mint: input_mint_pubkey,
+                input_amount: amount,
+                swap_amount: quote.swap_amount,
+                min_swap_output: quote.min_swap_output,
+                liquidity: quote.liquidity,
+                token_max_a: quote.token_max_a,
+                token_max_b: quote.token_max_b,
+                rent_paid,
+                settlement,
+            })
+        })
+        .await
+    }
+
+    /// Splits a single-token deposit between a swap and the position
+    ///
+    /// The swap amount is found by bisection over the pool's swap simulation: more input is
+    /// swapped while the kept input would support more liquidity than the swap output at the
+    /// post-swap price. Liquidity is sized from the kept input and the minimum swap output, both
+    /// reduced by the slippage tolerance, so the deposit fits what the owner holds after the
+    /// swap even when the price moved.
+    ///
+    /// # Example
+    /// ```rust
+    /// let quote = client
+    ///     .quote_zap_in(&pool_info, &pool_info.token_mint_b, 2_000_000, -1024, 1024, 50)
+    ///     .await?;
+    /// println!("Swap {} of the input first", quote.swap_amount);
+    /// ```
+    pub async fn quote_zap_in(
+        &self,
+        pool: &PoolInfo,
+        input_mint: &str,
+        amount: u64,
+        lower_tick: i32,
+        upper_tick: i32,
+        slippage_bps: u16,
+    ) -> OrcaResult<ZapInQuote> {
+        validate_amount("amount", amount)?;
+        let (amount_a, amount_b) = if input_mint == pool.token_mint_a {
+            (amount, 0)
+        } else if input_mint == pool.token_mint_b {
+            (0, amount)
+        } else {
+            return Err(OrcaError::InvalidInput(InvalidInput::MintNotInPool {
+                pool: pool.address.clone(),
+                mint: input_mint.to_string(),
+            }));
+        };
+        let quote = self
+            .quote_balanced_deposit(
+                pool,
+                None,
+                (amount_a, amount_b),
+                lower_tick,
+                upper_tick,
+                slippage_bps,
+            )
+            .await?;
+        validate_amount(
+            "liquidity",
+            u64::try_from(quote.liquidity).unwrap_or(u64::MAX),
+        )?;
+        Ok(quote)
+    }
+
+    /// Swaps the surplus of whichever token exceeds the range's ratio and sizes the deposit
+    ///
+    /// The swap direction follows from the liquidity each token supports at the current price;
+    /// the amount is then searched as in `quote_zap_in`. With `withdrawn`, the swap is simulated
+    /// as if that position's liquidity had already left the pool. The quote's liquidity may be
+    /// zero when the amounts are too small to deposit.
+    pub(crate) async fn quote_balanced_deposit(
+        &self,
+        pool: &PoolInfo,
+        withdrawn: Option<&LiquidityPosition>,
+        (amount_a, amount_b): (u64, u64),
+        lower_tick: i32,
+        upper_tick: i32,
+        slippage_bps: u16,
+    ) -> OrcaResult<ZapInQuote> {
+        validate_tick_range(lower_tick, upper_tick, pool.tick_spacing)?;
+        validate_slippage_bps(slippage_bps)?;
+        let token_a_mint = parse_address("token_mint_a", &pool.token_mint_a)?;
+        let token_b_mint = parse_address("token_mint_b", &pool.token_mint_b)?;
+        let mint_infos = self.get_mint_infos(&[token_a_mint, token_b_mint]).await?;
+        let sqrt_lower = sqrt_price_x64_at_tick(lower_tick);
+        let sqrt_upper = sqrt_price_x64_at_tick(upper_tick);
+        let (from_a, from_b) = side_liquidity(
+            pool.sqrt_price,
+            sqrt_lower,
+            sqrt_upper,
+            mint_infos[0].amount_after_transfer_fee(amount_a),
+            mint_infos[1].amount_after_transfer_fee(amount_b),
+        );
+        let input_is_a = from_a > from_b;
+        let (input_info, output_info, input_amount, other_amount) = if input_is_a {
+            (&mint_infos[0], &mint_infos[1], amount_a, amount_b)
+        } else {
+            (&mint_infos[1], &mint_infos[0], amount_b, amount_a)
+        };
+        let mut tick_range = self.get_swap_tick_range(pool, input_is_a).await?;
+        let pool = &match withdrawn {
+            Some(position) => without_position(pool, &mut tick_range, position),
+            None => pool.clone(),
+        };
+        // Pool price and swap output (net of transfer fees) after swapping `swap_amount`,
+        // or None when the pool cannot fill it
+        let swap = |swap_amount: u64| -> Option<(u128, u64)> {
+            if swap_amount == 0 {
+                return Some((pool.sqrt_price, 0));
+            }
+            let simulation = simulate_swap(
+                pool,
+                &tick_range,
+                input_info.amount_after_transfer_fee(swap_amount),
+                true,
+                input_is_a,
+            );
+            simulation.filled.then(|| {
+                (
+                    simulation.sqrt_price,
+                    output_info.amount_after_transfer_fee(simulation.amount_out),
+                )
+            })
+        };
+        let needs_more_swap = |swap_amount: u64| {
+            let Some((sqrt_price, output)) = swap(swap_amount) else {
+                return false;
+            };
+            let kept = input_info.amount_after_transfer_fee(input_amount - swap_amount);
+            // `output` is already net of the transfer fee
+            let other = output_info
+                .amount_after_transfer_fee(other_amount)
+                .saturating_add(output);
+            if input_is_a {
+                let (from_a, from_b) =
+                    side_liquidity(sqrt_price, sqrt_lower, sqrt_upper, kept, other);
+                from_a > from_b
+            } else {
+                let (from_a, from_b) =
+                    side_liquidity(sqrt_price, sqrt_lower, sqrt_upper, other, kept);
+                from_b > from_a
+            }
+        };
+        let (mut low, mut high) = (0, input_amount);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if needs_more_swap(mid) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        let swap_amount = low;
+        let (sqrt_price_after_swap, swap_output) =
+            swap(swap_amount).ok_or_else(|| OrcaError::InsufficientLiquidity {
+                pool: pool.address.clone(),
+            })?;
+        let min_swap_output = min_amount_with_slippage(swap_output, slippage_bps);
+        let kept = input_amount - swap_amount;
+        let other = other_amount.saturating_add(min_swap_output);
+        let (token_max_a, token_max_b) = if input_is_a {
+            (kept, other)
+        } else {
+            (other, kept)
+        };
+        let deposit_amount = |info: &MintInfo, available: u64| {
+            info.amount_after_transfer_fee(min_amount_with_slippage(available, slippage_bps))
+        };
+        let liquidity = liquidity_from_token_amounts(
+            sqrt_price_after_swap,
+            lower_tick,
+            upper_tick,
+            deposit_amount(&mint_infos[0], token_max_a),
+            deposit_amount(&mint_infos[1], token_max_b),
+        );
+        Ok(ZapInQuote {
+            swap_mint: input_info.mint,
+            swap_amount,
+            swap_output,
+            min_swap_output,
+            sqrt_price_after_swap,
+            liquidity,
+            token_max_a,
+            token_max_b,
+        })
+    }
+
+    /// Builds the swap and deposit of a zap quote as one instruction group
+    ///
+    /// Missing token accounts and tick arrays are created first, then the swap executes, a new
+    /// position is opened when the target asks for one, and the deposit runs last.
+    pub async fn build_zap_in_ixs(
+        &self,
+        owner: &Pubkey,
+        pool: &PoolInfo,
+        quote: &ZapInQuote,
+        target: &ZapTarget,
+    ) -> OrcaResult<ZapInInstructions> {
+        let (lower_tick, upper_tick) = target.tick_range();
+        let token_a_mint = parse_address("token_mint_a", &pool.token_mint_a)?;
+        let token_b_mint = parse_address("token_mint_b", &pool.token_mint_b)?;
+        let pool_pubkey = parse_address("pool_address", &pool.address)?;
+        let mint_infos = self.get_mint_infos(&[token_a_mint, token_b_mint]).await?;
+        let (token_program_a, token_program_b) =
+            (mint_infos[0].token_program, mint_infos[1].token_program);
+        let token_a_account =
+            self.get_associated_token_address_with_program(owner, &token_a_mint, &token_program_a);
+        let token_b_account =
+            self.get_associated_token_address_with_program(owner, &token_b_mint, &token_program_b);
+        let mut instructions = Vec::new();
+        let mut compute_units = 0;
+        let missing = self
+            .missing_accounts(&[token_a_account, token_b_account])
+            .await?;
+        for (info, is_missing) in mint_infos.iter().zip(missing) {
+            if is_missing {
+                instructions.push(create_associated_token_account_idempotent(
+                    owner,
+                    owner,
+                    &info.mint,
+                    &info.token_program,
+                ));
+                compute_units += CREATE_TOKEN_ACCOUNT_COMPUTE_UNITS;
+            }
+        }
+        if quote.swap_amount > 0 {
+            let a_to_b = quote.swap_mint == token_a_mint;
+            let ((input_account, input_program), (output_account, output_program)) = if a_to_b {
+                (
+                    (&token_a_account, &token_program_a),
+                    (&token_b_account, &token_program_b),
+                )
+            } else {
+                (
+                    (&token_b_account, &token_program_b),
+                    (&token_a_account, &token_program_a),
+                )
+            };
+            instructions.push(self.build_swap_instruction(
+                owner,
+                pool,
+                SwapTokenAccounts {
+                    input_token_account: input_account,
+                    output_token_account: output_account,
+                    input_token_program: input_program,
+                    output_token_program: output_program,
+                },
+                SwapParams {
+                    amount: quote.swap_amount,
+                    other_amount_threshold: quote.min_swap_output,
+                    sqrt_price_limit: if a_to_b {
+                        MIN_SQRT_PRICE_X64
+                    } else {
+                        MAX_SQRT_PRICE_X64
+                    },
+                    amount_specified_is_input: true,
+                    a_to_b,
+                },
+            )?);
+            compute_units += SWAP_COMPUTE_UNITS;
+        }
+        let initialized_tick_arrays = self
+            .get_missing_tick_array_starts(pool, lower_tick, upper_tick)
+            .await?;
+        for start in &initialized_tick_arrays {
+            instructions.push(self.build_initialize_tick_array_instruction(
+                owner,
+                &pool_pubkey,
+                *start,
+            ));
+            compute_units += INITIALIZE_TICK_ARRAY_COMPUTE_UNITS;
+        }
+        let (new_position_mint, position_mint, position_token_account) = match target {
+            ZapTarget::Position(position) => (
+                None,
+                position.position_mint,
+                position.position_token_account,
+            ),
+            ZapTarget::NewPosition { .. } => {
+                let position_mint = Keypair::new();
+                let position_token_account =
+                    self.get_associated_token_address(owner, &position_mint.pubkey());
+                instructions.push(self.build_open_position_instruction(
+                    owner,
+                    &pool_pubkey,
+                    &position_mint.pubkey(),
+                    &position_token_account,
+                    lower_tick,
+                    upper_tick,
+                )?);
+                compute_units += OPEN_POSITION_COMPUTE_UNITS;
+                let address = position_mint.pubkey();
+                (Some(position_mint), address, position_token_account)
+            }
+        };
+        instructions.push(self.build_increase_liquidity_instruction(
+            owner,
+            pool,
+            PositionTokenAccounts {
+                position_mint: &position_mint,
+                position_token_account: &position_token_account,
+                token_accounts: (&token_a_account, &token_b_account),
+                token_programs: (&token_program_a, &token_program_b),
+            },
+            (lower_tick, upper_tick),
+            quote.liquidity,
+            (quote.token_max_a, quote.token_max_b),
+        )?);
+        compute_units += INCREASE_LIQUIDITY_COMPUTE_UNITS;
+        Ok(ZapInInstructions {
+            position_address: self.get_position_pda(&position_mint),
+            position_mint,
+            new_position_mint,
+            position_token_account,
+            initialized_tick_arrays,
+            group: InstructionGroup::new(instructions, compute_units),
+        })
+    }
+
+    /// Withdraws a position into a single token
+    ///
+    /// Decreases all of the position's liquidity, collects its fees and swaps the other token
+    /// into `output_mint`. With `close_position`, rewards are claimed first and the position is
+    /// closed afterwards. Everything is packed into as few transactions as fit; in Jito mode
+    /// they form one atomic bundle. Reward tokens are claimed as they are and not swapped.
+    ///
+    /// # Example
+    /// ```rust
+    /// let position = client.get_position(&keypair.pubkey(), &position_mint).await?;
+    /// let receipt = client
+    ///     .zap_out(&keypair, &position, "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", true, None)
+    ///     .await?;
+    /// println!("Received at least {}", receipt.min_output_amount);
+    /// ```
+    #[tracing::instrument(
+        skip(self, signer, position, config),
+        fields(
+            owner = %signer.pubkey(),
+            pool = %position.pool_address,
+            position_mint = %position.position_mint,
+        ),
+        err(Debug)
+    )]
+    pub async fn zap_out<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        position: &LiquidityPosition,
+        output_mint: &str,
+        close_position: bool,
+        config: Option<ZapConfig>,
+    ) -> OrcaResult<ZapOutReceipt> {
+        self.with_timeout("zap out", async {
+            let config = config.unwrap_or_default();
+            let owner = signer.pubkey();
+            let pool = self
+                .get_pool_state_onchain(&position.pool_address.to_string())
+                .await?;
+            self.check_pool_policy(&pool.address)?;
+            let quote = self
+                .quote_zap_out(&pool, position, output_mint, config.slippage_bps)
+                .await?;
+            let swap_mint = if quote.output_mint.to_string() == pool.token_mint_a {
+                &pool.token_mint_b
+            } else {
+                &pool.token_mint_a
+            };
+            if quote.swap_amount > 0 {
+                self.enforce_token_safety(swap_mint, output_mint, None)
+                    .await?;
+                self.enforce_price_guard(
+                    None,
+                    &pool,
+                    swap_mint,
+                    quote.swap_amount,
+                    quote.swap_output,
+                )
+                .await?;
+            }
+            let spends = [(swap_mint.as_str(), quote.swap_amount)];
+            let reservation = self.reserve_spend(&spends).await?;
+            let groups = self
+                .build_zap_out_ixs(&owner, &pool, position, &quote, close_position)
+                .await?;
+            let token_a_mint = parse_address("token_mint_a", &pool.token_mint_a)?;
+            let token_b_mint = parse_address("token_mint_b", &pool.token_mint_b)?;
+            let rent_refunded = if close_position {
+                self.estimate_rent(
+                    &RentPlan::new()
+                        .account("position account", POSITION_ACCOUNT_SIZE)
+                        .account("position token account", TOKEN_ACCOUNT_SIZE),
+                )
+                .await?
+            } else {
+                0
+            };
+            let mut rent_plan = RentPlan::new().signatures(groups.len());
+            if let Some(tip_lamports) = config.submit_mode.jito_tip() {
+                rent_plan = rent_plan.lamports("Jito tip", tip_lamports);
+            }
+            let rent_plan = self
+                .plan_token_accounts(rent_plan, &owner, &[token_a_mint, token_b_mint])
+                .await?;
+            self.ensure_sol_for_plan(&owner, &rent_plan).await?;
+            let signatures = self
+                .submit_packed(
+                    signer,
+                    &[],
+                    PackedSubmission {
+                        prefix: &self.build_compute_budget_instructions(
+                            config.compute_unit_limit,
+                            config.priority_fee_microlamports,
+                        )?,
+                        groups: &groups,
+                        packer: &TransactionPacker::default(),
+                        mode: &config.submit_mode,
+                    },
+                    "zap out",
+                    &AuditDetails::new(serde_json::json!({
+                        "pool": pool.address,
+                        "position_mint": position.position_mint.to_string(),
+                        "liquidity": position.lp_token_amount.to_string(),
+                        "output_mint": output_mint,
+                        "swap_amount": quote.swap_amount,
+                        "min_swap_output": quote.min_swap_output,
+                        "min_output_amount": quote.min_output_amount,
+                        "close_position": close_position,
+                    })),
+                )
+                .await?;
+            let signature = signatures
+                .last()
+                .copied()
+                .ok_or(OrcaError::Error("No transaction was submitted".to_string()))?;
+            reservation.commit();
+            let settlement = self
+                .settle_liquidity(&signature, &owner, &token_a_mint, &token_b_mint)
+                .await;
+            Ok(ZapOutReceipt {
+                signature,
+                signatures,
+                pool: position.pool_address,
+                position_mint: position.position_mint,
+                position_address: position.position_address,
+                liquidity: position.lp_token_amount,
+                output_mint: quote.output_mint,
+                swap_amount: quote.swap_amount,
+                min_swap_output: quote.min_swap_output,
+                min_output_amount: quote.min_output_amount,
+                closed: close_position,
+                rent_refunded,
+                settlement,
+            })
+        })
+        .await
+    }
+
+    /// Quotes withdrawing a position into a single token
+    ///
+    /// The withdrawal minimums are the amounts the position's liquidity is worth at the current
+    /// price, net of transfer fees, less `slippage_bps`. The other token's minimum plus its
+    /// recorded fees owed is swapped, simulated against the pool with the position's liquidity
+    /// already removed. Fees accrued since the position was last updated are collected too but
+    /// stay in the other token.
+    ///
+    /// # Example
+    /// ```rust
+    /// let quote = client
+    ///     .quote_zap_out(&pool_info, &position, &pool_info.token_mint_b, 50)
+    ///     .await?;
+    /// println!("At least {} out", quote.min_output_amount);
+    /// ```
+    pub async fn quote_zap_out(
+        &self,
+        pool: &PoolInfo,
+        position: &LiquidityPosition,
+        output_mint: &str,
+        slippage_bps: u16,
+    ) -> OrcaResult<ZapOutQuote> {
+        validate_slippage_bps(slippage_bps)?;
+        if position.pool_address.to_string() != pool.address {
+            return Err(OrcaError::InvalidInput(InvalidInput::PositionNotInPool {
+                position: position.position_address,
+                pool: pool.address.clone(),
+            }));
+        }
+        let output_is_a = output_mint == pool.token_mint_a;
+        if !output_is_a && output_mint != pool.token_mint_b {
+            return Err(OrcaError::InvalidInput(InvalidInput::MintNotInPool {
+                pool: pool.address.clone(),
+                mint: output_mint.to_string(),
+            }));
+        }
+        let mint_infos = self
+            .get_mint_infos(&[
+                parse_address("token_mint_a", &pool.token_mint_a)?,
+                parse_address("token_mint_b", &pool.token_mint_b)?,
+            ])
+            .await?;
+        let (token_min_a, token_min_b) =
+            withdrawal_minimums(pool, position, &mint_infos, slippage_bps);
+        let fees_a = mint_infos[0].amount_after_transfer_fee(position.fee_owed_a);
+        let fees_b = mint_infos[1].amount_after_transfer_fee(position.fee_owed_b);
+        let (kept, swap_amount, input_info, output_info) = if output_is_a {
+            (
+                token_min_a.saturating_add(fees_a),
+                token_min_b.saturating_add(fees_b),
+                &mint_infos[1],
+                &mint_infos[0],
+            )
+        } else {
+            (
+                token_min_b.saturating_add(fees_b),
+                token_min_a.saturating_add(fees_a),
+                &mint_infos[0],
+                &mint_infos[1],
+            )
+        };
+        let (swap_output, min_swap_output) = if swap_amount > 0 {
+            let a_to_b = !output_is_a;
+            // The swap executes after the withdrawal, so the position's liquidity is gone
+            let mut tick_range = self.get_swap_tick_range(pool, a_to_b).await?;
+            let pool_after = without_position(pool, &mut tick_range, position);
+            let simulation = simulate_swap(
+                &pool_after,
+                &tick_range,
+                input_info.amount_after_transfer_fee(swap_amount),
+                true,
+                a_to_b,
+            );
+            if !simulation.filled {
+                return Err(OrcaError::InsufficientLiquidity {
+                    pool: pool.address.clone(),
+                });
+            }
+            let swap_output = output_info.amount_after_transfer_fee(simulation.amount_out);
+            (
+                swap_output,
+                min_amount_with_slippage(swap_output, slippage_bps),
+            )
+        } else {
+            (0, 0)
+        };
+        Ok(ZapOutQuote {
+            output_mint: output_info.mint,
+            token_min_a,
+            token_min_b,
+            swap_amount,
+            swap_output,
+            min_swap_output,
+            min_output_amount: kept.saturating_add(min_swap_output),
+        })
+    }
+
+    /// Builds the withdrawal, fee collection and swap of a zap-out quote
+    ///
+    /// The withdrawal, fee collection, closing and swap form one group that must land in a
+    /// single transaction. With `close_position`, the reward claims come first in their own
+    /// group, since closing requires nothing left owed.
+    pub async fn build_zap_out_ixs(
+        &self,
+        owner: &Pubkey,
+        pool: &PoolInfo,
+        position: &LiquidityPosition,
+        quote: &ZapOutQuote,
+        close_position: bool,
+    ) -> OrcaResult<Vec<InstructionGroup>> {
+        let token_a_mint = parse_address("token_mint_a", &pool.token_mint_a)?;
+        let token_b_mint = parse_address("token_mint_b", &pool.token_mint_b)?;
+        let mint_infos = self.get_mint_infos(&[token_a_mint, token_b_mint]).await?;
+        let mut groups = self
+            .build_withdrawal_groups(
+                owner,
+                pool,
+                position,
+                &mint_infos,
+                (quote.token_min_a, quote.token_min_b),
+                close_position,
+            )
+            .await?;
+        if quote.swap_amount > 0
+            && let Some(group) = groups.last_mut()
+        {
+            let a_to_b = quote.output_mint == token_b_mint;
+            let (input_info, output_info) = if a_to_b {
+                (&mint_infos[0], &mint_infos[1])
+            } else {
+                (&mint_infos[1], &mint_infos[0])
+            };
+            let input_token_account = self.get_associated_token_address_with_program(
+                owner,
+                &input_info.mint,
+                &input_info.token_program,
+            );
+            let output_token_account = self.get_associated_token_address_with_program(
+                owner,
+                &output_info.mint,
+                &output_info.token_program,
+            );
+            group.instructions.push(self.build_swap_instruction(
+                owner,
+                pool,
+                SwapTokenAccounts {
+                    input_token_account: &input_token_account,
+                    output_token_account: &output_token_account,
+                    input_token_program: &input_info.token_program,
+                    output_token_program: &output_info.token_program,
+                },
+                SwapParams {
+                    amount: quote.swap_amount,
+                    other_amount_threshold: quote.min_swap_output,
+                    sqrt_price_limit: if a_to_b {
+                        MIN_SQRT_PRICE_X64
+                    } else {
+                        MAX_SQRT_PRICE_X64
+                    },
+                    amount_specified_is_input: true,
+                    a_to_b,
+                },
+            )?);
+            group.compute_units += SWAP_COMPUTE_UNITS;
+        }
+        Ok(groups)
+    }
+
+    /// Withdraws all of a position's liquidity and collects its fees into the owner's token
+    /// accounts, closing the position on request
+    ///
+    /// The last group holds the withdrawal; with `close_position`, it is preceded by a group
+    /// claiming the rewards.
+    pub(crate) async fn build_withdrawal_groups(
+        &self,
+        owner: &Pubkey,
+        pool: &PoolInfo,
+        position: &LiquidityPosition,
+        mint_infos: &[MintInfo],
+        (token_min_a, token_min_b): (u64, u64),
+        close_position: bool,
+    ) -> OrcaResult<Vec<InstructionGroup>> {
+        let (token_program_a, token_program_b) =
+            (mint_infos[0].token_program, mint_infos[1].token_program);
+        let token_a_account = self.get_associated_token_address_with_program(
+            owner,
+            &mint_infos[0].mint,
+            &token_program_a,
+        );
+        let token_b_account = self.get_associated_token_address_with_program(
+            owner,
+            &mint_infos[1].mint,
+            &token_program_b,
+        );
+        let mut groups = Vec::new();
+        if close_position {
+            let rewards = self.build_collect_rewards_ixs(owner, position).await?;
+            if !rewards.instructions.is_empty() {
+                let compute_units =
+                    COLLECT_REWARD_COMPUTE_UNITS * rewards.instructions.len() as u32;
+                groups.push(InstructionGroup::new(rewards.instructions, compute_units));
+            }
+        }
+        let mut instructions: Vec<Instruction> = mint_infos
+            .iter()
+            .map(|info| {
+                create_associated_token_account_idempotent(
+                    owner,
+                    owner,
+                    &info.mint,
+                    &info.token_program,
+                )
+            })
+            .collect();
+        let mut compute_units = CREATE_TOKEN_ACCOUNT_COMPUTE_UNITS * 2;
+        if position.lp_token_amount > 0 {
+            instructions.push(self.build_decrease_liquidity_instruction(
+                owner,
+                pool,
+                position,
+                (&token_a_account, &token_b_account),
+                (&token_program_a, &token_program_b),
+                (token_min_a, token_min_b),
+            )?);
+            compute_units += DECREASE_LIQUIDITY_COMPUTE_UNITS;
+        }
+        instructions.push(self.build_collect_fees_instruction(
+            owner,
+            pool,
+            position,
+            (&token_a_account, &token_b_account),
+            (&token_program_a, &token_program_b),
+        )?);
+        compute_units += COLLECT_FEES_COMPUTE_UNITS;
+        if close_position {
+            instructions.push(self.build_close_position_instruction(owner, position)?);
+            compute_units += CLOSE_POSITION_COMPUTE_UNITS;
+        }
+        groups.push(InstructionGroup::new(instructions, compute_units));
+        Ok(groups)
+    }
+}
+
+/// Liquidity token A and token B amounts each support at a price, clamped into the range
+///
+/// A side the range does not need at that price supports unlimited liquidity.
+fn side_liquidity(
+    sqrt_price: u128,
+    sqrt_lower: u128,
+    sqrt_upper: u128,
+    amount_a: u64,
+    amount_b: u64,
+) -> (u128, u128) {
+    let sqrt_price = sqrt_price.clamp(sqrt_lower, sqrt_upper);
+    let from_a = if sqrt_price < sqrt_upper {
+        liquidity_from_amount_a(sqrt_price, sqrt_upper, amount_a).unwrap_or(u128::MAX)
+    } else {
+        u128::MAX
+    };
+    let from_b = if sqrt_price > sqrt_lower {
+        liquidity_from_amount_b(sqrt_lower, sqrt_price, amount_b).unwrap_or(u128::MAX)
+    } else {
+        u128::MAX
+    };
+    (from_a, from_b)
+}
+
+/// Least token A and B a full withdrawal of `position` returns at the pool's current price,
+/// net of transfer fees and less `slippage_bps`
+pub(crate) fn withdrawal_minimums(
+    pool: &PoolInfo,
+    position: &LiquidityPosition,
+    mint_infos: &[MintInfo],
+    slippage_bps: u16,
+) -> (u64, u64) {
+    let (expected_a, expected_b) = token_amounts_from_liquidity(
+        position.lp_token_amount,
+        pool.sqrt_price,
+        position.lower_tick,
+        position.upper_tick,
+        false,
+    );
+    (
+        min_amount_with_slippage(
+            mint_infos[0].amount_after_transfer_fee(expected_a),
+            slippage_bps,
+        ),
+        min_amount_with_slippage(
+            mint_infos[1].amount_after_transfer_fee(expected_b),
+            slippage_bps,
+        ),
+    )
+}
+
+/// Pool state once `position`'s liquidity is withdrawn; the boundary ticks of the position in
+/// `tick_range` lose its liquidity as well
+fn without_position(
+    pool: &PoolInfo,
+    tick_range: &mut SwapTickRange,
+    position: &LiquidityPosition,
+) -> PoolInfo {
+    let mut pool = pool.clone();
+    if (position.lower_tick..position.upper_tick).contains(&pool.tick_current_index) {
+        pool.liquidity = pool.liquidity.saturating_sub(position.lp_token_amount);
+    }
+    let liquidity = i128::try_from(position.lp_token_amount).unwrap_or(i128::MAX);
+    for tick in &mut tick_range.ticks {
+        if tick.index == position.lower_tick {
+            tick.liquidity_net = tick.liquidity_net.saturating_sub(liquidity);
+        } else if tick.index == position.upper_tick {
+            tick.liquidity_net = tick.liquidity_net.saturating_add(liquidity);
+        }
+    }
+    pool
+}